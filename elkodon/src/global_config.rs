@@ -1,14 +1,45 @@
+//! The configuration of elkodon. [`Config::get_global_config()`] loads it in layers where every
+//! layer overrides the keys it defines:
+//!
+//!  1. the default values, see [`Entries::default()`]
+//!  2. the system config file [`SYSTEM_CONFIG_FILE`]
+//!  3. the user config file [`USER_CONFIG_FILE`] in the config directory of the user, which is
+//!     `$XDG_CONFIG_HOME` or `$HOME/.config` (`%APPDATA%` on windows)
+//!  4. the config file defined by the environment variable [`CONFIG_FILE_ENV_VAR`] or
+//!     [`DEFAULT_CONFIG_FILE`] in the working directory when it is not set. An explicitly
+//!     defined config file has to be loadable, otherwise [`Config::get_global_config()`] panics.
+//!  5. environment variables with the prefix [`ENV_OVERRIDE_PREFIX`] which override single keys,
+//!     the path of the key is separated by `__`, e.g.
+//!     `ELKODON__DEFAULTS__PUBLISH_SUBSCRIBE__SUBSCRIBER_BUFFER_SIZE=4`. The path is case
+//!     insensitive except for the service name in `ELKODON__SERVICES__<service name>__...`.
+//!
+//! The defaults of a single service can be overridden in a `[services."<service name>"]` table,
+//! see [`ServiceOverrides`].
+//!
+//! ```toml
+//! [services."camera/front"]
+//! subscriber_buffer_size = 8
+//! publisher_history_size = 4
+//! ```
+
 use elkodon_bb_container::semantic_string::SemanticString;
 use elkodon_bb_elementary::lazy_singleton::*;
-use elkodon_bb_posix::{file::FileBuilder, shared_memory::AccessMode};
+use elkodon_bb_posix::{
+    file::{File, FileBuilder},
+    shared_memory::AccessMode,
+};
 use elkodon_bb_system_types::file_path::FilePath;
 use elkodon_bb_system_types::{file_name::FileName, path::Path};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::Display;
 use std::time::Duration;
 
-use elkodon_bb_log::{fail, trace, warn};
+use elkodon_bb_log::{fail, fatal_panic, trace, warn};
 
+use crate::service::port_factory::producer::DistributionStrategy;
 use crate::service::port_factory::publisher::UnableToDeliverStrategy;
+use crate::service::service_name::ServiceName;
 
 #[cfg(target_os = "windows")]
 pub const DEFAULT_CONFIG_FILE: FilePath =
@@ -18,36 +49,111 @@ pub const DEFAULT_CONFIG_FILE: FilePath =
 pub const DEFAULT_CONFIG_FILE: FilePath =
     unsafe { FilePath::new_unchecked(b"config/iceoryx.toml") };
 
+#[cfg(target_os = "windows")]
+pub const SYSTEM_CONFIG_FILE: FilePath =
+    unsafe { FilePath::new_unchecked(b"C:\\ProgramData\\elkodon\\config.toml") };
+
+#[cfg(not(target_os = "windows"))]
+pub const SYSTEM_CONFIG_FILE: FilePath =
+    unsafe { FilePath::new_unchecked(b"/etc/elkodon/config.toml") };
+
+/// Path of the user config file relative to the config directory of the user, see the
+/// [module documentation](self).
+#[cfg(target_os = "windows")]
+pub const USER_CONFIG_FILE: &str = "elkodon\\config.toml";
+
+#[cfg(not(target_os = "windows"))]
+pub const USER_CONFIG_FILE: &str = "elkodon/config.toml";
+
+/// Environment variable with the path of the user config file which replaces
+/// [`DEFAULT_CONFIG_FILE`].
+pub const CONFIG_FILE_ENV_VAR: &str = "ELKODON_CONFIG_FILE";
+
+/// Prefix of the environment variables which override single keys of the config.
+pub const ENV_OVERRIDE_PREFIX: &str = "ELKODON__";
+
+const ENV_OVERRIDE_SEPARATOR: &str = "__";
+
+/// The keys of a `[services."<service name>"]` table, see [`ServiceOverrides`].
+const SERVICE_OVERRIDE_KEYS: [&str; 9] = [
+    "max_subscribers",
+    "max_publishers",
+    "subscriber_buffer_size",
+    "subscriber_max_borrowed_samples",
+    "publisher_history_size",
+    "enable_safe_overflow",
+    "enable_events",
+    "max_listeners",
+    "max_notifiers",
+];
+
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub enum ConfigCreationError {
     FailedToOpenConfigFile,
     FailedToReadConfigFileContents,
     UnableToDeserializeContents,
+    InvalidConfigFilePath,
+}
+
+/// A problem in the config which does not prevent it from being loaded.
+#[derive(Debug, Clone, Eq, Hash, PartialEq)]
+pub enum ConfigIssue {
+    /// The key is not known and therefore ignored.
+    UnknownKey(String),
+    /// The values of the section cannot be used together to create a service.
+    ImpossibleCombination { section: String, reason: String },
+}
+
+impl Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigIssue::UnknownKey(key) => write!(f, "unknown key \"{}\"", key),
+            ConfigIssue::ImpossibleCombination { section, reason } => {
+                write!(f, "impossible combination in [{}]: {}", section, reason)
+            }
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct Service {
     pub directory: String,
     pub publisher_data_segment_suffix: String,
+    pub client_data_segment_suffix: String,
+    pub server_data_segment_suffix: String,
+    pub producer_data_segment_suffix: String,
     pub static_config_storage_suffix: String,
     pub dynamic_config_storage_suffix: String,
     pub creation_timeout: Duration,
     pub connection_suffix: String,
+    pub max_owners: usize,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct Node {
+    pub directory: String,
+    pub details_suffix: String,
+    pub ports_suffix: String,
+    pub max_ports: usize,
 }
 
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct Global {
     pub root_path: String,
     pub service: Service,
+    pub node: Node,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Defaults {
     pub publish_subscribe: PublishSubscribe,
     pub event: Event,
+    pub request_response: RequestResponse,
+    pub blackboard: Blackboard,
+    pub pipeline: Pipeline,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct PublishSubscribe {
     pub max_subscribers: usize,
     pub max_publishers: usize,
@@ -56,19 +162,100 @@ pub struct PublishSubscribe {
     pub publisher_max_loaned_samples: usize,
     pub publisher_history_size: usize,
     pub enable_safe_overflow: bool,
+    pub enable_events: bool,
     pub unable_to_deliver_strategy: UnableToDeliverStrategy,
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct Event {
     pub max_listeners: usize,
     pub max_notifiers: usize,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct RequestResponse {
+    pub max_clients: usize,
+    pub max_servers: usize,
+    pub request_buffer_size: usize,
+    pub response_buffer_size: usize,
+    pub server_max_borrowed_requests: usize,
+    pub client_max_borrowed_responses: usize,
+    pub client_max_loaned_requests: usize,
+    pub server_max_loaned_responses: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Blackboard {
+    pub max_writers: usize,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Pipeline {
+    pub max_producers: usize,
+    pub max_consumers: usize,
+    pub consumer_buffer_size: usize,
+    pub consumer_max_borrowed_samples: usize,
+    pub producer_max_loaned_samples: usize,
+    pub distribution_strategy: DistributionStrategy,
+}
+
+/// Overrides the [`Defaults`] of a single service. Every key which is not set falls back to the
+/// value of the defaults.
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq, Eq)]
+pub struct ServiceOverrides {
+    pub max_subscribers: Option<usize>,
+    pub max_publishers: Option<usize>,
+    pub subscriber_buffer_size: Option<usize>,
+    pub subscriber_max_borrowed_samples: Option<usize>,
+    pub publisher_history_size: Option<usize>,
+    pub enable_safe_overflow: Option<bool>,
+    pub enable_events: Option<bool>,
+    pub max_listeners: Option<usize>,
+    pub max_notifiers: Option<usize>,
+}
+
+fn apply<T: Copy>(value: Option<T>, setting: &mut T) {
+    if let Some(value) = value {
+        *setting = value;
+    }
+}
+
+impl ServiceOverrides {
+    fn apply_to_publish_subscribe(&self, settings: &mut PublishSubscribe) {
+        apply(self.max_subscribers, &mut settings.max_subscribers);
+        apply(self.max_publishers, &mut settings.max_publishers);
+        apply(
+            self.subscriber_buffer_size,
+            &mut settings.subscriber_buffer_size,
+        );
+        apply(
+            self.subscriber_max_borrowed_samples,
+            &mut settings.subscriber_max_borrowed_samples,
+        );
+        apply(
+            self.publisher_history_size,
+            &mut settings.publisher_history_size,
+        );
+        apply(
+            self.enable_safe_overflow,
+            &mut settings.enable_safe_overflow,
+        );
+        apply(self.enable_events, &mut settings.enable_events);
+    }
+
+    fn apply_to_event(&self, settings: &mut Event) {
+        apply(self.max_listeners, &mut settings.max_listeners);
+        apply(self.max_notifiers, &mut settings.max_notifiers);
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Entries {
     pub global: Global,
     pub defaults: Defaults,
+    /// The per-service overrides of the [`Defaults`], the key is the service name.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub services: BTreeMap<String, ServiceOverrides>,
 }
 
 impl Default for Entries {
@@ -82,10 +269,20 @@ impl Default for Entries {
                 service: Service {
                     directory: "services".to_string(),
                     publisher_data_segment_suffix: ".publisher_data".to_string(),
+                    client_data_segment_suffix: ".client_data".to_string(),
+                    server_data_segment_suffix: ".server_data".to_string(),
+                    producer_data_segment_suffix: ".producer_data".to_string(),
                     static_config_storage_suffix: ".service".to_string(),
                     dynamic_config_storage_suffix: ".dynamic".to_string(),
                     creation_timeout: Duration::from_millis(500),
                     connection_suffix: ".connection".to_string(),
+                    max_owners: 128,
+                },
+                node: Node {
+                    directory: "nodes".to_string(),
+                    details_suffix: ".node".to_string(),
+                    ports_suffix: ".ports".to_string(),
+                    max_ports: 128,
                 },
             },
             defaults: Defaults {
@@ -97,17 +294,211 @@ impl Default for Entries {
                     subscriber_max_borrowed_samples: 2,
                     publisher_max_loaned_samples: 2,
                     enable_safe_overflow: true,
+                    enable_events: false,
                     unable_to_deliver_strategy: UnableToDeliverStrategy::Block,
                 },
                 event: Event {
                     max_listeners: 1,
                     max_notifiers: 16,
                 },
+                request_response: RequestResponse {
+                    max_clients: 8,
+                    max_servers: 2,
+                    request_buffer_size: 4,
+                    response_buffer_size: 4,
+                    server_max_borrowed_requests: 2,
+                    client_max_borrowed_responses: 2,
+                    client_max_loaned_requests: 2,
+                    server_max_loaned_responses: 2,
+                },
+                blackboard: Blackboard { max_writers: 4 },
+                pipeline: Pipeline {
+                    max_producers: 1,
+                    max_consumers: 8,
+                    consumer_buffer_size: 2,
+                    consumer_max_borrowed_samples: 2,
+                    producer_max_loaned_samples: 2,
+                    distribution_strategy: DistributionStrategy::RoundRobin,
+                },
             },
+            services: BTreeMap::new(),
         }
     }
 }
 
+impl Entries {
+    /// Returns the publish subscribe defaults with the overrides of the service applied.
+    pub fn publish_subscribe(&self, service_name: &ServiceName) -> PublishSubscribe {
+        self.publish_subscribe_of(&service_name.to_string())
+    }
+
+    /// Returns the event defaults with the overrides of the service applied.
+    pub fn event(&self, service_name: &ServiceName) -> Event {
+        self.event_of(&service_name.to_string())
+    }
+
+    /// Returns all combinations of values which cannot be used to create a service, in the
+    /// defaults as well as in the per-service overrides.
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        let mut issues = vec![];
+        validate_publish_subscribe(
+            "defaults.publish_subscribe",
+            &self.defaults.publish_subscribe,
+            &mut issues,
+        );
+
+        for service_name in self.services.keys() {
+            let settings = self.publish_subscribe_of(service_name);
+            // issues of the defaults are reported only once
+            if settings != self.defaults.publish_subscribe {
+                validate_publish_subscribe(
+                    &format!("services.\"{}\"", service_name),
+                    &settings,
+                    &mut issues,
+                );
+            }
+        }
+
+        issues
+    }
+
+    fn publish_subscribe_of(&self, service_name: &str) -> PublishSubscribe {
+        let mut settings = self.defaults.publish_subscribe.clone();
+        if let Some(overrides) = self.services.get(service_name) {
+            overrides.apply_to_publish_subscribe(&mut settings);
+        }
+        settings
+    }
+
+    fn event_of(&self, service_name: &str) -> Event {
+        let mut settings = self.defaults.event.clone();
+        if let Some(overrides) = self.services.get(service_name) {
+            overrides.apply_to_event(&mut settings);
+        }
+        settings
+    }
+}
+
+fn validate_publish_subscribe(
+    section: &str,
+    settings: &PublishSubscribe,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    // the same restriction is enforced when the service is created
+    if !settings.enable_safe_overflow
+        && settings.subscriber_buffer_size < settings.publisher_history_size
+    {
+        issues.push(ConfigIssue::ImpossibleCombination {
+            section: section.to_string(),
+            reason: format!(
+                "the subscriber_buffer_size {} is smaller than the publisher_history_size {} \
+                 without enable_safe_overflow",
+                settings.subscriber_buffer_size, settings.publisher_history_size
+            ),
+        });
+    }
+}
+
+/// Adds the keys of the layer to the base, nested tables are merged key by key.
+fn merge(base: &mut toml::Value, layer: toml::Value) {
+    match (base, layer) {
+        (toml::Value::Table(base), toml::Value::Table(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(entry) => merge(entry, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+fn collect_unknown_keys(
+    reference: &toml::value::Table,
+    layer: &toml::value::Table,
+    prefix: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    for (key, value) in layer {
+        let path = match prefix.is_empty() {
+            true => key.clone(),
+            false => format!("{}.{}", prefix, key),
+        };
+
+        match (reference.get(key), value) {
+            (Some(toml::Value::Table(reference)), toml::Value::Table(value)) => {
+                collect_unknown_keys(reference, value, &path, issues)
+            }
+            (Some(_), _) => (),
+            (None, toml::Value::Table(services)) if path == "services" => {
+                collect_unknown_service_keys(services, issues)
+            }
+            (None, _) => issues.push(ConfigIssue::UnknownKey(path)),
+        }
+    }
+}
+
+fn collect_unknown_service_keys(services: &toml::value::Table, issues: &mut Vec<ConfigIssue>) {
+    for (service_name, overrides) in services {
+        if let toml::Value::Table(overrides) = overrides {
+            for key in overrides.keys() {
+                if !SERVICE_OVERRIDE_KEYS.contains(&key.as_str()) {
+                    issues.push(ConfigIssue::UnknownKey(format!(
+                        "services.\"{}\".{}",
+                        service_name, key
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// Inserts the value at the path, the missing tables on the way are created.
+fn insert_at(table: &mut toml::value::Table, path: &[&str], value: toml::Value) -> bool {
+    match path {
+        [] => false,
+        [key] => {
+            table.insert(key.to_string(), value);
+            true
+        }
+        [key, rest @ ..] => match table
+            .entry(key.to_string())
+            .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+        {
+            toml::Value::Table(table) => insert_at(table, rest, value),
+            _ => false,
+        },
+    }
+}
+
+fn user_config_file() -> Option<FilePath> {
+    #[cfg(target_os = "windows")]
+    let config_dir = std::env::var("APPDATA").ok()?;
+
+    #[cfg(not(target_os = "windows"))]
+    let config_dir = match std::env::var("XDG_CONFIG_HOME") {
+        Ok(config_dir) if !config_dir.is_empty() => config_dir,
+        _ => format!("{}/.config", std::env::var("HOME").ok()?),
+    };
+
+    let user_config_file = std::path::Path::new(&config_dir).join(USER_CONFIG_FILE);
+    FilePath::new(user_config_file.to_str()?.as_bytes()).ok()
+}
+
+/// Interprets the value of the environment variable like a TOML value, everything which is not
+/// a valid TOML value, like `/tmp/`, is used as string.
+fn parse_env_value(value: &str) -> toml::Value {
+    match toml::from_str::<toml::value::Table>(&format!("value = {}", value)) {
+        Ok(mut table) => table
+            .remove("value")
+            .unwrap_or_else(|| toml::Value::String(value.to_string())),
+        Err(_) => toml::Value::String(value.to_string()),
+    }
+}
+
 impl Global {
     pub fn get_absolute_service_dir(&self) -> Path {
         let mut path = Path::new(self.root_path.as_bytes()).unwrap();
@@ -120,31 +511,130 @@ impl Global {
 #[derive(Debug, Default)]
 pub struct Config {
     entries: Entries,
+    issues: Vec<ConfigIssue>,
 }
 
 static ICEORYX_CONFIG: LazySingleton<Config> = LazySingleton::<Config>::new();
 
 impl Config {
+    /// Loads the config file on top of the default values. Keys which are not defined in the
+    /// file keep their default value.
     pub fn from_file(config_file: &FilePath) -> Result<Config, ConfigCreationError> {
-        let msg = "Failed to create config";
-        let mut new_config = Self::default();
+        Self::from_layers(vec![Self::read_layer(config_file)?])
+    }
+
+    /// Loads the config in layers, see the [module documentation](self).
+    pub fn load() -> Result<Config, ConfigCreationError> {
+        let msg = "Failed to load config";
+        let origin = "Config::load()";
+        let mut layers = vec![];
 
-        let file = fail!(from new_config, when FileBuilder::new(config_file).open_existing(AccessMode::Read),
+        if matches!(File::does_exist(&SYSTEM_CONFIG_FILE), Ok(true)) {
+            layers.push(Self::read_layer(&SYSTEM_CONFIG_FILE)?);
+        }
+
+        if let Some(user_config_file) = user_config_file() {
+            if matches!(File::does_exist(&user_config_file), Ok(true)) {
+                layers.push(Self::read_layer(&user_config_file)?);
+            }
+        }
+
+        match std::env::var(CONFIG_FILE_ENV_VAR) {
+            Ok(value) => {
+                let config_file = fail!(from origin, when FilePath::new(value.as_bytes()),
+                        with ConfigCreationError::InvalidConfigFilePath,
+                        "{} since the {} \"{}\" is not a valid file path.",
+                        msg, CONFIG_FILE_ENV_VAR, value);
+                layers.push(Self::read_layer(&config_file)?);
+            }
+            Err(_) => {
+                if matches!(File::does_exist(&DEFAULT_CONFIG_FILE), Ok(true)) {
+                    layers.push(Self::read_layer(&DEFAULT_CONFIG_FILE)?);
+                }
+            }
+        }
+
+        layers.push(Self::env_overrides());
+        Self::from_layers(layers)
+    }
+
+    fn read_layer(config_file: &FilePath) -> Result<toml::Value, ConfigCreationError> {
+        let msg = "Failed to read config file";
+        let origin = format!("Config::read_layer({})", config_file);
+
+        let file = fail!(from origin, when FileBuilder::new(config_file).open_existing(AccessMode::Read),
                 with ConfigCreationError::FailedToOpenConfigFile,
                 "{} since the config file could not be opened.", msg);
 
         let mut contents = String::new();
-        fail!(from new_config, when file.read_to_string(&mut contents),
+        fail!(from origin, when file.read_to_string(&mut contents),
                 with ConfigCreationError::FailedToReadConfigFileContents,
                 "{} since the config file contents could not be read.", msg);
 
         match toml::from_str(&contents) {
-            Ok(v) => new_config.entries = v,
+            Ok(v) => Ok(v),
             Err(e) => {
-                fail!(from new_config, with ConfigCreationError::UnableToDeserializeContents,
+                fail!(from origin, with ConfigCreationError::UnableToDeserializeContents,
                                 "{} since the contents could not be deserialized ({}).", msg, e);
             }
         }
+    }
+
+    fn env_overrides() -> toml::Value {
+        let mut overrides = toml::value::Table::new();
+        for (key, value) in std::env::vars() {
+            if let Some(key_path) = key.strip_prefix(ENV_OVERRIDE_PREFIX) {
+                let segments: Vec<&str> = key_path.split(ENV_OVERRIDE_SEPARATOR).collect();
+                let mut path: Vec<String> = segments
+                    .iter()
+                    .map(|segment| segment.to_lowercase())
+                    .collect();
+                // service names are case sensitive
+                if path.len() > 1 && path[0] == "services" {
+                    path[1] = segments[1].to_string();
+                }
+                let path: Vec<&str> = path.iter().map(|segment| segment.as_str()).collect();
+                if !insert_at(&mut overrides, &path, parse_env_value(&value)) {
+                    warn!(from "Config::env_overrides()",
+                        "Ignoring the environment variable \"{}\" since it conflicts with another override.", key);
+                }
+            }
+        }
+
+        toml::Value::Table(overrides)
+    }
+
+    fn from_layers(layers: Vec<toml::Value>) -> Result<Config, ConfigCreationError> {
+        let msg = "Failed to create config";
+        let origin = "Config::from_layers()";
+        let mut merged = fatal_panic!(from origin, when toml::Value::try_from(Entries::default()),
+                "This should never happen! Unable to serialize the default config.");
+        let reference = match &merged {
+            toml::Value::Table(reference) => reference.clone(),
+            _ => toml::value::Table::new(),
+        };
+
+        let mut issues = vec![];
+        for layer in layers {
+            if let toml::Value::Table(ref layer) = layer {
+                collect_unknown_keys(&reference, layer, "", &mut issues);
+            }
+            merge(&mut merged, layer);
+        }
+
+        let entries: Entries = match merged.try_into() {
+            Ok(v) => v,
+            Err(e) => {
+                fail!(from origin, with ConfigCreationError::UnableToDeserializeContents,
+                                "{} since the contents could not be deserialized ({}).", msg, e);
+            }
+        };
+        issues.append(&mut entries.validate());
+
+        let new_config = Self { entries, issues };
+        for issue in &new_config.issues {
+            warn!(from new_config, "{}", issue);
+        }
 
         trace!(from new_config, "Loaded.");
         Ok(new_config)
@@ -153,6 +643,7 @@ impl Config {
     pub fn from_entries(entries: &Entries) -> Self {
         Self {
             entries: entries.clone(),
+            issues: entries.validate(),
         }
     }
 
@@ -160,6 +651,11 @@ impl Config {
         &self.entries
     }
 
+    /// Returns the unknown keys and impossible combinations which were found while loading.
+    pub fn issues(&self) -> &[ConfigIssue] {
+        &self.issues
+    }
+
     pub fn setup_from_file(config_file: &FilePath) -> Result<&'static Config, ConfigCreationError> {
         if ICEORYX_CONFIG.is_initialized() {
             return Ok(ICEORYX_CONFIG.get());
@@ -177,12 +673,24 @@ impl Config {
         Ok(ICEORYX_CONFIG.get())
     }
 
+    /// Returns the global config. When it was not set up, it is loaded in layers, see the
+    /// [module documentation](self).
     pub fn get_global_config() -> &'static Config {
-        if !ICEORYX_CONFIG.is_initialized()
-            && Config::setup_from_file(&DEFAULT_CONFIG_FILE).is_err()
-        {
-            warn!(from "Config::get_global_config()", "Unable to load default config file, populate config with default values.");
-            ICEORYX_CONFIG.set_value(Config::default());
+        if !ICEORYX_CONFIG.is_initialized() {
+            match Config::load() {
+                Ok(config) => {
+                    ICEORYX_CONFIG.set_value(config);
+                }
+                Err(e) => {
+                    if let Ok(config_file) = std::env::var(CONFIG_FILE_ENV_VAR) {
+                        fatal_panic!(from "Config::get_global_config()",
+                            "Unable to load the config ({:?}) with the config file \"{}\" which was explicitly defined via {}.",
+                            e, config_file, CONFIG_FILE_ENV_VAR);
+                    }
+                    warn!(from "Config::get_global_config()", "Unable to load the config ({:?}), populate config with default values.", e);
+                    ICEORYX_CONFIG.set_value(Config::default());
+                }
+            }
         }
 
         ICEORYX_CONFIG.get()
@@ -1,6 +1,15 @@
 pub mod global_config;
 pub mod message;
+pub mod node;
 pub mod port;
+pub mod request;
+pub mod request_mut;
+pub mod response;
+pub mod response_mut;
 pub mod sample;
 pub mod sample_mut;
 pub mod service;
+pub mod waitset;
+pub mod work_item;
+pub mod work_item_mut;
+pub mod zero_copy_type;
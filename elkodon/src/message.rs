@@ -1,5 +1,76 @@
+use std::{alloc::Layout, fmt::Debug};
+
+use elkodon_bb_log::fatal_panic;
+
+use crate::zero_copy_type::{ShmSafe, TypeDescriptor, ZeroCopyType};
+
+/// Describes the memory representation of a payload. It is implemented for every sized
+/// [`ZeroCopyType`] which is [`ShmSafe`] and for slices of them whose length is only known at
+/// runtime.
+pub trait Payload: Debug {
+    /// Returns the layout of a payload consisting of `number_of_elements` elements.
+    fn payload_layout(number_of_elements: usize) -> Layout;
+
+    /// Returns a pointer to a payload with `number_of_elements` elements starting at `ptr`.
+    fn payload_ptr(ptr: *mut u8, number_of_elements: usize) -> *mut Self;
+
+    /// Returns the [`TypeDescriptor`] with which the compatibility of services is verified.
+    fn type_descriptor() -> TypeDescriptor;
+}
+
+impl<T: Debug + ZeroCopyType + ShmSafe> Payload for T {
+    fn payload_layout(_number_of_elements: usize) -> Layout {
+        Layout::new::<T>()
+    }
+
+    fn payload_ptr(ptr: *mut u8, _number_of_elements: usize) -> *mut Self {
+        ptr as *mut T
+    }
+
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::new::<T>()
+    }
+}
+
+impl<T: Debug + ZeroCopyType + ShmSafe> Payload for [T] {
+    fn payload_layout(number_of_elements: usize) -> Layout {
+        fatal_panic!(from "Payload::payload_layout()", when Layout::array::<T>(number_of_elements),
+            "The slice with {} elements of type {} exceeds the maximum supported size.",
+            number_of_elements, std::any::type_name::<T>())
+    }
+
+    fn payload_ptr(ptr: *mut u8, number_of_elements: usize) -> *mut Self {
+        std::ptr::slice_from_raw_parts_mut(ptr as *mut T, number_of_elements)
+    }
+
+    fn type_descriptor() -> TypeDescriptor {
+        TypeDescriptor::new_slice::<T>()
+    }
+}
+
 #[repr(C)]
-pub(crate) struct Message<Header, Data> {
+pub(crate) struct Message<Header, Data: ?Sized, UserHeader = ()> {
     pub(crate) header: Header,
+    pub(crate) user_header: UserHeader,
     pub(crate) data: Data,
 }
+
+impl<Header, Data: Payload + ?Sized, UserHeader> Message<Header, Data, UserHeader> {
+    /// Returns the layout of a message whose payload consists of `number_of_elements` elements.
+    pub(crate) fn layout(number_of_elements: usize) -> Layout {
+        let msg = "The message exceeds the maximum supported size";
+        let origin = "Message::layout()";
+        let (layout, _) = fatal_panic!(from origin,
+            when Layout::new::<Header>().extend(Layout::new::<UserHeader>()),
+            "{}.", msg);
+        fatal_panic!(from origin,
+            when layout.extend(Data::payload_layout(number_of_elements)),
+            "{} with {} elements.", msg, number_of_elements)
+        .0
+        .pad_to_align()
+    }
+
+    pub(crate) fn from_raw_parts(ptr: *mut u8, number_of_elements: usize) -> *mut Self {
+        Data::payload_ptr(ptr, number_of_elements) as *mut Self
+    }
+}
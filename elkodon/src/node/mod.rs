@@ -0,0 +1,687 @@
+//! A [`Node`] is the identity of an application, or a part of it, in the system. It registers
+//! itself in the node registry, where it can be listed with [`Node::list()`] by every process,
+//! and all services are created through it. The registry entry records the ids of the services
+//! of the node and is replaced whenever they change. The ids of its ports are kept in a shared
+//! memory next to the entry so that creating and dropping ports does not touch the file
+//! system. Since the services and their
+//! ports borrow the node, they cannot outlive it and dropping the node releases everything it
+//! owns including its registry entry.
+//!
+//! ```no_run
+//! use elkodon::node::{node_name::NodeName, Node, NodeBuilder};
+//! use elkodon::service::{service_name::ServiceName, zero_copy};
+//! use elkodon_bb_container::semantic_string::SemanticString;
+//!
+//! let node = NodeBuilder::new()
+//!     .name(&NodeName::new(b"planner").unwrap())
+//!     .create::<zero_copy::Service>()
+//!     .unwrap();
+//!
+//! let service = node
+//!     .service_builder(&ServiceName::new(b"My/Funk/ServiceName").unwrap())
+//!     .publish_subscribe()
+//!     .create::<u64>()
+//!     .unwrap();
+//! let publisher = service.publisher().create().unwrap();
+//!
+//! for node in Node::<zero_copy::Service>::list().unwrap() {
+//!     println!("{} {:?} alive: {} ports: {:?}", node.id().value(), node.name(), node.is_alive(),
+//!         node.ports());
+//! }
+//! ```
+
+pub mod node_name;
+
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_elementary::relocatable_container::RelocatableContainer;
+use elkodon_bb_lock_free::mpmc::container::Container;
+use elkodon_bb_log::{fail, fatal_panic, trace, warn};
+use elkodon_bb_posix::directory::Directory;
+use elkodon_bb_posix::process::{Process, ProcessId};
+use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+use elkodon_bb_system_types::file_name::FileName;
+use elkodon_bb_system_types::path::Path;
+use elkodon_cal::dynamic_storage::{
+    DynamicStorage, DynamicStorageBuilder, DynamicStorageCreateError,
+};
+use elkodon_cal::named_concept::*;
+use elkodon_cal::serialize::Serialize as _;
+use elkodon_cal::static_storage::*;
+use serde::{Deserialize, Serialize};
+
+use crate::global_config::{self, Config};
+use crate::service::builder::{blackboard, event, pipeline, publish_subscribe, request_response};
+use crate::service::{remove_resource, service_name::ServiceName, Details, Service};
+use crate::zero_copy_type::{PayloadFor, ZeroCopyType};
+
+use self::node_name::NodeName;
+
+type DetailsStorage<S> = <<S as Service>::Type<'static> as Details<'static>>::StaticStorage;
+type DetailsStorageConfig<S> = <DetailsStorage<S> as NamedConceptMgmt>::Configuration;
+type PortsStorage<S> = <<S as Service>::Type<'static> as Details<'static>>::NodePortsStorage;
+type PortsStorageConfig<S> = <PortsStorage<S> as NamedConceptMgmt>::Configuration;
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum NodeCreationError {
+    InsufficientPermissions,
+    InternalError,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum NodeListError {
+    InsufficientPermissions,
+    InternalError,
+}
+
+/// The system-wide unique id of a [`Node`]. It contains the id of the process which created
+/// the node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UniqueNodeId(UniqueSystemId);
+
+impl UniqueNodeId {
+    /// Returns the system-wide unique value of the id.
+    pub fn value(&self) -> u128 {
+        self.0.value()
+    }
+
+    /// Returns the id of the process which created the node.
+    pub fn pid(&self) -> ProcessId {
+        self.0.pid()
+    }
+
+    /// Returns true when the process which created the node is still alive.
+    pub fn is_owner_alive(&self) -> bool {
+        Process::from_pid(self.0.pid()).is_alive()
+    }
+}
+
+/// The details of a node which are stored in the node registry.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct NodeDetails {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    name: Option<NodeName>,
+    #[serde(default)]
+    services: Vec<String>,
+}
+
+/// A node of the node registry, see [`Node::list()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodeView {
+    id: UniqueNodeId,
+    name: Option<NodeName>,
+    services: Vec<String>,
+    ports: Vec<u128>,
+    generation: Option<u64>,
+    storage_names: Vec<FileName>,
+}
+
+impl NodeView {
+    pub fn id(&self) -> &UniqueNodeId {
+        &self.id
+    }
+
+    /// Returns the name of the node. It is [`None`] when the node was created without a name
+    /// or when its details are not readable, for instance since it is still being created.
+    pub fn name(&self) -> Option<&NodeName> {
+        self.name.as_ref()
+    }
+
+    /// Returns the ids, see [`crate::service::static_config::StaticConfig::uuid()`], of the
+    /// services which the node has created or opened.
+    pub fn services(&self) -> &[String] {
+        &self.services
+    }
+
+    /// Returns the ids of the ports which were created by the services of the node.
+    pub fn ports(&self) -> &[u128] {
+        &self.ports
+    }
+
+    /// Returns true when the process which created the node is still alive.
+    pub fn is_alive(&self) -> bool {
+        self.id.is_owner_alive()
+    }
+}
+
+// the generation is increased whenever the entry is replaced, see NodeEntry::update()
+fn details_storage_name(node_id: &UniqueNodeId, generation: u64) -> FileName {
+    fatal_panic!(from "details_storage_name()",
+        when FileName::new(format!("{}_{}", node_id.value(), generation).as_bytes()),
+        "This should never happen! The node id should be always a valid file name.")
+}
+
+fn parse_details_storage_name(storage_name: &FileName) -> Option<(UniqueNodeId, u64)> {
+    let (id, generation) = std::str::from_utf8(storage_name.as_bytes())
+        .ok()?
+        .split_once('_')?;
+    Some((
+        UniqueNodeId(UniqueSystemId::from(id.parse::<u128>().ok()?)),
+        generation.parse::<u64>().ok()?,
+    ))
+}
+
+fn details_storage_config<S: Service>(
+    global_config: &global_config::Entries,
+) -> DetailsStorageConfig<S> {
+    let origin = "details_storage_config()";
+
+    let suffix = match FileName::new(global_config.global.node.details_suffix.as_bytes()) {
+        Err(_) => {
+            fatal_panic!(from origin, "The node.details_suffix \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.node.details_suffix);
+        }
+        Ok(v) => v,
+    };
+
+    let mut path_hint = match Path::new(global_config.global.root_path.as_bytes()) {
+        Err(_) => {
+            fatal_panic!(from origin, "The root_path \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.root_path);
+        }
+        Ok(v) => v,
+    };
+
+    if path_hint
+        .push_bytes(global_config.global.node.directory.as_bytes())
+        .is_err()
+    {
+        fatal_panic!(from origin, "The node.directory \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.node.directory);
+    }
+
+    DetailsStorageConfig::<S>::default()
+        .suffix(suffix)
+        .path_hint(path_hint)
+}
+
+fn read_details<S: Service>(
+    storage_name: &FileName,
+    config: &DetailsStorageConfig<S>,
+) -> Option<NodeDetails> {
+    let origin = "read_details()";
+    let storage = match <<DetailsStorage<S> as StaticStorage>::Builder as NamedConceptBuilder<
+        DetailsStorage<S>,
+    >>::new(storage_name)
+    .config(config)
+    .has_ownership(false)
+    .open()
+    {
+        Ok(storage) => storage,
+        Err(e) => {
+            trace!(from origin, "Unable to open the node details \"{}\" ({:?}).", storage_name, e);
+            return None;
+        }
+    };
+
+    let mut content = vec![0u8; storage.len() as usize];
+    if let Err(e) = storage.read(&mut content) {
+        trace!(from origin, "Unable to read the node details \"{}\" ({:?}).", storage_name, e);
+        return None;
+    }
+
+    <S::Type<'static> as Details<'static>>::ConfigSerializer::deserialize(&content).ok()
+}
+
+fn create_details_storage<S: Service>(
+    node_id: &UniqueNodeId,
+    generation: u64,
+    details: &NodeDetails,
+    config: &DetailsStorageConfig<S>,
+) -> Result<DetailsStorage<S>, NodeCreationError> {
+    let msg = "Unable to write the node details";
+    let origin = "create_details_storage()";
+    let content = fail!(from origin,
+            when <S::Type<'static> as Details<'static>>::ConfigSerializer::serialize(details),
+            with NodeCreationError::InternalError,
+            "{} since they could not be serialized.", msg);
+
+    Ok(fail!(from origin,
+            when <<DetailsStorage<S> as StaticStorage>::Builder as NamedConceptBuilder<
+                    DetailsStorage<S>,
+                >>::new(&details_storage_name(node_id, generation))
+                .config(config)
+                .create(&content),
+            map StaticStorageCreateError::InsufficientPermissions => NodeCreationError::InsufficientPermissions,
+            unmatched NodeCreationError::InternalError,
+            "{} into the node registry.", msg))
+}
+
+fn ports_storage_name(node_id: &UniqueNodeId) -> FileName {
+    fatal_panic!(from "ports_storage_name()",
+        when FileName::new(node_id.value().to_string().as_bytes()),
+        "This should never happen! The node id should be always a valid file name.")
+}
+
+fn ports_storage_config<S: Service>(
+    global_config: &global_config::Entries,
+) -> PortsStorageConfig<S> {
+    let suffix = match FileName::new(global_config.global.node.ports_suffix.as_bytes()) {
+        Err(_) => {
+            fatal_panic!(from "ports_storage_config()", "The node.ports_suffix \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.node.ports_suffix);
+        }
+        Ok(v) => v,
+    };
+
+    PortsStorageConfig::<S>::default().suffix(suffix)
+}
+
+fn create_ports_storage<S: Service>(
+    node_id: &UniqueNodeId,
+    max_ports: usize,
+    config: &PortsStorageConfig<S>,
+) -> Result<PortsStorage<S>, NodeCreationError> {
+    Ok(fail!(from "create_ports_storage()",
+            when <<PortsStorage<S> as DynamicStorage<Container<u128>>>::Builder as NamedConceptBuilder<
+                    PortsStorage<S>,
+                >>::new(&ports_storage_name(node_id))
+                .config(config)
+                .supplementary_size(Container::<u128>::memory_size(max_ports))
+                .create_and_initialize(unsafe { Container::new_uninit(max_ports) },
+                    |ports, allocator| unsafe { ports.init(allocator) }.is_ok()),
+            map DynamicStorageCreateError::InsufficientPermissions => NodeCreationError::InsufficientPermissions,
+            unmatched NodeCreationError::InternalError,
+            "Unable to create the storage for the ports of the node."))
+}
+
+fn read_ports<S: Service>(node_id: &UniqueNodeId, config: &PortsStorageConfig<S>) -> Vec<u128> {
+    let storage = match <<PortsStorage<S> as DynamicStorage<Container<u128>>>::Builder as NamedConceptBuilder<
+        PortsStorage<S>,
+    >>::new(&ports_storage_name(node_id))
+    .config(config)
+    .has_ownership(false)
+    .open()
+    {
+        Ok(storage) => storage,
+        Err(e) => {
+            trace!(from "read_ports()", "Unable to open the ports of the node {} ({:?}).", node_id.value(), e);
+            return vec![];
+        }
+    };
+
+    let mut ports = vec![];
+    unsafe { storage.get().get_state() }.for_each(|_, id| ports.push(*id));
+    ports
+}
+
+/// Records the services of a node in its registry entry and its ports in the shared memory of
+/// the node. The services created
+/// through a [`Node`] hold a reference to it.
+pub(crate) trait NodeRegistry: Debug + Send + Sync {
+    fn register_service(&self, service_id: &str);
+    fn unregister_service(&self, service_id: &str);
+    /// Returns the index of the port in the node or [`None`] when the node cannot hold any
+    /// more ports.
+    fn register_port(&self, port_id: u128) -> Option<u32>;
+    fn unregister_port(&self, index: u32);
+}
+
+/// Removes a port from its node when it goes out of scope, see
+/// [`crate::service::ServiceState::register_port()`].
+#[derive(Debug)]
+pub(crate) struct PortRegistration<'a> {
+    node: Option<&'a dyn NodeRegistry>,
+    index: Option<u32>,
+}
+
+impl<'a> PortRegistration<'a> {
+    pub(crate) fn new(node: Option<&'a dyn NodeRegistry>, port_id: u128) -> Self {
+        Self {
+            node,
+            index: node.and_then(|node| node.register_port(port_id)),
+        }
+    }
+}
+
+impl Drop for PortRegistration<'_> {
+    fn drop(&mut self) {
+        if let (Some(node), Some(index)) = (self.node, self.index) {
+            node.unregister_port(index);
+        }
+    }
+}
+
+struct NodeEntryState<S: Service> {
+    details: NodeDetails,
+    generation: u64,
+    // removes the entry from the node registry when it is replaced or the node is dropped
+    storage: DetailsStorage<S>,
+}
+
+/// The registry entry of a [`Node`].
+struct NodeEntry<S: Service> {
+    id: UniqueNodeId,
+    config: DetailsStorageConfig<S>,
+    state: Mutex<NodeEntryState<S>>,
+    // removes the ports of the node when the node is dropped
+    ports: PortsStorage<S>,
+}
+
+impl<S: Service> Debug for NodeEntry<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "NodeEntry<{}> {{ id: {} }}",
+            std::any::type_name::<S>(),
+            self.id.value()
+        )
+    }
+}
+
+// the details storage is only accessed while the mutex is held and the ports are stored in a
+// lock-free container
+unsafe impl<S: Service> Send for NodeEntry<S> {}
+unsafe impl<S: Service> Sync for NodeEntry<S> {}
+
+impl<S: Service> NodeEntry<S> {
+    /// Replaces the registry entry with one which contains the updated details. It is only
+    /// called when the services of the node change. The new entry is created before the old one is removed so that the node never vanishes from the
+    /// registry, [`Node::list_from_config()`] uses the entry with the latest generation.
+    fn update<F: FnOnce(&mut NodeDetails)>(&self, modify: F) {
+        let mut state = match self.state.lock() {
+            Ok(state) => state,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+
+        let mut details = state.details.clone();
+        modify(&mut details);
+        match create_details_storage::<S>(&self.id, state.generation + 1, &details, &self.config) {
+            Ok(storage) => {
+                state.details = details;
+                state.generation += 1;
+                state.storage = storage;
+            }
+            Err(e) => {
+                warn!(from self, "Unable to update the node registry entry ({:?}), it does not reflect the current services of the node.", e);
+            }
+        }
+    }
+}
+
+impl<S: Service> NodeRegistry for NodeEntry<S> {
+    fn register_service(&self, service_id: &str) {
+        self.update(|details| details.services.push(service_id.to_string()));
+    }
+
+    fn unregister_service(&self, service_id: &str) {
+        self.update(|details| {
+            if let Some(index) = details.services.iter().position(|id| id == service_id) {
+                details.services.remove(index);
+            }
+        });
+    }
+
+    fn register_port(&self, port_id: u128) -> Option<u32> {
+        let index = unsafe { self.ports.get().add_raw(port_id) };
+        if index.is_none() {
+            warn!(from self, "Unable to register the port {} since the node already holds the maximum number of {} ports, the node registry does not list it.",
+                port_id, self.ports.get().capacity());
+        }
+        index
+    }
+
+    fn unregister_port(&self, index: u32) {
+        unsafe { self.ports.get().remove_raw_index(index) };
+    }
+}
+
+/// Creates a [`Node`] and registers it in the node registry.
+#[derive(Debug, Default)]
+pub struct NodeBuilder {
+    name: Option<NodeName>,
+    config: Option<global_config::Entries>,
+}
+
+impl NodeBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Defines the name of the node. It does not have to be unique.
+    pub fn name(mut self, value: &NodeName) -> Self {
+        self.name = Some(*value);
+        self
+    }
+
+    /// Defines the config which is used by the node and all services created through it. By
+    /// default [`Config::get_global_config()`] is used.
+    pub fn config(mut self, value: &Config) -> Self {
+        self.config = Some(value.get().clone());
+        self
+    }
+
+    pub fn create<S: Service>(self) -> Result<Node<S>, NodeCreationError> {
+        let msg = "Unable to create node";
+        let config = match &self.config {
+            Some(entries) => Config::from_entries(entries),
+            None => Config::from_entries(Config::get_global_config().get()),
+        };
+
+        let id = UniqueNodeId(fail!(from self, when UniqueSystemId::new(),
+                with NodeCreationError::InternalError,
+                "{} since the unique node id could not be generated.", msg));
+
+        let details = NodeDetails {
+            name: self.name,
+            ..Default::default()
+        };
+        let details_config = details_storage_config::<S>(config.get());
+        // the ports are created first so that every listed node has them
+        let ports = fail!(from self,
+                when create_ports_storage::<S>(&id, config.get().global.node.max_ports,
+                    &ports_storage_config::<S>(config.get())),
+                "{} since the storage for its ports could not be created.", msg);
+        let storage = fail!(from self,
+                when create_details_storage::<S>(&id, 0, &details, &details_config),
+                "{} since the node could not be registered in the node registry.", msg);
+
+        let node = Node {
+            id,
+            name: self.name,
+            config,
+            entry: NodeEntry {
+                id,
+                config: details_config,
+                state: Mutex::new(NodeEntryState {
+                    details,
+                    generation: 0,
+                    storage,
+                }),
+                ports,
+            },
+            _service: PhantomData,
+        };
+        trace!(from node, "create node");
+        Ok(node)
+    }
+}
+
+/// Groups the services and ports of an application under a registered identity, see the
+/// [module documentation](self).
+pub struct Node<S: Service> {
+    id: UniqueNodeId,
+    name: Option<NodeName>,
+    config: Config,
+    entry: NodeEntry<S>,
+    _service: PhantomData<S>,
+}
+
+impl<S: Service> Debug for Node<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Node<{}> {{ id: {}, name: {:?} }}",
+            std::any::type_name::<S>(),
+            self.id.value(),
+            self.name
+        )
+    }
+}
+
+impl<S: Service> Node<S> {
+    pub fn id(&self) -> &UniqueNodeId {
+        &self.id
+    }
+
+    pub fn name(&self) -> Option<&NodeName> {
+        self.name.as_ref()
+    }
+
+    /// Returns the config which is used by all services created through the node.
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Returns a builder which creates or opens the service with the config of the node. The
+    /// service and its ports cannot outlive the node.
+    pub fn service_builder(&self, name: &ServiceName) -> ServiceBuilder<'_, S> {
+        ServiceBuilder {
+            name: *name,
+            node: self,
+        }
+    }
+
+    /// Returns all nodes of the node registry, including the ones whose process is dead.
+    pub fn list() -> Result<Vec<NodeView>, NodeListError> {
+        Self::list_from_config(Config::get_global_config())
+    }
+
+    pub fn list_from_config(config: &Config) -> Result<Vec<NodeView>, NodeListError> {
+        let msg = "Unable to list all nodes";
+        let origin = "Node::list_from_config()";
+        let details_config = details_storage_config::<S>(config.get());
+        let ports_config = ports_storage_config::<S>(config.get());
+
+        let storages = match <DetailsStorage<S> as NamedConceptMgmt>::list_cfg(&details_config) {
+            Ok(storages) => storages,
+            // the node directory is created with the first node
+            Err(NamedConceptListError::InternalError)
+                if !matches!(
+                    Directory::does_exist(details_config.get_path_hint()),
+                    Ok(true)
+                ) =>
+            {
+                return Ok(vec![]);
+            }
+            Err(NamedConceptListError::InsufficientPermissions) => {
+                fail!(from origin, with NodeListError::InsufficientPermissions,
+                    "{} due to insufficient permissions to read the node registry.", msg);
+            }
+            Err(e) => {
+                fail!(from origin, with NodeListError::InternalError,
+                    "{} due to a failure while reading the node registry ({:?}).", msg, e);
+            }
+        };
+
+        let mut nodes: Vec<NodeView> = vec![];
+        for storage_name in storages {
+            let (id, generation) = match parse_details_storage_name(&storage_name) {
+                Some(v) => v,
+                None => {
+                    trace!(from origin, "Skipping \"{}\" since it is not a node.", storage_name);
+                    continue;
+                }
+            };
+
+            let index = match nodes.iter().position(|node| node.id == id) {
+                Some(index) => index,
+                None => {
+                    nodes.push(NodeView {
+                        id,
+                        name: None,
+                        services: vec![],
+                        ports: vec![],
+                        generation: None,
+                        storage_names: vec![],
+                    });
+                    nodes.len() - 1
+                }
+            };
+
+            // the previous entry of a node exists until its replacement was written
+            let node = &mut nodes[index];
+            node.storage_names.push(storage_name);
+            if node.generation.map_or(false, |latest| latest > generation) {
+                continue;
+            }
+
+            if let Some(details) = read_details::<S>(&storage_name, &details_config) {
+                node.name = details.name;
+                // a node can open the same service multiple times
+                node.services = details.services;
+                node.services.sort();
+                node.services.dedup();
+                node.ports = read_ports::<S>(&id, &ports_config);
+                node.generation = Some(generation);
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    /// Removes the nodes whose process is dead from the node registry and returns their ids.
+    /// Their services are not removed, this is done with
+    /// [`Service::cleanup_stale_services()`].
+    pub fn cleanup_dead_nodes() -> Result<Vec<UniqueNodeId>, NodeListError> {
+        let config = Config::get_global_config();
+        let details_config = details_storage_config::<S>(config.get());
+        let ports_config = ports_storage_config::<S>(config.get());
+        let mut removed_nodes = vec![];
+
+        for node in Self::list_from_config(config)? {
+            if !node.is_alive() {
+                for storage_name in &node.storage_names {
+                    remove_resource::<DetailsStorage<S>>(storage_name, &details_config);
+                }
+                remove_resource::<PortsStorage<S>>(&ports_storage_name(node.id()), &ports_config);
+                removed_nodes.push(*node.id());
+            }
+        }
+
+        Ok(removed_nodes)
+    }
+}
+
+/// Creates or opens a service with the config of a [`Node`], see [`Node::service_builder()`].
+pub struct ServiceBuilder<'node, S: Service> {
+    name: ServiceName,
+    node: &'node Node<S>,
+}
+
+impl<'node, S: Service> ServiceBuilder<'node, S> {
+    pub fn publish_subscribe(self) -> publish_subscribe::Builder<'node, S::Type<'node>> {
+        S::new(&self.name)
+            .publish_subscribe_with_custom_config(&self.node.config)
+            .node(&self.node.entry)
+    }
+
+    pub fn event(self) -> event::Builder<'node, S::Type<'node>> {
+        S::new(&self.name)
+            .event_with_custom_config(&self.node.config)
+            .node(&self.node.entry)
+    }
+
+    pub fn request_response(self) -> request_response::Builder<'node, S::Type<'node>> {
+        S::new(&self.name)
+            .request_response_with_custom_config(&self.node.config)
+            .node(&self.node.entry)
+    }
+
+    pub fn blackboard<KeyType: Copy + Eq + Debug + ZeroCopyType + PayloadFor<S::Type<'node>>>(
+        self,
+    ) -> blackboard::Builder<'node, S::Type<'node>, KeyType> {
+        S::new(&self.name)
+            .blackboard_with_custom_config(&self.node.config)
+            .node(&self.node.entry)
+    }
+
+    pub fn pipeline(self) -> pipeline::Builder<'node, S::Type<'node>> {
+        S::new(&self.name)
+            .pipeline_with_custom_config(&self.node.config)
+            .node(&self.node.entry)
+    }
+}
@@ -0,0 +1,52 @@
+use elkodon_bb_container::semantic_string;
+use elkodon_bb_container::semantic_string::SemanticString;
+use serde::{de::Visitor, Deserialize, Serialize};
+
+const NODE_NAME_LENGTH: usize = 128;
+
+semantic_string! {
+  name: NodeName,
+  capacity: NODE_NAME_LENGTH,
+  invalid_content: |value: &[u8]| {
+                        matches!(value, b"")
+                    },
+  invalid_characters: |_: &[u8]| { false }
+}
+
+struct NodeNameVisitor;
+
+impl<'de> Visitor<'de> for NodeNameVisitor {
+    type Value = NodeName;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string containing the node name")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match NodeName::new(v.as_bytes()) {
+            Ok(v) => Ok(v),
+            Err(v) => Err(E::custom(format!("invalid node name provided {:?}.", v))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(NodeNameVisitor)
+    }
+}
+
+impl Serialize for NodeName {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(std::str::from_utf8(self.as_bytes()).unwrap())
+    }
+}
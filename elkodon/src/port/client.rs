@@ -0,0 +1,492 @@
+use std::cell::UnsafeCell;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::{alloc::Layout, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+use super::details::server_connections::*;
+use super::port_identifiers::{UniqueClientId, UniqueServerId};
+use super::publisher::LoanError;
+use crate::global_config;
+use crate::message::Message;
+use crate::node::PortRegistration;
+use crate::request_mut::RequestMut;
+use crate::response::Response;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::header::request_response::{RequestHeader, ResponseHeader};
+use crate::service::port_factory::client::LocalClientConfig;
+use crate::service::static_config::request_response;
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_elementary::allocator::AllocationError;
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_lock_free::mpmc::container::ContainerState;
+use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_system_types::file_name::FileName;
+use elkodon_cal::dynamic_storage::DynamicStorage;
+use elkodon_cal::named_concept::{
+    NamedConceptBuilder, NamedConceptConfiguration, NamedConceptMgmt,
+};
+use elkodon_cal::shared_memory::{SharedMemory, SharedMemoryBuilder, SharedMemoryCreateError};
+use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
+use elkodon_cal::shm_allocator::{self, PointerOffset, ShmAllocationError};
+use elkodon_cal::zero_copy_connection::*;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ClientCreateError {
+    ExceedsMaxSupportedClients,
+    UnableToCreateDataSegment,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ClientReceiveError {
+    ExceedsMaxBorrowedResponses,
+    ConnectionFailure(ServerConnectionFailure),
+}
+
+enum_gen! { ClientSendCopyError
+  mapping:
+    LoanError to LoanError,
+    ServerConnectionFailure to ConnectionError
+}
+
+pub(crate) fn data_segment_name(client_id: UniqueClientId) -> FileName {
+    let msg =
+        "The system does not support the required file name length for the clients data segment.";
+    let origin = "data_segment_name()";
+
+    let mut file = fatal_panic!(from origin, when FileName::new(client_id.0.pid().to_string().as_bytes()), "{}", msg);
+    fatal_panic!(from origin, when file.push(b'_'), "{}", msg);
+    fatal_panic!(from origin, when file.push_bytes(client_id.0.value().to_string().as_bytes()), "{}", msg);
+    file
+}
+
+pub(crate) fn data_segment_config<'global_config, Service: service::Details<'global_config>>(
+    global_config: &global_config::Entries,
+) -> <Service::SharedMemory as NamedConceptMgmt>::Configuration {
+    let origin = "data_segment_config()";
+
+    let f = match FileName::new(
+        global_config
+            .global
+            .service
+            .client_data_segment_suffix
+            .as_bytes(),
+    ) {
+        Err(_) => {
+            fatal_panic!(from origin, "The client_data_segment_suffix \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.service.client_data_segment_suffix);
+        }
+        Ok(v) => v,
+    };
+
+    <Service::SharedMemory as NamedConceptMgmt>::Configuration::default().suffix(f)
+}
+
+/// Sends every request to exactly one of the connected [`crate::port::server::Server`]s,
+/// distributing them in a round-robin fashion, and receives the corresponding responses.
+/// Responses are routed only to the [`Client`] which sent the request.
+#[derive(Debug)]
+pub struct Client<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    port_id: UniqueClientId,
+    pub(crate) request_reference_counter: Vec<AtomicU64>,
+    pub(crate) data_segment: Service::SharedMemory,
+    config: LocalClientConfig,
+
+    server_connections: ServerConnections<'global_config, Service>,
+    server_list_state: UnsafeCell<ContainerState<'a, UniqueServerId>>,
+    pub(crate) loan_counter: AtomicUsize,
+    request_id_counter: AtomicU64,
+    next_server_index: AtomicUsize,
+    _dynamic_config_guard: UniqueIndex<'a>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
+    _phantom_request_type: PhantomData<RequestType>,
+    _phantom_response_type: PhantomData<ResponseType>,
+}
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Client<'a, 'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(
+        service: &'a Service,
+        static_config: &request_response::StaticConfig,
+        config: &LocalClientConfig,
+    ) -> Result<Self, ClientCreateError> {
+        let msg = "Unable to create Client port";
+        let origin = "Client::new()";
+        let port_id = UniqueClientId::new();
+        let server_list = &service
+            .state()
+            .dynamic_storage
+            .get()
+            .request_response()
+            .servers;
+
+        let number_of_requests =
+            static_config.required_amount_of_requests_per_data_segment(config.max_loaned_requests);
+
+        let access_settings = service.state().static_config.access_settings();
+        let data_segment = fail!(from origin, when Self::create_data_segment(port_id, service.state().global_config, &access_settings, number_of_requests),
+                with ClientCreateError::UnableToCreateDataSegment,
+                "{} since the data segment could not be acquired.", msg);
+
+        // !MUST! be the last task otherwise a client is added to the dynamic config without the
+        // creation of all required resources
+        let _dynamic_config_guard = match service
+            .state()
+            .dynamic_storage
+            .get()
+            .request_response()
+            .add_client_id(port_id)
+        {
+            Some(unique_index) => unique_index,
+            None => {
+                fail!(from origin, with ClientCreateError::ExceedsMaxSupportedClients,
+                            "{} since it would exceed the maximum supported amount of clients of {}.",
+                            msg, static_config.max_clients);
+            }
+        };
+
+        let new_self = Self {
+            port_id,
+            request_reference_counter: {
+                let mut v = Vec::with_capacity(number_of_requests);
+                for _ in 0..number_of_requests {
+                    v.push(AtomicU64::new(0));
+                }
+                v
+            },
+            data_segment,
+            config: *config,
+            server_connections: ServerConnections::new(
+                server_list.capacity(),
+                port_id,
+                service.state().global_config,
+                static_config,
+                access_settings,
+            ),
+            server_list_state: unsafe { UnsafeCell::new(server_list.get_state()) },
+            loan_counter: AtomicUsize::new(0),
+            request_id_counter: AtomicU64::new(0),
+            next_server_index: AtomicUsize::new(0),
+            _dynamic_config_guard,
+            _node_registration: service.state().register_port(port_id.value()),
+            _phantom_request_type: PhantomData,
+            _phantom_response_type: PhantomData,
+        };
+
+        if let Err(e) = new_self.populate_server_channels() {
+            warn!(from new_self, "The new Client port is unable to connect to every Server port, caused by {:?}.", e);
+        }
+
+        Ok(new_self)
+    }
+
+    fn create_data_segment(
+        port_id: UniqueClientId,
+        global_config: &'global_config global_config::Entries,
+        access_settings: &AccessSettings,
+        number_of_requests: usize,
+    ) -> Result<Service::SharedMemory, SharedMemoryCreateError> {
+        let allocator_config = shm_allocator::pool_allocator::Config {
+            bucket_layout: Layout::new::<Message<RequestHeader, RequestType>>(),
+        };
+        let chunk_size = allocator_config.bucket_layout.size();
+        let chunk_align = allocator_config.bucket_layout.align();
+
+        Ok(fail!(from "Client::create_data_segment()",
+            when <<Service::SharedMemory as SharedMemory<PoolAllocator>>::Builder as NamedConceptBuilder<
+            Service::SharedMemory,
+                >>::new(&data_segment_name(port_id))
+                .config(&data_segment_config::<Service>(global_config).access_settings(access_settings))
+                .size(chunk_size * number_of_requests + chunk_align - 1)
+                .create(&allocator_config),
+            "Unable to create the data segment."))
+    }
+
+    fn populate_server_channels(&self) -> Result<(), ServerConnectionFailure> {
+        let mut visited_indices = vec![];
+        visited_indices.resize(self.server_connections.capacity(), None);
+
+        unsafe {
+            (*self.server_list_state.get()).for_each(|index, server_id| {
+                visited_indices[index as usize] = Some(*server_id);
+            })
+        };
+
+        // retrieve requests before destroying channel
+        self.retrieve_returned_requests();
+
+        let mut result = Ok(());
+        for (i, index) in visited_indices.iter().enumerate() {
+            match index {
+                Some(server_id) => {
+                    if let Err(e) = self.server_connections.create(i, *server_id) {
+                        warn!(from self, "Unable to establish connection to new server {:?}.", server_id);
+                        result = Err(e);
+                    }
+                }
+                None => self.server_connections.remove(i),
+            }
+        }
+
+        result
+    }
+
+    fn request_index(distance_to_chunk: usize) -> usize {
+        distance_to_chunk / std::mem::size_of::<Message<RequestHeader, RequestType>>()
+    }
+
+    pub(crate) fn release_request(&self, distance_to_chunk: PointerOffset) {
+        if self.request_reference_counter[Self::request_index(distance_to_chunk.value())]
+            .fetch_sub(1, Ordering::Relaxed)
+            == 1
+        {
+            unsafe {
+                fatal_panic!(from self, when self.data_segment
+                .deallocate(
+                    distance_to_chunk,
+                    Layout::new::<Message<RequestHeader, RequestType>>(),
+                ), "Internal logic error. The request should always contain a valid memory chunk from the provided allocator.");
+            };
+        }
+    }
+
+    pub(crate) fn release_response(&self, channel_id: usize, response: *const u8) {
+        match self.server_connections.get(channel_id) {
+            Some(c) => {
+                let distance = response as usize - c.data_segment.allocator_data_start_address();
+                match c.response_receiver.release(PointerOffset::new(distance)) {
+                    Ok(()) => (),
+                    Err(ZeroCopyReleaseError::RetrieveBufferFull) => {
+                        fatal_panic!(from self, when c.response_receiver.release(PointerOffset::new(distance)),
+                                    "This should never happen! The servers retrieve channel is full and the response cannot be returned.");
+                    }
+                }
+            }
+            None => {
+                warn!(from self, "Unable to release response since the connection is broken. The response will be discarded and has to be reclaimed manually by the server.");
+            }
+        }
+    }
+
+    fn retrieve_returned_requests(&self) {
+        for i in 0..self.server_connections.len() {
+            match self.server_connections.get(i) {
+                Some(ref connection) => loop {
+                    match connection.request_sender.reclaim() {
+                        Ok(Some(ptr_dist)) => self.release_request(ptr_dist),
+                        Ok(None) => break,
+                        Err(e) => {
+                            warn!(from self, "Unable to reclaim requests from connection {:?} due to {:?}. This may lead to a situation where no more requests will be delivered to this connection.", connection, e);
+                            break;
+                        }
+                    }
+                },
+                None => (),
+            }
+        }
+    }
+
+    fn deliver_request(&self, address_to_chunk: usize) -> usize {
+        let number_of_connections = self.server_connections.len();
+        let start = self.next_server_index.load(Ordering::Relaxed);
+
+        for n in 0..number_of_connections {
+            let i = (start + n) % number_of_connections;
+            if let Some(ref connection) = self.server_connections.get(i) {
+                match connection
+                    .request_sender
+                    .try_send(PointerOffset::new(address_to_chunk))
+                {
+                    Err(ZeroCopySendError::ReceiveBufferFull) => {
+                        warn!(from self, "Unable to deliver request to server via connection {:?} since its request buffer is full. Trying the next server.", connection);
+                    }
+                    Err(ZeroCopySendError::ClearRetrieveChannelBeforeSend) => {
+                        warn!(from self, "Unable to send request via connection {:?} since the retrieve buffer is full. This can be caused by a corrupted retrieve channel. Trying the next server.", connection);
+                    }
+                    Ok(overflow) => {
+                        self.request_reference_counter[Self::request_index(address_to_chunk)]
+                            .fetch_add(1, Ordering::Relaxed);
+                        self.next_server_index
+                            .store((i + 1) % number_of_connections, Ordering::Relaxed);
+
+                        if let Some(old) = overflow {
+                            self.release_request(old)
+                        }
+                        return 1;
+                    }
+                }
+            }
+        }
+
+        0
+    }
+
+    pub fn update_connections(&self) -> Result<(), ServerConnectionFailure> {
+        if unsafe { (*self.server_list_state.get()).update() } {
+            fail!(from self, when self.populate_server_channels(),
+                "Connections were updated only partially since at least one connection to a Server port failed.");
+        }
+
+        Ok(())
+    }
+
+    pub fn number_of_servers(&self) -> usize {
+        self.server_connections.number_of_servers()
+    }
+
+    /// Sends the request to exactly one connected [`crate::port::server::Server`]. The servers
+    /// are selected in a round-robin fashion, a server whose request buffer is full is skipped.
+    /// Returns 1 when a server received the request, otherwise 0.
+    pub fn send<'client>(
+        &'client self,
+        request: RequestMut<'a, 'client, 'global_config, Service, RequestType, ResponseType>,
+    ) -> Result<usize, ServerConnectionFailure> {
+        fail!(from self, when self.update_connections(),
+            "Unable to send request since the connections could not be updated.");
+
+        Ok(self.deliver_request(request.offset_to_chunk().value()))
+    }
+
+    pub fn send_copy(&self, value: RequestType) -> Result<usize, ClientSendCopyError> {
+        let msg = "Unable to send copy of request";
+        let mut request = fail!(from self, when self.loan(),
+                                    "{} since the loan of a request failed.", msg);
+
+        unsafe { request.as_mut_ptr().write(value) };
+        Ok(fail!(from self, when self.send(request),
+            "{} since the underlying send operation failed.", msg))
+    }
+
+    pub fn loan<'client>(
+        &'client self,
+    ) -> Result<
+        RequestMut<'a, 'client, 'global_config, Service, RequestType, ResponseType>,
+        LoanError,
+    > {
+        self.retrieve_returned_requests();
+        let msg = "Unable to loan Request";
+
+        if self.loan_counter.load(Ordering::Relaxed) >= self.config.max_loaned_requests {
+            fail!(from self, with LoanError::ExceedsMaxLoanedChunks,
+                "{} since already {} requests were loaned and it would exceed the maximum of parallel loans of {}. Release or send a loaned request to loan another request.",
+                msg, self.loan_counter.load(Ordering::Relaxed), self.config.max_loaned_requests);
+        }
+
+        match self
+            .data_segment
+            .allocate(Layout::new::<Message<RequestHeader, RequestType>>())
+        {
+            Ok(chunk) => {
+                if self.request_reference_counter[Self::request_index(chunk.offset.value())]
+                    .fetch_add(1, Ordering::Relaxed)
+                    != 0
+                {
+                    fatal_panic!(from self,
+                                "{} since the allocated request is already in use! This should never happen!", msg);
+                }
+
+                let mut chunk_ptr;
+                unsafe {
+                    chunk_ptr = NonNull::new_unchecked(
+                        chunk.data_ptr as *mut MaybeUninit<Message<RequestHeader, RequestType>>,
+                    );
+                    let header_ptr =
+                        std::ptr::addr_of_mut!((*chunk_ptr.as_mut().as_mut_ptr()).header);
+                    header_ptr.write(RequestHeader::new(
+                        self.port_id,
+                        self.request_id_counter.fetch_add(1, Ordering::Relaxed),
+                    ))
+                }
+
+                Ok(RequestMut::new(self, chunk_ptr, chunk.offset))
+            }
+            Err(ShmAllocationError::AllocationError(AllocationError::OutOfMemory)) => {
+                fail!(from self, with LoanError::OutOfMemory,
+                    "{} since the underlying shared memory is out of memory.", msg);
+            }
+            Err(ShmAllocationError::AllocationError(AllocationError::SizeTooLarge))
+            | Err(ShmAllocationError::AllocationError(AllocationError::AlignmentFailure)) => {
+                fatal_panic!(from self, "{} since the system seems to be corrupted.", msg);
+            }
+            Err(v) => {
+                fail!(from self, with LoanError::InternalFailure,
+                    "{} since an internal failure occurred ({:?}).", msg, v);
+            }
+        }
+    }
+
+    fn receive_from_connection<'client>(
+        &'client self,
+        channel_id: usize,
+        connection: &Connection<'global_config, Service>,
+    ) -> Result<
+        Option<Response<'a, 'client, 'global_config, Service, RequestType, ResponseType>>,
+        ClientReceiveError,
+    > {
+        let msg = "Unable to receive another response";
+        match connection.response_receiver.receive() {
+            Ok(data) => match data {
+                None => Ok(None),
+                Some(relative_addr) => {
+                    let absolute_address = relative_addr.value()
+                        + connection.data_segment.allocator_data_start_address();
+                    Ok(Some(Response {
+                        client: self,
+                        channel_id,
+                        ptr: unsafe {
+                            NonNull::new_unchecked(
+                                absolute_address as *mut Message<ResponseHeader, ResponseType>,
+                            )
+                        },
+                    }))
+                }
+            },
+            Err(ZeroCopyReceiveError::ReceiveWouldExceedMaxBorrowValue) => {
+                fail!(from self, with ClientReceiveError::ExceedsMaxBorrowedResponses,
+                    "{} since it would exceed the maximum {} of borrowed responses.",
+                    msg, connection.response_receiver.max_borrowed_samples());
+            }
+        }
+    }
+
+    /// Receives the next response to one of the requests sent by this [`Client`].
+    pub fn receive<'client>(
+        &'client self,
+    ) -> Result<
+        Option<Response<'a, 'client, 'global_config, Service, RequestType, ResponseType>>,
+        ClientReceiveError,
+    > {
+        if let Err(e) = self.update_connections() {
+            fail!(from self,
+                with ClientReceiveError::ConnectionFailure(e),
+                "Some responses are not being received since not all connections to servers could be established.");
+        }
+
+        for id in 0..self.server_connections.len() {
+            match self.server_connections.get(id) {
+                Some(ref connection) => {
+                    if let Some(response) = self.receive_from_connection(id, connection)? {
+                        return Ok(Some(response));
+                    }
+                }
+                None => (),
+            }
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,222 @@
+use std::cell::UnsafeCell;
+use std::fmt::Debug;
+use std::{marker::PhantomData, ptr::NonNull};
+
+use elkodon_bb_lock_free::mpmc::container::ContainerState;
+use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_cal::dynamic_storage::DynamicStorage;
+use elkodon_cal::{shared_memory::*, zero_copy_connection::*};
+
+use crate::node::PortRegistration;
+use crate::service::static_config::pipeline::StaticConfig;
+use crate::{message::Message, service, service::header::pipeline::Header, work_item::WorkItem};
+
+use super::details::producer_connections::{
+    Connection, ProducerConnectionFailure, ProducerConnections,
+};
+use super::port_identifiers::{UniqueConsumerId, UniqueProducerId};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ConsumerReceiveError {
+    ExceedsMaxBorrowedSamples,
+    ConnectionFailure(ProducerConnectionFailure),
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ConsumerCreateError {
+    ExceedsMaxSupportedConsumers,
+}
+
+/// Receives the samples a [`crate::port::producer::Producer`] assigned to it. Every sample is
+/// received by exactly one [`Consumer`].
+#[derive(Debug)]
+pub struct Consumer<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    // !MUST! be declared before the dynamic config guard, the connections have to be closed
+    // before the consumer is removed from the service so that the producers can take back and
+    // redistribute the samples which were not received
+    producer_connections: ProducerConnections<'global_config, Service>,
+    dynamic_config_guard: Option<UniqueIndex<'a>>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
+
+    producer_list_state: UnsafeCell<ContainerState<'a, UniqueProducerId>>,
+    _phantom_message_type: PhantomData<MessageType>,
+}
+
+impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageType: Debug>
+    Consumer<'a, 'global_config, Service, MessageType>
+{
+    pub(crate) fn new(
+        service: &'a Service,
+        static_config: &StaticConfig,
+    ) -> Result<Self, ConsumerCreateError> {
+        let msg = "Failed to create Consumer port";
+        let origin = "Consumer::new()";
+        let port_id = UniqueConsumerId::new();
+
+        let producer_list = &service.state().dynamic_storage.get().pipeline().producers;
+
+        let mut new_self = Self {
+            producer_connections: ProducerConnections::new(
+                producer_list.capacity(),
+                port_id,
+                service.state().global_config,
+                static_config,
+                service.state().static_config.access_settings(),
+            ),
+            producer_list_state: UnsafeCell::new(unsafe { producer_list.get_state() }),
+            dynamic_config_guard: None,
+            _node_registration: service.state().register_port(port_id.value()),
+            _phantom_message_type: PhantomData,
+        };
+
+        if let Err(e) = new_self.populate_producer_channels() {
+            warn!(from new_self, "The new consumer is unable to connect to every producer, caused by {:?}.", e);
+        }
+
+        // !MUST! be the last task otherwise a consumer is added to the dynamic config without
+        // the creation of all required channels
+        new_self.dynamic_config_guard = Some(
+            match service
+                .state()
+                .dynamic_storage
+                .get()
+                .pipeline()
+                .add_consumer_id(port_id)
+            {
+                Some(unique_index) => unique_index,
+                None => {
+                    fail!(from origin, with ConsumerCreateError::ExceedsMaxSupportedConsumers,
+                                "{} since it would exceed the maximum supported amount of consumers of {}.",
+                                msg, static_config.max_consumers);
+                }
+            },
+        );
+
+        Ok(new_self)
+    }
+
+    pub(crate) fn release_sample(&self, channel_id: usize, sample: *const u8) {
+        match self.producer_connections.get(channel_id) {
+            Some(c) => {
+                let distance = sample as usize - c.data_segment.allocator_data_start_address();
+                match c.receiver.release(PointerOffset::new(distance)) {
+                    Ok(()) => (),
+                    Err(ZeroCopyReleaseError::RetrieveBufferFull) => {
+                        fatal_panic!(from self, when c.receiver.release(PointerOffset::new(distance)),
+                                    "This should never happen! The producers retrieve channel is full and the sample cannot be returned.");
+                    }
+                }
+            }
+            None => {
+                warn!(from self, "Unable to release sample since the connection is broken. The sample will be discarded and has to be reclaimed manually by the producer.");
+            }
+        }
+    }
+
+    fn populate_producer_channels(&self) -> Result<(), ProducerConnectionFailure> {
+        let mut visited_indices = vec![];
+        visited_indices.resize(self.producer_connections.capacity(), None);
+
+        unsafe {
+            (*self.producer_list_state.get()).for_each(|index, producer_id| {
+                visited_indices[index as usize] = Some(*producer_id);
+            })
+        };
+
+        // update all connections
+        let mut result = Ok(());
+        for (i, index) in visited_indices.iter().enumerate() {
+            match index {
+                Some(producer_id) => {
+                    if let Err(e) = self.producer_connections.create(i, *producer_id) {
+                        warn!(from self, "Unable to establish connection to new producer {:?}.", producer_id);
+                        result = Err(e);
+                    }
+                }
+                None => self.producer_connections.remove(i),
+            }
+        }
+
+        result
+    }
+
+    fn receive_from_connection<'consumer>(
+        &'consumer self,
+        channel_id: usize,
+        connection: &mut Connection<'global_config, Service>,
+    ) -> Result<
+        Option<WorkItem<'a, 'consumer, 'global_config, Service, MessageType>>,
+        ConsumerReceiveError,
+    > {
+        let msg = "Unable to receive another sample";
+        match connection.receiver.receive() {
+            Ok(data) => match data {
+                None => Ok(None),
+                Some(relative_addr) => {
+                    let absolute_address = relative_addr.value()
+                        + connection.data_segment.allocator_data_start_address();
+                    Ok(Some(WorkItem {
+                        consumer: self,
+                        channel_id,
+                        ptr: unsafe {
+                            NonNull::new_unchecked(
+                                absolute_address as *mut Message<Header, MessageType>,
+                            )
+                        },
+                    }))
+                }
+            },
+            Err(ZeroCopyReceiveError::ReceiveWouldExceedMaxBorrowValue) => {
+                fail!(from self, with ConsumerReceiveError::ExceedsMaxBorrowedSamples,
+                    "{} since it would exceed the maximum {} of borrowed samples.",
+                    msg, connection.receiver.max_borrowed_samples());
+            }
+        }
+    }
+
+    pub fn receive<'consumer>(
+        &'consumer self,
+    ) -> Result<
+        Option<WorkItem<'a, 'consumer, 'global_config, Service, MessageType>>,
+        ConsumerReceiveError,
+    > {
+        if let Err(e) = self.update_connections() {
+            fail!(from self,
+                with ConsumerReceiveError::ConnectionFailure(e),
+                "Some samples are not being received since not all connections to producers could be established.");
+        }
+
+        for id in 0..self.producer_connections.len() {
+            match &mut self.producer_connections.get_mut(id) {
+                Some(ref mut connection) => {
+                    if let Some(sample) = self.receive_from_connection(id, connection)? {
+                        return Ok(Some(sample));
+                    }
+                }
+                None => (),
+            }
+        }
+
+        Ok(None)
+    }
+
+    pub fn update_connections(&self) -> Result<(), ProducerConnectionFailure> {
+        if unsafe { (*self.producer_list_state.get()).update() } {
+            fail!(from self, when self.populate_producer_channels(),
+                "Connections were updated only partially since at least one connection to a producer failed.");
+        }
+
+        Ok(())
+    }
+
+    pub fn number_of_producers(&self) -> usize {
+        self.producer_connections.number_of_producers()
+    }
+}
@@ -0,0 +1,117 @@
+//! Deadline monitoring of the [`crate::port::subscriber::Subscriber`], see
+//! [`crate::service::port_factory::subscriber::PortFactorySubscriber::deadline()`]. A deadline
+//! is missed for every full period in which a connected publisher did not send a sample. The
+//! time between two samples is measured with the timestamps of their headers and the time since
+//! the last sample with the local clock.
+
+use std::cell::Cell;
+use std::time::Duration;
+
+use elkodon_bb_log::fatal_panic;
+use elkodon_bb_posix::clock::Time;
+
+use crate::port::port_identifiers::UniquePublisherId;
+
+/// Reported by [`crate::port::subscriber::Subscriber::check_deadlines()`] for every publisher
+/// which missed deadlines since the last check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissedDeadline {
+    publisher_id: UniquePublisherId,
+    missed_deadlines: u64,
+    time_since_last_sample: Duration,
+}
+
+impl MissedDeadline {
+    pub fn publisher_id(&self) -> UniquePublisherId {
+        self.publisher_id
+    }
+
+    /// Returns the number of deadlines the publisher missed since the last check.
+    pub fn missed_deadlines(&self) -> u64 {
+        self.missed_deadlines
+    }
+
+    /// Returns the time which has passed since the publisher sent its last sample or since the
+    /// connection was established when it did not send a sample yet.
+    pub fn time_since_last_sample(&self) -> Duration {
+        self.time_since_last_sample
+    }
+}
+
+/// Tracks the missed deadlines of a single publisher connection.
+#[derive(Debug)]
+pub(crate) struct DeadlineMonitor {
+    last_sample: Cell<Duration>,
+    misses_since_last_sample: Cell<u64>,
+    unreported_misses: Cell<u64>,
+}
+
+impl DeadlineMonitor {
+    pub(crate) fn new() -> Self {
+        Self {
+            last_sample: Cell::new(now()),
+            misses_since_last_sample: Cell::new(0),
+            unreported_misses: Cell::new(0),
+        }
+    }
+
+    /// Counts the deadlines which were missed until the provided timestamp and returns the
+    /// number of newly missed deadlines.
+    pub(crate) fn update(&self, timestamp: Duration, deadline: Duration) -> u64 {
+        let gap = timestamp.saturating_sub(self.last_sample.get());
+        let misses = (gap.as_nanos() / deadline.as_nanos().max(1)) as u64;
+        let new_misses = misses.saturating_sub(self.misses_since_last_sample.get());
+
+        self.misses_since_last_sample
+            .set(self.misses_since_last_sample.get() + new_misses);
+        self.unreported_misses
+            .set(self.unreported_misses.get() + new_misses);
+        new_misses
+    }
+
+    /// Counts the deadlines which were missed until the sample with the provided header
+    /// timestamp was sent and starts the next period. Returns the number of newly missed
+    /// deadlines.
+    pub(crate) fn sample_received(&self, timestamp: Duration, deadline: Duration) -> u64 {
+        let new_misses = self.update(timestamp, deadline);
+
+        // samples of the history can be older than the connection
+        if timestamp > self.last_sample.get() {
+            self.last_sample.set(timestamp);
+            self.misses_since_last_sample.set(0);
+        }
+
+        new_misses
+    }
+
+    /// Returns the time at which the next deadline is missed unless a sample arrives.
+    pub(crate) fn next_miss(&self, deadline: Duration) -> Duration {
+        let periods = u32::try_from(self.misses_since_last_sample.get() + 1).unwrap_or(u32::MAX);
+        self.last_sample.get() + deadline.saturating_mul(periods)
+    }
+
+    /// Returns the deadlines which were missed since the last report, when there are any.
+    pub(crate) fn report(
+        &self,
+        publisher_id: UniquePublisherId,
+        timestamp: Duration,
+    ) -> Option<MissedDeadline> {
+        let missed_deadlines = self.unreported_misses.replace(0);
+        if missed_deadlines == 0 {
+            return None;
+        }
+
+        Some(MissedDeadline {
+            publisher_id,
+            missed_deadlines,
+            time_since_last_sample: timestamp.saturating_sub(self.last_sample.get()),
+        })
+    }
+}
+
+/// Returns the current time of the clock which is used for the timestamps of the sample headers.
+pub(crate) fn now() -> Duration {
+    fatal_panic!(from "deadline::now()", when Time::now(),
+        "This should never happen! Unable to acquire the current time.")
+    .as_duration()
+}
@@ -0,0 +1,75 @@
+use std::fmt::Debug;
+
+use elkodon_bb_lock_free::spmc::unrestricted_atomic::{UnrestrictedAtomic, UnrestrictedAtomicMgmt};
+use elkodon_cal::dynamic_storage::DynamicStorage;
+
+use crate::service;
+use crate::zero_copy_type::{TypeDescriptor, ZeroCopyType};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub(crate) enum EntryLookupFailure {
+    EntryDoesNotExist,
+    IncompatibleValueType,
+}
+
+/// Acquires the entry of the blackboard which corresponds to the provided key and verifies that
+/// it stores values of type `ValueType`. Returns the index of the entry together with the entry.
+pub(crate) fn lookup_entry<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug,
+    ValueType: Copy + ZeroCopyType,
+>(
+    service: &'a Service,
+    key: &KeyType,
+) -> Result<(usize, &'a UnrestrictedAtomic<ValueType>), EntryLookupFailure> {
+    let static_config = service.state().static_config.blackboard();
+    let entries_ptr = service
+        .state()
+        .dynamic_storage
+        .get()
+        .blackboard()
+        .entries_ptr();
+
+    for (i, entry) in static_config.entries.iter().enumerate() {
+        if unsafe { *(entries_ptr as *const KeyType).add(i) } != *key {
+            continue;
+        }
+
+        if entry.type_descriptor != TypeDescriptor::new::<ValueType>() {
+            return Err(EntryLookupFailure::IncompatibleValueType);
+        }
+
+        return Ok((i, unsafe {
+            &*(entries_ptr.add(entry.offset) as *const UnrestrictedAtomic<ValueType>)
+        }));
+    }
+
+    Err(EntryLookupFailure::EntryDoesNotExist)
+}
+
+/// Releases the producer of the entry with the index `entry` without knowing its value type.
+///
+/// # Safety
+///
+///  * the writer which acquired the producer must be dead
+///
+pub(crate) unsafe fn release_entry_producer<
+    'global_config,
+    Service: service::Details<'global_config>,
+>(
+    service: &Service,
+    entry: usize,
+) {
+    let offset = service.state().static_config.blackboard().entries[entry].offset;
+    let entries_ptr = service
+        .state()
+        .dynamic_storage
+        .get()
+        .blackboard()
+        .entries_ptr();
+
+    // the management part is always stored at the beginning of the UnrestrictedAtomic
+    (*(entries_ptr.add(offset) as *const UnrestrictedAtomicMgmt)).release_producer();
+}
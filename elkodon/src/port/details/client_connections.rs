@@ -0,0 +1,161 @@
+use std::cell::UnsafeCell;
+
+use crate::{
+    global_config,
+    port::{
+        client::{data_segment_config, data_segment_name},
+        port_identifiers::{UniqueClientId, UniqueServerId},
+    },
+    service::{self, access::AccessSettings, connection_config},
+    service::{connection_name, static_config::request_response::StaticConfig},
+};
+
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_log::fail;
+use elkodon_cal::named_concept::{NamedConceptBuilder, NamedConceptConfiguration};
+use elkodon_cal::{
+    shared_memory::SharedMemory,
+    shared_memory::{SharedMemoryBuilder, SharedMemoryOpenError},
+    shm_allocator::pool_allocator::PoolAllocator,
+    zero_copy_connection::*,
+};
+
+enum_gen! { ClientConnectionFailure
+  mapping:
+    ZeroCopyCreationError to FailedToEstablishConnection,
+    SharedMemoryOpenError to UnableToMapClientsDataSegment
+}
+
+#[derive(Debug)]
+pub(crate) struct Connection<'global_config, Service: service::Details<'global_config>> {
+    pub(crate) client_id: UniqueClientId,
+    pub(crate) request_receiver:
+        <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Receiver,
+    pub(crate) response_sender:
+        <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Sender,
+    pub(crate) data_segment: Service::SharedMemory,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    Connection<'global_config, Service>
+{
+    fn new(
+        this: &ClientConnections<'global_config, Service>,
+        client_id: UniqueClientId,
+    ) -> Result<Self, ClientConnectionFailure> {
+        let msg = format!(
+            "Unable to establish connection to client {:?} from server {:?}.",
+            client_id, this.server_id
+        );
+
+        let request_receiver = fail!(from this,
+                        when <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::
+                            Builder::new( &connection_name(client_id.0, this.server_id.0))
+                                    .config(&connection_config::<Service>(this.global_config).access_settings(&this.access_settings))
+                                    .buffer_size(this.static_config.request_buffer_size)
+                                    .receiver_max_borrowed_samples(this.static_config.server_max_borrowed_requests)
+                                    .enable_safe_overflow(false)
+                                    .create_receiver(),
+                        "{} since the request connection could not be established.", msg);
+
+        let response_sender = fail!(from this,
+                        when <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::
+                            Builder::new( &connection_name(this.server_id.0, client_id.0))
+                                    .config(&connection_config::<Service>(this.global_config).access_settings(&this.access_settings))
+                                    .buffer_size(this.static_config.response_buffer_size)
+                                    .receiver_max_borrowed_samples(this.static_config.client_max_borrowed_responses)
+                                    .enable_safe_overflow(false)
+                                    .create_sender(),
+                        "{} since the response connection could not be established.", msg);
+
+        let data_segment = fail!(from this,
+                            when <Service::SharedMemory as SharedMemory<PoolAllocator>>::
+                                Builder::new(&data_segment_name(client_id))
+                                .config(&data_segment_config::<Service>(this.global_config))
+                                .open(),
+                            "{} since the clients data segment could not be mapped into the process.", msg);
+
+        Ok(Self {
+            client_id,
+            request_receiver,
+            response_sender,
+            data_segment,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ClientConnections<'global_config, Service: service::Details<'global_config>> {
+    connections: Vec<UnsafeCell<Option<Connection<'global_config, Service>>>>,
+    server_id: UniqueServerId,
+    global_config: &'global_config global_config::Entries,
+    static_config: StaticConfig,
+    access_settings: AccessSettings,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    ClientConnections<'global_config, Service>
+{
+    pub(crate) fn new(
+        capacity: usize,
+        server_id: UniqueServerId,
+        global_config: &'global_config global_config::Entries,
+        static_config: &StaticConfig,
+        access_settings: AccessSettings,
+    ) -> Self {
+        Self {
+            connections: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
+            server_id,
+            global_config,
+            static_config: static_config.clone(),
+            access_settings,
+        }
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &Option<Connection<'global_config, Service>> {
+        unsafe { &*self.connections[index].get() }
+    }
+
+    // only used internally as convinience function
+    #[allow(clippy::mut_from_ref)]
+    fn get_mut(&self, index: usize) -> &mut Option<Connection<'global_config, Service>> {
+        #[deny(clippy::mut_from_ref)]
+        unsafe {
+            &mut *self.connections[index].get()
+        }
+    }
+
+    pub(crate) fn create(
+        &self,
+        index: usize,
+        client_id: UniqueClientId,
+    ) -> Result<(), ClientConnectionFailure> {
+        if self.get(index).is_none() {
+            *self.get_mut(index) = Some(Connection::new(self, client_id)?);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn number_of_clients(&self) -> usize {
+        self.connections
+            .iter()
+            .filter(|&connection| match unsafe { &*connection.get() } {
+                None => false,
+                Some(c) => c.response_sender.is_connected(),
+            })
+            .count()
+    }
+
+    pub(crate) fn remove(&self, index: usize) {
+        *self.get_mut(index) = None;
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.connections.capacity()
+    }
+}
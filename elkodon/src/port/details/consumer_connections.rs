@@ -0,0 +1,149 @@
+use std::cell::{RefCell, UnsafeCell};
+
+use elkodon_bb_log::fail;
+use elkodon_cal::named_concept::{NamedConceptBuilder, NamedConceptConfiguration};
+use elkodon_cal::zero_copy_connection::{
+    ZeroCopyConnection, ZeroCopyConnectionBuilder, ZeroCopyCreationError, ZeroCopyPortDetails,
+};
+
+use crate::service::access::AccessSettings;
+use crate::service::connection_config;
+use crate::{
+    global_config,
+    port::port_identifiers::{UniqueConsumerId, UniqueProducerId},
+    service,
+    service::{connection_name, static_config::pipeline::StaticConfig},
+};
+
+#[derive(Debug)]
+pub(crate) struct Connection<'global_config, Service: service::Details<'global_config>> {
+    pub(crate) sender:
+        <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Sender,
+    pub(crate) consumer_id: UniqueConsumerId,
+    // offsets of all samples which were delivered via this connection and not yet returned, they
+    // are released when the consumer disappears without returning them
+    delivered_samples: RefCell<Vec<usize>>,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    Connection<'global_config, Service>
+{
+    fn new(
+        this: &ConsumerConnections<'global_config, Service>,
+        consumer_id: UniqueConsumerId,
+    ) -> Result<Self, ZeroCopyCreationError> {
+        let sender = fail!(from this, when <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::
+                        Builder::new( &connection_name(this.port_id.0, consumer_id.0))
+                                .config(&connection_config::<Service>(this.global_config).access_settings(&this.access_settings))
+                                .buffer_size(this.static_config.consumer_buffer_size)
+                                .receiver_max_borrowed_samples(this.static_config.consumer_max_borrowed_samples)
+                                .enable_safe_overflow(false)
+                                .create_sender(),
+                        "Unable to establish connection to consumer {:?} from producer {:?}.",
+                        consumer_id, this.port_id);
+
+        Ok(Self {
+            sender,
+            consumer_id,
+            delivered_samples: RefCell::new(vec![]),
+        })
+    }
+
+    pub(crate) fn samples_in_flight(&self) -> usize {
+        self.delivered_samples.borrow().len()
+    }
+
+    pub(crate) fn add_delivered_sample(&self, offset: usize) {
+        self.delivered_samples.borrow_mut().push(offset);
+    }
+
+    pub(crate) fn remove_delivered_sample(&self, offset: usize) {
+        let mut delivered_samples = self.delivered_samples.borrow_mut();
+        if let Some(position) = delivered_samples.iter().position(|v| *v == offset) {
+            delivered_samples.swap_remove(position);
+        }
+    }
+
+    pub(crate) fn take_delivered_samples(&self) -> Vec<usize> {
+        std::mem::take(&mut *self.delivered_samples.borrow_mut())
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ConsumerConnections<'global_config, Service: service::Details<'global_config>> {
+    connections: Vec<UnsafeCell<Option<Connection<'global_config, Service>>>>,
+    port_id: UniqueProducerId,
+    global_config: &'global_config global_config::Entries,
+    static_config: StaticConfig,
+    access_settings: AccessSettings,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    ConsumerConnections<'global_config, Service>
+{
+    pub(crate) fn new(
+        capacity: usize,
+        global_config: &'global_config global_config::Entries,
+        port_id: UniqueProducerId,
+        static_config: &StaticConfig,
+        access_settings: AccessSettings,
+    ) -> Self {
+        Self {
+            connections: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
+            global_config,
+            port_id,
+            static_config: static_config.clone(),
+            access_settings,
+        }
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &Option<Connection<'global_config, Service>> {
+        unsafe { &(*self.connections[index].get()) }
+    }
+
+    // only used internally as convinience function
+    #[allow(clippy::mut_from_ref)]
+    fn get_mut(&self, index: usize) -> &mut Option<Connection<'global_config, Service>> {
+        #[deny(clippy::mut_from_ref)]
+        unsafe {
+            &mut (*self.connections[index].get())
+        }
+    }
+
+    pub(crate) fn remove(&self, index: usize) {
+        *self.get_mut(index) = None
+    }
+
+    pub(crate) fn create(
+        &self,
+        index: usize,
+        consumer_id: UniqueConsumerId,
+    ) -> Result<(), ZeroCopyCreationError> {
+        if self.get(index).is_none() {
+            *self.get_mut(index) = Some(Connection::new(self, consumer_id)?);
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn number_of_consumers(&self) -> usize {
+        self.connections
+            .iter()
+            .filter(|&connection| {
+                let connection = unsafe { &*connection.get() };
+                match connection {
+                    None => false,
+                    Some(c) => c.sender.is_connected(),
+                }
+            })
+            .count()
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.connections.capacity()
+    }
+}
@@ -1,2 +1,7 @@
+pub(crate) mod blackboard_entries;
+pub(crate) mod client_connections;
+pub(crate) mod consumer_connections;
+pub(crate) mod producer_connections;
 pub(crate) mod publisher_connections;
+pub(crate) mod server_connections;
 pub(crate) mod subscriber_connections;
@@ -0,0 +1,146 @@
+use std::cell::UnsafeCell;
+
+use crate::{
+    global_config,
+    port::{
+        port_identifiers::{UniqueConsumerId, UniqueProducerId},
+        producer::{data_segment_config, data_segment_name},
+    },
+    service::{self, access::AccessSettings, connection_config},
+    service::{connection_name, static_config::pipeline::StaticConfig},
+};
+
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_log::fail;
+use elkodon_cal::named_concept::{NamedConceptBuilder, NamedConceptConfiguration};
+use elkodon_cal::{
+    shared_memory::SharedMemory,
+    shared_memory::{SharedMemoryBuilder, SharedMemoryOpenError},
+    shm_allocator::pool_allocator::PoolAllocator,
+    zero_copy_connection::*,
+};
+
+enum_gen! { ProducerConnectionFailure
+  mapping:
+    ZeroCopyCreationError to FailedToEstablishConnection,
+    SharedMemoryOpenError to UnableToMapProducersDataSegment
+}
+
+#[derive(Debug)]
+pub(crate) struct Connection<'global_config, Service: service::Details<'global_config>> {
+    pub(crate) receiver:
+        <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Receiver,
+    pub(crate) data_segment: Service::SharedMemory,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    Connection<'global_config, Service>
+{
+    fn new(
+        this: &ProducerConnections<'global_config, Service>,
+        producer_id: UniqueProducerId,
+    ) -> Result<Self, ProducerConnectionFailure> {
+        let msg = format!(
+            "Unable to establish connection to producer {:?} from consumer {:?}.",
+            producer_id, this.consumer_id
+        );
+
+        let receiver = fail!(from this,
+                        when <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::
+                            Builder::new( &connection_name(producer_id.0, this.consumer_id.0))
+                                    .config(&connection_config::<Service>(this.global_config).access_settings(&this.access_settings))
+                                    .buffer_size(this.static_config.consumer_buffer_size)
+                                    .receiver_max_borrowed_samples(this.static_config.consumer_max_borrowed_samples)
+                                    .enable_safe_overflow(false)
+                                    .create_receiver(),
+                        "{} since the zero copy connection could not be established.", msg);
+
+        let data_segment = fail!(from this,
+                            when <Service::SharedMemory as SharedMemory<PoolAllocator>>::
+                                Builder::new(&data_segment_name(producer_id))
+                                .config(&data_segment_config::<Service>(this.global_config))
+                                .open(),
+                            "{} since the producers data segment could not be mapped into the process.", msg);
+
+        Ok(Self {
+            receiver,
+            data_segment,
+        })
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct ProducerConnections<'global_config, Service: service::Details<'global_config>> {
+    connections: Vec<UnsafeCell<Option<Connection<'global_config, Service>>>>,
+    consumer_id: UniqueConsumerId,
+    global_config: &'global_config global_config::Entries,
+    static_config: StaticConfig,
+    access_settings: AccessSettings,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    ProducerConnections<'global_config, Service>
+{
+    pub(crate) fn new(
+        capacity: usize,
+        consumer_id: UniqueConsumerId,
+        global_config: &'global_config global_config::Entries,
+        static_config: &StaticConfig,
+        access_settings: AccessSettings,
+    ) -> Self {
+        Self {
+            connections: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
+            consumer_id,
+            global_config,
+            static_config: static_config.clone(),
+            access_settings,
+        }
+    }
+
+    pub(crate) fn get(&self, index: usize) -> &Option<Connection<'global_config, Service>> {
+        unsafe { &*self.connections[index].get() }
+    }
+
+    // only used internally as convinience function
+    #[allow(clippy::mut_from_ref)]
+    pub(crate) fn get_mut(&self, index: usize) -> &mut Option<Connection<'global_config, Service>> {
+        #[deny(clippy::mut_from_ref)]
+        unsafe {
+            &mut *self.connections[index].get()
+        }
+    }
+
+    pub(crate) fn create(
+        &self,
+        index: usize,
+        producer_id: UniqueProducerId,
+    ) -> Result<(), ProducerConnectionFailure> {
+        if self.get(index).is_none() {
+            *self.get_mut(index) = Some(Connection::new(self, producer_id)?);
+        }
+
+        Ok(())
+    }
+
+    pub fn number_of_producers(&self) -> usize {
+        self.connections
+            .iter()
+            .filter(|&connection| match unsafe { &*connection.get() } {
+                None => false,
+                Some(c) => c.receiver.is_connected(),
+            })
+            .count()
+    }
+
+    pub(crate) fn remove(&self, index: usize) {
+        *self.get_mut(index) = None;
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub(crate) fn capacity(&self) -> usize {
+        self.connections.capacity()
+    }
+}
@@ -1,16 +1,19 @@
-use std::cell::UnsafeCell;
+use std::cell::{Cell, UnsafeCell};
 
 use crate::{
     global_config,
     port::{
+        deadline::DeadlineMonitor,
+        liveliness::LivelinessState,
         port_identifiers::{UniquePublisherId, UniqueSubscriberId},
         publisher::{data_segment_config, data_segment_name},
+        sample_loss::SequenceTracker,
     },
-    service::{self, connection_config},
+    service::{self, access::AccessSettings, connection_config},
     service::{connection_name, static_config::publish_subscribe::StaticConfig},
 };
 
-use elkodon_cal::named_concept::NamedConceptBuilder;
+use elkodon_cal::named_concept::{NamedConceptBuilder, NamedConceptConfiguration};
 use elkodon_cal::{
     shared_memory::SharedMemory,
     shared_memory::{SharedMemoryBuilder, SharedMemoryOpenError},
@@ -31,6 +34,11 @@ pub(crate) struct Connection<'global_config, Service: service::Details<'global_c
     pub(crate) receiver:
         <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Receiver,
     pub(crate) data_segment: Service::SharedMemory,
+    pub(crate) publisher_id: UniquePublisherId,
+    // the state which was reported last by the subscriber
+    pub(crate) liveliness: Cell<LivelinessState>,
+    pub(crate) deadline_monitor: DeadlineMonitor,
+    pub(crate) sequence_tracker: SequenceTracker,
 }
 
 impl<'global_config, Service: service::Details<'global_config>>
@@ -47,8 +55,8 @@ impl<'global_config, Service: service::Details<'global_config>>
 
         let receiver = fail!(from this,
                         when <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::
-                            Builder::new( &connection_name(publisher_id, this.subscriber_id))
-                                    .config(&connection_config::<Service>(this.global_config))
+                            Builder::new( &connection_name(publisher_id.0, this.subscriber_id.0))
+                                    .config(&connection_config::<Service>(this.global_config).access_settings(&this.access_settings))
                                     .buffer_size(this.static_config.subscriber_buffer_size)
                                     .receiver_max_borrowed_samples(this.static_config.subscriber_max_borrowed_samples)
                                     .enable_safe_overflow(this.static_config.enable_safe_overflow)
@@ -65,6 +73,10 @@ impl<'global_config, Service: service::Details<'global_config>>
         Ok(Self {
             receiver,
             data_segment,
+            publisher_id,
+            liveliness: Cell::new(LivelinessState::Alive),
+            deadline_monitor: DeadlineMonitor::new(),
+            sequence_tracker: SequenceTracker::default(),
         })
     }
 }
@@ -74,6 +86,7 @@ pub(crate) struct PublisherConnections<'global_config, Service: service::Details
     subscriber_id: UniqueSubscriberId,
     global_config: &'global_config global_config::Entries,
     static_config: StaticConfig,
+    access_settings: AccessSettings,
 }
 
 impl<'global_config, Service: service::Details<'global_config>>
@@ -84,12 +97,14 @@ impl<'global_config, Service: service::Details<'global_config>>
         subscriber_id: UniqueSubscriberId,
         global_config: &'global_config global_config::Entries,
         static_config: &StaticConfig,
+        access_settings: AccessSettings,
     ) -> Self {
         Self {
             connections: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
             subscriber_id,
             global_config,
             static_config: static_config.clone(),
+            access_settings,
         }
     }
 
@@ -115,10 +130,16 @@ impl<'global_config, Service: service::Details<'global_config>>
         index: usize,
         publisher_id: UniquePublisherId,
     ) -> Result<(), ConnectionFailure> {
-        if self.get(index).is_none() {
-            *self.get_mut(index) = Some(Connection::new(self, publisher_id)?);
+        if let Some(connection) = self.get(index) {
+            if connection.publisher_id == publisher_id {
+                return Ok(());
+            }
+
+            // the publisher was replaced by another one in the meantime
+            self.remove(index);
         }
 
+        *self.get_mut(index) = Some(Connection::new(self, publisher_id)?);
         Ok(())
     }
 
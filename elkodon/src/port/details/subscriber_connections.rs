@@ -1,12 +1,14 @@
-use std::cell::UnsafeCell;
+use std::cell::{Cell, RefCell, UnsafeCell};
 
-use elkodon_cal::named_concept::NamedConceptBuilder;
+use elkodon_cal::event::{Event, Notifier, NotifierBuilder};
+use elkodon_cal::named_concept::{NamedConceptBuilder, NamedConceptConfiguration};
 use elkodon_cal::zero_copy_connection::{
     ZeroCopyConnection, ZeroCopyConnectionBuilder, ZeroCopyCreationError, ZeroCopyPortDetails,
 };
-use elkodon_bb_log::fail;
+use elkodon_bb_log::{fail, warn};
 
-use crate::service::connection_config;
+use crate::service::access::AccessSettings;
+use crate::service::{connection_config, subscriber_event_concept_name};
 use crate::{
     global_config,
     port::port_identifiers::{UniquePublisherId, UniqueSubscriberId},
@@ -14,10 +16,23 @@ use crate::{
     service::{connection_name, static_config::publish_subscribe::StaticConfig},
 };
 
+// the subscriber only waits for the notification, the trigger id itself carries no information
+const NEW_SAMPLE_TRIGGER_ID: u64 = 0;
+
 #[derive(Debug)]
 pub(crate) struct Connection<'global_config, Service: service::Details<'global_config>> {
     pub(crate) sender:
         <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Sender,
+    pub(crate) subscriber_id: UniqueSubscriberId,
+    notifier: Option<<Service::Event as Event<u64>>::Notifier>,
+    // offsets of all samples which were delivered via this connection and not yet returned, they
+    // are released when the subscriber disappears without returning them
+    delivered_samples: RefCell<Vec<usize>>,
+    // the id of the last history request of the subscriber which was served
+    pub(crate) served_history_request: Cell<u64>,
+    // set when the degration callback disconnected the subscriber, the connection is kept until
+    // the subscriber is gone so that the samples it still holds can be returned
+    is_delivery_stopped: Cell<bool>,
 }
 
 impl<'global_config, Service: service::Details<'global_config>>
@@ -28,8 +43,8 @@ impl<'global_config, Service: service::Details<'global_config>>
         subscriber_id: UniqueSubscriberId,
     ) -> Result<Self, ZeroCopyCreationError> {
         let sender = fail!(from this, when <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::
-                        Builder::new( &connection_name(this.port_id, subscriber_id))
-                                .config(&connection_config::<Service>(this.global_config))
+                        Builder::new( &connection_name(this.port_id.0, subscriber_id.0))
+                                .config(&connection_config::<Service>(this.global_config).access_settings(&this.access_settings))
                                 .buffer_size(this.static_config.subscriber_buffer_size)
                                 .receiver_max_borrowed_samples(this.static_config.subscriber_max_borrowed_samples)
                                 .enable_safe_overflow(this.static_config.enable_safe_overflow)
@@ -37,7 +52,64 @@ impl<'global_config, Service: service::Details<'global_config>>
                         "Unable to establish connection to subscriber {:?} from publisher {:?}.",
                         subscriber_id, this.port_id);
 
-        Ok(Self { sender })
+        let notifier = if this.static_config.enable_events {
+            match <Service::Event as Event<u64>>::NotifierBuilder::new(
+                &subscriber_event_concept_name(&subscriber_id),
+            )
+            .open()
+            {
+                Ok(notifier) => Some(notifier),
+                Err(e) => {
+                    warn!(from this, "Unable to open the event of subscriber {:?} ({:?}), the subscriber will not be notified about new samples.", subscriber_id, e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        Ok(Self {
+            sender,
+            subscriber_id,
+            notifier,
+            delivered_samples: RefCell::new(vec![]),
+            served_history_request: Cell::new(0),
+            is_delivery_stopped: Cell::new(false),
+        })
+    }
+
+    /// Wakes up the subscriber when the service has events enabled.
+    pub(crate) fn notify(&self) {
+        if let Some(notifier) = &self.notifier {
+            if let Err(e) = notifier.notify(NEW_SAMPLE_TRIGGER_ID) {
+                warn!(from self, "Unable to notify subscriber {:?} about a new sample ({:?}).", self.subscriber_id, e);
+            }
+        }
+    }
+
+    pub(crate) fn add_delivered_sample(&self, offset: usize) {
+        self.delivered_samples.borrow_mut().push(offset);
+    }
+
+    pub(crate) fn remove_delivered_sample(&self, offset: usize) {
+        let mut delivered_samples = self.delivered_samples.borrow_mut();
+        if let Some(position) = delivered_samples.iter().position(|v| *v == offset) {
+            delivered_samples.swap_remove(position);
+        }
+    }
+
+    pub(crate) fn take_delivered_samples(&self) -> Vec<usize> {
+        std::mem::take(&mut *self.delivered_samples.borrow_mut())
+    }
+
+    /// No sample is delivered via the connection anymore but the subscriber can still return
+    /// the samples it has received.
+    pub(crate) fn stop_delivery(&self) {
+        self.is_delivery_stopped.set(true);
+    }
+
+    pub(crate) fn is_delivery_stopped(&self) -> bool {
+        self.is_delivery_stopped.get()
     }
 }
 
@@ -47,6 +119,7 @@ pub(crate) struct SubscriberConnections<'global_config, Service: service::Detail
     port_id: UniquePublisherId,
     global_config: &'global_config global_config::Entries,
     static_config: StaticConfig,
+    access_settings: AccessSettings,
 }
 
 impl<'global_config, Service: service::Details<'global_config>>
@@ -57,12 +130,14 @@ impl<'global_config, Service: service::Details<'global_config>>
         global_config: &'global_config global_config::Entries,
         port_id: UniquePublisherId,
         static_config: &StaticConfig,
+        access_settings: AccessSettings,
     ) -> Self {
         Self {
             connections: (0..capacity).map(|_| UnsafeCell::new(None)).collect(),
             global_config,
             port_id,
             static_config: static_config.clone(),
+            access_settings,
         }
     }
 
@@ -103,7 +178,7 @@ impl<'global_config, Service: service::Details<'global_config>>
                 let connection = unsafe { &*connection.get() };
                 match connection {
                     None => false,
-                    Some(c) => c.sender.is_connected(),
+                    Some(c) => c.sender.is_connected() && !c.is_delivery_stopped(),
                 }
             })
             .count()
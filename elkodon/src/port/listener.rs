@@ -4,7 +4,9 @@ use elkodon_cal::named_concept::NamedConceptBuilder;
 use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
 use elkodon_bb_log::fail;
 
-use crate::service::event_concept_name;
+use crate::node::PortRegistration;
+use crate::service::port_factory::event::remove_dead_ports;
+use crate::service::{event_concept_name, event_config};
 use crate::{port::port_identifiers::UniqueListenerId, service};
 use std::{marker::PhantomData, time::Duration};
 
@@ -17,6 +19,8 @@ pub enum ListenerCreateError {
 #[derive(Debug)]
 pub struct Listener<'a, 'global_config: 'a, Service: service::Details<'global_config>> {
     _dynamic_config_guard: Option<UniqueIndex<'a>>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
     listener: <Service::Event as elkodon_cal::event::Event<u64>>::Listener,
     _phantom_a: PhantomData<&'a Service>,
     _phantom_b: PhantomData<&'global_config ()>,
@@ -32,12 +36,15 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>>
 
         let event_name = event_concept_name(&port_id);
         let listener = fail!(from origin,
-                             when <Service::Event as elkodon_cal::event::Event<u64>>::ListenerBuilder::new(&event_name).create(),
+                             when <Service::Event as elkodon_cal::event::Event<u64>>::ListenerBuilder::new(&event_name)
+                                .config(&event_config::<Service>(&service.state().static_config))
+                                .create(),
                              with ListenerCreateError::ResourceCreationFailed,
                              "{} since the underlying event concept \"{}\" could not be created.", msg, event_name);
 
         let mut new_self = Self {
             _dynamic_config_guard: None,
+            _node_registration: service.state().register_port(port_id.value()),
             listener,
             _phantom_a: PhantomData,
             _phantom_b: PhantomData,
@@ -45,26 +52,31 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>>
 
         // !MUST! be the last task otherwise a listener is added to the dynamic config without
         // the creation of all required channels
-        new_self._dynamic_config_guard = Some(
-            match service
-                .state()
-                .dynamic_storage
-                .get()
-                .event()
-                .add_listener_id(port_id)
-            {
-                Some(unique_index) => unique_index,
-                None => {
-                    fail!(from origin, with ListenerCreateError::ExceedsMaxSupportedListeners,
-                                 "{} since it would exceed the maximum supported amount of listeners of {}.",
-                                 msg, service.state().static_config.event().max_listeners);
-                }
-            },
-        );
+        let dynamic_config = service.state().dynamic_storage.get().event();
+        let mut unique_index = dynamic_config.add_listener_id(port_id);
+        // the slots may still be occupied by ports of dead processes
+        if unique_index.is_none() && remove_dead_ports(service) > 0 {
+            unique_index = dynamic_config.add_listener_id(port_id);
+        }
+
+        new_self._dynamic_config_guard = Some(match unique_index {
+            Some(unique_index) => unique_index,
+            None => {
+                fail!(from origin, with ListenerCreateError::ExceedsMaxSupportedListeners,
+                             "{} since it would exceed the maximum supported amount of listeners of {}.",
+                             msg, service.state().static_config.event().max_listeners);
+            }
+        });
 
         Ok(new_self)
     }
 
+    pub(crate) fn event_listener(
+        &self,
+    ) -> &<Service::Event as elkodon_cal::event::Event<u64>>::Listener {
+        &self.listener
+    }
+
     pub fn try_wait<F: FnMut(u64) -> bool>(
         &self,
         mut notification_received_callback: F,
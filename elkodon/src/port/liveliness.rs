@@ -0,0 +1,68 @@
+//! The liveliness contract of a publish-subscribe service, see
+//! [`crate::service::builder::publish_subscribe::Builder::max_heartbeat_interval()`]. Every
+//! publisher announces its liveliness with a heartbeat in the dynamic service config, either on
+//! every send or explicitly with [`crate::port::publisher::Publisher::assert_liveliness()`]. A
+//! publisher whose last heartbeat is older than the max heartbeat interval is considered as hung
+//! and the subscribers report the violation in
+//! [`crate::port::subscriber::Subscriber::check_liveliness()`].
+
+use std::time::Duration;
+
+use elkodon_bb_log::fatal_panic;
+use elkodon_bb_posix::clock::{ClockType, Time};
+
+use crate::port::port_identifiers::UniquePublisherId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LivelinessState {
+    /// The publisher announced its liveliness within the max heartbeat interval.
+    Alive,
+    /// The last heartbeat of the publisher is older than the max heartbeat interval.
+    Violated,
+}
+
+/// Reported by the subscriber whenever the [`LivelinessState`] of a connected publisher changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LivelinessChange {
+    publisher_id: UniquePublisherId,
+    state: LivelinessState,
+    time_since_last_heartbeat: Duration,
+}
+
+impl LivelinessChange {
+    pub(crate) fn new(
+        publisher_id: UniquePublisherId,
+        state: LivelinessState,
+        time_since_last_heartbeat: Duration,
+    ) -> Self {
+        Self {
+            publisher_id,
+            state,
+            time_since_last_heartbeat,
+        }
+    }
+
+    pub fn publisher_id(&self) -> UniquePublisherId {
+        self.publisher_id
+    }
+
+    pub fn state(&self) -> LivelinessState {
+        self.state
+    }
+
+    /// Returns the time which has passed between the last heartbeat of the publisher and the
+    /// detection of the change.
+    pub fn time_since_last_heartbeat(&self) -> Duration {
+        self.time_since_last_heartbeat
+    }
+}
+
+/// Returns the current heartbeat timestamp. The monotonic clock is used since it is shared by
+/// all processes and does not jump when the system time is adjusted.
+pub(crate) fn heartbeat_timestamp() -> u64 {
+    fatal_panic!(from "liveliness::heartbeat_timestamp()",
+        when Time::now_with_clock(ClockType::Monotonic),
+        "This should never happen! Unable to acquire the time of the monotonic clock.")
+    .as_duration()
+    .as_nanos() as u64
+}
@@ -2,12 +2,22 @@ use std::fmt::Debug;
 
 use tiny_fn::tiny_fn;
 
+pub mod client;
+pub mod consumer;
+pub mod deadline;
 pub(crate) mod details;
 pub mod listener;
+pub mod liveliness;
 pub mod notifier;
 pub mod port_identifiers;
+pub mod producer;
 pub mod publisher;
+pub mod reader;
+pub mod sample_loss;
+pub mod server;
+pub mod statistics;
 pub mod subscriber;
+pub mod writer;
 
 use crate::port::port_identifiers::*;
 use crate::service;
@@ -28,3 +38,26 @@ impl<'a> Debug for DegrationCallback<'a> {
         write!(f, "")
     }
 }
+
+/// Decides how a [`publisher::Publisher`] continues with a subscriber whose buffer stayed full
+/// for the whole timeout of
+/// [`crate::service::port_factory::publisher::UnableToDeliverStrategy::BlockWithTimeout`]. The
+/// sample is discarded for this subscriber in every case.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum DeliveryTimeoutAction {
+    Ignore,
+    Warn,
+    /// The subscriber receives no more samples from the publisher, see
+    /// [`subscriber::Subscriber::number_of_disconnections()`].
+    Disconnect,
+}
+
+tiny_fn! {
+    pub struct DeliveryTimeoutCallback = Fn(service: service::static_config::StaticConfig, publisher_id: UniquePublisherId, subscriber_id: UniqueSubscriberId) -> DeliveryTimeoutAction;
+}
+
+impl<'a> Debug for DeliveryTimeoutCallback<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "")
+    }
+}
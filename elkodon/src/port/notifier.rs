@@ -1,6 +1,7 @@
 use crate::{
+    node::PortRegistration,
     port::port_identifiers::UniqueNotifierId,
-    service::{self, event_concept_name},
+    service::{self, event_concept_name, port_factory::event::remove_dead_ports},
 };
 use elkodon_cal::named_concept::NamedConceptBuilder;
 use elkodon_cal::{dynamic_storage::DynamicStorage, event::NotifierBuilder};
@@ -78,6 +79,8 @@ pub struct Notifier<'a, 'global_config: 'a, Service: service::Details<'global_co
     listener_list_state: UnsafeCell<ContainerState<'a, UniqueListenerId>>,
     default_trigger_id: u64,
     _dynamic_config_guard: Option<UniqueIndex<'a>>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
     _phantom_a: PhantomData<&'a Service>,
     _phantom_b: PhantomData<&'global_config ()>,
 }
@@ -100,19 +103,21 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>>
             default_trigger_id,
             listener_list_state: unsafe { UnsafeCell::new(listener_list.get_state()) },
             _dynamic_config_guard: None,
+            _node_registration: service.state().register_port(port_id.value()),
             _phantom_a: PhantomData,
             _phantom_b: PhantomData,
         };
 
         // !MUST! be the last task otherwise a publisher is added to the dynamic config without the
         // creation of all required resources
-        let _dynamic_config_guard = match service
-            .state()
-            .dynamic_storage
-            .get()
-            .event()
-            .add_notifier_id(port_id)
-        {
+        let dynamic_config = service.state().dynamic_storage.get().event();
+        let mut unique_index = dynamic_config.add_notifier_id(port_id);
+        // the slots may still be occupied by ports of dead processes
+        if unique_index.is_none() && remove_dead_ports(service) > 0 {
+            unique_index = dynamic_config.add_notifier_id(port_id);
+        }
+
+        let _dynamic_config_guard = match unique_index {
             Some(unique_index) => unique_index,
             None => {
                 fail!(from origin, with NotifierCreateError::ExceedsMaxSupportedNotifiers,
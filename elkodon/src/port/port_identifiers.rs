@@ -1,4 +1,5 @@
 use elkodon_bb_log::fatal_panic;
+use elkodon_bb_posix::process::Process;
 use elkodon_bb_posix::unique_system_id::UniqueSystemId;
 
 macro_rules! generate_id {
@@ -19,6 +20,16 @@ macro_rules! generate_id {
             pub fn new() -> Self {
                 Self::default()
             }
+
+            /// Returns the system-wide unique value of the id.
+            pub fn value(&self) -> u128 {
+                self.0.value()
+            }
+
+            /// Returns true when the process which created the port is still alive.
+            pub fn is_owner_alive(&self) -> bool {
+                Process::from_pid(self.0.pid()).is_alive()
+            }
         }
     };
 }
@@ -27,3 +38,8 @@ generate_id! { UniquePublisherId }
 generate_id! { UniqueSubscriberId }
 generate_id! { UniqueNotifierId }
 generate_id! { UniqueListenerId }
+generate_id! { UniqueClientId }
+generate_id! { UniqueServerId }
+generate_id! { UniqueWriterId }
+generate_id! { UniqueProducerId }
+generate_id! { UniqueConsumerId }
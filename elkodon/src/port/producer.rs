@@ -0,0 +1,546 @@
+use std::cell::UnsafeCell;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::{alloc::Layout, cell::Cell, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+use super::details::consumer_connections::*;
+use super::port_identifiers::{UniqueConsumerId, UniqueProducerId};
+use super::publisher::LoanError;
+use crate::global_config;
+use crate::message::Message;
+use crate::node::PortRegistration;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::header::pipeline::Header;
+use crate::service::port_factory::producer::{DistributionStrategy, LocalProducerConfig};
+use crate::service::static_config::pipeline;
+use crate::work_item_mut::WorkItemMut;
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_elementary::allocator::AllocationError;
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_lock_free::mpmc::container::ContainerState;
+use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_system_types::file_name::FileName;
+use elkodon_cal::dynamic_storage::DynamicStorage;
+use elkodon_cal::named_concept::{
+    NamedConceptBuilder, NamedConceptConfiguration, NamedConceptMgmt,
+};
+use elkodon_cal::shared_memory::{SharedMemory, SharedMemoryBuilder, SharedMemoryCreateError};
+use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
+use elkodon_cal::shm_allocator::{self, PointerOffset, ShmAllocationError};
+use elkodon_cal::zero_copy_connection::{ZeroCopyCreationError, ZeroCopySendError, ZeroCopySender};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ProducerCreateError {
+    ExceedsMaxSupportedProducers,
+    UnableToCreateDataSegment,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum ProducerSendError {
+    NoConsumerConnected,
+    AllConsumerBuffersAreFull,
+    ConnectionError(ZeroCopyCreationError),
+}
+
+enum_gen! { ProducerSendCopyError
+  mapping:
+    LoanError to LoanError,
+    ProducerSendError to SendError
+}
+
+pub(crate) fn data_segment_name(producer_id: UniqueProducerId) -> FileName {
+    let msg =
+        "The system does not support the required file name length for the producers data segment.";
+    let origin = "data_segment_name()";
+
+    let mut file = fatal_panic!(from origin, when FileName::new(producer_id.0.pid().to_string().as_bytes()), "{}", msg);
+    fatal_panic!(from origin, when file.push(b'_'), "{}", msg);
+    fatal_panic!(from origin, when file.push_bytes(producer_id.0.value().to_string().as_bytes()), "{}", msg);
+    file
+}
+
+pub(crate) fn data_segment_config<'global_config, Service: service::Details<'global_config>>(
+    global_config: &global_config::Entries,
+) -> <Service::SharedMemory as NamedConceptMgmt>::Configuration {
+    let origin = "data_segment_config()";
+
+    let f = match FileName::new(
+        global_config
+            .global
+            .service
+            .producer_data_segment_suffix
+            .as_bytes(),
+    ) {
+        Err(_) => {
+            fatal_panic!(from origin, "The producer_data_segment_suffix \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.service.producer_data_segment_suffix);
+        }
+        Ok(v) => v,
+    };
+
+    <Service::SharedMemory as NamedConceptMgmt>::Configuration::default().suffix(f)
+}
+
+/// Hands every sample to exactly one connected [`crate::port::consumer::Consumer`]. The
+/// [`DistributionStrategy`] decides which one. When a consumer is removed from the service,
+/// all samples it has not yet received are redistributed to the remaining consumers. A consumer
+/// whose process died is detected before a sample would be delivered to it and is removed in the
+/// same way.
+#[derive(Debug)]
+pub struct Producer<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    port_id: UniqueProducerId,
+    service: &'a Service,
+    pub(crate) sample_reference_counter: Vec<AtomicU64>,
+    pub(crate) data_segment: Service::SharedMemory,
+    config: LocalProducerConfig,
+
+    consumer_connections: ConsumerConnections<'global_config, Service>,
+    consumer_list_state: UnsafeCell<ContainerState<'a, UniqueConsumerId>>,
+    next_consumer: Cell<usize>,
+    pub(crate) loan_counter: AtomicUsize,
+    _dynamic_config_guard: UniqueIndex<'a>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
+    _phantom_message_type: PhantomData<MessageType>,
+}
+
+impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageType: Debug>
+    Producer<'a, 'global_config, Service, MessageType>
+{
+    pub(crate) fn new(
+        service: &'a Service,
+        static_config: &pipeline::StaticConfig,
+        config: &LocalProducerConfig,
+    ) -> Result<Self, ProducerCreateError> {
+        let msg = "Unable to create Producer port";
+        let origin = "Producer::new()";
+        let port_id = UniqueProducerId::new();
+        let consumer_list = &service.state().dynamic_storage.get().pipeline().consumers;
+
+        let number_of_samples =
+            static_config.required_amount_of_samples_per_data_segment(config.max_loaned_samples);
+
+        let access_settings = service.state().static_config.access_settings();
+        let data_segment = fail!(from origin, when Self::create_data_segment(port_id, service.state().global_config, &access_settings, number_of_samples),
+                with ProducerCreateError::UnableToCreateDataSegment,
+                "{} since the data segment could not be acquired.", msg);
+
+        // !MUST! be the last task otherwise a producer is added to the dynamic config without the
+        // creation of all required resources
+        let _dynamic_config_guard = match service
+            .state()
+            .dynamic_storage
+            .get()
+            .pipeline()
+            .add_producer_id(port_id)
+        {
+            Some(unique_index) => unique_index,
+            None => {
+                fail!(from origin, with ProducerCreateError::ExceedsMaxSupportedProducers,
+                            "{} since it would exceed the maximum supported amount of producers of {}.",
+                            msg, static_config.max_producers);
+            }
+        };
+
+        let new_self = Self {
+            port_id,
+            service,
+            sample_reference_counter: {
+                let mut v = Vec::with_capacity(number_of_samples);
+                for _ in 0..number_of_samples {
+                    v.push(AtomicU64::new(0));
+                }
+                v
+            },
+            data_segment,
+            config: *config,
+            consumer_connections: ConsumerConnections::new(
+                consumer_list.capacity(),
+                service.state().global_config,
+                port_id,
+                static_config,
+                access_settings,
+            ),
+            consumer_list_state: unsafe { UnsafeCell::new(consumer_list.get_state()) },
+            next_consumer: Cell::new(0),
+            loan_counter: AtomicUsize::new(0),
+            _dynamic_config_guard,
+            _node_registration: service.state().register_port(port_id.value()),
+            _phantom_message_type: PhantomData,
+        };
+
+        if let Err(e) = new_self.populate_consumer_channels() {
+            warn!(from new_self, "The new Producer port is unable to connect to every Consumer port, caused by {:?}.", e);
+        }
+
+        Ok(new_self)
+    }
+
+    fn populate_consumer_channels(&self) -> Result<(), ZeroCopyCreationError> {
+        let mut visited_indices = vec![];
+        visited_indices.resize(self.consumer_connections.capacity(), None);
+
+        unsafe {
+            (*self.consumer_list_state.get()).for_each(|index, consumer_id| {
+                visited_indices[index as usize] = Some(*consumer_id);
+            })
+        };
+
+        // retrieve samples before destroying channel
+        self.retrieve_returned_samples();
+
+        let mut result = Ok(());
+        let mut undelivered_samples = vec![];
+        for (i, index) in visited_indices.iter_mut().enumerate() {
+            // the slot of a removed consumer may already be occupied by a new consumer
+            if let Some(connection) = self.consumer_connections.get(i) {
+                if *index != Some(connection.consumer_id) {
+                    self.acquire_undelivered_samples(connection, &mut undelivered_samples);
+                    self.consumer_connections.remove(i);
+                } else if !connection.consumer_id.is_owner_alive() {
+                    self.remove_dead_consumer(i, &mut undelivered_samples);
+                    *index = None;
+                }
+            }
+
+            if let Some(consumer_id) = index {
+                if let Err(e) = self.consumer_connections.create(i, *consumer_id) {
+                    warn!(from self, "Unable to establish connection to new consumer {:?}.", consumer_id);
+                    result = Err(e);
+                }
+            }
+        }
+
+        self.redistribute(undelivered_samples);
+
+        result
+    }
+
+    fn acquire_undelivered_samples(
+        &self,
+        connection: &Connection<'global_config, Service>,
+        undelivered_samples: &mut Vec<PointerOffset>,
+    ) {
+        if !connection.consumer_id.is_owner_alive() {
+            // a dead consumer never disconnects from the channel
+            unsafe { connection.sender.detach_dead_receiver() };
+        }
+
+        loop {
+            match connection.sender.acquire_unreceived() {
+                Ok(Some(ptr_dist)) => {
+                    connection.remove_delivered_sample(ptr_dist.value());
+                    undelivered_samples.push(ptr_dist);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    warn!(from self, "Unable to acquire the undelivered samples of the removed consumer {:?} due to {:?}. The samples will not be redistributed.",
+                        connection.consumer_id, e);
+                    break;
+                }
+            }
+        }
+
+        // the consumer is gone, every sample it did not return will never be returned
+        for offset in connection.take_delivered_samples() {
+            self.release_sample(PointerOffset::new(offset));
+        }
+    }
+
+    /// A consumer whose process died never receives its samples and never removes itself from
+    /// the service. It is removed from the service and its undelivered samples are acquired.
+    fn remove_dead_consumer(&self, index: usize, undelivered_samples: &mut Vec<PointerOffset>) {
+        if let Some(connection) = self.consumer_connections.get(index) {
+            warn!(from self, "The consumer {:?} is dead, its samples are redistributed to the remaining consumers.",
+                connection.consumer_id);
+            self.acquire_undelivered_samples(connection, undelivered_samples);
+            self.unregister_dead_consumer(index, connection.consumer_id);
+        }
+
+        self.consumer_connections.remove(index);
+    }
+
+    fn unregister_dead_consumer(&self, index: usize, consumer_id: UniqueConsumerId) {
+        let dynamic_config = self.service.state().dynamic_storage.get();
+        // another process is already removing the dead ports of the service
+        if !dynamic_config.try_lock_dead_port_cleanup() {
+            return;
+        }
+
+        let pipeline = dynamic_config.pipeline();
+        let mut is_registered = false;
+        unsafe { pipeline.consumers.get_state() }.for_each(|i, id: &UniqueConsumerId| {
+            is_registered |= i as usize == index && *id == consumer_id;
+        });
+
+        if is_registered {
+            unsafe { pipeline.remove_dead_consumer_id(index as u32) };
+        }
+
+        dynamic_config.unlock_dead_port_cleanup();
+    }
+
+    fn redistribute(&self, undelivered_samples: Vec<PointerOffset>) {
+        for ptr_dist in undelivered_samples {
+            if let Err(e) = self.deliver_sample(ptr_dist.value()) {
+                warn!(from self, "Unable to redistribute the sample {:?} of a removed consumer due to {:?}. The sample is discarded.",
+                    ptr_dist, e);
+            }
+
+            // the removed connection no longer holds the sample
+            self.release_sample(ptr_dist);
+        }
+    }
+
+    fn sample_index(distance_to_chunk: usize) -> usize {
+        distance_to_chunk / std::mem::size_of::<Message<Header, MessageType>>()
+    }
+
+    fn create_data_segment(
+        port_id: UniqueProducerId,
+        global_config: &'global_config global_config::Entries,
+        access_settings: &AccessSettings,
+        number_of_samples: usize,
+    ) -> Result<Service::SharedMemory, SharedMemoryCreateError> {
+        let allocator_config = shm_allocator::pool_allocator::Config {
+            bucket_layout: Layout::new::<Message<Header, MessageType>>(),
+        };
+        let chunk_size = allocator_config.bucket_layout.size();
+        let chunk_align = allocator_config.bucket_layout.align();
+
+        Ok(fail!(from "Producer::create_data_segment()",
+            when <<Service::SharedMemory as SharedMemory<PoolAllocator>>::Builder as NamedConceptBuilder<
+            Service::SharedMemory,
+                >>::new(&data_segment_name(port_id))
+                .config(&data_segment_config::<Service>(global_config).access_settings(access_settings))
+                .size(chunk_size * number_of_samples + chunk_align - 1)
+                .create(&allocator_config),
+            "Unable to create the data segment."))
+    }
+
+    fn send_impl(&self, address_to_chunk: usize) -> Result<UniqueConsumerId, ProducerSendError> {
+        if let Err(e) = self.update_connections() {
+            fail!(from self, with ProducerSendError::ConnectionError(e),
+                "Unable to send sample since the connections could not be updated.");
+        }
+
+        self.retrieve_returned_samples();
+        self.deliver_sample(address_to_chunk)
+    }
+
+    fn least_loaded_consumer(&self) -> usize {
+        let number_of_connections = self.consumer_connections.len();
+        let start = self.next_consumer.get();
+        let mut least_loaded = start;
+        let mut min_load = usize::MAX;
+
+        for n in 0..number_of_connections {
+            let i = (start + n) % number_of_connections;
+            if let Some(connection) = self.consumer_connections.get(i) {
+                if connection.samples_in_flight() < min_load {
+                    min_load = connection.samples_in_flight();
+                    least_loaded = i;
+                }
+            }
+        }
+
+        least_loaded
+    }
+
+    fn deliver_sample(
+        &self,
+        address_to_chunk: usize,
+    ) -> Result<UniqueConsumerId, ProducerSendError> {
+        let mut undelivered_samples = vec![];
+        let result = self.try_deliver_sample(address_to_chunk, &mut undelivered_samples);
+        self.redistribute(undelivered_samples);
+        result
+    }
+
+    fn try_deliver_sample(
+        &self,
+        address_to_chunk: usize,
+        undelivered_samples: &mut Vec<PointerOffset>,
+    ) -> Result<UniqueConsumerId, ProducerSendError> {
+        let number_of_connections = self.consumer_connections.len();
+        let start = match self.config.distribution_strategy {
+            DistributionStrategy::RoundRobin => self.next_consumer.get(),
+            DistributionStrategy::LeastLoaded => self.least_loaded_consumer(),
+        };
+
+        let mut has_consumers = false;
+        for n in 0..number_of_connections {
+            let i = (start + n) % number_of_connections;
+            match self.consumer_connections.get(i) {
+                Some(ref connection) => {
+                    // a dead consumer never receives the sample, it would be stuck in its buffer
+                    if !connection.consumer_id.is_owner_alive() {
+                        self.remove_dead_consumer(i, undelivered_samples);
+                        continue;
+                    }
+
+                    match connection
+                        .sender
+                        .try_send(PointerOffset::new(address_to_chunk))
+                    {
+                        Err(ZeroCopySendError::ReceiveBufferFull) => {
+                            // the consumer may be busy but the next one may have space left
+                            has_consumers = true;
+                        }
+                        Err(ZeroCopySendError::ClearRetrieveChannelBeforeSend) => {
+                            has_consumers = true;
+                            warn!(from self, "Unable to send sample via connection {:?} since the retrieve buffer is full. This can be caused by a corrupted retrieve channel.", connection);
+                        }
+                        Ok(_) => {
+                            self.sample_reference_counter[Self::sample_index(address_to_chunk)]
+                                .fetch_add(1, Ordering::Relaxed);
+                            connection.add_delivered_sample(address_to_chunk);
+                            self.next_consumer.set((i + 1) % number_of_connections);
+                            return Ok(connection.consumer_id);
+                        }
+                    }
+                }
+                None => (),
+            }
+        }
+
+        if has_consumers {
+            fail!(from self, with ProducerSendError::AllConsumerBuffersAreFull,
+                "Unable to deliver sample since the buffers of all consumers are full.");
+        }
+
+        fail!(from self, with ProducerSendError::NoConsumerConnected,
+            "Unable to deliver sample since no consumer is connected.");
+    }
+
+    pub(crate) fn release_sample(&self, distance_to_chunk: PointerOffset) {
+        if self.sample_reference_counter[Self::sample_index(distance_to_chunk.value())]
+            .fetch_sub(1, Ordering::Relaxed)
+            == 1
+        {
+            unsafe {
+                fatal_panic!(from self, when self.data_segment
+                .deallocate(
+                    distance_to_chunk,
+                    Layout::new::<Message<Header, MessageType>>(),
+                ), "Internal logic error. The sample should always contain a valid memory chunk from the provided allocator.");
+            };
+        }
+    }
+
+    fn retrieve_returned_samples(&self) {
+        for i in 0..self.consumer_connections.len() {
+            match self.consumer_connections.get(i) {
+                Some(ref connection) => loop {
+                    match connection.sender.reclaim() {
+                        Ok(Some(ptr_dist)) => {
+                            connection.remove_delivered_sample(ptr_dist.value());
+                            self.release_sample(ptr_dist);
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            warn!(from self, "Unable to reclaim samples from connection {:?} due to {:?}. This may lead to a situation where no more samples will be delivered to this connection.", connection, e);
+                            break;
+                        }
+                    }
+                },
+                None => (),
+            }
+        }
+    }
+
+    pub fn update_connections(&self) -> Result<(), ZeroCopyCreationError> {
+        if unsafe { (*self.consumer_list_state.get()).update() } {
+            fail!(from self, when self.populate_consumer_channels(),
+                "Connections were updated only partially since at least one connection to a Consumer port failed.");
+        }
+
+        Ok(())
+    }
+
+    pub fn number_of_consumers(&self) -> usize {
+        self.consumer_connections.number_of_consumers()
+    }
+
+    /// Sends the sample to exactly one consumer and returns its id.
+    pub fn send<'producer>(
+        &'producer self,
+        sample: WorkItemMut<'a, 'producer, 'global_config, Service, MessageType>,
+    ) -> Result<UniqueConsumerId, ProducerSendError> {
+        Ok(
+            fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
+            "Unable to send sample since the underlying send failed."),
+        )
+    }
+
+    pub fn send_copy(&self, value: MessageType) -> Result<UniqueConsumerId, ProducerSendCopyError> {
+        let msg = "Unable to send copy of message";
+        let mut sample = fail!(from self, when self.loan(),
+                                    "{} since the loan of a sample failed.", msg);
+
+        unsafe { sample.as_mut_ptr().write(value) };
+        Ok(
+            fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
+            "{} since the underlying send operation failed.", msg),
+        )
+    }
+
+    pub fn loan<'producer>(
+        &'producer self,
+    ) -> Result<WorkItemMut<'a, 'producer, 'global_config, Service, MessageType>, LoanError> {
+        self.retrieve_returned_samples();
+        let msg = "Unable to loan Sample";
+
+        if self.loan_counter.load(Ordering::Relaxed) >= self.config.max_loaned_samples {
+            fail!(from self, with LoanError::ExceedsMaxLoanedChunks,
+                "{} since already {} samples were loaned and it would exceed the maximum of parallel loans of {}. Release or send a loaned sample to loan another sample.",
+                msg, self.loan_counter.load(Ordering::Relaxed), self.config.max_loaned_samples);
+        }
+
+        match self
+            .data_segment
+            .allocate(Layout::new::<Message<Header, MessageType>>())
+        {
+            Ok(chunk) => {
+                if self.sample_reference_counter[Self::sample_index(chunk.offset.value())]
+                    .fetch_add(1, Ordering::Relaxed)
+                    != 0
+                {
+                    fatal_panic!(from self,
+                                "{} since the allocated sample is already in use! This should never happen!", msg);
+                }
+
+                let mut chunk_ptr;
+                unsafe {
+                    chunk_ptr = NonNull::new_unchecked(
+                        chunk.data_ptr as *mut MaybeUninit<Message<Header, MessageType>>,
+                    );
+                    let header_ptr =
+                        std::ptr::addr_of_mut!((*chunk_ptr.as_mut().as_mut_ptr()).header);
+                    header_ptr.write(Header::new(self.port_id))
+                }
+
+                Ok(WorkItemMut::new(self, chunk_ptr, chunk.offset))
+            }
+            Err(ShmAllocationError::AllocationError(AllocationError::OutOfMemory)) => {
+                fail!(from self, with LoanError::OutOfMemory,
+                    "{} since the underlying shared memory is out of memory.", msg);
+            }
+            Err(ShmAllocationError::AllocationError(AllocationError::SizeTooLarge))
+            | Err(ShmAllocationError::AllocationError(AllocationError::AlignmentFailure)) => {
+                fatal_panic!(from self, "{} since the system seems to be corrupted.", msg);
+            }
+            Err(v) => {
+                fail!(from self, with LoanError::InternalFailure,
+                    "{} since an internal failure occurred ({:?}).", msg, v);
+            }
+        }
+    }
+}
@@ -1,14 +1,22 @@
 use std::cell::UnsafeCell;
 use std::fmt::Debug;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
-use std::{alloc::Layout, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+use std::time::Instant;
+use std::{alloc::Layout, marker::PhantomData, ptr::NonNull};
 
 use super::port_identifiers::{UniquePublisherId, UniqueSubscriberId};
-use crate::message::Message;
+use crate::message::{Message, Payload};
+use crate::node::PortRegistration;
 use crate::port::details::subscriber_connections::*;
-use crate::port::{DegrationAction, DegrationCallback};
+use crate::port::liveliness::heartbeat_timestamp;
+use crate::port::{
+    DegrationAction, DegrationCallback, DeliveryTimeoutAction, DeliveryTimeoutCallback,
+};
 use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::dynamic_config::publish_subscribe::DynamicConfig;
 use crate::service::header::publish_subscribe::Header;
+use crate::service::port_factory::publish_subscribe::remove_dead_ports;
 use crate::service::port_factory::publisher::{LocalPublisherConfig, UnableToDeliverStrategy};
 use crate::service::static_config::publish_subscribe;
 use crate::{global_config, sample_mut::SampleMut};
@@ -19,9 +27,7 @@ use elkodon_cal::named_concept::{
 use elkodon_cal::shared_memory::{SharedMemory, SharedMemoryBuilder, SharedMemoryCreateError};
 use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
 use elkodon_cal::shm_allocator::{self, PointerOffset, ShmAllocationError};
-use elkodon_cal::zero_copy_connection::{
-    ZeroCopyConnection, ZeroCopyCreationError, ZeroCopySendError, ZeroCopySender,
-};
+use elkodon_cal::zero_copy_connection::{ZeroCopyCreationError, ZeroCopySendError, ZeroCopySender};
 use elkodon_bb_container::queue::Queue;
 use elkodon_bb_container::semantic_string::SemanticString;
 use elkodon_bb_elementary::allocator::AllocationError;
@@ -29,6 +35,7 @@ use elkodon_bb_elementary::enum_gen;
 use elkodon_bb_lock_free::mpmc::container::ContainerState;
 use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
 use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_posix::clock::Time;
 use elkodon_bb_system_types::file_name::FileName;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
@@ -41,6 +48,7 @@ pub enum PublisherCreateError {
 pub enum LoanError {
     OutOfMemory,
     ExceedsMaxLoanedChunks,
+    ExceedsMaxLoanSize,
     InternalFailure,
 }
 
@@ -50,6 +58,26 @@ enum_gen! { SendCopyError
     ZeroCopyCreationError to ConnectionError
 }
 
+enum_gen! { SendSerializedError
+  entry:
+    SerializationFormatNotDefined,
+    SerializationFailure
+  mapping:
+    SendCopyError
+}
+
+impl From<LoanError> for SendSerializedError {
+    fn from(value: LoanError) -> Self {
+        SendSerializedError::SendCopyError(value.into())
+    }
+}
+
+impl From<ZeroCopyCreationError> for SendSerializedError {
+    fn from(value: ZeroCopyCreationError) -> Self {
+        SendSerializedError::SendCopyError(value.into())
+    }
+}
+
 pub(crate) fn data_segment_name(publisher_id: UniquePublisherId) -> FileName {
     let msg = "The system does not support the required file name length for the publishers data segment.";
     let origin = "data_segment_name()";
@@ -87,25 +115,37 @@ pub struct Publisher<
     'a,
     'global_config: 'a,
     Service: service::Details<'global_config>,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug + Default = (),
 > {
     port_id: UniquePublisherId,
     pub(crate) sample_reference_counter: Vec<AtomicU64>,
     pub(crate) data_segment: Service::SharedMemory,
     config: LocalPublisherConfig,
+    message_layout: Layout,
 
     subscriber_connections: SubscriberConnections<'global_config, Service>,
     subscriber_list_state: UnsafeCell<ContainerState<'a, UniqueSubscriberId>>,
     history: Option<UnsafeCell<Queue<usize>>>,
     service: &'a Service,
     degration_callback: Option<DegrationCallback<'a>>,
+    delivery_timeout_callback: Option<DeliveryTimeoutCallback<'a>>,
     pub(crate) loan_counter: AtomicUsize,
-    _dynamic_config_guard: UniqueIndex<'a>,
+    next_sequence_number: AtomicU64,
+    dynamic_config_guard: UniqueIndex<'a>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
     _phantom_message_type: PhantomData<MessageType>,
+    _phantom_user_header: PhantomData<UserHeader>,
 }
 
-impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageType: Debug>
-    Publisher<'a, 'global_config, Service, MessageType>
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > Publisher<'a, 'global_config, Service, MessageType, UserHeader>
 {
     pub(crate) fn new(
         service: &'a Service,
@@ -128,19 +168,23 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
             .messaging_pattern
             .required_amount_of_samples_per_data_segment(config.max_loaned_samples);
 
-        let data_segment = fail!(from origin, when Self::create_data_segment(port_id, service.state().global_config, number_of_samples),
+        let message_layout =
+            Message::<Header, MessageType, UserHeader>::layout(config.max_slice_len);
+        let access_settings = service.state().static_config.access_settings();
+        let data_segment = fail!(from origin, when Self::create_data_segment(port_id, service.state().global_config, &access_settings, number_of_samples, message_layout),
                 with PublisherCreateError::UnableToCreateDataSegment,
                 "{} since the data segment could not be acquired.", msg);
 
         // !MUST! be the last task otherwise a publisher is added to the dynamic config without the
         // creation of all required resources
-        let _dynamic_config_guard = match service
-            .state()
-            .dynamic_storage
-            .get()
-            .publish_subscribe()
-            .add_publisher_id(port_id)
-        {
+        let dynamic_config = service.state().dynamic_storage.get().publish_subscribe();
+        let mut unique_index = dynamic_config.add_publisher_id(port_id);
+        // the slots may still be occupied by ports of dead processes
+        if unique_index.is_none() && remove_dead_ports(service) > 0 {
+            unique_index = dynamic_config.add_publisher_id(port_id);
+        }
+
+        let dynamic_config_guard = match unique_index {
             Some(unique_index) => unique_index,
             None => {
                 fail!(from origin, with PublisherCreateError::ExceedsMaxSupportedPublishers,
@@ -156,9 +200,11 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                 service.state().global_config,
                 port_id,
                 static_config,
+                access_settings,
             ),
             data_segment,
             config: *config,
+            message_layout,
             sample_reference_counter: {
                 let mut v = Vec::with_capacity(number_of_samples);
                 for _ in 0..number_of_samples {
@@ -173,9 +219,13 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
             },
             service,
             degration_callback: None,
+            delivery_timeout_callback: None,
             loan_counter: AtomicUsize::new(0),
-            _dynamic_config_guard,
+            next_sequence_number: AtomicU64::new(0),
+            dynamic_config_guard,
+            _node_registration: service.state().register_port(port_id.value()),
             _phantom_message_type: PhantomData,
+            _phantom_user_header: PhantomData,
         };
 
         if let Err(e) = new_self.populate_subscriber_channels() {
@@ -201,10 +251,26 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         for (i, index) in visited_indices.iter().enumerate() {
             match index {
                 Some(subscriber_id) => {
+                    if let Some(connection) = self.subscriber_connections.get(i) {
+                        if connection.subscriber_id != *subscriber_id {
+                            // the subscriber was replaced by another one in the meantime
+                            self.remove_connection(i);
+                        }
+                    }
+
                     match self.subscriber_connections.create(i, *subscriber_id) {
                         Ok(false) => (),
                         Ok(true) => match &self.subscriber_connections.get(i) {
-                            Some(connection) => self.deliver_history(connection),
+                            Some(connection) => {
+                                let counters = self.dynamic_config().subscriber_counters(i);
+                                connection
+                                    .served_history_request
+                                    .set(counters.history_request_id.load(Ordering::Acquire));
+                                self.deliver_history(
+                                    connection,
+                                    counters.requested_history_size.load(Ordering::Relaxed),
+                                )
+                            }
                             None => {
                                 fatal_panic!(from self, "This should never happen! Unable to acquire previously created subscriber connection.")
                             }
@@ -230,46 +296,111 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                         },
                     }
                 }
-                None => self.subscriber_connections.remove(i),
+                None => self.remove_connection(i),
             }
         }
 
         Ok(())
     }
 
-    fn deliver_history(&self, connection: &Connection<'global_config, Service>) {
+    fn remove_connection(&self, index: usize) {
+        // the subscriber is gone, every sample it did not return will never be returned
+        if let Some(connection) = self.subscriber_connections.get(index) {
+            for offset in connection.take_delivered_samples() {
+                self.release_sample(PointerOffset::new(offset));
+            }
+        }
+
+        self.subscriber_connections.remove(index);
+    }
+
+    /// Serves the history requests the subscribers made since the last call, see
+    /// [`crate::port::subscriber::Subscriber::request_history()`].
+    fn serve_history_requests(&self) {
+        for i in 0..self.subscriber_connections.len() {
+            if let Some(connection) = self.subscriber_connections.get(i) {
+                if connection.is_delivery_stopped() {
+                    continue;
+                }
+
+                let counters = self.dynamic_config().subscriber_counters(i);
+                let request_id = counters.history_request_id.load(Ordering::Acquire);
+                if connection.served_history_request.replace(request_id) != request_id {
+                    self.deliver_history(
+                        connection,
+                        counters.requested_history_size.load(Ordering::Relaxed),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Delivers the newest `max_samples` samples of the history, limited by the buffer size of
+    /// the subscriber.
+    fn deliver_history(&self, connection: &Connection<'global_config, Service>, max_samples: u64) {
         match &self.history {
             None => (),
             Some(history) => {
                 let history = unsafe { &mut *history.get() };
-                for i in 0..history.len() {
+                let number_of_samples = history
+                    .len()
+                    .min(usize::try_from(max_samples).unwrap_or(usize::MAX))
+                    .min(
+                        self.service
+                            .state()
+                            .static_config
+                            .publish_subscribe()
+                            .subscriber_buffer_size,
+                    );
+                if number_of_samples == 0 {
+                    return;
+                }
+
+                for i in history.len() - number_of_samples..history.len() {
                     let ptr_distance = unsafe { history.get_unchecked(i) };
 
                     match connection.sender.try_send(PointerOffset::new(ptr_distance)) {
-                        Ok(_) => {
-                            self.sample_reference_counter[Self::sample_index(ptr_distance)]
+                        Ok(overflow) => {
+                            self.sample_reference_counter[self.sample_index(ptr_distance)]
                                 .fetch_add(1, Ordering::Relaxed);
+                            connection.add_delivered_sample(ptr_distance);
+
+                            if let Some(old) = overflow {
+                                connection.remove_delivered_sample(old.value());
+                                self.release_sample(old)
+                            }
                         }
                         Err(e) => {
                             warn!(from self, "Failed to deliver history to new subscriber via {:?} due to {:?}", connection, e);
                         }
                     }
                 }
+                connection.notify();
             }
         }
     }
 
-    fn sample_index(distance_to_chunk: usize) -> usize {
-        distance_to_chunk / std::mem::size_of::<Message<Header, MessageType>>()
+    fn dynamic_config(&self) -> &DynamicConfig {
+        self.service
+            .state()
+            .dynamic_storage
+            .get()
+            .publish_subscribe()
+    }
+
+    fn sample_index(&self, distance_to_chunk: usize) -> usize {
+        distance_to_chunk / self.message_layout.size()
     }
 
     fn create_data_segment(
         port_id: UniquePublisherId,
         global_config: &'global_config global_config::Entries,
+        access_settings: &AccessSettings,
         number_of_samples: usize,
+        message_layout: Layout,
     ) -> Result<Service::SharedMemory, SharedMemoryCreateError> {
         let allocator_config = shm_allocator::pool_allocator::Config {
-            bucket_layout: Layout::new::<Message<Header, MessageType>>(),
+            bucket_layout: message_layout,
         };
         let chunk_size = allocator_config.bucket_layout.size();
         let chunk_align = allocator_config.bucket_layout.align();
@@ -278,7 +409,7 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
             when <<Service::SharedMemory as SharedMemory<PoolAllocator>>::Builder as NamedConceptBuilder<
             Service::SharedMemory,
                 >>::new(&data_segment_name(port_id))
-                .config(&data_segment_config::<Service>(global_config))
+                .config(&data_segment_config::<Service>(global_config).access_settings(access_settings))
                 .size(chunk_size * number_of_samples + chunk_align - 1)
                 .create(&allocator_config),
             "Unable to create the data segment."))
@@ -288,8 +419,25 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         fail!(from self, when self.update_connections(),
             "Unable to send sample since the connections could not be updated.");
 
+        // the sequence number and the time stamp are assigned on send since loaned samples can
+        // be sent in any order and at any time, the subscriber measures its deadline from it
+        let now = fatal_panic!(from self, when Time::now(),
+            "Unable to send sample since the current time could not be acquired for its time stamp.");
+        let header =
+            (self.data_segment.allocator_data_start_address() + address_to_chunk) as *mut Header;
+        unsafe {
+            (*header).set_sequence_number(self.next_sequence_number.fetch_add(1, Ordering::Relaxed));
+            (*header).set_time_stamp(now);
+        };
+
         self.add_to_history(address_to_chunk);
-        Ok(self.deliver_sample(address_to_chunk))
+        let number_of_recipients = self.deliver_sample(address_to_chunk);
+        self.dynamic_config()
+            .publisher_counters(self.dynamic_config_guard.value() as usize)
+            .sent_samples
+            .fetch_add(1, Ordering::Relaxed);
+        self.assert_liveliness();
+        Ok(number_of_recipients)
     }
 
     fn add_to_history(&self, address_to_chunk: usize) {
@@ -297,7 +445,7 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
             None => (),
             Some(history) => {
                 let history = unsafe { &mut *history.get() };
-                self.sample_reference_counter[Self::sample_index(address_to_chunk)]
+                self.sample_reference_counter[self.sample_index(address_to_chunk)]
                     .fetch_add(1, Ordering::Relaxed);
                 match unsafe { history.push_with_overflow(address_to_chunk) } {
                     None => (),
@@ -307,36 +455,114 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         }
     }
 
-    fn deliver_sample(&self, address_to_chunk: usize) -> usize {
-        let deliver_call = match self.config.unable_to_deliver_strategy {
-            UnableToDeliverStrategy::Block => <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Sender::blocking_send,
-            UnableToDeliverStrategy::DiscardSample => <<Service as service::Details<'global_config>>::Connection as ZeroCopyConnection>::Sender::try_send,
+    /// Asks the delivery timeout callback what to do with a subscriber whose buffer stayed full
+    /// for the whole timeout. Returns true when the subscriber shall be disconnected.
+    fn handle_delivery_timeout(&self, subscriber_id: UniqueSubscriberId) -> bool {
+        let action = match &self.delivery_timeout_callback {
+            Some(c) => c.call(
+                self.service.state().static_config.clone(),
+                self.port_id,
+                subscriber_id,
+            ),
+            None => DeliveryTimeoutAction::Warn,
         };
 
+        match action {
+            DeliveryTimeoutAction::Ignore => false,
+            DeliveryTimeoutAction::Warn => {
+                warn!(from self, "Unable to deliver sample to subscriber {:?} within the timeout, the sample is discarded for this subscriber.", subscriber_id);
+                false
+            }
+            DeliveryTimeoutAction::Disconnect => {
+                warn!(from self, "Disconnecting subscriber {:?} since it was unable to receive a sample within the timeout.", subscriber_id);
+                true
+            }
+        }
+    }
+
+    fn disconnect_subscriber(&self, index: usize) {
+        // the connection is not removed since the subscriber is still alive and returns the
+        // samples it holds via the connection, they are released by remove_connection() when
+        // it is gone
+        if let Some(connection) = self.subscriber_connections.get(index) {
+            connection.stop_delivery();
+            // the connections are stored at the index of the subscriber in the dynamic config
+            self.dynamic_config()
+                .subscriber_counters(index)
+                .disconnections
+                .fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn deliver_sample(&self, address_to_chunk: usize) -> usize {
+        let publisher_counters = self
+            .dynamic_config()
+            .publisher_counters(self.dynamic_config_guard.value() as usize);
         let mut number_of_recipients = 0;
         for i in 0..self.subscriber_connections.len() {
             match self.subscriber_connections.get(i) {
+                Some(ref connection) if connection.is_delivery_stopped() => (),
                 Some(ref connection) => {
-                    match deliver_call(&connection.sender, PointerOffset::new(address_to_chunk)) {
+                    // the connections are stored at the index of the subscriber in the dynamic
+                    // config
+                    let subscriber_counters = self.dynamic_config().subscriber_counters(i);
+                    let ptr = PointerOffset::new(address_to_chunk);
+                    let start = Instant::now();
+                    let result = match self.config.unable_to_deliver_strategy {
+                        UnableToDeliverStrategy::Block => connection.sender.blocking_send(ptr),
+                        UnableToDeliverStrategy::BlockWithTimeout(timeout) => {
+                            connection.sender.timed_send(ptr, timeout)
+                        }
+                        UnableToDeliverStrategy::DiscardSample => connection.sender.try_send(ptr),
+                    };
+                    if self.config.unable_to_deliver_strategy
+                        != UnableToDeliverStrategy::DiscardSample
+                    {
+                        publisher_counters
+                            .blocked_time_in_ns
+                            .fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+                    }
+
+                    let mut disconnect = false;
+                    match result {
                         Err(ZeroCopySendError::ReceiveBufferFull) => {
                             /* causes no problem
                              *   blocking_send => can never happen
+                             *   timed_send => the subscriber did not make space in time
                              *   try_send => we tried and expect that the buffer is full
                              * */
+                            subscriber_counters
+                                .samples_dropped_by_full_buffer
+                                .fetch_add(1, Ordering::Relaxed);
+                            if let UnableToDeliverStrategy::BlockWithTimeout(_) =
+                                self.config.unable_to_deliver_strategy
+                            {
+                                disconnect = self.handle_delivery_timeout(connection.subscriber_id);
+                            }
                         }
                         Err(ZeroCopySendError::ClearRetrieveChannelBeforeSend) => {
                             warn!(from self, "Unable to send sample via connection {:?} since the retrieve buffer is full. This can be caused by a corrupted retrieve channel.", connection);
                         }
                         Ok(overflow) => {
-                            self.sample_reference_counter[Self::sample_index(address_to_chunk)]
+                            self.sample_reference_counter[self.sample_index(address_to_chunk)]
                                 .fetch_add(1, Ordering::Relaxed);
+                            connection.add_delivered_sample(address_to_chunk);
                             number_of_recipients += 1;
 
                             if let Some(old) = overflow {
+                                subscriber_counters
+                                    .samples_dropped_by_overflow
+                                    .fetch_add(1, Ordering::Relaxed);
+                                connection.remove_delivered_sample(old.value());
                                 self.release_sample(old)
                             }
+                            connection.notify();
                         }
                     }
+
+                    if disconnect {
+                        self.disconnect_subscriber(i);
+                    }
                 }
                 None => (),
             }
@@ -345,7 +571,7 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
     }
 
     pub(crate) fn release_sample(&self, distance_to_chunk: PointerOffset) {
-        if self.sample_reference_counter[Self::sample_index(distance_to_chunk.value())]
+        if self.sample_reference_counter[self.sample_index(distance_to_chunk.value())]
             .fetch_sub(1, Ordering::Relaxed)
             == 1
         {
@@ -353,7 +579,7 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                 fatal_panic!(from self, when self.data_segment
                 .deallocate(
                     distance_to_chunk,
-                    Layout::new::<MessageType>(),
+                    self.message_layout,
                 ), "Internal logic error. The sample should always contain a valid memory chunk from the provided allocator.");
             };
         }
@@ -365,7 +591,8 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                 Some(ref connection) => loop {
                     match connection.sender.reclaim() {
                         Ok(Some(ptr_dist)) => {
-                            let sample_index = Self::sample_index(ptr_dist.value());
+                            connection.remove_delivered_sample(ptr_dist.value());
+                            let sample_index = self.sample_index(ptr_dist.value());
 
                             if self.sample_reference_counter[sample_index]
                                 .fetch_sub(1, Ordering::Relaxed)
@@ -375,7 +602,7 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                                     fatal_panic!(from self, when self.data_segment
                                     .deallocate(
                                         ptr_dist,
-                                        Layout::new::<Message<Header, MessageType>>(),
+                                        self.message_layout,
                                     ), "This should never happen! Failed to deallocate the reclaimed ptr. Either the data was corrupted or an invalid ptr was returned.")
                                 };
                             }
@@ -408,22 +635,56 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         }
     }
 
+    /// Sets the callback which decides how to continue with a subscriber that was unable to
+    /// receive a sample within the timeout of [`UnableToDeliverStrategy::BlockWithTimeout`].
+    /// Without a callback a warning is logged and the subscriber stays connected.
+    pub fn set_delivery_timeout_callback<
+        F: Fn(
+                service::static_config::StaticConfig,
+                UniquePublisherId,
+                UniqueSubscriberId,
+            ) -> DeliveryTimeoutAction
+            + 'a,
+    >(
+        &mut self,
+        callback: Option<F>,
+    ) {
+        match callback {
+            Some(c) => self.delivery_timeout_callback = Some(DeliveryTimeoutCallback::new(c)),
+            None => self.delivery_timeout_callback = None,
+        }
+    }
+
     pub fn update_connections(&self) -> Result<(), ZeroCopyCreationError> {
         if unsafe { (*self.subscriber_list_state.get()).update() } {
             fail!(from self, when self.populate_subscriber_channels(),
                 "Connections were updated only partially since at least one connection to a Subscriber port failed.");
         }
 
+        self.serve_history_requests();
         Ok(())
     }
 
+    pub fn id(&self) -> UniquePublisherId {
+        self.port_id
+    }
+
     pub fn number_of_subscribers(&self) -> usize {
         self.subscriber_connections.number_of_subscribers()
     }
 
+    /// Announces to the subscribers that the publisher is alive without sending a sample. Every
+    /// send announces the liveliness automatically, see [`crate::port::liveliness`].
+    pub fn assert_liveliness(&self) {
+        self.dynamic_config()
+            .publisher_counters(self.dynamic_config_guard.value() as usize)
+            .last_heartbeat_in_ns
+            .store(heartbeat_timestamp(), Ordering::Relaxed);
+    }
+
     pub fn send<'publisher>(
         &'publisher self,
-        sample: SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType>,
+        sample: SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType, UserHeader>,
     ) -> Result<usize, ZeroCopyCreationError> {
         Ok(
             fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
@@ -431,37 +692,31 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         )
     }
 
-    pub fn send_copy(&self, value: MessageType) -> Result<usize, SendCopyError> {
-        let msg = "Unable to send copy of message";
-        let mut sample = fail!(from self, when self.loan(),
-                                    "{} since the loan of a sample failed.", msg);
-
-        unsafe { sample.as_mut_ptr().write(value) };
-        Ok(
-            fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
-            "{} since the underlying send operation failed.", msg),
-        )
-    }
-
-    pub fn loan<'publisher>(
+    fn loan_impl<'publisher>(
         &'publisher self,
-    ) -> Result<SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType>, LoanError>
-    {
+        number_of_elements: usize,
+    ) -> Result<
+        SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType, UserHeader>,
+        LoanError,
+    > {
         self.retrieve_returned_samples();
         let msg = "Unable to loan Sample";
 
+        if number_of_elements > self.config.max_slice_len {
+            fail!(from self, with LoanError::ExceedsMaxLoanSize,
+                "{} since the requested {} elements exceed the maximum slice length of {}.",
+                msg, number_of_elements, self.config.max_slice_len);
+        }
+
         if self.loan_counter.load(Ordering::Relaxed) >= self.config.max_loaned_samples {
             fail!(from self, with LoanError::ExceedsMaxLoanedChunks,
                 "{} since already {} samples were loaned and it would exceed the maximum of parallel loans of {}. Release or send a loaned sample to loan another sample.",
                 msg, self.loan_counter.load(Ordering::Relaxed), self.config.max_loaned_samples);
         }
 
-        match self
-            .data_segment
-            .allocate(Layout::new::<Message<Header, MessageType>>())
-        {
+        match self.data_segment.allocate(self.message_layout) {
             Ok(chunk) => {
-                if self.sample_reference_counter[Self::sample_index(chunk.offset.value())]
+                if self.sample_reference_counter[self.sample_index(chunk.offset.value())]
                     .fetch_add(1, Ordering::Relaxed)
                     != 0
                 {
@@ -469,19 +724,27 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                                 "{} since the allocated sample is already in use! This should never happen!", msg);
                 }
 
-                let mut chunk_ptr;
+                let chunk_ptr;
                 unsafe {
                     chunk_ptr = NonNull::new_unchecked(
-                        chunk.data_ptr as *mut MaybeUninit<Message<Header, MessageType>>,
+                        Message::<Header, MessageType, UserHeader>::from_raw_parts(
+                            chunk.data_ptr,
+                            number_of_elements,
+                        ),
                     );
-                    let header_ptr =
-                        std::ptr::addr_of_mut!((*chunk_ptr.as_mut().as_mut_ptr()).header);
-                    header_ptr.write(Header::new(self.port_id))
+                    let header_ptr = std::ptr::addr_of_mut!((*chunk_ptr.as_ptr()).header);
+                    header_ptr.write(Header::new(self.port_id, number_of_elements));
+                    let user_header_ptr = std::ptr::addr_of_mut!((*chunk_ptr.as_ptr()).user_header);
+                    user_header_ptr.write(UserHeader::default());
                 }
 
                 Ok(SampleMut::new(self, chunk_ptr, chunk.offset))
             }
             Err(ShmAllocationError::AllocationError(AllocationError::OutOfMemory)) => {
+                self.dynamic_config()
+                    .publisher_counters(self.dynamic_config_guard.value() as usize)
+                    .failed_loans
+                    .fetch_add(1, Ordering::Relaxed);
                 fail!(from self, with LoanError::OutOfMemory,
                     "{} since the underlying shared memory is out of memory.", msg);
             }
@@ -496,3 +759,127 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         }
     }
 }
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        MessageType: Debug,
+        UserHeader: Debug + Default,
+    > Publisher<'a, 'global_config, Service, MessageType, UserHeader>
+{
+    pub fn send_copy(&self, value: MessageType) -> Result<usize, SendCopyError> {
+        let msg = "Unable to send copy of message";
+        let mut sample = fail!(from self, when self.loan(),
+                                    "{} since the loan of a sample failed.", msg);
+
+        unsafe { sample.as_mut_ptr().write(value) };
+        Ok(
+            fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
+            "{} since the underlying send operation failed.", msg),
+        )
+    }
+
+    pub fn loan<'publisher>(
+        &'publisher self,
+    ) -> Result<
+        SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType, UserHeader>,
+        LoanError,
+    > {
+        self.loan_impl(1)
+    }
+}
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        MessageType: Debug,
+        UserHeader: Debug + Default,
+    > Publisher<'a, 'global_config, Service, [MessageType], UserHeader>
+{
+    /// Copies the provided values into a newly loaned slice and sends it.
+    pub fn send_copy_slice(&self, values: &[MessageType]) -> Result<usize, SendCopyError>
+    where
+        MessageType: Copy,
+    {
+        let msg = "Unable to send copy of slice";
+        let mut sample = fail!(from self, when self.loan_slice(values.len()),
+                                    "{} since the loan of a sample failed.", msg);
+
+        unsafe {
+            (sample.as_mut_ptr() as *mut MessageType)
+                .copy_from_nonoverlapping(values.as_ptr(), values.len())
+        };
+        Ok(
+            fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
+            "{} since the underlying send operation failed.", msg),
+        )
+    }
+
+    /// Loans an uninitialized slice with `number_of_elements` elements. The number of elements
+    /// must not exceed the max slice length the publisher was created with.
+    pub fn loan_slice<'publisher>(
+        &'publisher self,
+        number_of_elements: usize,
+    ) -> Result<
+        SampleMut<'a, 'publisher, 'global_config, Service, Header, [MessageType], UserHeader>,
+        LoanError,
+    > {
+        self.loan_impl(number_of_elements)
+    }
+}
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        UserHeader: Debug + Default,
+    > Publisher<'a, 'global_config, Service, [u8], UserHeader>
+{
+    /// Serializes the value with the [`crate::service::serialization::SerializationFormat`] of
+    /// the service directly into a loaned byte slice of the serialized size and sends it. When
+    /// the serialized value exceeds the max slice length of the publisher it fails with
+    /// [`LoanError::ExceedsMaxLoanSize`].
+    pub fn send_serialized<T: serde::Serialize>(
+        &self,
+        value: &T,
+    ) -> Result<usize, SendSerializedError> {
+        let msg = "Unable to send serialized value";
+        let format = match self
+            .service
+            .state()
+            .static_config
+            .publish_subscribe()
+            .serialization_format
+        {
+            Some(format) => format,
+            None => {
+                fail!(from self, with SendSerializedError::SerializationFormatNotDefined,
+                    "{} since the service has no serialization format defined.", msg);
+            }
+        };
+
+        let size = fail!(from self, when format.serialized_size(value),
+            with SendSerializedError::SerializationFailure,
+            "{} since the size of the serialized value could not be determined.", msg);
+        let mut sample = fail!(from self, when self.loan_slice(size),
+            "{} since the serialized value requires {} bytes and the loan of a slice of this size failed.",
+            msg, size);
+
+        let payload = unsafe {
+            let payload = sample.as_mut_ptr() as *mut u8;
+            // the serializer writes into an initialized buffer
+            payload.write_bytes(0, size);
+            std::slice::from_raw_parts_mut(payload, size)
+        };
+        fail!(from self, when format.serialize_into(value, payload),
+            with SendSerializedError::SerializationFailure,
+            "{} since the value could not be serialized into the loaned slice.", msg);
+
+        Ok(
+            fail!(from self, when self.send_impl(sample.offset_to_chunk().value()),
+            "{} since the underlying send operation failed.", msg),
+        )
+    }
+}
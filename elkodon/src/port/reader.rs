@@ -0,0 +1,86 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use elkodon_bb_lock_free::spmc::unrestricted_atomic::UnrestrictedAtomic;
+use elkodon_bb_log::fail;
+
+use crate::port::details::blackboard_entries::{lookup_entry, EntryLookupFailure};
+use crate::service;
+use crate::zero_copy_type::ZeroCopyType;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ReaderEntryError {
+    EntryDoesNotExist,
+    IncompatibleValueType,
+}
+
+/// Provides lock-free read access to a single entry of the blackboard. Every read returns a
+/// consistent copy of the latest value.
+#[derive(Debug)]
+pub struct ReaderEntry<'reader, ValueType: Copy + Debug> {
+    atomic: &'reader UnrestrictedAtomic<ValueType>,
+}
+
+impl<'reader, ValueType: Copy + Debug> ReaderEntry<'reader, ValueType> {
+    pub fn get(&self) -> ValueType {
+        self.atomic.load()
+    }
+}
+
+/// Reads the entries of a blackboard. A [`Reader`] does not hold any connection and is not
+/// registered in the service.
+#[derive(Debug)]
+pub struct Reader<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug,
+> {
+    service: &'a Service,
+    _phantom_key_type: PhantomData<KeyType>,
+    _phantom_lifetime_b: PhantomData<&'global_config ()>,
+}
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        KeyType: Copy + Eq + Debug,
+    > Reader<'a, 'global_config, Service, KeyType>
+{
+    pub(crate) fn new(service: &'a Service) -> Self {
+        Self {
+            service,
+            _phantom_key_type: PhantomData,
+            _phantom_lifetime_b: PhantomData,
+        }
+    }
+
+    pub fn entry<ValueType: Copy + Debug + ZeroCopyType>(
+        &self,
+        key: &KeyType,
+    ) -> Result<ReaderEntry<'a, ValueType>, ReaderEntryError> {
+        let msg = "Unable to acquire blackboard entry";
+        match lookup_entry::<Service, KeyType, ValueType>(self.service, key) {
+            Ok((_, atomic)) => Ok(ReaderEntry { atomic }),
+            Err(EntryLookupFailure::EntryDoesNotExist) => {
+                fail!(from self, with ReaderEntryError::EntryDoesNotExist,
+                    "{} since the key {:?} does not exist.", msg, key);
+            }
+            Err(EntryLookupFailure::IncompatibleValueType) => {
+                fail!(from self, with ReaderEntryError::IncompatibleValueType,
+                    "{} since the entry with the key {:?} does not store values of type \"{}\".",
+                    msg, key, ValueType::type_identifier());
+            }
+        }
+    }
+
+    pub fn get<ValueType: Copy + Debug + ZeroCopyType>(
+        &self,
+        key: &KeyType,
+    ) -> Result<ValueType, ReaderEntryError> {
+        Ok(fail!(from self, when self.entry::<ValueType>(key),
+                "Unable to read the value of the blackboard entry {:?}.", key)
+        .get())
+    }
+}
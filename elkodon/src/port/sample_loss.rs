@@ -0,0 +1,85 @@
+//! Detects lost samples with the sequence number of the
+//! [`crate::service::header::publish_subscribe::Header`]. Every publisher increments it on each
+//! send, therefore a gap between two received samples of the same publisher means that the
+//! samples in between were lost, for instance since they were replaced by newer samples in a
+//! service with safe overflow or since they were discarded by the publisher due to a full
+//! subscriber buffer, see
+//! [`crate::service::port_factory::publisher::UnableToDeliverStrategy::DiscardSample`].
+
+use std::cell::Cell;
+use std::fmt::Debug;
+
+use tiny_fn::tiny_fn;
+
+use crate::port::port_identifiers::UniquePublisherId;
+
+/// Reported by the [`crate::port::subscriber::Subscriber`] whenever it detects a gap in the
+/// samples of a publisher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleLoss {
+    publisher_id: UniquePublisherId,
+    number_of_lost_samples: u64,
+    sequence_number: u64,
+}
+
+impl SampleLoss {
+    pub fn publisher_id(&self) -> UniquePublisherId {
+        self.publisher_id
+    }
+
+    pub fn number_of_lost_samples(&self) -> u64 {
+        self.number_of_lost_samples
+    }
+
+    /// Returns the sequence number of the received sample which revealed the gap.
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+}
+
+tiny_fn! {
+    pub struct SampleLossCallback = Fn(loss: SampleLoss);
+}
+
+impl<'a> Debug for SampleLossCallback<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "")
+    }
+}
+
+/// Tracks the sequence numbers of the samples received from a single publisher.
+#[derive(Debug, Default)]
+pub(crate) struct SequenceTracker {
+    last_sequence_number: Cell<Option<u64>>,
+    lost_samples: Cell<u64>,
+}
+
+impl SequenceTracker {
+    /// Returns the loss when the provided sequence number reveals a gap. The first received
+    /// sample never reveals a gap since the subscriber may have connected later.
+    pub(crate) fn sample_received(
+        &self,
+        publisher_id: UniquePublisherId,
+        sequence_number: u64,
+    ) -> Option<SampleLoss> {
+        let last_sequence_number = self.last_sequence_number.replace(Some(sequence_number))?;
+        let number_of_lost_samples = sequence_number
+            .saturating_sub(last_sequence_number)
+            .saturating_sub(1);
+        if number_of_lost_samples == 0 {
+            return None;
+        }
+
+        self.lost_samples
+            .set(self.lost_samples.get() + number_of_lost_samples);
+        Some(SampleLoss {
+            publisher_id,
+            number_of_lost_samples,
+            sequence_number,
+        })
+    }
+
+    pub(crate) fn lost_samples(&self) -> u64 {
+        self.lost_samples.get()
+    }
+}
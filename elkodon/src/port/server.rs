@@ -0,0 +1,496 @@
+use std::cell::UnsafeCell;
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::{alloc::Layout, marker::PhantomData, mem::MaybeUninit, ptr::NonNull};
+
+use super::details::client_connections::*;
+use super::port_identifiers::{UniqueClientId, UniqueServerId};
+use super::publisher::LoanError;
+use crate::global_config;
+use crate::message::Message;
+use crate::node::PortRegistration;
+use crate::request::Request;
+use crate::response_mut::ResponseMut;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::header::request_response::{RequestHeader, ResponseHeader};
+use crate::service::port_factory::server::LocalServerConfig;
+use crate::service::static_config::request_response;
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_elementary::allocator::AllocationError;
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_lock_free::mpmc::container::ContainerState;
+use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_system_types::file_name::FileName;
+use elkodon_cal::dynamic_storage::DynamicStorage;
+use elkodon_cal::named_concept::{
+    NamedConceptBuilder, NamedConceptConfiguration, NamedConceptMgmt,
+};
+use elkodon_cal::shared_memory::{SharedMemory, SharedMemoryBuilder, SharedMemoryCreateError};
+use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
+use elkodon_cal::shm_allocator::{self, PointerOffset, ShmAllocationError};
+use elkodon_cal::zero_copy_connection::*;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ServerCreateError {
+    ExceedsMaxSupportedServers,
+    UnableToCreateDataSegment,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ServerReceiveError {
+    ExceedsMaxBorrowedRequests,
+    ConnectionFailure(ClientConnectionFailure),
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub enum ServerSendError {
+    ClientNotConnected,
+    ResponseBufferFull,
+    RetrieveBufferFull,
+    ConnectionFailure(ClientConnectionFailure),
+}
+
+enum_gen! { ServerSendCopyError
+  mapping:
+    LoanError to LoanError,
+    ServerSendError to SendError
+}
+
+pub(crate) fn data_segment_name(server_id: UniqueServerId) -> FileName {
+    let msg =
+        "The system does not support the required file name length for the servers data segment.";
+    let origin = "data_segment_name()";
+
+    let mut file = fatal_panic!(from origin, when FileName::new(server_id.0.pid().to_string().as_bytes()), "{}", msg);
+    fatal_panic!(from origin, when file.push(b'_'), "{}", msg);
+    fatal_panic!(from origin, when file.push_bytes(server_id.0.value().to_string().as_bytes()), "{}", msg);
+    file
+}
+
+pub(crate) fn data_segment_config<'global_config, Service: service::Details<'global_config>>(
+    global_config: &global_config::Entries,
+) -> <Service::SharedMemory as NamedConceptMgmt>::Configuration {
+    let origin = "data_segment_config()";
+
+    let f = match FileName::new(
+        global_config
+            .global
+            .service
+            .server_data_segment_suffix
+            .as_bytes(),
+    ) {
+        Err(_) => {
+            fatal_panic!(from origin, "The server_data_segment_suffix \"{}\" provided by the config contains either invalid file name characters or is too long.",
+                                       global_config.global.service.server_data_segment_suffix);
+        }
+        Ok(v) => v,
+    };
+
+    <Service::SharedMemory as NamedConceptMgmt>::Configuration::default().suffix(f)
+}
+
+/// Receives requests from all connected [`crate::port::client::Client`]s and sends the
+/// responses back to the [`crate::port::client::Client`] which sent the request.
+#[derive(Debug)]
+pub struct Server<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    port_id: UniqueServerId,
+    pub(crate) response_reference_counter: Vec<AtomicU64>,
+    pub(crate) data_segment: Service::SharedMemory,
+    config: LocalServerConfig,
+
+    client_connections: ClientConnections<'global_config, Service>,
+    client_list_state: UnsafeCell<ContainerState<'a, UniqueClientId>>,
+    pub(crate) loan_counter: AtomicUsize,
+    _dynamic_config_guard: UniqueIndex<'a>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
+    _phantom_request_type: PhantomData<RequestType>,
+    _phantom_response_type: PhantomData<ResponseType>,
+}
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Server<'a, 'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(
+        service: &'a Service,
+        static_config: &request_response::StaticConfig,
+        config: &LocalServerConfig,
+    ) -> Result<Self, ServerCreateError> {
+        let msg = "Unable to create Server port";
+        let origin = "Server::new()";
+        let port_id = UniqueServerId::new();
+        let client_list = &service
+            .state()
+            .dynamic_storage
+            .get()
+            .request_response()
+            .clients;
+
+        let number_of_responses = static_config
+            .required_amount_of_responses_per_data_segment(config.max_loaned_responses);
+
+        let access_settings = service.state().static_config.access_settings();
+        let data_segment = fail!(from origin, when Self::create_data_segment(port_id, service.state().global_config, &access_settings, number_of_responses),
+                with ServerCreateError::UnableToCreateDataSegment,
+                "{} since the data segment could not be acquired.", msg);
+
+        // !MUST! be the last task otherwise a server is added to the dynamic config without the
+        // creation of all required resources
+        let _dynamic_config_guard = match service
+            .state()
+            .dynamic_storage
+            .get()
+            .request_response()
+            .add_server_id(port_id)
+        {
+            Some(unique_index) => unique_index,
+            None => {
+                fail!(from origin, with ServerCreateError::ExceedsMaxSupportedServers,
+                            "{} since it would exceed the maximum supported amount of servers of {}.",
+                            msg, static_config.max_servers);
+            }
+        };
+
+        let new_self = Self {
+            port_id,
+            response_reference_counter: {
+                let mut v = Vec::with_capacity(number_of_responses);
+                for _ in 0..number_of_responses {
+                    v.push(AtomicU64::new(0));
+                }
+                v
+            },
+            data_segment,
+            config: *config,
+            client_connections: ClientConnections::new(
+                client_list.capacity(),
+                port_id,
+                service.state().global_config,
+                static_config,
+                access_settings,
+            ),
+            client_list_state: unsafe { UnsafeCell::new(client_list.get_state()) },
+            loan_counter: AtomicUsize::new(0),
+            _dynamic_config_guard,
+            _node_registration: service.state().register_port(port_id.value()),
+            _phantom_request_type: PhantomData,
+            _phantom_response_type: PhantomData,
+        };
+
+        if let Err(e) = new_self.populate_client_channels() {
+            warn!(from new_self, "The new Server port is unable to connect to every Client port, caused by {:?}.", e);
+        }
+
+        Ok(new_self)
+    }
+
+    fn create_data_segment(
+        port_id: UniqueServerId,
+        global_config: &'global_config global_config::Entries,
+        access_settings: &AccessSettings,
+        number_of_responses: usize,
+    ) -> Result<Service::SharedMemory, SharedMemoryCreateError> {
+        let allocator_config = shm_allocator::pool_allocator::Config {
+            bucket_layout: Layout::new::<Message<ResponseHeader, ResponseType>>(),
+        };
+        let chunk_size = allocator_config.bucket_layout.size();
+        let chunk_align = allocator_config.bucket_layout.align();
+
+        Ok(fail!(from "Server::create_data_segment()",
+            when <<Service::SharedMemory as SharedMemory<PoolAllocator>>::Builder as NamedConceptBuilder<
+            Service::SharedMemory,
+                >>::new(&data_segment_name(port_id))
+                .config(&data_segment_config::<Service>(global_config).access_settings(access_settings))
+                .size(chunk_size * number_of_responses + chunk_align - 1)
+                .create(&allocator_config),
+            "Unable to create the data segment."))
+    }
+
+    fn populate_client_channels(&self) -> Result<(), ClientConnectionFailure> {
+        let mut visited_indices = vec![];
+        visited_indices.resize(self.client_connections.capacity(), None);
+
+        unsafe {
+            (*self.client_list_state.get()).for_each(|index, client_id| {
+                visited_indices[index as usize] = Some(*client_id);
+            })
+        };
+
+        // retrieve responses before destroying channel
+        self.retrieve_returned_responses();
+
+        let mut result = Ok(());
+        for (i, index) in visited_indices.iter().enumerate() {
+            match index {
+                Some(client_id) => {
+                    if let Err(e) = self.client_connections.create(i, *client_id) {
+                        warn!(from self, "Unable to establish connection to new client {:?}.", client_id);
+                        result = Err(e);
+                    }
+                }
+                None => self.client_connections.remove(i),
+            }
+        }
+
+        result
+    }
+
+    fn response_index(distance_to_chunk: usize) -> usize {
+        distance_to_chunk / std::mem::size_of::<Message<ResponseHeader, ResponseType>>()
+    }
+
+    pub(crate) fn release_response(&self, distance_to_chunk: PointerOffset) {
+        if self.response_reference_counter[Self::response_index(distance_to_chunk.value())]
+            .fetch_sub(1, Ordering::Relaxed)
+            == 1
+        {
+            unsafe {
+                fatal_panic!(from self, when self.data_segment
+                .deallocate(
+                    distance_to_chunk,
+                    Layout::new::<Message<ResponseHeader, ResponseType>>(),
+                ), "Internal logic error. The response should always contain a valid memory chunk from the provided allocator.");
+            };
+        }
+    }
+
+    pub(crate) fn release_request(&self, channel_id: usize, request: *const u8) {
+        match self.client_connections.get(channel_id) {
+            Some(c) => {
+                let distance = request as usize - c.data_segment.allocator_data_start_address();
+                match c.request_receiver.release(PointerOffset::new(distance)) {
+                    Ok(()) => (),
+                    Err(ZeroCopyReleaseError::RetrieveBufferFull) => {
+                        fatal_panic!(from self, when c.request_receiver.release(PointerOffset::new(distance)),
+                                    "This should never happen! The clients retrieve channel is full and the request cannot be returned.");
+                    }
+                }
+            }
+            None => {
+                warn!(from self, "Unable to release request since the connection is broken. The request will be discarded and has to be reclaimed manually by the client.");
+            }
+        }
+    }
+
+    fn retrieve_returned_responses(&self) {
+        for i in 0..self.client_connections.len() {
+            match self.client_connections.get(i) {
+                Some(ref connection) => loop {
+                    match connection.response_sender.reclaim() {
+                        Ok(Some(ptr_dist)) => self.release_response(ptr_dist),
+                        Ok(None) => break,
+                        Err(e) => {
+                            warn!(from self, "Unable to reclaim responses from connection {:?} due to {:?}. This may lead to a situation where no more responses will be delivered to this connection.", connection, e);
+                            break;
+                        }
+                    }
+                },
+                None => (),
+            }
+        }
+    }
+
+    pub fn update_connections(&self) -> Result<(), ClientConnectionFailure> {
+        if unsafe { (*self.client_list_state.get()).update() } {
+            fail!(from self, when self.populate_client_channels(),
+                "Connections were updated only partially since at least one connection to a Client port failed.");
+        }
+
+        Ok(())
+    }
+
+    pub fn number_of_clients(&self) -> usize {
+        self.client_connections.number_of_clients()
+    }
+
+    fn receive_from_connection<'server>(
+        &'server self,
+        channel_id: usize,
+        connection: &Connection<'global_config, Service>,
+    ) -> Result<
+        Option<Request<'a, 'server, 'global_config, Service, RequestType, ResponseType>>,
+        ServerReceiveError,
+    > {
+        let msg = "Unable to receive another request";
+        match connection.request_receiver.receive() {
+            Ok(data) => match data {
+                None => Ok(None),
+                Some(relative_addr) => {
+                    let absolute_address = relative_addr.value()
+                        + connection.data_segment.allocator_data_start_address();
+                    Ok(Some(Request {
+                        server: self,
+                        channel_id,
+                        ptr: unsafe {
+                            NonNull::new_unchecked(
+                                absolute_address as *mut Message<RequestHeader, RequestType>,
+                            )
+                        },
+                    }))
+                }
+            },
+            Err(ZeroCopyReceiveError::ReceiveWouldExceedMaxBorrowValue) => {
+                fail!(from self, with ServerReceiveError::ExceedsMaxBorrowedRequests,
+                    "{} since it would exceed the maximum {} of borrowed requests.",
+                    msg, connection.request_receiver.max_borrowed_samples());
+            }
+        }
+    }
+
+    pub fn receive<'server>(
+        &'server self,
+    ) -> Result<
+        Option<Request<'a, 'server, 'global_config, Service, RequestType, ResponseType>>,
+        ServerReceiveError,
+    > {
+        if let Err(e) = self.update_connections() {
+            fail!(from self,
+                with ServerReceiveError::ConnectionFailure(e),
+                "Some requests are not being received since not all connections to clients could be established.");
+        }
+
+        for id in 0..self.client_connections.len() {
+            match self.client_connections.get(id) {
+                Some(ref connection) => {
+                    if let Some(request) = self.receive_from_connection(id, connection)? {
+                        return Ok(Some(request));
+                    }
+                }
+                None => (),
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Loans a response for the provided request. The response is delivered only to the
+    /// [`crate::port::client::Client`] which sent the request.
+    pub fn loan<'server>(
+        &'server self,
+        request_header: &RequestHeader,
+    ) -> Result<
+        ResponseMut<'a, 'server, 'global_config, Service, RequestType, ResponseType>,
+        LoanError,
+    > {
+        self.retrieve_returned_responses();
+        let msg = "Unable to loan Response";
+
+        if self.loan_counter.load(Ordering::Relaxed) >= self.config.max_loaned_responses {
+            fail!(from self, with LoanError::ExceedsMaxLoanedChunks,
+                "{} since already {} responses were loaned and it would exceed the maximum of parallel loans of {}. Release or send a loaned response to loan another response.",
+                msg, self.loan_counter.load(Ordering::Relaxed), self.config.max_loaned_responses);
+        }
+
+        match self
+            .data_segment
+            .allocate(Layout::new::<Message<ResponseHeader, ResponseType>>())
+        {
+            Ok(chunk) => {
+                if self.response_reference_counter[Self::response_index(chunk.offset.value())]
+                    .fetch_add(1, Ordering::Relaxed)
+                    != 0
+                {
+                    fatal_panic!(from self,
+                                "{} since the allocated response is already in use! This should never happen!", msg);
+                }
+
+                let mut chunk_ptr;
+                unsafe {
+                    chunk_ptr = NonNull::new_unchecked(
+                        chunk.data_ptr as *mut MaybeUninit<Message<ResponseHeader, ResponseType>>,
+                    );
+                    let header_ptr =
+                        std::ptr::addr_of_mut!((*chunk_ptr.as_mut().as_mut_ptr()).header);
+                    header_ptr.write(ResponseHeader::new(self.port_id, request_header))
+                }
+
+                Ok(ResponseMut::new(self, chunk_ptr, chunk.offset))
+            }
+            Err(ShmAllocationError::AllocationError(AllocationError::OutOfMemory)) => {
+                fail!(from self, with LoanError::OutOfMemory,
+                    "{} since the underlying shared memory is out of memory.", msg);
+            }
+            Err(ShmAllocationError::AllocationError(AllocationError::SizeTooLarge))
+            | Err(ShmAllocationError::AllocationError(AllocationError::AlignmentFailure)) => {
+                fatal_panic!(from self, "{} since the system seems to be corrupted.", msg);
+            }
+            Err(v) => {
+                fail!(from self, with LoanError::InternalFailure,
+                    "{} since an internal failure occurred ({:?}).", msg, v);
+            }
+        }
+    }
+
+    pub fn send<'server>(
+        &'server self,
+        response: ResponseMut<'a, 'server, 'global_config, Service, RequestType, ResponseType>,
+    ) -> Result<(), ServerSendError> {
+        let msg = "Unable to send response";
+        if let Err(e) = self.update_connections() {
+            fail!(from self, with ServerSendError::ConnectionFailure(e),
+                "{} since the connections could not be updated.", msg);
+        }
+
+        let client_id = response.header().client_id();
+        let address_to_chunk = response.offset_to_chunk();
+
+        for i in 0..self.client_connections.len() {
+            match self.client_connections.get(i) {
+                Some(ref connection) if connection.client_id == client_id => {
+                    match connection.response_sender.try_send(address_to_chunk) {
+                        Ok(overflow) => {
+                            self.response_reference_counter
+                                [Self::response_index(address_to_chunk.value())]
+                            .fetch_add(1, Ordering::Relaxed);
+
+                            if let Some(old) = overflow {
+                                self.release_response(old)
+                            }
+                            return Ok(());
+                        }
+                        Err(ZeroCopySendError::ReceiveBufferFull) => {
+                            fail!(from self, with ServerSendError::ResponseBufferFull,
+                                "{} since the response buffer of the client {:?} is full.", msg, client_id);
+                        }
+                        Err(ZeroCopySendError::ClearRetrieveChannelBeforeSend) => {
+                            fail!(from self, with ServerSendError::RetrieveBufferFull,
+                                "{} via connection {:?} since the retrieve buffer is full. This can be caused by a corrupted retrieve channel.", msg, connection);
+                        }
+                    }
+                }
+                _ => (),
+            }
+        }
+
+        fail!(from self, with ServerSendError::ClientNotConnected,
+            "{} since the client {:?} is not connected.", msg, client_id);
+    }
+
+    pub fn send_copy(
+        &self,
+        request_header: &RequestHeader,
+        value: ResponseType,
+    ) -> Result<(), ServerSendCopyError> {
+        let msg = "Unable to send copy of response";
+        let mut response = fail!(from self, when self.loan(request_header),
+                                    "{} since the loan of a response failed.", msg);
+
+        unsafe { response.as_mut_ptr().write(value) };
+        fail!(from self, when self.send(response),
+            "{} since the underlying send operation failed.", msg);
+        Ok(())
+    }
+}
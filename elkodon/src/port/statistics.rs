@@ -0,0 +1,153 @@
+//! Snapshots of the runtime statistics of the ports of a publish-subscribe service. The
+//! counters are stored in the dynamic service config so that every process which opens the
+//! service, like an external monitor, can read them without creating a port, see
+//! [`crate::service::port_factory::publish_subscribe::PortFactory::statistics()`].
+
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use crate::port::port_identifiers::{UniquePublisherId, UniqueSubscriberId};
+use crate::service::dynamic_config::publish_subscribe::{
+    DynamicConfig, PublisherCounters, SubscriberCounters,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PublisherStatistics {
+    publisher_id: UniquePublisherId,
+    sent_samples: u64,
+    failed_loans: u64,
+    blocked_time: Duration,
+}
+
+impl PublisherStatistics {
+    fn new(publisher_id: UniquePublisherId, counters: &PublisherCounters) -> Self {
+        Self {
+            publisher_id,
+            sent_samples: counters.sent_samples.load(Ordering::Relaxed),
+            failed_loans: counters.failed_loans.load(Ordering::Relaxed),
+            blocked_time: Duration::from_nanos(counters.blocked_time_in_ns.load(Ordering::Relaxed)),
+        }
+    }
+
+    pub fn publisher_id(&self) -> UniquePublisherId {
+        self.publisher_id
+    }
+
+    /// Returns the number of sent samples, independent of the number of recipients.
+    pub fn sent_samples(&self) -> u64 {
+        self.sent_samples
+    }
+
+    /// Returns the number of loans which failed since the data segment was out of memory.
+    pub fn failed_loans(&self) -> u64 {
+        self.failed_loans
+    }
+
+    /// Returns the accumulated time the publisher was blocked while delivering samples to full
+    /// subscriber buffers.
+    pub fn blocked_time(&self) -> Duration {
+        self.blocked_time
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubscriberStatistics {
+    subscriber_id: UniqueSubscriberId,
+    received_samples: u64,
+    samples_dropped_by_full_buffer: u64,
+    samples_dropped_by_overflow: u64,
+    disconnections: u64,
+}
+
+impl SubscriberStatistics {
+    fn new(subscriber_id: UniqueSubscriberId, counters: &SubscriberCounters) -> Self {
+        Self {
+            subscriber_id,
+            received_samples: counters.received_samples.load(Ordering::Relaxed),
+            samples_dropped_by_full_buffer: counters
+                .samples_dropped_by_full_buffer
+                .load(Ordering::Relaxed),
+            samples_dropped_by_overflow: counters
+                .samples_dropped_by_overflow
+                .load(Ordering::Relaxed),
+            disconnections: counters.disconnections.load(Ordering::Relaxed),
+        }
+    }
+
+    pub fn subscriber_id(&self) -> UniqueSubscriberId {
+        self.subscriber_id
+    }
+
+    pub fn received_samples(&self) -> u64 {
+        self.received_samples
+    }
+
+    /// Returns the number of samples which were not delivered since the buffer of the
+    /// subscriber was full.
+    pub fn samples_dropped_by_full_buffer(&self) -> u64 {
+        self.samples_dropped_by_full_buffer
+    }
+
+    /// Returns the number of unread samples which were replaced by newer ones since the service
+    /// has safe overflow enabled.
+    pub fn samples_dropped_by_overflow(&self) -> u64 {
+        self.samples_dropped_by_overflow
+    }
+
+    /// Returns the number of publishers which stopped delivering samples to the subscriber
+    /// since it was unable to receive a sample within their delivery timeout.
+    pub fn disconnections(&self) -> u64 {
+        self.disconnections
+    }
+}
+
+/// The statistics of all publishers and subscribers which are connected to a service at the
+/// time the snapshot was acquired.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Statistics {
+    publishers: Vec<PublisherStatistics>,
+    subscribers: Vec<SubscriberStatistics>,
+}
+
+impl Statistics {
+    pub(crate) fn new(dynamic_config: &DynamicConfig) -> Self {
+        let mut publishers = vec![];
+        unsafe { dynamic_config.publishers.get_state() }.for_each(|index, id| {
+            publishers.push(PublisherStatistics::new(
+                *id,
+                dynamic_config.publisher_counters(index as usize),
+            ))
+        });
+
+        let mut subscribers = vec![];
+        unsafe { dynamic_config.subscribers.get_state() }.for_each(|index, id| {
+            subscribers.push(SubscriberStatistics::new(
+                *id,
+                dynamic_config.subscriber_counters(index as usize),
+            ))
+        });
+
+        Self {
+            publishers,
+            subscribers,
+        }
+    }
+
+    pub fn publishers(&self) -> &[PublisherStatistics] {
+        &self.publishers
+    }
+
+    pub fn subscribers(&self) -> &[SubscriberStatistics] {
+        &self.subscribers
+    }
+
+    /// Returns the statistics of the publisher with the provided id.
+    pub fn publisher(&self, id: UniquePublisherId) -> Option<&PublisherStatistics> {
+        self.publishers.iter().find(|p| p.publisher_id == id)
+    }
+
+    /// Returns the statistics of the subscriber with the provided id.
+    pub fn subscriber(&self, id: UniqueSubscriberId) -> Option<&SubscriberStatistics> {
+        self.subscribers.iter().find(|s| s.subscriber_id == id)
+    }
+}
@@ -1,32 +1,59 @@
-use std::cell::UnsafeCell;
+use std::cell::{Cell, UnsafeCell};
 use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{marker::PhantomData, ptr::NonNull};
 
 use elkodon_cal::dynamic_storage::DynamicStorage;
+use elkodon_cal::event::{Event, Listener, ListenerBuilder};
+use elkodon_cal::named_concept::NamedConceptBuilder;
 use elkodon_cal::{shared_memory::*, zero_copy_connection::*};
+use elkodon_bb_elementary::enum_gen;
 use elkodon_bb_lock_free::mpmc::container::ContainerState;
 use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
 use elkodon_bb_log::{fail, fatal_panic, warn};
 
+use crate::node::PortRegistration;
 use crate::port::DegrationAction;
+use crate::service::port_factory::publish_subscribe::remove_dead_ports;
+use crate::service::port_factory::subscriber::LocalSubscriberConfig;
 use crate::service::static_config::publish_subscribe::StaticConfig;
+use crate::service::{event_config, subscriber_event_concept_name};
 use crate::{
-    message::Message, sample::Sample, service, service::header::publish_subscribe::Header,
+    message::{Message, Payload},
+    sample::Sample,
+    service,
+    service::header::publish_subscribe::Header,
 };
 
+use super::deadline::{self, MissedDeadline};
 use super::details::publisher_connections::{Connection, ConnectionFailure, PublisherConnections};
+use super::liveliness::{heartbeat_timestamp, LivelinessChange, LivelinessState};
 use super::port_identifiers::{UniquePublisherId, UniqueSubscriberId};
+use super::sample_loss::{SampleLoss, SampleLossCallback};
 use super::DegrationCallback;
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum ReceiveError {
     ExceedsMaxBorrowedSamples,
     ConnectionFailure(ConnectionFailure),
+    EventsNotEnabled,
+    WaitFailure,
+}
+
+enum_gen! { ReceiveDeserializedError
+  entry:
+    SerializationFormatNotDefined,
+    DeserializationFailure
+  mapping:
+    ReceiveError
 }
 
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub enum SubscriberCreateError {
     ExceedsMaxSupportedSubscribers,
+    ResourceCreationFailed,
 }
 
 #[derive(Debug)]
@@ -34,23 +61,46 @@ pub struct Subscriber<
     'a,
     'global_config: 'a,
     Service: service::Details<'global_config>,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug = (),
 > {
     dynamic_config_guard: Option<UniqueIndex<'a>>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
+    // only present when the service has events enabled, the publishers notify it on every
+    // delivered sample
+    listener: Option<<Service::Event as Event<u64>>::Listener>,
     publisher_connections: PublisherConnections<'global_config, Service>,
     service: &'a Service,
     degration_callback: Option<DegrationCallback<'a>>,
+    sample_loss_callback: Option<SampleLossCallback<'a>>,
+    config: LocalSubscriberConfig,
+    max_heartbeat_interval: Option<Duration>,
+    // publishers which violated the liveliness contract and are never connected again
+    closed_publishers: UnsafeCell<Vec<UniquePublisherId>>,
+    missed_deadlines: Cell<u64>,
+    // the time of the next deadline miss of all publishers in nanoseconds of the clock of
+    // deadline::now(), a WaitSet derives the wake-up of the attached deadline from it
+    next_deadline_miss: Arc<AtomicU64>,
+    lost_samples: Cell<u64>,
 
     publisher_list_state: UnsafeCell<ContainerState<'a, UniquePublisherId>>,
     _phantom_message_type: PhantomData<MessageType>,
+    _phantom_user_header: PhantomData<UserHeader>,
 }
 
-impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageType: Debug>
-    Subscriber<'a, 'global_config, Service, MessageType>
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug,
+    > Subscriber<'a, 'global_config, Service, MessageType, UserHeader>
 {
     pub(crate) fn new(
         service: &'a Service,
         static_config: &StaticConfig,
+        config: &LocalSubscriberConfig,
     ) -> Result<Self, SubscriberCreateError> {
         let msg = "Failed to create Subscriber port";
         let origin = "Subscriber::new()";
@@ -63,18 +113,40 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
             .publish_subscribe()
             .publishers;
 
+        let listener = if static_config.enable_events {
+            Some(fail!(from origin,
+                    when <Service::Event as Event<u64>>::ListenerBuilder::new(&subscriber_event_concept_name(&port_id))
+                        .config(&event_config::<Service>(&service.state().static_config))
+                        .create(),
+                    with SubscriberCreateError::ResourceCreationFailed,
+                    "{} since the underlying event concept could not be created.", msg))
+        } else {
+            None
+        };
+
         let mut new_self = Self {
+            listener,
             publisher_connections: PublisherConnections::new(
                 publisher_list.capacity(),
                 port_id,
                 service.state().global_config,
                 static_config,
+                service.state().static_config.access_settings(),
             ),
             publisher_list_state: UnsafeCell::new(unsafe { publisher_list.get_state() }),
             dynamic_config_guard: None,
+            _node_registration: service.state().register_port(port_id.value()),
             service,
             degration_callback: None,
+            sample_loss_callback: None,
+            config: *config,
+            max_heartbeat_interval: static_config.max_heartbeat_interval,
+            closed_publishers: UnsafeCell::new(vec![]),
+            missed_deadlines: Cell::new(0),
+            next_deadline_miss: Arc::new(AtomicU64::new(0)),
+            lost_samples: Cell::new(0),
             _phantom_message_type: PhantomData,
+            _phantom_user_header: PhantomData,
         };
 
         if let Err(e) = new_self.populate_publisher_channels() {
@@ -83,27 +155,27 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
 
         // !MUST! be the last task otherwise a subscriber is added to the dynamic config without
         // the creation of all required channels
-        new_self.dynamic_config_guard = Some(
-            match service
-                .state()
-                .dynamic_storage
-                .get()
-                .publish_subscribe()
-                .add_subscriber_id(port_id)
-            {
-                Some(unique_index) => unique_index,
-                None => {
-                    fail!(from origin, with SubscriberCreateError::ExceedsMaxSupportedSubscribers,
-                                "{} since it would exceed the maximum supported amount of subscribers of {}.",
-                                msg, service.state().static_config.publish_subscribe().max_subscribers);
-                }
-            },
-        );
+        let dynamic_config = service.state().dynamic_storage.get().publish_subscribe();
+        let history_request = config.history_request.unwrap_or(usize::MAX);
+        let mut unique_index = dynamic_config.add_subscriber_id(port_id, history_request);
+        // the slots may still be occupied by ports of dead processes
+        if unique_index.is_none() && remove_dead_ports(service) > 0 {
+            unique_index = dynamic_config.add_subscriber_id(port_id, history_request);
+        }
+
+        new_self.dynamic_config_guard = Some(match unique_index {
+            Some(unique_index) => unique_index,
+            None => {
+                fail!(from origin, with SubscriberCreateError::ExceedsMaxSupportedSubscribers,
+                            "{} since it would exceed the maximum supported amount of subscribers of {}.",
+                            msg, service.state().static_config.publish_subscribe().max_subscribers);
+            }
+        });
 
         Ok(new_self)
     }
 
-    pub(crate) fn release_sample(&self, channel_id: usize, message: *const MessageType) {
+    pub(crate) fn release_sample(&self, channel_id: usize, message: *const u8) {
         match self.publisher_connections.get(channel_id) {
             Some(c) => {
                 let distance = message as usize - c.data_segment.allocator_data_start_address();
@@ -131,9 +203,14 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
             })
         };
 
+        // a closed publisher which is gone will never be seen again
+        unsafe { &mut *self.closed_publishers.get() }
+            .retain(|id| visited_indices.contains(&Some(*id)));
+
         // update all connections
         for (i, index) in visited_indices.iter().enumerate() {
             match index {
+                Some(publisher_id) if self.is_closed(publisher_id) => (),
                 Some(publisher_id) => match self.publisher_connections.create(i, *publisher_id) {
                     Ok(()) => (),
                     Err(e) => match &self.degration_callback {
@@ -164,12 +241,16 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         Ok(())
     }
 
+    fn is_closed(&self, publisher_id: &UniquePublisherId) -> bool {
+        unsafe { &*self.closed_publishers.get() }.contains(publisher_id)
+    }
+
     fn receive_from_connection<'subscriber>(
         &'subscriber self,
         channel_id: usize,
         connection: &mut Connection<'global_config, Service>,
     ) -> Result<
-        Option<Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType>>,
+        Option<Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>>,
         ReceiveError,
     > {
         let msg = "Unable to receive another sample";
@@ -179,12 +260,44 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                 Some(relative_addr) => {
                     let absolute_address = relative_addr.value()
                         + connection.data_segment.allocator_data_start_address();
+                    let header = unsafe { &*(absolute_address as *const Header) };
+                    let number_of_elements = header.number_of_elements();
+                    if let Some(deadline) = self.config.deadline {
+                        let new_misses = connection
+                            .deadline_monitor
+                            .sample_received(header.time_stamp().as_duration(), deadline);
+                        self.missed_deadlines
+                            .set(self.missed_deadlines.get() + new_misses);
+                    }
+                    if let Some(loss) = connection
+                        .sequence_tracker
+                        .sample_received(connection.publisher_id, header.sequence_number())
+                    {
+                        self.lost_samples
+                            .set(self.lost_samples.get() + loss.number_of_lost_samples());
+                        if let Some(callback) = &self.sample_loss_callback {
+                            callback.call(loss);
+                        }
+                    }
+                    if let Some(index) = &self.dynamic_config_guard {
+                        self.service
+                            .state()
+                            .dynamic_storage
+                            .get()
+                            .publish_subscribe()
+                            .subscriber_counters(index.value() as usize)
+                            .received_samples
+                            .fetch_add(1, Ordering::Relaxed);
+                    }
                     Ok(Some(Sample {
                         subscriber: self,
                         channel_id,
                         ptr: unsafe {
                             NonNull::new_unchecked(
-                                absolute_address as *mut Message<Header, MessageType>,
+                                Message::<Header, MessageType, UserHeader>::from_raw_parts(
+                                    absolute_address as *mut u8,
+                                    number_of_elements,
+                                ),
                             )
                         },
                     }))
@@ -215,10 +328,61 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         }
     }
 
+    /// Sets the callback which is called on [`Subscriber::receive()`] whenever a gap in the
+    /// samples of a publisher is detected, see [`crate::port::sample_loss`].
+    pub fn set_sample_loss_callback<F: Fn(SampleLoss) + 'a>(&mut self, callback: Option<F>) {
+        match callback {
+            Some(c) => self.sample_loss_callback = Some(SampleLossCallback::new(c)),
+            None => self.sample_loss_callback = None,
+        }
+    }
+
+    pub(crate) fn event_listener(&self) -> Option<&<Service::Event as Event<u64>>::Listener> {
+        self.listener.as_ref()
+    }
+
+    fn discard_notifications(&self) -> Result<(), ReceiveError> {
+        if let Some(listener) = &self.listener {
+            while fail!(from self, when listener.try_wait(),
+                    with ReceiveError::WaitFailure,
+                    "Unable to acquire the pending notifications from the underlying event concept.")
+            .is_some()
+            {}
+        }
+
+        Ok(())
+    }
+
+    fn wait_for_notification(&self, timeout: Option<Duration>) -> Result<(), ReceiveError> {
+        let msg = "Unable to wait for new samples";
+        let listener = match &self.listener {
+            Some(listener) => listener,
+            None => {
+                fail!(from self, with ReceiveError::EventsNotEnabled,
+                    "{} since the service does not have events enabled.", msg);
+            }
+        };
+
+        let result = match timeout {
+            Some(timeout) => listener.timed_wait(timeout),
+            None => listener.blocking_wait(),
+        };
+
+        if let Err(e) = result {
+            fail!(from self, with ReceiveError::WaitFailure,
+                "{} due to a failure in the underlying event concept ({:?}).", msg, e);
+        }
+
+        Ok(())
+    }
+
+    /// Receives a sample. When the service has events enabled, the pending notifications are
+    /// discarded as soon as no sample is left, so that a [`crate::waitset::WaitSet`] keeps
+    /// waking up as long as samples are available and afterwards only for new samples.
     pub fn receive<'subscriber>(
         &'subscriber self,
     ) -> Result<
-        Option<Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType>>,
+        Option<Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>>,
         ReceiveError,
     > {
         if let Err(e) = self.update_connections() {
@@ -227,10 +391,27 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
                 "Some samples are not being received since not all connections to publishers could be established.");
         }
 
+        if let Some(sample) = self.receive_from_publishers()? {
+            return Ok(Some(sample));
+        }
+
+        // a sample which was delivered after the connections were checked may have been
+        // announced by one of the discarded notifications
+        self.discard_notifications()?;
+        self.receive_from_publishers()
+    }
+
+    fn receive_from_publishers<'subscriber>(
+        &'subscriber self,
+    ) -> Result<
+        Option<Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>>,
+        ReceiveError,
+    > {
         for id in 0..self.publisher_connections.len() {
             match &mut self.publisher_connections.get_mut(id) {
                 Some(ref mut connection) => {
                     if let Some(sample) = self.receive_from_connection(id, connection)? {
+                        self.update_next_deadline_miss();
                         return Ok(Some(sample));
                     }
                 }
@@ -241,6 +422,46 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         Ok(None)
     }
 
+    /// Blocks until a sample was received. Requires a service with events enabled.
+    pub fn blocking_receive<'subscriber>(
+        &'subscriber self,
+    ) -> Result<
+        Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>,
+        ReceiveError,
+    > {
+        loop {
+            if let Some(sample) = self.receive()? {
+                return Ok(sample);
+            }
+
+            self.wait_for_notification(None)?;
+        }
+    }
+
+    /// Blocks until a sample was received or the timeout has passed. Requires a service with
+    /// events enabled.
+    pub fn timed_receive<'subscriber>(
+        &'subscriber self,
+        timeout: Duration,
+    ) -> Result<
+        Option<Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>>,
+        ReceiveError,
+    > {
+        let start = Instant::now();
+        loop {
+            if let Some(sample) = self.receive()? {
+                return Ok(Some(sample));
+            }
+
+            let remaining_timeout = timeout.saturating_sub(start.elapsed());
+            if remaining_timeout == Duration::ZERO {
+                return Ok(None);
+            }
+
+            self.wait_for_notification(Some(remaining_timeout))?;
+        }
+    }
+
     pub fn update_connections(&self) -> Result<(), ConnectionFailure> {
         if unsafe { (*self.publisher_list_state.get()).update() } {
             fail!(from self, when self.populate_publisher_channels(),
@@ -250,7 +471,237 @@ impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, MessageT
         Ok(())
     }
 
+    /// Checks the liveliness of all connected publishers and calls the callback for every
+    /// publisher whose [`LivelinessState`] changed since the last check. Returns the number of
+    /// reported changes. Without a liveliness contract every publisher is considered as alive.
+    pub fn check_liveliness<F: FnMut(LivelinessChange)>(
+        &self,
+        mut callback: F,
+    ) -> Result<usize, ConnectionFailure> {
+        let max_heartbeat_interval = match self.max_heartbeat_interval {
+            Some(v) => v,
+            None => return Ok(0),
+        };
+
+        fail!(from self, when self.update_connections(),
+            "Unable to check the liveliness of all publishers since the connections could not be updated.");
+
+        let dynamic_config = self
+            .service
+            .state()
+            .dynamic_storage
+            .get()
+            .publish_subscribe();
+        let now = heartbeat_timestamp();
+        let mut number_of_changes = 0;
+        for i in 0..self.publisher_connections.len() {
+            let connection = match self.publisher_connections.get(i) {
+                Some(connection) => connection,
+                None => continue,
+            };
+
+            // the connections are stored at the index of the publisher in the dynamic config
+            let last_heartbeat = dynamic_config
+                .publisher_counters(i)
+                .last_heartbeat_in_ns
+                .load(Ordering::Relaxed);
+            let time_since_last_heartbeat =
+                Duration::from_nanos(now.saturating_sub(last_heartbeat));
+            let state = if time_since_last_heartbeat > max_heartbeat_interval {
+                LivelinessState::Violated
+            } else {
+                LivelinessState::Alive
+            };
+
+            if state == connection.liveliness.get() {
+                continue;
+            }
+
+            connection.liveliness.set(state);
+            number_of_changes += 1;
+            let publisher_id = connection.publisher_id;
+            callback(LivelinessChange::new(
+                publisher_id,
+                state,
+                time_since_last_heartbeat,
+            ));
+
+            if state == LivelinessState::Violated
+                && self.config.close_connection_on_liveliness_violation
+            {
+                warn!(from self, "Closing the connection to publisher {:?} since it violated the liveliness contract.", publisher_id);
+                unsafe { &mut *self.closed_publishers.get() }.push(publisher_id);
+                self.publisher_connections.remove(i);
+            }
+        }
+
+        Ok(number_of_changes)
+    }
+
+    /// Calls the callback for every connected publisher which missed deadlines since the last
+    /// check and returns the number of reported publishers. Without a deadline nothing is
+    /// reported, see [`crate::port::deadline`].
+    pub fn check_deadlines<F: FnMut(MissedDeadline)>(
+        &self,
+        mut callback: F,
+    ) -> Result<usize, ConnectionFailure> {
+        let deadline = match self.config.deadline {
+            Some(v) => v,
+            None => return Ok(0),
+        };
+
+        fail!(from self, when self.update_connections(),
+            "Unable to check the deadlines of all publishers since the connections could not be updated.");
+
+        let now = deadline::now();
+        let mut number_of_reports = 0;
+        for i in 0..self.publisher_connections.len() {
+            if let Some(connection) = self.publisher_connections.get(i) {
+                let new_misses = connection.deadline_monitor.update(now, deadline);
+                self.missed_deadlines
+                    .set(self.missed_deadlines.get() + new_misses);
+
+                if let Some(report) = connection
+                    .deadline_monitor
+                    .report(connection.publisher_id, now)
+                {
+                    number_of_reports += 1;
+                    callback(report);
+                }
+            }
+        }
+        self.update_next_deadline_miss();
+
+        Ok(number_of_reports)
+    }
+
+    fn update_next_deadline_miss(&self) {
+        if let Some(deadline) = self.config.deadline {
+            let next_miss = (0..self.publisher_connections.len())
+                .filter_map(|i| {
+                    self.publisher_connections
+                        .get(i)
+                        .as_ref()
+                        .map(|connection| connection.deadline_monitor.next_miss(deadline))
+                })
+                .min()
+                .unwrap_or(Duration::ZERO);
+            self.next_deadline_miss
+                .store(next_miss.as_nanos() as u64, Ordering::Relaxed);
+        }
+    }
+
+    pub(crate) fn next_deadline_miss(&self) -> Arc<AtomicU64> {
+        self.next_deadline_miss.clone()
+    }
+
+    /// Returns the deadline in which every publisher is expected to send a sample.
+    pub fn deadline(&self) -> Option<Duration> {
+        self.config.deadline
+    }
+
+    /// Returns the total number of deadlines which were missed by all publishers. Deadlines
+    /// are counted when a sample is received or in [`Subscriber::check_deadlines()`].
+    pub fn missed_deadlines(&self) -> u64 {
+        self.missed_deadlines.get()
+    }
+
+    /// Requests the newest `max_samples` samples of the history from every connected publisher.
+    /// A publisher serves the request only on its next send or
+    /// [`crate::port::publisher::Publisher::update_connections()`], a publisher which does
+    /// neither never answers it. The delivered history is limited by the history size of the
+    /// publisher and the buffer of the subscriber. Samples which were already received are
+    /// delivered again.
+    pub fn request_history(&self, max_samples: usize) {
+        if let Some(index) = &self.dynamic_config_guard {
+            let counters = self
+                .service
+                .state()
+                .dynamic_storage
+                .get()
+                .publish_subscribe()
+                .subscriber_counters(index.value() as usize);
+            counters
+                .requested_history_size
+                .store(max_samples as u64, Ordering::Relaxed);
+            counters.history_request_id.fetch_add(1, Ordering::Release);
+        }
+    }
+
+    /// Returns the number of publishers which stopped delivering samples to the subscriber
+    /// since its buffer stayed full for the whole timeout of
+    /// [`crate::service::port_factory::publisher::UnableToDeliverStrategy::BlockWithTimeout`],
+    /// see [`crate::port::DeliveryTimeoutAction::Disconnect`].
+    pub fn number_of_disconnections(&self) -> u64 {
+        match &self.dynamic_config_guard {
+            Some(index) => self
+                .service
+                .state()
+                .dynamic_storage
+                .get()
+                .publish_subscribe()
+                .subscriber_counters(index.value() as usize)
+                .disconnections
+                .load(Ordering::Relaxed),
+            None => 0,
+        }
+    }
+
+    /// Returns the total number of samples which were lost, independent of the publisher.
+    pub fn lost_samples(&self) -> u64 {
+        self.lost_samples.get()
+    }
+
+    /// Returns the number of samples which were lost from the connected publisher with the
+    /// provided id.
+    pub fn lost_samples_of(&self, publisher_id: UniquePublisherId) -> Option<u64> {
+        (0..self.publisher_connections.len()).find_map(|i| {
+            self.publisher_connections
+                .get(i)
+                .as_ref()
+                .filter(|connection| connection.publisher_id == publisher_id)
+                .map(|connection| connection.sequence_tracker.lost_samples())
+        })
+    }
+
+    pub fn id(&self) -> UniqueSubscriberId {
+        self.publisher_connections.subscriber_id()
+    }
+
     pub fn number_of_publishers(&self) -> usize {
         self.publisher_connections.number_of_publishers()
     }
 }
+
+impl<'a, 'global_config: 'a, Service: service::Details<'global_config>, UserHeader: Debug>
+    Subscriber<'a, 'global_config, Service, [u8], UserHeader>
+{
+    /// Receives a sample and deserializes it with the
+    /// [`crate::service::serialization::SerializationFormat`] of the service. The sample is
+    /// released right after the value was deserialized.
+    pub fn receive_deserialized<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<Option<T>, ReceiveDeserializedError> {
+        let msg = "Unable to receive deserialized value";
+        let format = match self
+            .service
+            .state()
+            .static_config
+            .publish_subscribe()
+            .serialization_format
+        {
+            Some(format) => format,
+            None => {
+                fail!(from self, with ReceiveDeserializedError::SerializationFormatNotDefined,
+                    "{} since the service has no serialization format defined.", msg);
+            }
+        };
+
+        match fail!(from self, when self.receive(), "{} since the receive failed.", msg) {
+            None => Ok(None),
+            Some(sample) => Ok(Some(fail!(from self, when format.deserialize(&sample),
+                    with ReceiveDeserializedError::DeserializationFailure,
+                    "{} since the received sample could not be deserialized.", msg))),
+        }
+    }
+}
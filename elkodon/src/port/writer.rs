@@ -0,0 +1,156 @@
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use elkodon_bb_lock_free::mpmc::unique_index_set::UniqueIndex;
+use elkodon_bb_lock_free::spmc::unrestricted_atomic::Producer;
+use elkodon_bb_log::fail;
+use elkodon_cal::dynamic_storage::DynamicStorage;
+
+use crate::node::PortRegistration;
+use crate::port::details::blackboard_entries::{lookup_entry, EntryLookupFailure};
+use crate::port::port_identifiers::UniqueWriterId;
+use crate::service;
+use crate::service::dynamic_config::blackboard::DynamicConfig;
+use crate::service::port_factory::blackboard::remove_dead_ports;
+use crate::zero_copy_type::ZeroCopyType;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WriterCreateError {
+    ExceedsMaxSupportedWriters,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WriterEntryError {
+    EntryDoesNotExist,
+    IncompatibleValueType,
+    EntryIsAlreadyOwnedByAnotherWriter,
+}
+
+/// Exclusive write access to a single entry of the blackboard. As long as the [`WriterEntry`]
+/// exists no other [`Writer`] can acquire the same entry.
+pub struct WriterEntry<'writer, ValueType: Copy + Debug> {
+    producer: Producer<'writer, ValueType>,
+    // !MUST! be dropped after the producer, otherwise a writer that dies in between leaks the
+    // producer since it is no longer recorded as owner of the entry
+    _ownership: EntryOwnership<'writer>,
+}
+
+// records the writer as owner of the entry so that the entry can be released when it dies
+struct EntryOwnership<'writer> {
+    dynamic_config: &'writer DynamicConfig,
+    index: usize,
+}
+
+impl<'writer> Drop for EntryOwnership<'writer> {
+    fn drop(&mut self) {
+        self.dynamic_config.release_entry(self.index);
+    }
+}
+
+impl<'writer, ValueType: Copy + Debug> Debug for WriterEntry<'writer, ValueType> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "WriterEntry<{}> {{ }}",
+            std::any::type_name::<ValueType>()
+        )
+    }
+}
+
+impl<'writer, ValueType: Copy + Debug> WriterEntry<'writer, ValueType> {
+    pub fn update(&self, value: ValueType) {
+        self.producer.store(value)
+    }
+}
+
+/// Updates the entries of a blackboard.
+#[derive(Debug)]
+pub struct Writer<
+    'a,
+    'global_config: 'a,
+    Service: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug,
+> {
+    service: &'a Service,
+    dynamic_config_guard: UniqueIndex<'a>,
+    // removes the port from the registry entry of the node when it is dropped
+    _node_registration: PortRegistration<'a>,
+    _phantom_key_type: PhantomData<KeyType>,
+    _phantom_lifetime_b: PhantomData<&'global_config ()>,
+}
+
+impl<
+        'a,
+        'global_config: 'a,
+        Service: service::Details<'global_config>,
+        KeyType: Copy + Eq + Debug,
+    > Writer<'a, 'global_config, Service, KeyType>
+{
+    pub(crate) fn new(service: &'a Service) -> Result<Self, WriterCreateError> {
+        let msg = "Failed to create Writer port";
+        let origin = "Writer::new()";
+        let port_id = UniqueWriterId::new();
+
+        let dynamic_config = service.state().dynamic_storage.get().blackboard();
+        let mut unique_index = dynamic_config.add_writer_id(port_id);
+        // the slots may still be occupied by writers of dead processes
+        if unique_index.is_none() && remove_dead_ports(service) > 0 {
+            unique_index = dynamic_config.add_writer_id(port_id);
+        }
+
+        let dynamic_config_guard = match unique_index {
+            Some(unique_index) => unique_index,
+            None => {
+                fail!(from origin, with WriterCreateError::ExceedsMaxSupportedWriters,
+                    "{} since it would exceed the maximum supported amount of writers of {}.",
+                    msg, service.state().static_config.blackboard().max_writers);
+            }
+        };
+
+        Ok(Self {
+            service,
+            dynamic_config_guard,
+            _node_registration: service.state().register_port(port_id.value()),
+            _phantom_key_type: PhantomData,
+            _phantom_lifetime_b: PhantomData,
+        })
+    }
+
+    pub fn entry<'writer, ValueType: Copy + Debug + ZeroCopyType>(
+        &'writer self,
+        key: &KeyType,
+    ) -> Result<WriterEntry<'writer, ValueType>, WriterEntryError> {
+        let msg = "Unable to acquire blackboard entry for writing";
+        match lookup_entry::<Service, KeyType, ValueType>(self.service, key) {
+            Ok((index, atomic)) => {
+                // the ownership is recorded first so that the producer of a dead writer can
+                // always be released
+                let dynamic_config = self.service.state().dynamic_storage.get().blackboard();
+                if dynamic_config.acquire_entry(index, self.dynamic_config_guard.value()) {
+                    let _ownership = EntryOwnership {
+                        dynamic_config,
+                        index,
+                    };
+                    if let Some(producer) = atomic.acquire_producer() {
+                        return Ok(WriterEntry {
+                            producer,
+                            _ownership,
+                        });
+                    }
+                }
+
+                fail!(from self, with WriterEntryError::EntryIsAlreadyOwnedByAnotherWriter,
+                    "{} since the entry with the key {:?} is already owned by another writer.", msg, key);
+            }
+            Err(EntryLookupFailure::EntryDoesNotExist) => {
+                fail!(from self, with WriterEntryError::EntryDoesNotExist,
+                    "{} since the key {:?} does not exist.", msg, key);
+            }
+            Err(EntryLookupFailure::IncompatibleValueType) => {
+                fail!(from self, with WriterEntryError::IncompatibleValueType,
+                    "{} since the entry with the key {:?} does not store values of type \"{}\".",
+                    msg, key, ValueType::type_identifier());
+            }
+        }
+    }
+}
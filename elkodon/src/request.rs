@@ -0,0 +1,64 @@
+use std::{fmt::Debug, ops::Deref, ptr::NonNull};
+
+use crate::{
+    message::Message, port::server::Server, service,
+    service::header::request_response::RequestHeader,
+};
+
+#[derive(Debug)]
+pub struct Request<
+    'a,
+    'server,
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    pub(crate) server: &'server Server<'a, 'global_config, Service, RequestType, ResponseType>,
+    pub(crate) ptr: NonNull<Message<RequestHeader, RequestType>>,
+    pub(crate) channel_id: usize,
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Deref for Request<'_, '_, 'global_config, Service, RequestType, ResponseType>
+{
+    type Target = RequestType;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &(*self.ptr.as_ptr()).data }
+    }
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Drop for Request<'_, '_, 'global_config, Service, RequestType, ResponseType>
+{
+    fn drop(&mut self) {
+        self.server
+            .release_request(self.channel_id, self.ptr.as_ptr() as *const u8);
+    }
+}
+
+impl<
+        'a,
+        'server,
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Request<'a, 'server, 'global_config, Service, RequestType, ResponseType>
+{
+    pub fn as_ptr(&self) -> *const RequestType {
+        &unsafe { self.ptr.as_ref() }.data
+    }
+
+    pub fn header(&self) -> &RequestHeader {
+        &unsafe { self.ptr.as_ref() }.header
+    }
+}
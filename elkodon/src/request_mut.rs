@@ -0,0 +1,77 @@
+use elkodon_cal::shared_memory::*;
+use std::{fmt::Debug, mem::MaybeUninit, ptr::NonNull, sync::atomic::Ordering};
+
+use crate::{
+    message::Message, port::client::Client, service,
+    service::header::request_response::RequestHeader,
+};
+
+/// # Important
+///
+/// Does not implement [`Send`] since it releases unsent requests in the [`Client`] and the
+/// [`Client`] is not thread-safe!
+#[derive(Debug)]
+pub struct RequestMut<
+    'a,
+    'client,
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    client: &'client Client<'a, 'global_config, Service, RequestType, ResponseType>,
+    ptr: NonNull<MaybeUninit<Message<RequestHeader, RequestType>>>,
+    offset_to_chunk: PointerOffset,
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Drop for RequestMut<'_, '_, 'global_config, Service, RequestType, ResponseType>
+{
+    fn drop(&mut self) {
+        self.client.release_request(self.offset_to_chunk);
+        self.client.loan_counter.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<
+        'a,
+        'client,
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > RequestMut<'a, 'client, 'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(
+        client: &'client Client<'a, 'global_config, Service, RequestType, ResponseType>,
+        ptr: NonNull<MaybeUninit<Message<RequestHeader, RequestType>>>,
+        offset_to_chunk: PointerOffset,
+    ) -> Self {
+        client.loan_counter.fetch_add(1, Ordering::Relaxed);
+        Self {
+            client,
+            ptr,
+            offset_to_chunk,
+        }
+    }
+
+    pub(crate) fn offset_to_chunk(&self) -> PointerOffset {
+        self.offset_to_chunk
+    }
+
+    pub fn header(&self) -> &RequestHeader {
+        &unsafe { &*self.ptr.as_ref().as_ptr() }.header
+    }
+
+    pub fn as_ptr(&self) -> *const RequestType {
+        &unsafe { &*self.ptr.as_ref().as_ptr() }.data
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut RequestType {
+        &mut unsafe { &mut *self.ptr.as_mut().as_mut_ptr() }.data
+    }
+}
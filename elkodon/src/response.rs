@@ -0,0 +1,64 @@
+use std::{fmt::Debug, ops::Deref, ptr::NonNull};
+
+use crate::{
+    message::Message, port::client::Client, service,
+    service::header::request_response::ResponseHeader,
+};
+
+#[derive(Debug)]
+pub struct Response<
+    'a,
+    'client,
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    pub(crate) client: &'client Client<'a, 'global_config, Service, RequestType, ResponseType>,
+    pub(crate) ptr: NonNull<Message<ResponseHeader, ResponseType>>,
+    pub(crate) channel_id: usize,
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Deref for Response<'_, '_, 'global_config, Service, RequestType, ResponseType>
+{
+    type Target = ResponseType;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &(*self.ptr.as_ptr()).data }
+    }
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Drop for Response<'_, '_, 'global_config, Service, RequestType, ResponseType>
+{
+    fn drop(&mut self) {
+        self.client
+            .release_response(self.channel_id, self.ptr.as_ptr() as *const u8);
+    }
+}
+
+impl<
+        'a,
+        'client,
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Response<'a, 'client, 'global_config, Service, RequestType, ResponseType>
+{
+    pub fn as_ptr(&self) -> *const ResponseType {
+        &unsafe { self.ptr.as_ref() }.data
+    }
+
+    pub fn header(&self) -> &ResponseHeader {
+        &unsafe { self.ptr.as_ref() }.header
+    }
+}
@@ -0,0 +1,77 @@
+use elkodon_cal::shared_memory::*;
+use std::{fmt::Debug, mem::MaybeUninit, ptr::NonNull, sync::atomic::Ordering};
+
+use crate::{
+    message::Message, port::server::Server, service,
+    service::header::request_response::ResponseHeader,
+};
+
+/// # Important
+///
+/// Does not implement [`Send`] since it releases unsent responses in the [`Server`] and the
+/// [`Server`] is not thread-safe!
+#[derive(Debug)]
+pub struct ResponseMut<
+    'a,
+    'server,
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    server: &'server Server<'a, 'global_config, Service, RequestType, ResponseType>,
+    ptr: NonNull<MaybeUninit<Message<ResponseHeader, ResponseType>>>,
+    offset_to_chunk: PointerOffset,
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Drop for ResponseMut<'_, '_, 'global_config, Service, RequestType, ResponseType>
+{
+    fn drop(&mut self) {
+        self.server.release_response(self.offset_to_chunk);
+        self.server.loan_counter.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<
+        'a,
+        'server,
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > ResponseMut<'a, 'server, 'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(
+        server: &'server Server<'a, 'global_config, Service, RequestType, ResponseType>,
+        ptr: NonNull<MaybeUninit<Message<ResponseHeader, ResponseType>>>,
+        offset_to_chunk: PointerOffset,
+    ) -> Self {
+        server.loan_counter.fetch_add(1, Ordering::Relaxed);
+        Self {
+            server,
+            ptr,
+            offset_to_chunk,
+        }
+    }
+
+    pub(crate) fn offset_to_chunk(&self) -> PointerOffset {
+        self.offset_to_chunk
+    }
+
+    pub fn header(&self) -> &ResponseHeader {
+        &unsafe { &*self.ptr.as_ref().as_ptr() }.header
+    }
+
+    pub fn as_ptr(&self) -> *const ResponseType {
+        &unsafe { &*self.ptr.as_ref().as_ptr() }.data
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut ResponseType {
+        &mut unsafe { &mut *self.ptr.as_mut().as_mut_ptr() }.data
+    }
+}
@@ -1,6 +1,10 @@
 use std::{fmt::Debug, ops::Deref, ptr::NonNull};
 
-use crate::{message::Message, port::subscriber::Subscriber, service};
+use crate::{
+    message::{Message, Payload},
+    port::subscriber::Subscriber,
+    service,
+};
 
 #[derive(Debug)]
 pub struct Sample<
@@ -9,10 +13,12 @@ pub struct Sample<
     'global_config,
     Service: service::Details<'global_config>,
     Header: Debug,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug = (),
 > {
-    pub(crate) subscriber: &'subscriber Subscriber<'a, 'global_config, Service, MessageType>,
-    pub(crate) ptr: NonNull<Message<Header, MessageType>>,
+    pub(crate) subscriber:
+        &'subscriber Subscriber<'a, 'global_config, Service, MessageType, UserHeader>,
+    pub(crate) ptr: NonNull<Message<Header, MessageType, UserHeader>>,
     pub(crate) channel_id: usize,
 }
 
@@ -20,8 +26,9 @@ impl<
         'global_config,
         Service: service::Details<'global_config>,
         Header: Debug,
-        MessageType: Debug,
-    > Deref for Sample<'_, '_, 'global_config, Service, Header, MessageType>
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug,
+    > Deref for Sample<'_, '_, 'global_config, Service, Header, MessageType, UserHeader>
 {
     type Target = MessageType;
     fn deref(&self) -> &Self::Target {
@@ -35,12 +42,13 @@ impl<
         'global_config,
         Service: service::Details<'global_config>,
         Header: Debug,
-        MessageType: Debug,
-    > Drop for Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType>
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug,
+    > Drop for Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>
 {
     fn drop(&mut self) {
         self.subscriber
-            .release_sample(self.channel_id, self.as_ptr());
+            .release_sample(self.channel_id, self.ptr.as_ptr() as *const u8);
     }
 }
 
@@ -50,8 +58,9 @@ impl<
         'global_config,
         Service: service::Details<'global_config>,
         Header: Debug,
-        MessageType: Debug,
-    > Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType>
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug,
+    > Sample<'a, 'subscriber, 'global_config, Service, Header, MessageType, UserHeader>
 {
     pub fn as_ptr(&self) -> *const MessageType {
         &unsafe { self.ptr.as_ref() }.data
@@ -60,4 +69,8 @@ impl<
     pub fn header(&self) -> &Header {
         &unsafe { self.ptr.as_ref() }.header
     }
+
+    pub fn user_header(&self) -> &UserHeader {
+        &unsafe { self.ptr.as_ref() }.user_header
+    }
 }
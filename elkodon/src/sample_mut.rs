@@ -1,7 +1,11 @@
 use elkodon_cal::shared_memory::*;
-use std::{fmt::Debug, mem::MaybeUninit, ptr::NonNull, sync::atomic::Ordering};
+use std::{fmt::Debug, ptr::NonNull, sync::atomic::Ordering};
 
-use crate::{message::Message, port::publisher::Publisher, service};
+use crate::{
+    message::{Message, Payload},
+    port::publisher::Publisher,
+    service,
+};
 
 /// # Important
 ///
@@ -14,10 +18,11 @@ pub struct SampleMut<
     'global_config,
     Service: service::Details<'global_config>,
     Header: Debug,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug + Default = (),
 > {
-    publisher: &'publisher Publisher<'a, 'global_config, Service, MessageType>,
-    ptr: NonNull<MaybeUninit<Message<Header, MessageType>>>,
+    publisher: &'publisher Publisher<'a, 'global_config, Service, MessageType, UserHeader>,
+    ptr: NonNull<Message<Header, MessageType, UserHeader>>,
     offset_to_chunk: PointerOffset,
 }
 
@@ -25,8 +30,9 @@ impl<
         'global_config,
         Service: service::Details<'global_config>,
         Header: Debug,
-        MessageType: Debug,
-    > Drop for SampleMut<'_, '_, 'global_config, Service, Header, MessageType>
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > Drop for SampleMut<'_, '_, 'global_config, Service, Header, MessageType, UserHeader>
 {
     fn drop(&mut self) {
         self.publisher.release_sample(self.offset_to_chunk);
@@ -40,12 +46,13 @@ impl<
         'global_config,
         Service: service::Details<'global_config>,
         Header: Debug,
-        MessageType: Debug,
-    > SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType>
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > SampleMut<'a, 'publisher, 'global_config, Service, Header, MessageType, UserHeader>
 {
     pub(crate) fn new(
-        publisher: &'publisher Publisher<'a, 'global_config, Service, MessageType>,
-        ptr: NonNull<MaybeUninit<Message<Header, MessageType>>>,
+        publisher: &'publisher Publisher<'a, 'global_config, Service, MessageType, UserHeader>,
+        ptr: NonNull<Message<Header, MessageType, UserHeader>>,
         offset_to_chunk: PointerOffset,
     ) -> Self {
         publisher.loan_counter.fetch_add(1, Ordering::Relaxed);
@@ -61,14 +68,23 @@ impl<
     }
 
     pub fn header(&self) -> &Header {
-        &unsafe { &*self.ptr.as_ref().as_ptr() }.header
+        // the payload may be uninitialized, therefore no reference to the message is created
+        unsafe { &*std::ptr::addr_of!((*self.ptr.as_ptr()).header) }
+    }
+
+    pub fn user_header(&self) -> &UserHeader {
+        unsafe { &*std::ptr::addr_of!((*self.ptr.as_ptr()).user_header) }
+    }
+
+    pub fn user_header_mut(&mut self) -> &mut UserHeader {
+        unsafe { &mut *std::ptr::addr_of_mut!((*self.ptr.as_ptr()).user_header) }
     }
 
     pub fn as_ptr(&self) -> *const MessageType {
-        &unsafe { &*self.ptr.as_ref().as_ptr() }.data
+        unsafe { std::ptr::addr_of!((*self.ptr.as_ptr()).data) }
     }
 
     pub fn as_mut_ptr(&mut self) -> *mut MessageType {
-        &mut unsafe { &mut *self.ptr.as_mut().as_mut_ptr() }.data
+        unsafe { std::ptr::addr_of_mut!((*self.ptr.as_ptr()).data) }
     }
 }
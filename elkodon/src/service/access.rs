@@ -0,0 +1,185 @@
+//! Restricts which users can open a service. By default only the creating user has access. The
+//! [`AccessSettings`] are defined on creation, stored in the static service config and applied
+//! to all underlying resources of the service, like the static and dynamic service information,
+//! the data segments of the ports, the zero copy connections and the event sockets, independent of
+//! the process which creates them.
+//!
+//! ```no_run
+//! use elkodon::service::access::*;
+//! use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+//! use elkodon_bb_container::semantic_string::SemanticString;
+//!
+//! let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+//! let access = AccessSettings::default()
+//!     .ownership(OwnershipBuilder::new().gid(1000).create())
+//!     .permission(Permission::GROUP_READ | Permission::GROUP_WRITE)
+//!     .acl_entry(AclEntry::User(1001, AclPermission::ReadWrite));
+//!
+//! let service = zero_copy::Service::new(&service_name)
+//!     .publish_subscribe()
+//!     .access_settings(&access)
+//!     .create::<u64>()
+//!     .unwrap();
+//! ```
+
+use serde::{Deserialize, Serialize};
+
+pub use elkodon_bb_posix::access_control_list::AclPermission;
+pub use elkodon_bb_posix::ownership::{Ownership, OwnershipBuilder};
+pub use elkodon_bb_posix::permission::Permission;
+pub use elkodon_cal::named_concept::{AccessSettings, AclEntry};
+
+// the owner is unchanged when the uid or gid is u32::MAX, see `OwnershipBuilder`
+const UNCHANGED_ID: u32 = u32::MAX;
+
+const ACL_PERMISSIONS: [(AclPermission, &str); 8] = [
+    (AclPermission::None, "---"),
+    (AclPermission::Read, "r--"),
+    (AclPermission::Write, "-w-"),
+    (AclPermission::Execute, "--x"),
+    (AclPermission::ReadWrite, "rw-"),
+    (AclPermission::ReadExecute, "r-x"),
+    (AclPermission::WriteExecute, "-wx"),
+    (AclPermission::ReadWriteExecute, "rwx"),
+];
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize)]
+enum AclEntryKind {
+    User,
+    Group,
+}
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+struct AclConfigEntry {
+    kind: AclEntryKind,
+    id: u32,
+    rights: String,
+}
+
+/// The representation of the [`AccessSettings`] in the static service config. The rights are
+/// stored in the `rwx` notation of `ls -l`.
+#[derive(Debug, Default, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub(crate) struct AccessConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    uid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    gid: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    group_rights: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    others_rights: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    acl_entries: Vec<AclConfigEntry>,
+}
+
+fn rights_of(permission: Permission, flags: [Permission; 3]) -> Option<String> {
+    if !permission.intersects(flags[0] | flags[1] | flags[2]) {
+        return None;
+    }
+
+    Some(
+        flags
+            .iter()
+            .zip(['r', 'w', 'x'])
+            .map(|(flag, c)| if permission.contains(*flag) { c } else { '-' })
+            .collect(),
+    )
+}
+
+fn permission_of(rights: &Option<String>, flags: [Permission; 3]) -> Permission {
+    let mut permission = Permission::NONE;
+    if let Some(rights) = rights {
+        for ((flag, c), r) in flags.iter().zip(['r', 'w', 'x']).zip(rights.chars()) {
+            if c == r {
+                permission |= *flag;
+            }
+        }
+    }
+    permission
+}
+
+const GROUP_FLAGS: [Permission; 3] = [
+    Permission::GROUP_READ,
+    Permission::GROUP_WRITE,
+    Permission::GROUP_EXEC,
+];
+
+const OTHERS_FLAGS: [Permission; 3] = [
+    Permission::OTHERS_READ,
+    Permission::OTHERS_WRITE,
+    Permission::OTHERS_EXEC,
+];
+
+impl From<&AccessSettings> for AccessConfig {
+    fn from(value: &AccessSettings) -> Self {
+        let ownership = value.get_ownership();
+        let id = |id: u32| Some(id).filter(|id| *id != UNCHANGED_ID);
+        let rights = |permission: AclPermission| {
+            ACL_PERMISSIONS
+                .iter()
+                .find(|(p, _)| *p == permission)
+                .map(|(_, rights)| rights.to_string())
+                .unwrap_or_default()
+        };
+
+        Self {
+            uid: ownership.and_then(|o| id(o.uid())),
+            gid: ownership.and_then(|o| id(o.gid())),
+            group_rights: rights_of(value.get_permission(), GROUP_FLAGS),
+            others_rights: rights_of(value.get_permission(), OTHERS_FLAGS),
+            acl_entries: value
+                .get_acl_entries()
+                .iter()
+                .map(|entry| match *entry {
+                    AclEntry::User(id, permission) => AclConfigEntry {
+                        kind: AclEntryKind::User,
+                        id,
+                        rights: rights(permission),
+                    },
+                    AclEntry::Group(id, permission) => AclConfigEntry {
+                        kind: AclEntryKind::Group,
+                        id,
+                        rights: rights(permission),
+                    },
+                })
+                .collect(),
+        }
+    }
+}
+
+impl AccessConfig {
+    pub(crate) fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    pub(crate) fn settings(&self) -> AccessSettings {
+        let mut settings = AccessSettings::default().permission(
+            permission_of(&self.group_rights, GROUP_FLAGS)
+                | permission_of(&self.others_rights, OTHERS_FLAGS),
+        );
+
+        if self.uid.is_some() || self.gid.is_some() {
+            settings = settings.ownership(
+                OwnershipBuilder::new()
+                    .uid(self.uid.unwrap_or(UNCHANGED_ID))
+                    .gid(self.gid.unwrap_or(UNCHANGED_ID))
+                    .create(),
+            );
+        }
+
+        for entry in &self.acl_entries {
+            // unknown rights grant no access
+            let permission = ACL_PERMISSIONS
+                .iter()
+                .find(|(_, rights)| *rights == entry.rights)
+                .map_or(AclPermission::None, |(p, _)| *p);
+
+            settings = settings.acl_entry(match entry.kind {
+                AclEntryKind::User => AclEntry::User(entry.id, permission),
+                AclEntryKind::Group => AclEntry::Group(entry.id, permission),
+            });
+        }
+
+        settings
+    }
+}
@@ -0,0 +1,66 @@
+//! Key-value attributes which tag a service with additional metadata like its owner, its unit or
+//! its schema version. They are defined on creation, stored in the static service config and can
+//! be required when a service is opened or used to discover services with
+//! [`crate::service::Service::list_matching()`].
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct Attribute {
+    key: String,
+    value: String,
+}
+
+impl Attribute {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+}
+
+/// The attributes of a service. Every key is unique.
+#[derive(Debug, Default, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct AttributeSet(Vec<Attribute>);
+
+impl AttributeSet {
+    /// Adds an attribute or replaces the value of an attribute with the same key.
+    pub(crate) fn set(&mut self, key: &str, value: &str) {
+        match self.0.iter_mut().find(|a| a.key == key) {
+            Some(attribute) => attribute.value = value.to_string(),
+            None => self.0.push(Attribute {
+                key: key.to_string(),
+                value: value.to_string(),
+            }),
+        }
+    }
+
+    /// Returns the value of the attribute with the given key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|a| a.key == key)
+            .map(|a| a.value.as_str())
+    }
+
+    /// Returns true when the attribute set contains every attribute of `other`.
+    pub fn contains_all(&self, other: &AttributeSet) -> bool {
+        other
+            .iter()
+            .all(|a| self.get(&a.key) == Some(a.value.as_str()))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Attribute> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
@@ -0,0 +1,471 @@
+use std::alloc::Layout;
+use std::marker::PhantomData;
+
+use crate::node::NodeRegistry;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::dynamic_config::blackboard::DynamicConfigSettings;
+use crate::service::port_factory::blackboard;
+use crate::service::static_config::blackboard::EntryDescriptor;
+use crate::service::static_config::MessagingPattern;
+use crate::service::*;
+use crate::zero_copy_type::{PayloadFor, TypeDescriptor, ZeroCopyType};
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_elementary::math::align;
+use elkodon_bb_lock_free::spmc::unrestricted_atomic::UnrestrictedAtomic;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_posix::adaptive_wait::AdaptiveWaitBuilder;
+use elkodon_cal::serialize::Serialize;
+use elkodon_cal::static_storage::StaticStorageLocked;
+
+use super::{OpenDynamicStorageFailure, ServiceState};
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum BlackboardOpenError {
+    DoesNotExist,
+    InternalFailure,
+    IncompatibleKeyType,
+    IncompatibleMessagingPattern,
+    IncompatibleAttributes,
+    DoesNotSupportRequestedAmountOfWriters,
+    PermissionDenied,
+    ServiceInCorruptedState,
+    HangsInCreation,
+    UnableToOpenDynamicServiceInformation,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum BlackboardCreateError {
+    Corrupted,
+    NoEntriesProvided,
+    DuplicateKey,
+    AlreadyExists,
+    PermissionDenied,
+    InternalFailure,
+    IsBeingCreatedByAnotherInstance,
+    UnableToCreateStaticServiceInformation,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+enum ServiceAvailabilityState {
+    ServiceState(ServiceState),
+    IncompatibleKeyType,
+}
+
+enum_gen! {
+    BlackboardOpenOrCreateError
+  mapping:
+    BlackboardOpenError,
+    BlackboardCreateError
+}
+
+struct BuilderEntry<KeyType> {
+    key: KeyType,
+    initializer: Box<dyn Fn(*mut u8)>,
+}
+
+impl<KeyType: Debug> Debug for BuilderEntry<KeyType> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "BuilderEntry {{ key: {:?} }}", self.key)
+    }
+}
+
+/// Creates or opens a blackboard service. A blackboard consists of a fixed set of keys which
+/// are defined on creation. Every key refers to a value of a [`Copy`] [`ZeroCopyType`] which
+/// can be updated by a [`crate::port::writer::Writer`] and read lock-free by any number of
+/// [`crate::port::reader::Reader`]s.
+#[derive(Debug)]
+pub struct Builder<
+    'global_config,
+    ServiceType: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug + ZeroCopyType + PayloadFor<ServiceType>,
+> {
+    base: builder::BuilderWithServiceType<'global_config, ServiceType>,
+    entries: Vec<BuilderEntry<KeyType>>,
+    verify_max_writers: bool,
+    _phantom_key_type: PhantomData<KeyType>,
+}
+
+impl<
+        'global_config,
+        ServiceType: service::Details<'global_config>,
+        KeyType: Copy + Eq + Debug + ZeroCopyType + PayloadFor<ServiceType>,
+    > Builder<'global_config, ServiceType, KeyType>
+{
+    pub(crate) fn new(base: builder::BuilderWithServiceType<'global_config, ServiceType>) -> Self {
+        let mut new_self = Self {
+            base,
+            entries: vec![],
+            verify_max_writers: false,
+            _phantom_key_type: PhantomData,
+        };
+
+        let mut config = static_config::blackboard::StaticConfig::new(new_self.base.global_config);
+        config.key_type_descriptor = TypeDescriptor::new::<KeyType>();
+
+        new_self.base.service_config.messaging_pattern = MessagingPattern::Blackboard(config);
+
+        new_self
+    }
+
+    /// Records the created or opened service in the registry entry of the node.
+    pub(crate) fn node(mut self, value: &'global_config dyn NodeRegistry) -> Self {
+        self.base.node = Some(value);
+        self
+    }
+
+    /// Defines who besides the creator can access the created service. When an existing
+    /// service is opened, the access settings it was created with are used.
+    pub fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.base.service_config.access = value.into();
+        self
+    }
+
+    /// Tags the created service with an attribute. When the service is opened, the existing
+    /// service is required to have the same attribute.
+    pub fn attribute(mut self, key: &str, value: &str) -> Self {
+        self.base.service_config.attributes.set(key, value);
+        self
+    }
+
+    fn config_details_mut(&mut self) -> &mut static_config::blackboard::StaticConfig {
+        match self.base.service_config.messaging_pattern {
+            MessagingPattern::Blackboard(ref mut v) => v,
+            _ => {
+                fatal_panic!(from self, "This should never happen! Accessing wrong messaging pattern in Blackboard builder!");
+            }
+        }
+    }
+
+    fn config_details(&self) -> &static_config::blackboard::StaticConfig {
+        match self.base.service_config.messaging_pattern {
+            MessagingPattern::Blackboard(ref v) => v,
+            _ => {
+                fatal_panic!(from self, "This should never happen! Accessing wrong messaging pattern in Blackboard builder!");
+            }
+        }
+    }
+
+    pub fn max_writers(mut self, value: usize) -> Self {
+        self.config_details_mut().max_writers = value;
+        self.verify_max_writers = true;
+        self
+    }
+
+    /// Adds a new entry with the provided key and initial value to the blackboard. The entries
+    /// are only considered when the blackboard is created.
+    pub fn add<ValueType: Copy + Debug + ZeroCopyType + PayloadFor<ServiceType> + 'static>(
+        mut self,
+        key: KeyType,
+        value: ValueType,
+    ) -> Self {
+        let layout = Layout::new::<UnrestrictedAtomic<ValueType>>();
+        self.config_details_mut().entries.push(EntryDescriptor {
+            type_descriptor: TypeDescriptor::new::<ValueType>(),
+            offset: 0,
+            size: layout.size(),
+            alignment: layout.align(),
+        });
+
+        self.entries.push(BuilderEntry {
+            key,
+            initializer: Box::new(move |ptr| unsafe {
+                (ptr as *mut UnrestrictedAtomic<ValueType>).write(UnrestrictedAtomic::new(value))
+            }),
+        });
+
+        self
+    }
+
+    fn is_service_available(
+        &mut self,
+        error_msg: &str,
+    ) -> Result<Option<(StaticConfig, ServiceType::StaticStorage)>, ServiceAvailabilityState> {
+        match self.base.is_service_available() {
+            Ok(Some((config, storage))) => {
+                let offered = &config.blackboard().key_type_descriptor;
+                let requested = &self.config_details().key_type_descriptor;
+                if offered != requested {
+                    fail!(from self, with ServiceAvailabilityState::IncompatibleKeyType,
+                        "{} since the service uses the key type \"{}\" with size {} and alignment {} but the requested key type is \"{}\" with size {} and alignment {}.",
+                        error_msg, offered.type_identifier(), offered.size(), offered.alignment(),
+                        requested.type_identifier(), requested.size(), requested.alignment());
+                }
+
+                Ok(Some((config, storage)))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(ServiceAvailabilityState::ServiceState(e)),
+        }
+    }
+
+    pub fn open_or_create(
+        mut self,
+    ) -> Result<
+        blackboard::PortFactory<'global_config, ServiceType, KeyType>,
+        BlackboardOpenOrCreateError,
+    > {
+        let msg = "Unable to open or create blackboard service";
+
+        self.base.remove_stale_service();
+
+        match self.is_service_available(msg) {
+            Ok(Some(_)) => Ok(self.open()?),
+            Ok(None) => Ok(self.create()?),
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IsBeingCreatedByAnotherInstance,
+            )) => Ok(self.open()?),
+            Err(ServiceAvailabilityState::IncompatibleKeyType) => {
+                fail!(from self, with BlackboardOpenOrCreateError::BlackboardOpenError(BlackboardOpenError::IncompatibleKeyType),
+                    "{} since the service uses a different key type.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IncompatibleMessagingPattern,
+            )) => {
+                fail!(from self, with BlackboardOpenOrCreateError::BlackboardOpenError(BlackboardOpenError::IncompatibleMessagingPattern),
+                    "{} since the services messaging pattern does not match.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                fail!(from self, with BlackboardOpenOrCreateError::BlackboardOpenError(BlackboardOpenError::ServiceInCorruptedState),
+                    "{} since the service is in a corrupted state.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                fail!(from self, with BlackboardOpenOrCreateError::BlackboardOpenError(BlackboardOpenError::PermissionDenied),
+                    "{} due to insufficient permissions to access the service.", msg);
+            }
+        }
+    }
+
+    pub fn open(
+        mut self,
+    ) -> Result<blackboard::PortFactory<'global_config, ServiceType, KeyType>, BlackboardOpenError>
+    {
+        let msg = "Unable to open blackboard service";
+
+        let mut adaptive_wait = fail!(from self, when AdaptiveWaitBuilder::new().create(),
+                                        with BlackboardOpenError::InternalFailure,
+                                        "{} since the adaptive wait could not be created.", msg);
+
+        loop {
+            match self.is_service_available(msg) {
+                Ok(None) => {
+                    fail!(from self, with BlackboardOpenError::DoesNotExist,
+                        "{} since the service does not exist.", msg);
+                }
+                Ok(Some((static_config, static_storage))) => {
+                    let dynamic_config = fail!(from self, when self.base.open_dynamic_config_storage(),
+                            map OpenDynamicStorageFailure::PermissionDenied => BlackboardOpenError::PermissionDenied,
+                            unmatched BlackboardOpenError::UnableToOpenDynamicServiceInformation,
+                            "{} since the dynamic service information could not be opened.", msg);
+                    let attributes = static_config.attributes().clone();
+                    let access = static_config.access.clone();
+                    let static_config = self.verify_service_properties(&static_config)?;
+                    self.base.service_config.attributes = attributes;
+                    self.base.service_config.access = access;
+
+                    self.base.service_config.messaging_pattern =
+                        MessagingPattern::Blackboard(static_config.clone());
+
+                    return Ok(blackboard::PortFactory::new(ServiceType::from_state(
+                        service::ServiceState::new(
+                            self.base.service_config,
+                            self.base.global_config,
+                            dynamic_config,
+                            static_storage,
+                            self.base.node,
+                        ),
+                    )));
+                }
+                Err(ServiceAvailabilityState::ServiceState(
+                    ServiceState::IsBeingCreatedByAnotherInstance,
+                )) => {
+                    let timeout = fail!(from self, when adaptive_wait.wait(),
+                                        with BlackboardOpenError::InternalFailure,
+                                        "{} since the adaptive wait failed.", msg);
+
+                    if timeout > self.base.global_config.global.service.creation_timeout {
+                        fail!(from self, with BlackboardOpenError::HangsInCreation,
+                            "{} since the service hangs while being created, max timeout for service creation of {:?} exceeded. Waited for {:?} but the state did not change.",
+                            msg, self.base.global_config.global.service.creation_timeout, timeout);
+                    }
+                }
+                Err(ServiceAvailabilityState::IncompatibleKeyType) => {
+                    fail!(from self, with BlackboardOpenError::IncompatibleKeyType,
+                        "{} since the service uses a different key type.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(
+                    ServiceState::IncompatibleMessagingPattern,
+                )) => {
+                    fail!(from self, with BlackboardOpenError::IncompatibleMessagingPattern,
+                        "{} since the services messaging pattern does not match.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                    fail!(from self, with BlackboardOpenError::ServiceInCorruptedState,
+                        "{} since the service is in a corrupted state.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                    fail!(from self, with BlackboardOpenError::PermissionDenied,
+                        "{} due to insufficient permissions to access the service.", msg);
+                }
+            }
+        }
+    }
+
+    pub fn create(
+        mut self,
+    ) -> Result<blackboard::PortFactory<'global_config, ServiceType, KeyType>, BlackboardCreateError>
+    {
+        self.adjust_properties_to_meaningful_values();
+
+        let msg = "Unable to create blackboard service";
+
+        if self.entries.is_empty() {
+            fail!(from self, with BlackboardCreateError::NoEntriesProvided,
+                "{} since no entries were added to the blackboard.", msg);
+        }
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            if self.entries[i + 1..].iter().any(|e| e.key == entry.key) {
+                fail!(from self, with BlackboardCreateError::DuplicateKey,
+                    "{} since the key {:?} was added more than once.", msg, entry.key);
+            }
+        }
+
+        self.calculate_entry_offsets();
+
+        match self.is_service_available(msg) {
+            Ok(None) => {
+                // create static config
+                let static_config = fail!(from self, when self.base.create_static_config_storage(),
+                    with BlackboardCreateError::UnableToCreateStaticServiceInformation,
+                    "{} since the static service information could not be created.", msg);
+
+                let blackboard_config = self.base.service_config.blackboard();
+
+                // create dynamic config
+                let dynamic_config_setting = DynamicConfigSettings {
+                    number_of_writers: blackboard_config.max_writers,
+                    number_of_entries: blackboard_config.entries.len(),
+                    entries_layout: blackboard_config.entries_layout(),
+                };
+
+                let dynamic_config = self.base.create_dynamic_config_storage(
+                    dynamic_config::MessagingPattern::Blackboard(
+                        dynamic_config::blackboard::DynamicConfig::new(&dynamic_config_setting),
+                    ),
+                    dynamic_config::blackboard::DynamicConfig::memory_size(&dynamic_config_setting),
+                );
+                let dynamic_config = fail!(from self, when dynamic_config,
+                    with BlackboardCreateError::InternalFailure,
+                    "{} since the dynamic service segment could not be created.", msg);
+
+                // the service is not accessible until the static details are unlocked, therefore
+                // the entries can be initialized without synchronization
+                let entries_ptr = dynamic_config.get().blackboard().entries_ptr();
+                for (i, entry) in self.entries.iter().enumerate() {
+                    unsafe { (entries_ptr as *mut KeyType).add(i).write(entry.key) };
+                    (entry.initializer)(unsafe {
+                        entries_ptr.add(self.config_details().entries[i].offset)
+                    });
+                }
+
+                let service_config = fail!(from self, when ServiceType::ConfigSerializer::serialize(&self.base.service_config),
+                            with BlackboardCreateError::Corrupted,
+                            "{} since the configuration could not be serialized.", msg);
+
+                // only unlock the static details when the service is successfully created
+                let unlocked_static_details = fail!(from self, when static_config.unlock(service_config.as_slice()),
+                            with BlackboardCreateError::Corrupted,
+                            "{} since the configuration could not be written to the static storage.", msg);
+
+                return Ok(blackboard::PortFactory::new(ServiceType::from_state(
+                    service::ServiceState::new(
+                        self.base.service_config.clone(),
+                        self.base.global_config,
+                        dynamic_config,
+                        unlocked_static_details,
+                        self.base.node,
+                    ),
+                )));
+            }
+            Ok(Some(_))
+            | Err(ServiceAvailabilityState::IncompatibleKeyType)
+            | Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IncompatibleMessagingPattern,
+            )) => {
+                fail!(from self, with BlackboardCreateError::AlreadyExists,
+                    "{} since the service already exists.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                fail!(from self, with BlackboardCreateError::PermissionDenied,
+                    "{} due to possible insufficient permissions to access the underlying service details.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                fail!(from self, with BlackboardCreateError::Corrupted,
+                    "{} since a service in a corrupted state already exists. A cleanup of the service constructs may help.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IsBeingCreatedByAnotherInstance,
+            )) => {
+                fail!(from self, with BlackboardCreateError::IsBeingCreatedByAnotherInstance,
+                    "{} since the service is being created by another instance.", msg);
+            }
+        }
+    }
+
+    fn calculate_entry_offsets(&mut self) {
+        let settings = self.config_details_mut();
+        let mut offset = settings.key_type_descriptor.size() * settings.entries.len();
+
+        for entry in settings.entries.iter_mut() {
+            offset = align(offset, entry.alignment);
+            entry.offset = offset;
+            offset += entry.size;
+        }
+    }
+
+    fn adjust_properties_to_meaningful_values(&mut self) {
+        let origin = format!("{:?}", self);
+        let settings = self.base.service_config.blackboard_mut();
+
+        if settings.max_writers == 0 {
+            warn!(from origin,
+                "Setting the maximum amount of writers to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.max_writers = 1;
+        }
+    }
+
+    fn verify_service_properties(
+        &self,
+        existing_settings: &static_config::StaticConfig,
+    ) -> Result<static_config::blackboard::StaticConfig, BlackboardOpenError> {
+        let msg = "Unable to open blackboard service";
+
+        let required_settings = self.base.service_config.blackboard();
+        if !existing_settings
+            .attributes()
+            .contains_all(&self.base.service_config.attributes)
+        {
+            fail!(from self, with BlackboardOpenError::IncompatibleAttributes,
+                "{} since the service has the attributes {:?} but the attributes {:?} are required.",
+                msg, existing_settings.attributes(), self.base.service_config.attributes);
+        }
+
+        let existing_settings = match &existing_settings.messaging_pattern {
+            MessagingPattern::Blackboard(ref v) => v,
+            p => {
+                fail!(from self, with BlackboardOpenError::IncompatibleMessagingPattern,
+                "{} since a service with the messaging pattern {:?} exists but MessagingPattern::Blackboard is required.", msg, p);
+            }
+        };
+
+        if self.verify_max_writers && existing_settings.max_writers < required_settings.max_writers
+        {
+            fail!(from self, with BlackboardOpenError::DoesNotSupportRequestedAmountOfWriters,
+                "{} since the service supports only {} writers but a support of {} writers was requested.",
+                msg, existing_settings.max_writers, required_settings.max_writers);
+        }
+
+        Ok(existing_settings.clone())
+    }
+}
@@ -1,3 +1,5 @@
+use crate::node::NodeRegistry;
+use crate::service::access::AccessSettings;
 use crate::service::port_factory::event;
 use crate::service::static_config::MessagingPattern;
 use crate::service::*;
@@ -6,7 +8,7 @@ use elkodon_bb_elementary::enum_gen;
 use elkodon_bb_log::{fail, fatal_panic};
 use elkodon_bb_posix::adaptive_wait::AdaptiveWaitBuilder;
 
-use super::ServiceState;
+use super::{OpenDynamicStorageFailure, ServiceState};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum EventOpenError {
@@ -18,6 +20,7 @@ pub enum EventOpenError {
     HangsInCreation,
     DoesNotSupportRequestedAmountOfNotifiers,
     DoesNotSupportRequestedAmountOfListeners,
+    IncompatibleAttributes,
     UnableToOpenDynamicServiceInformation,
 }
 
@@ -49,17 +52,17 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
     Builder<'global_config, ServiceType>
 {
     pub(crate) fn new(base: builder::BuilderWithServiceType<'global_config, ServiceType>) -> Self {
-        let mut new_self = Self {
+        Self {
             base,
             verify_max_notifiers: false,
             verify_max_listeners: false,
-        };
-
-        new_self.base.service_config.messaging_pattern = MessagingPattern::Event(
-            static_config::event::StaticConfig::new(new_self.base.global_config),
-        );
+        }
+    }
 
-        new_self
+    /// Records the created or opened service in the registry entry of the node.
+    pub(crate) fn node(mut self, value: &'global_config dyn NodeRegistry) -> Self {
+        self.base.node = Some(value);
+        self
     }
 
     fn config_details(&mut self) -> &mut static_config::event::StaticConfig {
@@ -77,6 +80,27 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         self
     }
 
+    /// Keeps the service alive when its last owner drops it so that it can be opened again
+    /// later. Such a service is removed with [`event::PortFactory::remove()`].
+    pub fn persistent(mut self, value: bool) -> Self {
+        self.base.service_config.persistent = value;
+        self
+    }
+
+    /// Defines who besides the creator can access the created service. When an existing
+    /// service is opened, the access settings it was created with are used.
+    pub fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.base.service_config.access = value.into();
+        self
+    }
+
+    /// Tags the created service with an attribute. When the service is opened, the existing
+    /// service is required to have the same attribute.
+    pub fn attribute(mut self, key: &str, value: &str) -> Self {
+        self.base.service_config.attributes.set(key, value);
+        self
+    }
+
     pub fn max_listeners(mut self, value: usize) -> Self {
         self.config_details().max_listeners = value;
         self.verify_max_listeners = true;
@@ -88,6 +112,8 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
     ) -> Result<event::PortFactory<'global_config, ServiceType>, EventOpenOrCreateError> {
         let msg = "Unable to open or create event service";
 
+        self.base.remove_stale_service();
+
         match self.base.is_service_available() {
             Ok(Some(_)) => Ok(self.open()?),
             Ok(None) => Ok(self.create()?),
@@ -124,9 +150,15 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                 }
                 Ok(Some((static_config, static_storage))) => {
                     let dynamic_config = fail!(from self, when self.base.open_dynamic_config_storage(),
-                            with EventOpenError::UnableToOpenDynamicServiceInformation,
+                            map OpenDynamicStorageFailure::PermissionDenied => EventOpenError::PermissionDenied,
+                            unmatched EventOpenError::UnableToOpenDynamicServiceInformation,
                             "{} since the dynamic service informations could not be opened.", msg);
+                    self.base.service_config.persistent = static_config.is_persistent();
+                    let attributes = static_config.attributes().clone();
+                    let access = static_config.access.clone();
                     let static_config = self.verify_service_properties(&static_config)?;
+                    self.base.service_config.attributes = attributes;
+                    self.base.service_config.access = access;
 
                     self.base.service_config.messaging_pattern =
                         MessagingPattern::Event(static_config.clone());
@@ -137,6 +169,7 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                             self.base.global_config,
                             dynamic_config,
                             static_storage,
+                            self.base.node,
                         ),
                     )));
                 }
@@ -211,6 +244,7 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                         self.base.global_config,
                         dynamic_config,
                         unlocked_static_details,
+                        self.base.node,
                     ),
                 )));
             }
@@ -255,6 +289,15 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         let msg = "Unable to open event";
 
         let required_settings = self.base.service_config.event();
+        if !existing_settings
+            .attributes()
+            .contains_all(&self.base.service_config.attributes)
+        {
+            fail!(from self, with EventOpenError::IncompatibleAttributes,
+                "{} since the service has the attributes {:?} but the attributes {:?} are required.",
+                msg, existing_settings.attributes(), self.base.service_config.attributes);
+        }
+
         let existing_settings = match &existing_settings.messaging_pattern {
             MessagingPattern::Event(ref v) => v,
             p => {
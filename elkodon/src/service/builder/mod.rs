@@ -1,14 +1,20 @@
+pub mod blackboard;
 pub mod event;
+pub mod pipeline;
 pub mod publish_subscribe;
+pub mod request_response;
 
 use crate::global_config;
+use crate::node::NodeRegistry;
 use crate::service;
 use crate::service::dynamic_config::DynamicConfig;
 use crate::service::static_config::*;
+use crate::zero_copy_type::{PayloadFor, ZeroCopyType};
 use elkodon_cal::dynamic_storage::DynamicStorageCreateError;
 use elkodon_cal::dynamic_storage::DynamicStorageOpenError;
 use elkodon_cal::dynamic_storage::{DynamicStorage, DynamicStorageBuilder};
 use elkodon_cal::named_concept::NamedConceptBuilder;
+use elkodon_cal::named_concept::NamedConceptConfiguration;
 use elkodon_cal::named_concept::NamedConceptDoesExistError;
 use elkodon_cal::named_concept::NamedConceptMgmt;
 use elkodon_cal::serialize::Serialize;
@@ -17,11 +23,14 @@ use elkodon_bb_container::semantic_string::SemanticString;
 use elkodon_bb_elementary::enum_gen;
 use elkodon_bb_log::fail;
 use elkodon_bb_log::fatal_panic;
+use elkodon_bb_log::warn;
 use elkodon_bb_system_types::file_name::FileName;
+use std::fmt::Debug;
 use std::marker::PhantomData;
 
 use super::dynamic_config_storage_config;
 use super::dynamic_config_storage_name;
+use super::remove_stale_service;
 use super::service_name::ServiceName;
 use super::static_config_storage_config;
 use super::static_config_storage_name;
@@ -38,7 +47,8 @@ enum ServiceState {
 enum_gen! {
     OpenDynamicStorageFailure
   entry:
-    IsMarkedForDestruction
+    IsMarkedForDestruction,
+    PermissionDenied
   mapping:
     DynamicStorageOpenError
 }
@@ -83,6 +93,69 @@ impl<S: Service> Builder<S> {
         .publish_subscribe()
     }
 
+    pub fn request_response<'global_config>(
+        self,
+    ) -> request_response::Builder<'global_config, S::Type<'global_config>> {
+        self.request_response_with_custom_config(global_config::Config::get_global_config())
+    }
+
+    pub fn request_response_with_custom_config(
+        self,
+        entries: &global_config::Config,
+    ) -> request_response::Builder<'_, S::Type<'_>> {
+        BuilderWithServiceType::new(
+            StaticConfig::new_request_response::<
+                <<S as service::Service>::Type<'_> as service::Details<'_>>::ServiceNameHasher,
+            >(&self.name, entries.get()),
+            entries.get(),
+        )
+        .request_response()
+    }
+
+    pub fn blackboard<
+        'global_config,
+        KeyType: Copy + Eq + Debug + ZeroCopyType + PayloadFor<S::Type<'global_config>>,
+    >(
+        self,
+    ) -> blackboard::Builder<'global_config, S::Type<'global_config>, KeyType> {
+        self.blackboard_with_custom_config(global_config::Config::get_global_config())
+    }
+
+    pub fn blackboard_with_custom_config<
+        'global_config,
+        KeyType: Copy + Eq + Debug + ZeroCopyType + PayloadFor<S::Type<'global_config>>,
+    >(
+        self,
+        entries: &'global_config global_config::Config,
+    ) -> blackboard::Builder<'global_config, S::Type<'global_config>, KeyType> {
+        BuilderWithServiceType::new(
+            StaticConfig::new_blackboard::<
+                <<S as service::Service>::Type<'_> as service::Details<'_>>::ServiceNameHasher,
+            >(&self.name, entries.get()),
+            entries.get(),
+        )
+        .blackboard()
+    }
+
+    pub fn pipeline<'global_config>(
+        self,
+    ) -> pipeline::Builder<'global_config, S::Type<'global_config>> {
+        self.pipeline_with_custom_config(global_config::Config::get_global_config())
+    }
+
+    pub fn pipeline_with_custom_config(
+        self,
+        entries: &global_config::Config,
+    ) -> pipeline::Builder<'_, S::Type<'_>> {
+        BuilderWithServiceType::new(
+            StaticConfig::new_pipeline::<
+                <<S as service::Service>::Type<'_> as service::Details<'_>>::ServiceNameHasher,
+            >(&self.name, entries.get()),
+            entries.get(),
+        )
+        .pipeline()
+    }
+
     pub fn event<'global_config>(self) -> event::Builder<'global_config, S::Type<'global_config>> {
         self.event_with_custom_config(global_config::Config::get_global_config())
     }
@@ -105,6 +178,7 @@ impl<S: Service> Builder<S> {
 pub struct BuilderWithServiceType<'global_config, ServiceType: service::Details<'global_config>> {
     service_config: StaticConfig,
     global_config: &'global_config global_config::Entries,
+    node: Option<&'global_config dyn NodeRegistry>,
     _phantom_data: PhantomData<ServiceType>,
     _phantom_lifetime_b: PhantomData<&'global_config ()>,
 }
@@ -119,6 +193,7 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         let new_builder = Self {
             service_config,
             global_config,
+            node: None,
             _phantom_data: PhantomData,
             _phantom_lifetime_b: PhantomData,
         };
@@ -129,6 +204,20 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         publish_subscribe::Builder::new(self)
     }
 
+    fn request_response(self) -> request_response::Builder<'global_config, ServiceType> {
+        request_response::Builder::new(self)
+    }
+
+    fn blackboard<KeyType: Copy + Eq + Debug + ZeroCopyType + PayloadFor<ServiceType>>(
+        self,
+    ) -> blackboard::Builder<'global_config, ServiceType, KeyType> {
+        blackboard::Builder::new(self)
+    }
+
+    fn pipeline(self) -> pipeline::Builder<'global_config, ServiceType> {
+        pipeline::Builder::new(self)
+    }
+
     fn event(self) -> event::Builder<'global_config, ServiceType> {
         event::Builder::new(self)
     }
@@ -151,6 +240,10 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                 fail!(from self, with ServiceState::IsBeingCreatedByAnotherInstance,
                         "{} since it is currently being created.", msg);
             }
+            Err(NamedConceptDoesExistError::InsufficientPermissions) => {
+                fail!(from self, with ServiceState::PermissionDenied,
+                        "{} due to insufficient permissions to access the static service information.", msg);
+            }
             Ok(true) => {
                 let storage = if let Ok(v) = <<ServiceType::StaticStorage as StaticStorage>::Builder as NamedConceptBuilder<
                                        <ServiceType as service::Details>::StaticStorage>>
@@ -198,20 +291,35 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         }
     }
 
+    /// Removes the service when it was left behind by processes which are all dead.
+    fn remove_stale_service(&self) {
+        if remove_stale_service::<ServiceType>(&self.service_config, self.global_config) {
+            warn!(from self, "Removed the stale service since all of its owners are dead.");
+        }
+    }
+
     fn create_dynamic_config_storage(
         &self,
         messaging_pattern: super::dynamic_config::MessagingPattern,
         additional_size: usize,
     ) -> Result<ServiceType::DynamicStorage, DynamicStorageCreateError> {
+        let max_owners = self.global_config.global.service.max_owners;
         match <<ServiceType::DynamicStorage as DynamicStorage<
             DynamicConfig,
         >>::Builder as NamedConceptBuilder<
             ServiceType::DynamicStorage,
         >>::new(&dynamic_config_storage_name(&self.service_config))
-            .config(&dynamic_config_storage_config::<ServiceType>(self.global_config))
-            .supplementary_size(additional_size)
+            .config(
+                &dynamic_config_storage_config::<ServiceType>(self.global_config)
+                    .access_settings(&self.service_config.access_settings()),
+            )
+            .supplementary_size(additional_size + DynamicConfig::memory_size(max_owners))
             .has_ownership(false)
-            .create_and_initialize(DynamicConfig::new_uninit(messaging_pattern),
+            .create_and_initialize(DynamicConfig::new_uninit(
+                    messaging_pattern,
+                    max_owners,
+                    self.service_config.persistent,
+                ),
                 |config, allocator| {
                     unsafe { config.init(allocator) };
                     true
@@ -228,16 +336,27 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         &self,
     ) -> Result<ServiceType::DynamicStorage, OpenDynamicStorageFailure> {
         let msg = "Failed to open dynamic service information";
-        let storage = fail!(from self, when
-            <<ServiceType::DynamicStorage as DynamicStorage<
+        let storage = match <<ServiceType::DynamicStorage as DynamicStorage<
                     DynamicConfig,
                 >>::Builder as NamedConceptBuilder<
                     ServiceType::DynamicStorage,
                 >>::new(&dynamic_config_storage_name(&self.service_config))
                     .config(&dynamic_config_storage_config::<ServiceType>(self.global_config))
                 .has_ownership(false)
-                .open(),
-            "{} since the dynamic storage could not be opened.", msg);
+                .open()
+        {
+            Ok(storage) => storage,
+            // the static service information is only readable after the dynamic storage was
+            // initialized, therefore it is inaccessible only due to missing permissions
+            Err(DynamicStorageOpenError::InsufficientPermissions) => {
+                fail!(from self, with OpenDynamicStorageFailure::PermissionDenied,
+                    "{} due to insufficient permissions.", msg);
+            }
+            Err(e) => {
+                fail!(from self, with e.into(),
+                    "{} since the dynamic storage could not be opened.", msg);
+            }
+        };
 
         fail!(from self, when storage.get().increment_reference_counter(),
                 with OpenDynamicStorageFailure::IsMarkedForDestruction,
@@ -258,7 +377,7 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                     >>::new(&static_config_storage_name(self.service_config.uuid()))
                     .config(&static_config_storage_config::<ServiceType>(
                         self.global_config,
-                    ))
+                    ).access_settings(&self.service_config.access_settings()))
                     .has_ownership(false)
                     .create_locked(),
                     "Failed to create static service information since the underlying static storage could not be created."),
@@ -0,0 +1,481 @@
+use crate::node::NodeRegistry;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::dynamic_config::pipeline::DynamicConfigSettings;
+use crate::service::port_factory::pipeline;
+use crate::service::static_config::MessagingPattern;
+use crate::service::*;
+use crate::zero_copy_type::{PayloadFor, TypeDescriptor, ZeroCopyType};
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_posix::adaptive_wait::AdaptiveWaitBuilder;
+use elkodon_cal::serialize::Serialize;
+use elkodon_cal::static_storage::StaticStorageLocked;
+
+use super::{OpenDynamicStorageFailure, ServiceState};
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum PipelineOpenError {
+    DoesNotExist,
+    InternalFailure,
+    IncompatibleTypes,
+    IncompatibleTypeLayout,
+    IncompatibleMessagingPattern,
+    IncompatibleAttributes,
+    DoesNotSupportRequestedMinBufferSize,
+    DoesNotSupportRequestedMinConsumerBorrowedSamples,
+    DoesNotSupportRequestedAmountOfProducers,
+    DoesNotSupportRequestedAmountOfConsumers,
+    PermissionDenied,
+    ServiceInCorruptedState,
+    HangsInCreation,
+    UnableToOpenDynamicServiceInformation,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum PipelineCreateError {
+    Corrupted,
+    AlreadyExists,
+    PermissionDenied,
+    InternalFailure,
+    IsBeingCreatedByAnotherInstance,
+    UnableToCreateStaticServiceInformation,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+enum ServiceAvailabilityState {
+    ServiceState(ServiceState),
+    IncompatibleTypes,
+    IncompatibleTypeLayout,
+}
+
+enum_gen! {
+    PipelineOpenOrCreateError
+  mapping:
+    PipelineOpenError,
+    PipelineCreateError
+}
+
+#[derive(Debug)]
+pub struct Builder<'global_config, ServiceType: service::Details<'global_config>> {
+    base: builder::BuilderWithServiceType<'global_config, ServiceType>,
+    verify_number_of_producers: bool,
+    verify_number_of_consumers: bool,
+    verify_consumer_buffer_size: bool,
+    verify_consumer_max_borrowed_samples: bool,
+}
+
+impl<'global_config, ServiceType: service::Details<'global_config>>
+    Builder<'global_config, ServiceType>
+{
+    pub(crate) fn new(base: builder::BuilderWithServiceType<'global_config, ServiceType>) -> Self {
+        let mut new_self = Self {
+            base,
+            verify_number_of_producers: false,
+            verify_number_of_consumers: false,
+            verify_consumer_buffer_size: false,
+            verify_consumer_max_borrowed_samples: false,
+        };
+
+        new_self.base.service_config.messaging_pattern = MessagingPattern::Pipeline(
+            static_config::pipeline::StaticConfig::new(new_self.base.global_config),
+        );
+
+        new_self
+    }
+
+    /// Records the created or opened service in the registry entry of the node.
+    pub(crate) fn node(mut self, value: &'global_config dyn NodeRegistry) -> Self {
+        self.base.node = Some(value);
+        self
+    }
+
+    /// Defines who besides the creator can access the created service. When an existing
+    /// service is opened, the access settings it was created with are used.
+    pub fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.base.service_config.access = value.into();
+        self
+    }
+
+    /// Tags the created service with an attribute. When the service is opened, the existing
+    /// service is required to have the same attribute.
+    pub fn attribute(mut self, key: &str, value: &str) -> Self {
+        self.base.service_config.attributes.set(key, value);
+        self
+    }
+
+    fn config_details_mut(&mut self) -> &mut static_config::pipeline::StaticConfig {
+        match self.base.service_config.messaging_pattern {
+            MessagingPattern::Pipeline(ref mut v) => v,
+            _ => {
+                fatal_panic!(from self, "This should never happen! Accessing wrong messaging pattern in Pipeline builder!");
+            }
+        }
+    }
+
+    fn config_details(&self) -> &static_config::pipeline::StaticConfig {
+        match self.base.service_config.messaging_pattern {
+            MessagingPattern::Pipeline(ref v) => v,
+            _ => {
+                fatal_panic!(from self, "This should never happen! Accessing wrong messaging pattern in Pipeline builder!");
+            }
+        }
+    }
+
+    pub fn max_producers(mut self, value: usize) -> Self {
+        self.config_details_mut().max_producers = value;
+        self.verify_number_of_producers = true;
+        self
+    }
+
+    pub fn max_consumers(mut self, value: usize) -> Self {
+        self.config_details_mut().max_consumers = value;
+        self.verify_number_of_consumers = true;
+        self
+    }
+
+    pub fn consumer_buffer_size(mut self, value: usize) -> Self {
+        self.config_details_mut().consumer_buffer_size = value;
+        self.verify_consumer_buffer_size = true;
+        self
+    }
+
+    pub fn consumer_max_borrowed_samples(mut self, value: usize) -> Self {
+        self.config_details_mut().consumer_max_borrowed_samples = std::cmp::max(value, 1);
+        self.verify_consumer_max_borrowed_samples = true;
+        self
+    }
+
+    fn verify_type_descriptor(
+        &self,
+        offered: &TypeDescriptor,
+        requested: &TypeDescriptor,
+        kind: &str,
+        error_msg: &str,
+    ) -> Result<(), ServiceAvailabilityState> {
+        if offered.type_identifier() != requested.type_identifier() {
+            fail!(from self, with ServiceAvailabilityState::IncompatibleTypes,
+                "{} since the service offers the {} \"{}\" but the requested {} is \"{}\".",
+                error_msg, kind, offered.type_identifier(), kind, requested.type_identifier());
+        }
+
+        if !offered.has_same_layout(requested) {
+            fail!(from self, with ServiceAvailabilityState::IncompatibleTypeLayout,
+                "{} since the service offers the {} \"{}\" with size {} and alignment {} but the requested {} has size {} and alignment {}.",
+                error_msg, kind, offered.type_identifier(), offered.size(), offered.alignment(),
+                kind, requested.size(), requested.alignment());
+        }
+
+        Ok(())
+    }
+
+    fn is_service_available(
+        &mut self,
+        error_msg: &str,
+    ) -> Result<Option<(StaticConfig, ServiceType::StaticStorage)>, ServiceAvailabilityState> {
+        match self.base.is_service_available() {
+            Ok(Some((config, storage))) => {
+                self.verify_type_descriptor(
+                    &config.pipeline().type_descriptor,
+                    &self.config_details().type_descriptor,
+                    "type",
+                    error_msg,
+                )?;
+
+                Ok(Some((config, storage)))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(ServiceAvailabilityState::ServiceState(e)),
+        }
+    }
+
+    pub fn open_or_create<MessageType: Debug + ZeroCopyType + PayloadFor<ServiceType>>(
+        mut self,
+    ) -> Result<
+        pipeline::PortFactory<'global_config, ServiceType, MessageType>,
+        PipelineOpenOrCreateError,
+    > {
+        let msg = "Unable to open or create pipeline service";
+        self.config_details_mut().type_descriptor = TypeDescriptor::new::<MessageType>();
+
+        self.base.remove_stale_service();
+
+        match self.is_service_available(msg) {
+            Ok(Some(_)) => Ok(self.open::<MessageType>()?),
+            Ok(None) => Ok(self.create::<MessageType>()?),
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IsBeingCreatedByAnotherInstance,
+            )) => Ok(self.open::<MessageType>()?),
+            Err(ServiceAvailabilityState::IncompatibleTypes) => {
+                fail!(from self, with PipelineOpenOrCreateError::PipelineOpenError(PipelineOpenError::IncompatibleTypes),
+                    "{} since the service is not type compatible.", msg);
+            }
+            Err(ServiceAvailabilityState::IncompatibleTypeLayout) => {
+                fail!(from self, with PipelineOpenOrCreateError::PipelineOpenError(PipelineOpenError::IncompatibleTypeLayout),
+                    "{} since the service has a different type layout.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IncompatibleMessagingPattern,
+            )) => {
+                fail!(from self, with PipelineOpenOrCreateError::PipelineOpenError(PipelineOpenError::IncompatibleMessagingPattern),
+                    "{} since the services messaging pattern does not match.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                fail!(from self, with PipelineOpenOrCreateError::PipelineOpenError(PipelineOpenError::ServiceInCorruptedState),
+                    "{} since the service is in a corrupted state.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                fail!(from self, with PipelineOpenOrCreateError::PipelineOpenError(PipelineOpenError::PermissionDenied),
+                    "{} due to insufficient permissions to access the service.", msg);
+            }
+        }
+    }
+
+    pub fn open<MessageType: Debug + ZeroCopyType + PayloadFor<ServiceType>>(
+        mut self,
+    ) -> Result<pipeline::PortFactory<'global_config, ServiceType, MessageType>, PipelineOpenError>
+    {
+        let msg = "Unable to open pipeline service";
+        self.config_details_mut().type_descriptor = TypeDescriptor::new::<MessageType>();
+
+        let mut adaptive_wait = fail!(from self, when AdaptiveWaitBuilder::new().create(),
+                                        with PipelineOpenError::InternalFailure,
+                                        "{} since the adaptive wait could not be created.", msg);
+
+        loop {
+            match self.is_service_available(msg) {
+                Ok(None) => {
+                    fail!(from self, with PipelineOpenError::DoesNotExist,
+                        "{} since the service does not exist.", msg);
+                }
+                Ok(Some((static_config, static_storage))) => {
+                    let dynamic_config = fail!(from self, when self.base.open_dynamic_config_storage(),
+                            map OpenDynamicStorageFailure::PermissionDenied => PipelineOpenError::PermissionDenied,
+                            unmatched PipelineOpenError::UnableToOpenDynamicServiceInformation,
+                            "{} since the dynamic service information could not be opened.", msg);
+                    let attributes = static_config.attributes().clone();
+                    let access = static_config.access.clone();
+                    let static_config = self.verify_service_properties(&static_config)?;
+                    self.base.service_config.attributes = attributes;
+                    self.base.service_config.access = access;
+
+                    self.base.service_config.messaging_pattern =
+                        MessagingPattern::Pipeline(static_config.clone());
+
+                    return Ok(pipeline::PortFactory::new(ServiceType::from_state(
+                        service::ServiceState::new(
+                            self.base.service_config,
+                            self.base.global_config,
+                            dynamic_config,
+                            static_storage,
+                            self.base.node,
+                        ),
+                    )));
+                }
+                Err(ServiceAvailabilityState::ServiceState(
+                    ServiceState::IsBeingCreatedByAnotherInstance,
+                )) => {
+                    let timeout = fail!(from self, when adaptive_wait.wait(),
+                                        with PipelineOpenError::InternalFailure,
+                                        "{} since the adaptive wait failed.", msg);
+
+                    if timeout > self.base.global_config.global.service.creation_timeout {
+                        fail!(from self, with PipelineOpenError::HangsInCreation,
+                            "{} since the service hangs while being created, max timeout for service creation of {:?} exceeded. Waited for {:?} but the state did not change.",
+                            msg, self.base.global_config.global.service.creation_timeout, timeout);
+                    }
+                }
+                Err(ServiceAvailabilityState::IncompatibleTypes) => {
+                    fail!(from self, with PipelineOpenError::IncompatibleTypes,
+                    "{} since the service is not type compatible.", msg);
+                }
+                Err(ServiceAvailabilityState::IncompatibleTypeLayout) => {
+                    fail!(from self, with PipelineOpenError::IncompatibleTypeLayout,
+                    "{} since the service has a different type layout.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(
+                    ServiceState::IncompatibleMessagingPattern,
+                )) => {
+                    fail!(from self, with PipelineOpenError::IncompatibleMessagingPattern,
+                    "{} since the services messaging pattern does not match.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                    fail!(from self, with PipelineOpenError::ServiceInCorruptedState,
+                    "{} since the service is in a corrupted state.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                    fail!(from self, with PipelineOpenError::PermissionDenied,
+                    "{} due to insufficient permissions to access the service.", msg);
+                }
+            }
+        }
+    }
+
+    pub fn create<MessageType: Debug + ZeroCopyType + PayloadFor<ServiceType>>(
+        mut self,
+    ) -> Result<pipeline::PortFactory<'global_config, ServiceType, MessageType>, PipelineCreateError>
+    {
+        self.adjust_properties_to_meaningful_values();
+
+        let msg = "Unable to create pipeline service";
+        self.config_details_mut().type_descriptor = TypeDescriptor::new::<MessageType>();
+
+        match self.is_service_available(msg) {
+            Ok(None) => {
+                // create static config
+                let static_config = fail!(from self, when self.base.create_static_config_storage(),
+                    with PipelineCreateError::UnableToCreateStaticServiceInformation,
+                    "{} since the static service information could not be created.", msg);
+
+                let pipeline_config = self.base.service_config.pipeline();
+
+                // create dynamic config
+                let dynamic_config_setting = DynamicConfigSettings {
+                    number_of_producers: pipeline_config.max_producers,
+                    number_of_consumers: pipeline_config.max_consumers,
+                };
+
+                let dynamic_config = self.base.create_dynamic_config_storage(
+                    dynamic_config::MessagingPattern::Pipeline(
+                        dynamic_config::pipeline::DynamicConfig::new(&dynamic_config_setting),
+                    ),
+                    dynamic_config::pipeline::DynamicConfig::memory_size(&dynamic_config_setting),
+                );
+                let dynamic_config = fail!(from self, when dynamic_config,
+                    with PipelineCreateError::InternalFailure,
+                    "{} since the dynamic service segment could not be created.", msg);
+
+                let service_config = fail!(from self, when ServiceType::ConfigSerializer::serialize(&self.base.service_config),
+                            with PipelineCreateError::Corrupted,
+                            "{} since the configuration could not be serialized.", msg);
+
+                // only unlock the static details when the service is successfully created
+                let unlocked_static_details = fail!(from self, when static_config.unlock(service_config.as_slice()),
+                            with PipelineCreateError::Corrupted,
+                            "{} since the configuration could not be written to the static storage.", msg);
+
+                return Ok(pipeline::PortFactory::new(ServiceType::from_state(
+                    service::ServiceState::new(
+                        self.base.service_config.clone(),
+                        self.base.global_config,
+                        dynamic_config,
+                        unlocked_static_details,
+                        self.base.node,
+                    ),
+                )));
+            }
+            Ok(Some(_))
+            | Err(ServiceAvailabilityState::IncompatibleTypes)
+            | Err(ServiceAvailabilityState::IncompatibleTypeLayout)
+            | Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IncompatibleMessagingPattern,
+            )) => {
+                fail!(from self, with PipelineCreateError::AlreadyExists,
+                    "{} since the service already exists.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                fail!(from self, with PipelineCreateError::PermissionDenied,
+                    "{} due to possible insufficient permissions to access the underlying service details.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                fail!(from self, with PipelineCreateError::Corrupted,
+                    "{} since a service in a corrupted state already exists. A cleanup of the service constructs may help.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IsBeingCreatedByAnotherInstance,
+            )) => {
+                fail!(from self, with PipelineCreateError::IsBeingCreatedByAnotherInstance,
+                    "{} since the service is being created by another instance.", msg);
+            }
+        }
+    }
+
+    fn adjust_properties_to_meaningful_values(&mut self) {
+        let origin = format!("{:?}", self);
+        let settings = self.base.service_config.pipeline_mut();
+
+        if settings.consumer_max_borrowed_samples == 0 {
+            warn!(from origin,
+                "Setting the consumers max borrowed samples to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.consumer_max_borrowed_samples = 1;
+        }
+
+        if settings.consumer_buffer_size == 0 {
+            warn!(from origin,
+                "Setting the consumer buffer size to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.consumer_buffer_size = 1;
+        }
+
+        if settings.max_producers == 0 {
+            warn!(from origin,
+                "Setting the maximum amount of producers to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.max_producers = 1;
+        }
+
+        if settings.max_consumers == 0 {
+            warn!(from origin,
+                "Setting the maximum amount of consumers to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.max_consumers = 1;
+        }
+    }
+
+    fn verify_service_properties(
+        &self,
+        existing_settings: &static_config::StaticConfig,
+    ) -> Result<static_config::pipeline::StaticConfig, PipelineOpenError> {
+        let msg = "Unable to open pipeline service";
+
+        let required_settings = self.base.service_config.pipeline();
+        if !existing_settings
+            .attributes()
+            .contains_all(&self.base.service_config.attributes)
+        {
+            fail!(from self, with PipelineOpenError::IncompatibleAttributes,
+                "{} since the service has the attributes {:?} but the attributes {:?} are required.",
+                msg, existing_settings.attributes(), self.base.service_config.attributes);
+        }
+
+        let existing_settings = match &existing_settings.messaging_pattern {
+            MessagingPattern::Pipeline(ref v) => v,
+            p => {
+                fail!(from self, with PipelineOpenError::IncompatibleMessagingPattern,
+                "{} since a service with the messaging pattern {:?} exists but MessagingPattern::Pipeline is required.", msg, p);
+            }
+        };
+
+        if self.verify_number_of_producers
+            && existing_settings.max_producers < required_settings.max_producers
+        {
+            fail!(from self, with PipelineOpenError::DoesNotSupportRequestedAmountOfProducers,
+                                "{} since the service supports only {} producers but a support of {} producers was requested.",
+                                msg, existing_settings.max_producers, required_settings.max_producers);
+        }
+
+        if self.verify_number_of_consumers
+            && existing_settings.max_consumers < required_settings.max_consumers
+        {
+            fail!(from self, with PipelineOpenError::DoesNotSupportRequestedAmountOfConsumers,
+                                "{} since the service supports only {} consumers but a support of {} consumers was requested.",
+                                msg, existing_settings.max_consumers, required_settings.max_consumers);
+        }
+
+        if self.verify_consumer_buffer_size
+            && existing_settings.consumer_buffer_size < required_settings.consumer_buffer_size
+        {
+            fail!(from self, with PipelineOpenError::DoesNotSupportRequestedMinBufferSize,
+                                "{} since the service supports only a consumer buffer size of {} but a buffer size of {} was requested.",
+                                msg, existing_settings.consumer_buffer_size, required_settings.consumer_buffer_size);
+        }
+
+        if self.verify_consumer_max_borrowed_samples
+            && existing_settings.consumer_max_borrowed_samples
+                < required_settings.consumer_max_borrowed_samples
+        {
+            fail!(from self, with PipelineOpenError::DoesNotSupportRequestedMinConsumerBorrowedSamples,
+                                "{} since the service supports only {} borrowed samples per consumer but {} borrowed samples were requested.",
+                                msg, existing_settings.consumer_max_borrowed_samples, required_settings.consumer_max_borrowed_samples);
+        }
+
+        Ok(existing_settings.clone())
+    }
+}
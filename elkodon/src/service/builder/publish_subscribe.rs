@@ -1,21 +1,29 @@
+use crate::message::Payload;
+use crate::node::NodeRegistry;
 use crate::service;
+use crate::service::access::AccessSettings;
 use crate::service::dynamic_config::publish_subscribe::DynamicConfigSettings;
 use crate::service::port_factory::publish_subscribe;
+use crate::service::serialization::SerializationFormat;
 use crate::service::static_config::MessagingPattern;
 use crate::service::*;
+use crate::zero_copy_type::{ShmSafe, TypeDescriptor, ZeroCopyType};
 use elkodon_cal::serialize::Serialize;
 use elkodon_cal::static_storage::StaticStorageLocked;
 use elkodon_bb_elementary::enum_gen;
 use elkodon_bb_log::{fail, fatal_panic, warn};
 use elkodon_bb_posix::adaptive_wait::AdaptiveWaitBuilder;
+use std::marker::PhantomData;
+use std::time::Duration;
 
-use super::ServiceState;
+use super::{OpenDynamicStorageFailure, ServiceState};
 
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub enum PublishSubscribeOpenError {
     DoesNotExist,
     InternalFailure,
     IncompatibleTypes,
+    IncompatibleTypeLayout,
     IncompatibleMessagingPattern,
     DoesNotSupportRequestedMinBufferSize,
     DoesNotSupportRequestedMinHistorySize,
@@ -23,6 +31,10 @@ pub enum PublishSubscribeOpenError {
     DoesNotSupportRequestedAmountOfPublishers,
     DoesNotSupportRequestedAmountOfSubscribers,
     IncompatibleOverflowBehavior,
+    IncompatibleEventBehavior,
+    IncompatibleLivelinessContract,
+    IncompatibleSerializationFormat,
+    IncompatibleAttributes,
     Inaccessible,
     PermissionDenied,
     ServiceInCorruptedState,
@@ -39,12 +51,14 @@ pub enum PublishSubscribeCreateError {
     InternalFailure,
     IsBeingCreatedByAnotherInstance,
     UnableToCreateStaticServiceInformation,
+    SerializationFormatRequiresByteSlicePayload,
 }
 
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 enum ServiceAvailabilityState {
     ServiceState(ServiceState),
     IncompatibleTypes,
+    IncompatibleTypeLayout,
 }
 
 enum_gen! {
@@ -55,7 +69,11 @@ enum_gen! {
 }
 
 #[derive(Debug)]
-pub struct Builder<'global_config, ServiceType: service::Details<'global_config>> {
+pub struct Builder<
+    'global_config,
+    ServiceType: service::Details<'global_config>,
+    UserHeader: Debug + Default + ZeroCopyType = (),
+> {
     base: builder::BuilderWithServiceType<'global_config, ServiceType>,
     verify_number_of_subscribers: bool,
     verify_number_of_publishers: bool,
@@ -63,13 +81,20 @@ pub struct Builder<'global_config, ServiceType: service::Details<'global_config>
     verify_subscriber_max_borrowed_samples: bool,
     verify_publisher_history_size: bool,
     verify_enable_safe_overflow: bool,
+    verify_enable_events: bool,
+    verify_max_heartbeat_interval: bool,
+    verify_serialization_format: bool,
+    _phantom_user_header: PhantomData<UserHeader>,
 }
 
-impl<'global_config, ServiceType: service::Details<'global_config>>
-    Builder<'global_config, ServiceType>
+impl<
+        'global_config,
+        ServiceType: service::Details<'global_config>,
+        UserHeader: Debug + Default + ZeroCopyType,
+    > Builder<'global_config, ServiceType, UserHeader>
 {
     pub(crate) fn new(base: builder::BuilderWithServiceType<'global_config, ServiceType>) -> Self {
-        let mut new_self = Self {
+        Self {
             base,
             verify_number_of_publishers: false,
             verify_number_of_subscribers: false,
@@ -77,13 +102,17 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
             verify_publisher_history_size: false,
             verify_subscriber_max_borrowed_samples: false,
             verify_enable_safe_overflow: false,
-        };
-
-        new_self.base.service_config.messaging_pattern = MessagingPattern::PublishSubscribe(
-            static_config::publish_subscribe::StaticConfig::new(new_self.base.global_config),
-        );
+            verify_enable_events: false,
+            verify_max_heartbeat_interval: false,
+            verify_serialization_format: false,
+            _phantom_user_header: PhantomData,
+        }
+    }
 
-        new_self
+    /// Records the created or opened service in the registry entry of the node.
+    pub(crate) fn node(mut self, value: &'global_config dyn NodeRegistry) -> Self {
+        self.base.node = Some(value);
+        self
     }
 
     fn config_details_mut(&mut self) -> &mut static_config::publish_subscribe::StaticConfig {
@@ -104,12 +133,85 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         }
     }
 
+    /// Defines the user header type which every sample carries in addition to its payload.
+    pub fn user_header<NewUserHeader: Debug + Default + ZeroCopyType + ShmSafe>(
+        self,
+    ) -> Builder<'global_config, ServiceType, NewUserHeader> {
+        Builder {
+            base: self.base,
+            verify_number_of_subscribers: self.verify_number_of_subscribers,
+            verify_number_of_publishers: self.verify_number_of_publishers,
+            verify_subscriber_buffer_size: self.verify_subscriber_buffer_size,
+            verify_subscriber_max_borrowed_samples: self.verify_subscriber_max_borrowed_samples,
+            verify_publisher_history_size: self.verify_publisher_history_size,
+            verify_enable_safe_overflow: self.verify_enable_safe_overflow,
+            verify_enable_events: self.verify_enable_events,
+            verify_max_heartbeat_interval: self.verify_max_heartbeat_interval,
+            verify_serialization_format: self.verify_serialization_format,
+            _phantom_user_header: PhantomData,
+        }
+    }
+
+    /// Keeps the service alive when its last owner drops it so that it can be opened again
+    /// later. Such a service is removed with [`publish_subscribe::PortFactory::remove()`].
+    pub fn persistent(mut self, value: bool) -> Self {
+        self.base.service_config.persistent = value;
+        self
+    }
+
+    /// Defines who besides the creator can access the created service. When an existing
+    /// service is opened, the access settings it was created with are used.
+    pub fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.base.service_config.access = value.into();
+        self
+    }
+
+    /// Tags the created service with an attribute. When the service is opened, the existing
+    /// service is required to have the same attribute.
+    pub fn attribute(mut self, key: &str, value: &str) -> Self {
+        self.base.service_config.attributes.set(key, value);
+        self
+    }
+
     pub fn enable_safe_overflow(mut self, value: bool) -> Self {
         self.config_details_mut().enable_safe_overflow = value;
         self.verify_enable_safe_overflow = true;
         self
     }
 
+    /// Attaches an event channel to every subscriber. The publishers notify the subscribers on
+    /// every delivered sample so that they can wait with
+    /// [`crate::port::subscriber::Subscriber::blocking_receive()`] or
+    /// [`crate::port::subscriber::Subscriber::timed_receive()`].
+    pub fn enable_events(mut self, value: bool) -> Self {
+        self.config_details_mut().enable_events = value;
+        self.verify_enable_events = true;
+        self
+    }
+
+    /// Defines the liveliness contract of the service. Every publisher has to announce its
+    /// liveliness at least once per interval, either by sending a sample or with
+    /// [`crate::port::publisher::Publisher::assert_liveliness()`], otherwise the subscribers
+    /// consider it as hung, see [`crate::port::subscriber::Subscriber::check_liveliness()`].
+    /// When the service is opened, the existing contract must be at least as strict.
+    pub fn max_heartbeat_interval(mut self, value: Duration) -> Self {
+        self.config_details_mut().max_heartbeat_interval = Some(value);
+        self.verify_max_heartbeat_interval = true;
+        self
+    }
+
+    /// Defines how the payload is serialized with
+    /// [`crate::port::publisher::Publisher::send_serialized()`] and
+    /// [`crate::port::subscriber::Subscriber::receive_deserialized()`]. Requires a byte slice
+    /// payload, otherwise the creation fails with
+    /// [`PublishSubscribeCreateError::SerializationFormatRequiresByteSlicePayload`]. When the
+    /// service is opened, the existing format must be the same.
+    pub fn serialization_format(mut self, value: SerializationFormat) -> Self {
+        self.config_details_mut().serialization_format = Some(value);
+        self.verify_serialization_format = true;
+        self
+    }
+
     pub fn subscriber_max_borrowed_samples(mut self, value: usize) -> Self {
         self.config_details_mut().subscriber_max_borrowed_samples = std::cmp::max(value, 1);
         self.verify_subscriber_max_borrowed_samples = true;
@@ -140,17 +242,52 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         self
     }
 
+    fn set_type_descriptors<MessageType: Payload + ?Sized>(&mut self) {
+        self.config_details_mut().type_descriptor = MessageType::type_descriptor();
+        self.config_details_mut().user_header_type_descriptor = TypeDescriptor::new::<UserHeader>();
+    }
+
+    fn verify_type_descriptor(
+        &self,
+        offered: &TypeDescriptor,
+        requested: &TypeDescriptor,
+        kind: &str,
+        error_msg: &str,
+    ) -> Result<(), ServiceAvailabilityState> {
+        if offered.type_identifier() != requested.type_identifier() {
+            fail!(from self, with ServiceAvailabilityState::IncompatibleTypes,
+                "{} since the service offers the {} \"{}\" but the requested {} is \"{}\".",
+                error_msg, kind, offered.type_identifier(), kind, requested.type_identifier());
+        }
+
+        if !offered.has_same_layout(requested) {
+            fail!(from self, with ServiceAvailabilityState::IncompatibleTypeLayout,
+                "{} since the service offers the {} \"{}\" with size {} and alignment {} but the requested {} has size {} and alignment {}.",
+                error_msg, kind, offered.type_identifier(), offered.size(), offered.alignment(),
+                kind, requested.size(), requested.alignment());
+        }
+
+        Ok(())
+    }
+
     fn is_service_available(
         &mut self,
         error_msg: &str,
     ) -> Result<Option<(StaticConfig, ServiceType::StaticStorage)>, ServiceAvailabilityState> {
         match self.base.is_service_available() {
             Ok(Some((config, storage))) => {
-                if config.publish_subscribe().type_name != self.config_details().type_name {
-                    fail!(from self, with ServiceAvailabilityState::IncompatibleTypes,
-                        "{} since the service offers the type \"{}\" but the requested type is \"{}\".",
-                        error_msg, &config.publish_subscribe().type_name , self.config_details().type_name);
-                }
+                self.verify_type_descriptor(
+                    &config.publish_subscribe().type_descriptor,
+                    &self.config_details().type_descriptor,
+                    "type",
+                    error_msg,
+                )?;
+                self.verify_type_descriptor(
+                    &config.publish_subscribe().user_header_type_descriptor,
+                    &self.config_details().user_header_type_descriptor,
+                    "user header type",
+                    error_msg,
+                )?;
 
                 Ok(Some((config, storage)))
             }
@@ -159,14 +296,16 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         }
     }
 
-    pub fn open_or_create<MessageType: Debug>(
+    pub fn open_or_create<MessageType: Payload + ?Sized>(
         mut self,
     ) -> Result<
-        publish_subscribe::PortFactory<'global_config, ServiceType, MessageType>,
+        publish_subscribe::PortFactory<'global_config, ServiceType, MessageType, UserHeader>,
         PublishSubscribeOpenOrCreateError,
     > {
         let msg = "Unable to open or create publish subscribe service";
-        self.config_details_mut().type_name = std::any::type_name::<MessageType>().to_string();
+        self.set_type_descriptors::<MessageType>();
+
+        self.base.remove_stale_service();
 
         match self.is_service_available(msg) {
             Ok(Some(_)) => Ok(self.open::<MessageType>()?),
@@ -178,6 +317,10 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                 fail!(from self, with PublishSubscribeOpenOrCreateError::PublishSubscribeOpenError(PublishSubscribeOpenError::IncompatibleTypes),
                     "{} since the service is not type compatible.", msg);
             }
+            Err(ServiceAvailabilityState::IncompatibleTypeLayout) => {
+                fail!(from self, with PublishSubscribeOpenOrCreateError::PublishSubscribeOpenError(PublishSubscribeOpenError::IncompatibleTypeLayout),
+                    "{} since the service has a different type layout.", msg);
+            }
             Err(ServiceAvailabilityState::ServiceState(
                 ServiceState::IncompatibleMessagingPattern,
             )) => {
@@ -195,14 +338,14 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         }
     }
 
-    pub fn open<MessageType: Debug>(
+    pub fn open<MessageType: Payload + ?Sized>(
         mut self,
     ) -> Result<
-        publish_subscribe::PortFactory<'global_config, ServiceType, MessageType>,
+        publish_subscribe::PortFactory<'global_config, ServiceType, MessageType, UserHeader>,
         PublishSubscribeOpenError,
     > {
         let msg = "Unable to open publish subscribe service";
-        self.config_details_mut().type_name = std::any::type_name::<MessageType>().to_string();
+        self.set_type_descriptors::<MessageType>();
 
         let mut adaptive_wait = fail!(from self, when AdaptiveWaitBuilder::new().create(),
                                         with PublishSubscribeOpenError::InternalFailure,
@@ -216,9 +359,15 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                 }
                 Ok(Some((static_config, static_storage))) => {
                     let dynamic_config = fail!(from self, when self.base.open_dynamic_config_storage(),
-                            with PublishSubscribeOpenError::UnableToOpenDynamicServiceInformation,
+                            map OpenDynamicStorageFailure::PermissionDenied => PublishSubscribeOpenError::PermissionDenied,
+                            unmatched PublishSubscribeOpenError::UnableToOpenDynamicServiceInformation,
                             "{} since the dynamic service information could not be opened.", msg);
+                    self.base.service_config.persistent = static_config.is_persistent();
+                    let attributes = static_config.attributes().clone();
+                    let access = static_config.access.clone();
                     let static_config = self.verify_service_properties(&static_config)?;
+                    self.base.service_config.attributes = attributes;
+                    self.base.service_config.access = access;
 
                     self.base.service_config.messaging_pattern =
                         MessagingPattern::PublishSubscribe(static_config.clone());
@@ -229,6 +378,7 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                             self.base.global_config,
                             dynamic_config,
                             static_storage,
+                            self.base.node,
                         )),
                     ));
                 }
@@ -249,6 +399,10 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                     fail!(from self, with PublishSubscribeOpenError::IncompatibleTypes,
                     "{} since the service is not type compatible.", msg);
                 }
+                Err(ServiceAvailabilityState::IncompatibleTypeLayout) => {
+                    fail!(from self, with PublishSubscribeOpenError::IncompatibleTypeLayout,
+                    "{} since the service has a different type layout.", msg);
+                }
                 Err(ServiceAvailabilityState::ServiceState(
                     ServiceState::IncompatibleMessagingPattern,
                 )) => {
@@ -267,16 +421,16 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         }
     }
 
-    pub fn create<MessageType: Debug>(
+    pub fn create<MessageType: Payload + ?Sized>(
         mut self,
     ) -> Result<
-        publish_subscribe::PortFactory<'global_config, ServiceType, MessageType>,
+        publish_subscribe::PortFactory<'global_config, ServiceType, MessageType, UserHeader>,
         PublishSubscribeCreateError,
     > {
         self.adjust_properties_to_meaningful_values();
 
         let msg = "Unable to create publish subscribe service";
-        self.config_details_mut().type_name = std::any::type_name::<MessageType>().to_string();
+        self.set_type_descriptors::<MessageType>();
 
         if !self.config_details().enable_safe_overflow
             && (self.config_details().subscriber_buffer_size < self.config_details().history_size)
@@ -285,6 +439,14 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                 "{} since the history size is greater than the subscriber buffer size. The subscriber buffer size must be always greater or equal to the history size in the non-overflowing setup.", msg);
         }
 
+        if self.config_details().serialization_format.is_some()
+            && MessageType::type_descriptor() != <[u8] as Payload>::type_descriptor()
+        {
+            fail!(from self, with PublishSubscribeCreateError::SerializationFormatRequiresByteSlicePayload,
+                "{} since a serialization format is defined but the payload type \"{}\" is not a byte slice.",
+                msg, std::any::type_name::<MessageType>());
+        }
+
         match self.is_service_available(msg) {
             Ok(None) => {
                 // create static config
@@ -329,11 +491,13 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                         self.base.global_config,
                         dynamic_config,
                         unlocked_static_details,
+                        self.base.node,
                     )),
                 ));
             }
             Ok(Some(_))
             | Err(ServiceAvailabilityState::IncompatibleTypes)
+            | Err(ServiceAvailabilityState::IncompatibleTypeLayout)
             | Err(ServiceAvailabilityState::ServiceState(
                 ServiceState::IncompatibleMessagingPattern,
             )) => {
@@ -393,6 +557,15 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
         let msg = "Unable to open publish subscribe service";
 
         let required_settings = self.base.service_config.publish_subscribe();
+        if !existing_settings
+            .attributes()
+            .contains_all(&self.base.service_config.attributes)
+        {
+            fail!(from self, with PublishSubscribeOpenError::IncompatibleAttributes,
+                "{} since the service has the attributes {:?} but the attributes {:?} are required.",
+                msg, existing_settings.attributes(), self.base.service_config.attributes);
+        }
+
         let existing_settings = match &existing_settings.messaging_pattern {
             MessagingPattern::PublishSubscribe(ref v) => v,
             p => {
@@ -450,6 +623,32 @@ impl<'global_config, ServiceType: service::Details<'global_config>>
                                 msg);
         }
 
+        if self.verify_enable_events
+            && existing_settings.enable_events != required_settings.enable_events
+        {
+            fail!(from self, with PublishSubscribeOpenError::IncompatibleEventBehavior,
+                                "{} since the service has an incompatible event behavior.",
+                                msg);
+        }
+
+        if self.verify_max_heartbeat_interval
+            && (existing_settings.max_heartbeat_interval.is_none()
+                || existing_settings.max_heartbeat_interval
+                    > required_settings.max_heartbeat_interval)
+        {
+            fail!(from self, with PublishSubscribeOpenError::IncompatibleLivelinessContract,
+                                "{} since the service has a max heartbeat interval of {:?} but a max heartbeat interval of {:?} was requested.",
+                                msg, existing_settings.max_heartbeat_interval, required_settings.max_heartbeat_interval);
+        }
+
+        if self.verify_serialization_format
+            && existing_settings.serialization_format != required_settings.serialization_format
+        {
+            fail!(from self, with PublishSubscribeOpenError::IncompatibleSerializationFormat,
+                                "{} since the service has the serialization format {:?} but the serialization format {:?} was requested.",
+                                msg, existing_settings.serialization_format, required_settings.serialization_format);
+        }
+
         Ok(existing_settings.clone())
     }
 }
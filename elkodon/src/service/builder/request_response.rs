@@ -0,0 +1,556 @@
+use crate::node::NodeRegistry;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::dynamic_config::request_response::DynamicConfigSettings;
+use crate::service::port_factory::request_response;
+use crate::service::static_config::MessagingPattern;
+use crate::service::*;
+use crate::zero_copy_type::{PayloadFor, TypeDescriptor, ZeroCopyType};
+use elkodon_bb_elementary::enum_gen;
+use elkodon_bb_log::{fail, fatal_panic, warn};
+use elkodon_bb_posix::adaptive_wait::AdaptiveWaitBuilder;
+use elkodon_cal::serialize::Serialize;
+use elkodon_cal::static_storage::StaticStorageLocked;
+
+use super::{OpenDynamicStorageFailure, ServiceState};
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum RequestResponseOpenError {
+    DoesNotExist,
+    InternalFailure,
+    IncompatibleTypes,
+    IncompatibleTypeLayout,
+    IncompatibleMessagingPattern,
+    IncompatibleAttributes,
+    DoesNotSupportRequestedMinRequestBufferSize,
+    DoesNotSupportRequestedMinResponseBufferSize,
+    DoesNotSupportRequestedMinServerBorrowedRequests,
+    DoesNotSupportRequestedMinClientBorrowedResponses,
+    DoesNotSupportRequestedAmountOfClients,
+    DoesNotSupportRequestedAmountOfServers,
+    PermissionDenied,
+    ServiceInCorruptedState,
+    HangsInCreation,
+    UnableToOpenDynamicServiceInformation,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum RequestResponseCreateError {
+    Corrupted,
+    AlreadyExists,
+    PermissionDenied,
+    InternalFailure,
+    IsBeingCreatedByAnotherInstance,
+    UnableToCreateStaticServiceInformation,
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+enum ServiceAvailabilityState {
+    ServiceState(ServiceState),
+    IncompatibleTypes,
+    IncompatibleTypeLayout,
+}
+
+enum_gen! {
+    RequestResponseOpenOrCreateError
+  mapping:
+    RequestResponseOpenError,
+    RequestResponseCreateError
+}
+
+#[derive(Debug)]
+pub struct Builder<'global_config, ServiceType: service::Details<'global_config>> {
+    base: builder::BuilderWithServiceType<'global_config, ServiceType>,
+    verify_number_of_clients: bool,
+    verify_number_of_servers: bool,
+    verify_request_buffer_size: bool,
+    verify_response_buffer_size: bool,
+    verify_server_max_borrowed_requests: bool,
+    verify_client_max_borrowed_responses: bool,
+}
+
+impl<'global_config, ServiceType: service::Details<'global_config>>
+    Builder<'global_config, ServiceType>
+{
+    pub(crate) fn new(base: builder::BuilderWithServiceType<'global_config, ServiceType>) -> Self {
+        let mut new_self = Self {
+            base,
+            verify_number_of_clients: false,
+            verify_number_of_servers: false,
+            verify_request_buffer_size: false,
+            verify_response_buffer_size: false,
+            verify_server_max_borrowed_requests: false,
+            verify_client_max_borrowed_responses: false,
+        };
+
+        new_self.base.service_config.messaging_pattern = MessagingPattern::RequestResponse(
+            static_config::request_response::StaticConfig::new(new_self.base.global_config),
+        );
+
+        new_self
+    }
+
+    /// Records the created or opened service in the registry entry of the node.
+    pub(crate) fn node(mut self, value: &'global_config dyn NodeRegistry) -> Self {
+        self.base.node = Some(value);
+        self
+    }
+
+    /// Defines who besides the creator can access the created service. When an existing
+    /// service is opened, the access settings it was created with are used.
+    pub fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.base.service_config.access = value.into();
+        self
+    }
+
+    /// Tags the created service with an attribute. When the service is opened, the existing
+    /// service is required to have the same attribute.
+    pub fn attribute(mut self, key: &str, value: &str) -> Self {
+        self.base.service_config.attributes.set(key, value);
+        self
+    }
+
+    fn config_details_mut(&mut self) -> &mut static_config::request_response::StaticConfig {
+        match self.base.service_config.messaging_pattern {
+            MessagingPattern::RequestResponse(ref mut v) => v,
+            _ => {
+                fatal_panic!(from self, "This should never happen! Accessing wrong messaging pattern in RequestResponse builder!");
+            }
+        }
+    }
+
+    fn config_details(&self) -> &static_config::request_response::StaticConfig {
+        match self.base.service_config.messaging_pattern {
+            MessagingPattern::RequestResponse(ref v) => v,
+            _ => {
+                fatal_panic!(from self, "This should never happen! Accessing wrong messaging pattern in RequestResponse builder!");
+            }
+        }
+    }
+
+    pub fn max_clients(mut self, value: usize) -> Self {
+        self.config_details_mut().max_clients = value;
+        self.verify_number_of_clients = true;
+        self
+    }
+
+    pub fn max_servers(mut self, value: usize) -> Self {
+        self.config_details_mut().max_servers = value;
+        self.verify_number_of_servers = true;
+        self
+    }
+
+    pub fn request_buffer_size(mut self, value: usize) -> Self {
+        self.config_details_mut().request_buffer_size = value;
+        self.verify_request_buffer_size = true;
+        self
+    }
+
+    pub fn response_buffer_size(mut self, value: usize) -> Self {
+        self.config_details_mut().response_buffer_size = value;
+        self.verify_response_buffer_size = true;
+        self
+    }
+
+    pub fn server_max_borrowed_requests(mut self, value: usize) -> Self {
+        self.config_details_mut().server_max_borrowed_requests = std::cmp::max(value, 1);
+        self.verify_server_max_borrowed_requests = true;
+        self
+    }
+
+    pub fn client_max_borrowed_responses(mut self, value: usize) -> Self {
+        self.config_details_mut().client_max_borrowed_responses = std::cmp::max(value, 1);
+        self.verify_client_max_borrowed_responses = true;
+        self
+    }
+
+    fn set_type_descriptors<RequestType: ZeroCopyType, ResponseType: ZeroCopyType>(&mut self) {
+        self.config_details_mut().request_type_descriptor = TypeDescriptor::new::<RequestType>();
+        self.config_details_mut().response_type_descriptor = TypeDescriptor::new::<ResponseType>();
+    }
+
+    fn verify_type_descriptor(
+        &self,
+        offered: &TypeDescriptor,
+        requested: &TypeDescriptor,
+        kind: &str,
+        error_msg: &str,
+    ) -> Result<(), ServiceAvailabilityState> {
+        if offered.type_identifier() != requested.type_identifier() {
+            fail!(from self, with ServiceAvailabilityState::IncompatibleTypes,
+                "{} since the service offers the {} \"{}\" but the requested {} is \"{}\".",
+                error_msg, kind, offered.type_identifier(), kind, requested.type_identifier());
+        }
+
+        if !offered.has_same_layout(requested) {
+            fail!(from self, with ServiceAvailabilityState::IncompatibleTypeLayout,
+                "{} since the service offers the {} \"{}\" with size {} and alignment {} but the requested {} has size {} and alignment {}.",
+                error_msg, kind, offered.type_identifier(), offered.size(), offered.alignment(),
+                kind, requested.size(), requested.alignment());
+        }
+
+        Ok(())
+    }
+
+    fn is_service_available(
+        &mut self,
+        error_msg: &str,
+    ) -> Result<Option<(StaticConfig, ServiceType::StaticStorage)>, ServiceAvailabilityState> {
+        match self.base.is_service_available() {
+            Ok(Some((config, storage))) => {
+                self.verify_type_descriptor(
+                    &config.request_response().request_type_descriptor,
+                    &self.config_details().request_type_descriptor,
+                    "request type",
+                    error_msg,
+                )?;
+                self.verify_type_descriptor(
+                    &config.request_response().response_type_descriptor,
+                    &self.config_details().response_type_descriptor,
+                    "response type",
+                    error_msg,
+                )?;
+
+                Ok(Some((config, storage)))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(ServiceAvailabilityState::ServiceState(e)),
+        }
+    }
+
+    pub fn open_or_create<
+        RequestType: Debug + ZeroCopyType + PayloadFor<ServiceType>,
+        ResponseType: Debug + ZeroCopyType + PayloadFor<ServiceType>,
+    >(
+        mut self,
+    ) -> Result<
+        request_response::PortFactory<'global_config, ServiceType, RequestType, ResponseType>,
+        RequestResponseOpenOrCreateError,
+    > {
+        let msg = "Unable to open or create request response service";
+        self.set_type_descriptors::<RequestType, ResponseType>();
+
+        self.base.remove_stale_service();
+
+        match self.is_service_available(msg) {
+            Ok(Some(_)) => Ok(self.open::<RequestType, ResponseType>()?),
+            Ok(None) => Ok(self.create::<RequestType, ResponseType>()?),
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IsBeingCreatedByAnotherInstance,
+            )) => Ok(self.open::<RequestType, ResponseType>()?),
+            Err(ServiceAvailabilityState::IncompatibleTypes) => {
+                fail!(from self, with RequestResponseOpenOrCreateError::RequestResponseOpenError(RequestResponseOpenError::IncompatibleTypes),
+                    "{} since the service is not type compatible.", msg);
+            }
+            Err(ServiceAvailabilityState::IncompatibleTypeLayout) => {
+                fail!(from self, with RequestResponseOpenOrCreateError::RequestResponseOpenError(RequestResponseOpenError::IncompatibleTypeLayout),
+                    "{} since the service has a different type layout.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IncompatibleMessagingPattern,
+            )) => {
+                fail!(from self, with RequestResponseOpenOrCreateError::RequestResponseOpenError(RequestResponseOpenError::IncompatibleMessagingPattern),
+                    "{} since the services messaging pattern does not match.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                fail!(from self, with RequestResponseOpenOrCreateError::RequestResponseOpenError(RequestResponseOpenError::ServiceInCorruptedState),
+                    "{} since the service is in a corrupted state.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                fail!(from self, with RequestResponseOpenOrCreateError::RequestResponseOpenError(RequestResponseOpenError::PermissionDenied),
+                    "{} due to insufficient permissions to access the service.", msg);
+            }
+        }
+    }
+
+    pub fn open<
+        RequestType: Debug + ZeroCopyType + PayloadFor<ServiceType>,
+        ResponseType: Debug + ZeroCopyType + PayloadFor<ServiceType>,
+    >(
+        mut self,
+    ) -> Result<
+        request_response::PortFactory<'global_config, ServiceType, RequestType, ResponseType>,
+        RequestResponseOpenError,
+    > {
+        let msg = "Unable to open request response service";
+        self.set_type_descriptors::<RequestType, ResponseType>();
+
+        let mut adaptive_wait = fail!(from self, when AdaptiveWaitBuilder::new().create(),
+                                        with RequestResponseOpenError::InternalFailure,
+                                        "{} since the adaptive wait could not be created.", msg);
+
+        loop {
+            match self.is_service_available(msg) {
+                Ok(None) => {
+                    fail!(from self, with RequestResponseOpenError::DoesNotExist,
+                        "{} since the service does not exist.", msg);
+                }
+                Ok(Some((static_config, static_storage))) => {
+                    let dynamic_config = fail!(from self, when self.base.open_dynamic_config_storage(),
+                            map OpenDynamicStorageFailure::PermissionDenied => RequestResponseOpenError::PermissionDenied,
+                            unmatched RequestResponseOpenError::UnableToOpenDynamicServiceInformation,
+                            "{} since the dynamic service information could not be opened.", msg);
+                    let attributes = static_config.attributes().clone();
+                    let access = static_config.access.clone();
+                    let static_config = self.verify_service_properties(&static_config)?;
+                    self.base.service_config.attributes = attributes;
+                    self.base.service_config.access = access;
+
+                    self.base.service_config.messaging_pattern =
+                        MessagingPattern::RequestResponse(static_config.clone());
+
+                    return Ok(request_response::PortFactory::new(ServiceType::from_state(
+                        service::ServiceState::new(
+                            self.base.service_config,
+                            self.base.global_config,
+                            dynamic_config,
+                            static_storage,
+                            self.base.node,
+                        ),
+                    )));
+                }
+                Err(ServiceAvailabilityState::ServiceState(
+                    ServiceState::IsBeingCreatedByAnotherInstance,
+                )) => {
+                    let timeout = fail!(from self, when adaptive_wait.wait(),
+                                        with RequestResponseOpenError::InternalFailure,
+                                        "{} since the adaptive wait failed.", msg);
+
+                    if timeout > self.base.global_config.global.service.creation_timeout {
+                        fail!(from self, with RequestResponseOpenError::HangsInCreation,
+                            "{} since the service hangs while being created, max timeout for service creation of {:?} exceeded. Waited for {:?} but the state did not change.",
+                            msg, self.base.global_config.global.service.creation_timeout, timeout);
+                    }
+                }
+                Err(ServiceAvailabilityState::IncompatibleTypes) => {
+                    fail!(from self, with RequestResponseOpenError::IncompatibleTypes,
+                    "{} since the service is not type compatible.", msg);
+                }
+                Err(ServiceAvailabilityState::IncompatibleTypeLayout) => {
+                    fail!(from self, with RequestResponseOpenError::IncompatibleTypeLayout,
+                    "{} since the service has a different type layout.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(
+                    ServiceState::IncompatibleMessagingPattern,
+                )) => {
+                    fail!(from self, with RequestResponseOpenError::IncompatibleMessagingPattern,
+                    "{} since the services messaging pattern does not match.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                    fail!(from self, with RequestResponseOpenError::ServiceInCorruptedState,
+                    "{} since the service is in a corrupted state.", msg);
+                }
+                Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                    fail!(from self, with RequestResponseOpenError::PermissionDenied,
+                    "{} due to insufficient permissions to access the service.", msg);
+                }
+            }
+        }
+    }
+
+    pub fn create<
+        RequestType: Debug + ZeroCopyType + PayloadFor<ServiceType>,
+        ResponseType: Debug + ZeroCopyType + PayloadFor<ServiceType>,
+    >(
+        mut self,
+    ) -> Result<
+        request_response::PortFactory<'global_config, ServiceType, RequestType, ResponseType>,
+        RequestResponseCreateError,
+    > {
+        self.adjust_properties_to_meaningful_values();
+
+        let msg = "Unable to create request response service";
+        self.set_type_descriptors::<RequestType, ResponseType>();
+
+        match self.is_service_available(msg) {
+            Ok(None) => {
+                // create static config
+                let static_config = fail!(from self, when self.base.create_static_config_storage(),
+                    with RequestResponseCreateError::UnableToCreateStaticServiceInformation,
+                    "{} since the static service information could not be created.", msg);
+
+                let request_response_config = self.base.service_config.request_response();
+
+                // create dynamic config
+                let dynamic_config_setting = DynamicConfigSettings {
+                    number_of_clients: request_response_config.max_clients,
+                    number_of_servers: request_response_config.max_servers,
+                };
+
+                let dynamic_config = self.base.create_dynamic_config_storage(
+                    dynamic_config::MessagingPattern::RequestResponse(
+                        dynamic_config::request_response::DynamicConfig::new(
+                            &dynamic_config_setting,
+                        ),
+                    ),
+                    dynamic_config::request_response::DynamicConfig::memory_size(
+                        &dynamic_config_setting,
+                    ),
+                );
+                let dynamic_config = fail!(from self, when dynamic_config,
+                    with RequestResponseCreateError::InternalFailure,
+                    "{} since the dynamic service segment could not be created.", msg);
+
+                let service_config = fail!(from self, when ServiceType::ConfigSerializer::serialize(&self.base.service_config),
+                            with RequestResponseCreateError::Corrupted,
+                            "{} since the configuration could not be serialized.", msg);
+
+                // only unlock the static details when the service is successfully created
+                let unlocked_static_details = fail!(from self, when static_config.unlock(service_config.as_slice()),
+                            with RequestResponseCreateError::Corrupted,
+                            "{} since the configuration could not be written to the static storage.", msg);
+
+                return Ok(request_response::PortFactory::new(ServiceType::from_state(
+                    service::ServiceState::new(
+                        self.base.service_config.clone(),
+                        self.base.global_config,
+                        dynamic_config,
+                        unlocked_static_details,
+                        self.base.node,
+                    ),
+                )));
+            }
+            Ok(Some(_))
+            | Err(ServiceAvailabilityState::IncompatibleTypes)
+            | Err(ServiceAvailabilityState::IncompatibleTypeLayout)
+            | Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IncompatibleMessagingPattern,
+            )) => {
+                fail!(from self, with RequestResponseCreateError::AlreadyExists,
+                    "{} since the service already exists.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::PermissionDenied)) => {
+                fail!(from self, with RequestResponseCreateError::PermissionDenied,
+                    "{} due to possible insufficient permissions to access the underlying service details.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(ServiceState::Corrupted)) => {
+                fail!(from self, with RequestResponseCreateError::Corrupted,
+                    "{} since a service in a corrupted state already exists. A cleanup of the service constructs may help.", msg);
+            }
+            Err(ServiceAvailabilityState::ServiceState(
+                ServiceState::IsBeingCreatedByAnotherInstance,
+            )) => {
+                fail!(from self, with RequestResponseCreateError::IsBeingCreatedByAnotherInstance,
+                    "{} since the service is being created by another instance.", msg);
+            }
+        }
+    }
+
+    fn adjust_properties_to_meaningful_values(&mut self) {
+        let origin = format!("{:?}", self);
+        let settings = self.base.service_config.request_response_mut();
+
+        if settings.server_max_borrowed_requests == 0 {
+            warn!(from origin,
+                "Setting the servers max borrowed requests to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.server_max_borrowed_requests = 1;
+        }
+
+        if settings.client_max_borrowed_responses == 0 {
+            warn!(from origin,
+                "Setting the clients max borrowed responses to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.client_max_borrowed_responses = 1;
+        }
+
+        if settings.request_buffer_size == 0 {
+            warn!(from origin,
+                "Setting the request buffer size to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.request_buffer_size = 1;
+        }
+
+        if settings.response_buffer_size == 0 {
+            warn!(from origin,
+                "Setting the response buffer size to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.response_buffer_size = 1;
+        }
+
+        if settings.max_clients == 0 {
+            warn!(from origin,
+                "Setting the maximum amount of clients to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.max_clients = 1;
+        }
+
+        if settings.max_servers == 0 {
+            warn!(from origin,
+                "Setting the maximum amount of servers to 0 is not supported. Adjust it to 1, the smallest supported value.");
+            settings.max_servers = 1;
+        }
+    }
+
+    fn verify_service_properties(
+        &self,
+        existing_settings: &static_config::StaticConfig,
+    ) -> Result<static_config::request_response::StaticConfig, RequestResponseOpenError> {
+        let msg = "Unable to open request response service";
+
+        let required_settings = self.base.service_config.request_response();
+        if !existing_settings
+            .attributes()
+            .contains_all(&self.base.service_config.attributes)
+        {
+            fail!(from self, with RequestResponseOpenError::IncompatibleAttributes,
+                "{} since the service has the attributes {:?} but the attributes {:?} are required.",
+                msg, existing_settings.attributes(), self.base.service_config.attributes);
+        }
+
+        let existing_settings = match &existing_settings.messaging_pattern {
+            MessagingPattern::RequestResponse(ref v) => v,
+            p => {
+                fail!(from self, with RequestResponseOpenError::IncompatibleMessagingPattern,
+                "{} since a service with the messaging pattern {:?} exists but MessagingPattern::RequestResponse is required.", msg, p);
+            }
+        };
+
+        if self.verify_number_of_clients
+            && existing_settings.max_clients < required_settings.max_clients
+        {
+            fail!(from self, with RequestResponseOpenError::DoesNotSupportRequestedAmountOfClients,
+                                "{} since the service supports only {} clients but a support of {} clients was requested.",
+                                msg, existing_settings.max_clients, required_settings.max_clients);
+        }
+
+        if self.verify_number_of_servers
+            && existing_settings.max_servers < required_settings.max_servers
+        {
+            fail!(from self, with RequestResponseOpenError::DoesNotSupportRequestedAmountOfServers,
+                                "{} since the service supports only {} servers but a support of {} servers was requested.",
+                                msg, existing_settings.max_servers, required_settings.max_servers);
+        }
+
+        if self.verify_request_buffer_size
+            && existing_settings.request_buffer_size < required_settings.request_buffer_size
+        {
+            fail!(from self, with RequestResponseOpenError::DoesNotSupportRequestedMinRequestBufferSize,
+                                "{} since the service supports only a request buffer size of {} but a buffer size of {} was requested.",
+                                msg, existing_settings.request_buffer_size, required_settings.request_buffer_size);
+        }
+
+        if self.verify_response_buffer_size
+            && existing_settings.response_buffer_size < required_settings.response_buffer_size
+        {
+            fail!(from self, with RequestResponseOpenError::DoesNotSupportRequestedMinResponseBufferSize,
+                                "{} since the service supports only a response buffer size of {} but a buffer size of {} was requested.",
+                                msg, existing_settings.response_buffer_size, required_settings.response_buffer_size);
+        }
+
+        if self.verify_server_max_borrowed_requests
+            && existing_settings.server_max_borrowed_requests
+                < required_settings.server_max_borrowed_requests
+        {
+            fail!(from self, with RequestResponseOpenError::DoesNotSupportRequestedMinServerBorrowedRequests,
+                                "{} since the service supports only {} borrowed requests per server but {} borrowed requests were requested.",
+                                msg, existing_settings.server_max_borrowed_requests, required_settings.server_max_borrowed_requests);
+        }
+
+        if self.verify_client_max_borrowed_responses
+            && existing_settings.client_max_borrowed_responses
+                < required_settings.client_max_borrowed_responses
+        {
+            fail!(from self, with RequestResponseOpenError::DoesNotSupportRequestedMinClientBorrowedResponses,
+                                "{} since the service supports only {} borrowed responses per client but {} borrowed responses were requested.",
+                                msg, existing_settings.client_max_borrowed_responses, required_settings.client_max_borrowed_responses);
+        }
+
+        Ok(existing_settings.clone())
+    }
+}
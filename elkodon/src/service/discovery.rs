@@ -0,0 +1,277 @@
+//! The [`ServiceWatcher`] reports whenever a service of a service type was added or removed.
+//! It lists the names of the static service storages whenever the directory which contains them
+//! has changed and reads only the static configs of services which it has not seen before.
+//! Services which exist already when the watcher is created are reported as added on the first
+//! wait.
+//!
+//! The directory is watched with a [`DirectoryWatcher`]. When the static service storages do not
+//! reside in the file system, like the ones of [`crate::service::process_local::Service`], the
+//! directory does not exist yet when the watcher is created or the platform does not support it,
+//! the watcher falls back to listing the storages in every polling interval.
+//!
+//! It can be attached to a [`crate::waitset::WaitSet`] with
+//! [`crate::waitset::WaitSet::attach_service_watcher()`], the attachment fires whenever the
+//! directory has changed, or in the polling interval as fallback, and the changes are acquired
+//! with [`ServiceWatcher::try_wait()`].
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_log::{debug, fail, warn};
+use elkodon_bb_posix::clock::{nanosleep, NanosleepError};
+use elkodon_bb_posix::directory::Directory;
+use elkodon_bb_posix::directory_watcher::{DirectoryWatcher, DirectoryWatcherWaitError};
+use elkodon_cal::named_concept::{NamedConceptListError, NamedConceptMgmt};
+use elkodon_cal::static_storage::StaticStorage;
+
+use crate::global_config;
+use crate::service::service_name::ServiceName;
+use crate::service::static_config::StaticConfig;
+use crate::service::{self, read_static_config, static_config_storage_config};
+
+/// The interval in which the blocking waits of the [`ServiceWatcher`] look for changes when the
+/// directory of the static service storages cannot be watched.
+pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiscoveryEvent {
+    ServiceAdded(StaticConfig),
+    ServiceRemoved(ServiceName),
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum ServiceWatcherWaitError {
+    Interrupt,
+    InsufficientPermissions,
+    InternalError,
+}
+
+/// Watches all services of one service type for additions and removals.
+#[derive(Debug)]
+pub struct ServiceWatcher<'global_config, Service: service::Details<'global_config>> {
+    global_config: &'global_config global_config::Entries,
+    known_services: RefCell<HashMap<String, ServiceName>>,
+    directory_watcher: Option<DirectoryWatcher>,
+    pub(crate) polling_interval: Duration,
+    _phantom_service: PhantomData<Service>,
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    ServiceWatcher<'global_config, Service>
+{
+    pub fn new() -> Self {
+        Self::new_with_custom_config(global_config::Config::get_global_config())
+    }
+
+    pub fn new_with_custom_config(config: &'global_config global_config::Config) -> Self {
+        let mut new_self = Self {
+            global_config: config.get(),
+            known_services: RefCell::new(HashMap::new()),
+            directory_watcher: None,
+            polling_interval: DEFAULT_POLLING_INTERVAL,
+            _phantom_service: PhantomData,
+        };
+        new_self.directory_watcher = new_self.create_directory_watcher();
+        new_self
+    }
+
+    fn create_directory_watcher(&self) -> Option<DirectoryWatcher> {
+        let static_storage_config = static_config_storage_config::<Service>(self.global_config);
+        let directory =
+            <Service::StaticStorage as StaticStorage>::directory(&static_storage_config)?;
+
+        // the directory is created with the first service and its access settings, the watcher
+        // must not create it since it may have only read access
+        if !matches!(Directory::does_exist(&directory), Ok(true)) {
+            debug!(from self, "The service directory \"{}\" does not exist yet, falling back to polling.", directory);
+            return None;
+        }
+
+        match DirectoryWatcher::new(&directory) {
+            Ok(watcher) => Some(watcher),
+            Err(e) => {
+                warn!(from self, "Unable to watch the service directory \"{}\" ({:?}), falling back to polling.", directory, e);
+                None
+            }
+        }
+    }
+
+    fn is_service_directory_missing(
+        &self,
+        static_storage_config: &<Service::StaticStorage as NamedConceptMgmt>::Configuration,
+    ) -> bool {
+        match <Service::StaticStorage as StaticStorage>::directory(static_storage_config) {
+            Some(directory) => matches!(Directory::does_exist(&directory), Ok(false)),
+            None => false,
+        }
+    }
+
+    pub(crate) fn directory_watcher(&self) -> Option<&DirectoryWatcher> {
+        self.directory_watcher.as_ref()
+    }
+
+    /// Defines the interval in which [`ServiceWatcher::timed_wait()`] and
+    /// [`ServiceWatcher::blocking_wait()`] look for changes when the directory of the static
+    /// service storages cannot be watched.
+    pub fn polling_interval(mut self, value: Duration) -> Self {
+        self.polling_interval = value;
+        self
+    }
+
+    /// Calls the provided callback for every change since the last call without blocking.
+    pub fn try_wait<F: FnMut(DiscoveryEvent)>(
+        &self,
+        fn_call: F,
+    ) -> Result<(), ServiceWatcherWaitError> {
+        self.wait(fn_call, Some(Duration::ZERO))
+    }
+
+    /// Waits until at least one service was added or removed or the timeout has passed and
+    /// calls the provided callback for every change.
+    pub fn timed_wait<F: FnMut(DiscoveryEvent)>(
+        &self,
+        fn_call: F,
+        timeout: Duration,
+    ) -> Result<(), ServiceWatcherWaitError> {
+        self.wait(fn_call, Some(timeout))
+    }
+
+    /// Waits until at least one service was added or removed and calls the provided callback
+    /// for every change.
+    pub fn blocking_wait<F: FnMut(DiscoveryEvent)>(
+        &self,
+        fn_call: F,
+    ) -> Result<(), ServiceWatcherWaitError> {
+        self.wait(fn_call, None)
+    }
+
+    fn wait<F: FnMut(DiscoveryEvent)>(
+        &self,
+        mut fn_call: F,
+        timeout: Option<Duration>,
+    ) -> Result<(), ServiceWatcherWaitError> {
+        let start = Instant::now();
+        loop {
+            // changes which happen after the pending notifications were discarded wake up the
+            // next wait
+            if let Some(watcher) = &self.directory_watcher {
+                self.handle_watcher_result(watcher.try_wait())?;
+            }
+
+            let events = self.collect_events()?;
+            if !events.is_empty() {
+                for event in events {
+                    fn_call(event);
+                }
+                return Ok(());
+            }
+
+            let remaining = match timeout {
+                Some(timeout) => match timeout.checked_sub(start.elapsed()) {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => return Ok(()),
+                },
+                None => None,
+            };
+
+            self.wait_for_changes(remaining)?;
+        }
+    }
+
+    fn wait_for_changes(&self, timeout: Option<Duration>) -> Result<(), ServiceWatcherWaitError> {
+        match &self.directory_watcher {
+            Some(watcher) => match timeout {
+                Some(timeout) => self.handle_watcher_result(watcher.timed_wait(timeout)),
+                None => self.handle_watcher_result(watcher.blocking_wait().map(|_| true)),
+            },
+            None => {
+                let sleep_duration =
+                    timeout.map_or(self.polling_interval, |t| t.min(self.polling_interval));
+                match nanosleep(sleep_duration) {
+                    Ok(()) => Ok(()),
+                    Err(NanosleepError::InterruptedBySignal(_)) => {
+                        fail!(from self, with ServiceWatcherWaitError::Interrupt,
+                            "Unable to wait for service changes since an interrupt signal was received.");
+                    }
+                    Err(e) => {
+                        fail!(from self, with ServiceWatcherWaitError::InternalError,
+                            "Unable to wait for service changes due to an internal failure ({:?}).", e);
+                    }
+                }
+            }
+        }
+    }
+
+    fn handle_watcher_result(
+        &self,
+        result: Result<bool, DirectoryWatcherWaitError>,
+    ) -> Result<(), ServiceWatcherWaitError> {
+        match result {
+            Ok(_) => Ok(()),
+            Err(DirectoryWatcherWaitError::Interrupt) => {
+                fail!(from self, with ServiceWatcherWaitError::Interrupt,
+                    "Unable to wait for service changes since an interrupt signal was received.");
+            }
+            Err(e) => {
+                fail!(from self, with ServiceWatcherWaitError::InternalError,
+                    "Unable to wait for service changes due to a failure in the directory watcher ({:?}).", e);
+            }
+        }
+    }
+
+    fn collect_events(&self) -> Result<Vec<DiscoveryEvent>, ServiceWatcherWaitError> {
+        let static_storage_config = static_config_storage_config::<Service>(self.global_config);
+        // the directory does not exist until the first service is created
+        let services = if self.is_service_directory_missing(&static_storage_config) {
+            vec![]
+        } else {
+            fail!(from self,
+                when <Service::StaticStorage as NamedConceptMgmt>::list_cfg(&static_storage_config),
+                map NamedConceptListError::InsufficientPermissions => ServiceWatcherWaitError::InsufficientPermissions,
+                unmatched ServiceWatcherWaitError::InternalError,
+                "Unable to look for service changes due to a failure while collecting all active services.")
+        };
+
+        let current_uuids: Vec<String> = services
+            .iter()
+            .map(|s| String::from_utf8_lossy(s.as_bytes()).to_string())
+            .collect();
+
+        let mut events = vec![];
+        let mut known_services = self.known_services.borrow_mut();
+        known_services.retain(|uuid, service_name| {
+            let exists = current_uuids.contains(uuid);
+            if !exists {
+                events.push(DiscoveryEvent::ServiceRemoved(*service_name));
+            }
+            exists
+        });
+
+        for (service_storage, uuid) in services.iter().zip(current_uuids) {
+            if known_services.contains_key(&uuid) {
+                continue;
+            }
+
+            // services which are still being created are reported in a later call
+            if let Some(service_config) =
+                read_static_config::<Service>(service_storage, &static_storage_config)
+            {
+                known_services.insert(uuid, *service_config.service_name());
+                events.push(DiscoveryEvent::ServiceAdded(service_config));
+            }
+        }
+
+        Ok(events)
+    }
+}
+
+impl<'global_config, Service: service::Details<'global_config>> Default
+    for ServiceWatcher<'global_config, Service>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
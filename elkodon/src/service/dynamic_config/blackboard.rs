@@ -0,0 +1,139 @@
+use std::alloc::Layout;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use elkodon_bb_elementary::allocator::BaseAllocator;
+use elkodon_bb_elementary::relocatable_container::RelocatableContainer;
+use elkodon_bb_elementary::relocatable_ptr::{PointerTrait, RelocatablePointer};
+use elkodon_bb_lock_free::mpmc::{container::*, unique_index_set::UniqueIndex};
+use elkodon_bb_log::fatal_panic;
+use elkodon_bb_memory::bump_allocator::BumpAllocator;
+
+use crate::port::port_identifiers::UniqueWriterId;
+
+const NO_OWNER: u32 = u32::MAX;
+
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicConfigSettings {
+    pub number_of_writers: usize,
+    pub number_of_entries: usize,
+    pub entries_layout: Layout,
+}
+
+#[derive(Debug)]
+pub struct DynamicConfig {
+    pub(crate) writers: Container<UniqueWriterId>,
+    // the index of the writer in `writers` which owns the entry, required to release the entries
+    // of dead writers
+    entry_owners: RelocatablePointer<AtomicU32>,
+    number_of_entries: usize,
+    entries: RelocatablePointer<u8>,
+    entries_layout: Layout,
+}
+
+impl DynamicConfig {
+    pub fn new(config: &DynamicConfigSettings) -> Self {
+        Self {
+            writers: unsafe { Container::new_uninit(config.number_of_writers) },
+            entry_owners: unsafe { RelocatablePointer::new_uninit() },
+            number_of_entries: config.number_of_entries,
+            entries: unsafe { RelocatablePointer::new_uninit() },
+            entries_layout: config.entries_layout,
+        }
+    }
+
+    pub(crate) unsafe fn init(&self, allocator: &BumpAllocator) {
+        fatal_panic!(from "blackboard::DynamicConfig::init",
+            when self.writers.init(allocator),
+            "This should never happen! Unable to initialize writer port id container.");
+        let entry_owners = fatal_panic!(from "blackboard::DynamicConfig::init",
+            when allocator.allocate(Self::entry_owners_layout(self.number_of_entries)),
+            "This should never happen! Unable to allocate the memory for the blackboard entry owners.");
+        self.entry_owners.init(entry_owners);
+        for i in 0..self.number_of_entries {
+            (self.entry_owners.as_ptr() as *mut AtomicU32)
+                .add(i)
+                .write(AtomicU32::new(NO_OWNER));
+        }
+        let entries = fatal_panic!(from "blackboard::DynamicConfig::init",
+            when allocator.allocate(self.entries_layout),
+            "This should never happen! Unable to allocate the memory for the blackboard entries.");
+        self.entries.init(entries);
+    }
+
+    fn entry_owners_layout(number_of_entries: usize) -> Layout {
+        fatal_panic!(from "blackboard::DynamicConfig::entry_owners_layout",
+            when Layout::array::<AtomicU32>(number_of_entries),
+            "This should never happen! The entry owners of {} entries require an invalid layout.",
+            number_of_entries)
+    }
+
+    pub fn memory_size(config: &DynamicConfigSettings) -> usize {
+        let entry_owners_layout = Self::entry_owners_layout(config.number_of_entries);
+        Container::<UniqueWriterId>::memory_size(config.number_of_writers)
+            + entry_owners_layout.size()
+            + entry_owners_layout.align()
+            - 1
+            + config.entries_layout.size()
+            + config.entries_layout.align()
+            - 1
+    }
+
+    pub fn number_of_supported_writers(&self) -> usize {
+        self.writers.capacity()
+    }
+
+    pub fn add_writer_id(&self, id: UniqueWriterId) -> Option<UniqueIndex> {
+        unsafe { self.writers.add(id) }
+    }
+
+    /// Removes the writer at `index` whose process has died. The entries it owned have to be
+    /// released beforehand with [`DynamicConfig::release_entries_of_dead_writer()`].
+    ///
+    /// # Safety
+    ///
+    ///  * the writer at `index` must be dead, otherwise its [`UniqueIndex`] is freed twice
+    ///
+    pub(crate) unsafe fn remove_dead_writer_id(&self, index: u32) {
+        self.writers.remove_raw_index(index)
+    }
+
+    fn entry_owner(&self, entry: usize) -> &AtomicU32 {
+        debug_assert!(entry < self.number_of_entries);
+        unsafe { &*self.entry_owners.as_ptr().add(entry) }
+    }
+
+    /// Registers the writer at `writer_index` as owner of the entry with the index `entry`.
+    /// Returns false when the entry is already owned by another writer.
+    pub(crate) fn acquire_entry(&self, entry: usize, writer_index: u32) -> bool {
+        self.entry_owner(entry)
+            .compare_exchange(NO_OWNER, writer_index, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+    }
+
+    /// Removes the owner of the entry with the index `entry`.
+    pub(crate) fn release_entry(&self, entry: usize) {
+        self.entry_owner(entry).store(NO_OWNER, Ordering::Relaxed);
+    }
+
+    /// Returns the indices of all entries that are owned by the writer at `writer_index` and
+    /// removes the writer as their owner.
+    pub(crate) fn release_entries_of_dead_writer(&self, writer_index: u32) -> Vec<usize> {
+        let mut entries = vec![];
+        for entry in 0..self.number_of_entries {
+            if self
+                .entry_owner(entry)
+                .compare_exchange(writer_index, NO_OWNER, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+            {
+                entries.push(entry);
+            }
+        }
+        entries
+    }
+
+    /// Returns the start address of the entry memory. It contains all keys, followed by all
+    /// entries as described in the [`crate::service::static_config::blackboard::StaticConfig`].
+    pub(crate) fn entries_ptr(&self) -> *mut u8 {
+        unsafe { self.entries.as_ptr() as *mut u8 }
+    }
+}
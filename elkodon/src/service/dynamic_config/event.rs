@@ -54,4 +54,20 @@ impl DynamicConfig {
     pub fn add_notifier_id(&self, id: UniqueNotifierId) -> Option<UniqueIndex> {
         unsafe { self.notifiers.add(id) }
     }
+
+    /// # Safety
+    ///
+    ///  * The process which owns the listener must be dead, otherwise its [`UniqueIndex`] is
+    ///    released twice
+    pub(crate) unsafe fn remove_dead_listener_id(&self, index: u32) {
+        self.listeners.remove_raw_index(index)
+    }
+
+    /// # Safety
+    ///
+    ///  * The process which owns the notifier must be dead, otherwise its [`UniqueIndex`] is
+    ///    released twice
+    pub(crate) unsafe fn remove_dead_notifier_id(&self, index: u32) {
+        self.notifiers.remove_raw_index(index)
+    }
 }
@@ -1,15 +1,22 @@
+pub mod blackboard;
 pub mod event;
+pub mod pipeline;
 pub mod publish_subscribe;
+pub mod request_response;
 
 use std::{
     fmt::Display,
-    sync::atomic::{AtomicU64, Ordering},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
 };
 
+use elkodon_bb_elementary::relocatable_container::RelocatableContainer;
+use elkodon_bb_lock_free::mpmc::container::Container;
 use elkodon_bb_log::{fail, fatal_panic};
 use elkodon_bb_memory::bump_allocator::BumpAllocator;
+use elkodon_bb_posix::process::{Process, ProcessId};
 
 const MARKED_FOR_DESTRUCTION: u64 = u64::MAX - 1;
+const NO_DEAD_PORT_CLEANUP_OWNER: u64 = 0;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 pub(crate) enum DecrementReferenceCounterResult {
@@ -21,12 +28,18 @@ pub(crate) enum DecrementReferenceCounterResult {
 pub enum MessagingPattern {
     PublishSubscribe(publish_subscribe::DynamicConfig),
     Event(event::DynamicConfig),
+    RequestResponse(request_response::DynamicConfig),
+    Blackboard(blackboard::DynamicConfig),
+    Pipeline(pipeline::DynamicConfig),
 }
 
 #[derive(Debug)]
 pub struct DynamicConfig {
     messaging_pattern: MessagingPattern,
     reference_counter: AtomicU64,
+    is_persistent: AtomicBool,
+    owners: Container<ProcessId>,
+    dead_port_cleanup_owner: AtomicU64,
 }
 
 impl Display for DynamicConfig {
@@ -40,17 +53,34 @@ impl Display for DynamicConfig {
 }
 
 impl DynamicConfig {
-    pub(crate) fn new_uninit(messaging_pattern: MessagingPattern) -> Self {
+    pub(crate) fn new_uninit(
+        messaging_pattern: MessagingPattern,
+        max_owners: usize,
+        is_persistent: bool,
+    ) -> Self {
         Self {
             messaging_pattern,
             reference_counter: AtomicU64::new(1),
+            is_persistent: AtomicBool::new(is_persistent),
+            owners: unsafe { Container::new_uninit(max_owners) },
+            dead_port_cleanup_owner: AtomicU64::new(NO_DEAD_PORT_CLEANUP_OWNER),
         }
     }
 
+    pub(crate) fn memory_size(max_owners: usize) -> usize {
+        Container::<ProcessId>::memory_size(max_owners)
+    }
+
     pub(crate) unsafe fn init(&self, allocator: &BumpAllocator) {
+        fatal_panic!(from "service::DynamicConfig::init",
+            when self.owners.init(allocator),
+            "This should never happen! Unable to initialize owner process id container.");
         match &self.messaging_pattern {
             MessagingPattern::PublishSubscribe(ref v) => v.init(allocator),
             MessagingPattern::Event(ref v) => v.init(allocator),
+            MessagingPattern::RequestResponse(ref v) => v.init(allocator),
+            MessagingPattern::Blackboard(ref v) => v.init(allocator),
+            MessagingPattern::Pipeline(ref v) => v.init(allocator),
         }
     }
 
@@ -81,12 +111,24 @@ impl DynamicConfig {
         let mut current_value = self.reference_counter.load(Ordering::Relaxed);
 
         loop {
+            // the service was already removed, e.g. with a forced remove, and the remaining
+            // owners must neither change the counter nor remove the service a second time
+            if current_value == MARKED_FOR_DESTRUCTION {
+                return DecrementReferenceCounterResult::HasOwners;
+            }
+
             result = DecrementReferenceCounterResult::HasOwners;
             match self.reference_counter.compare_exchange(
                 current_value,
                 if current_value == 1 {
-                    result = DecrementReferenceCounterResult::NoMoreOwners;
-                    MARKED_FOR_DESTRUCTION
+                    // a persistent service stays available without owners and can be opened
+                    // again
+                    if self.is_persistent() {
+                        0
+                    } else {
+                        result = DecrementReferenceCounterResult::NoMoreOwners;
+                        MARKED_FOR_DESTRUCTION
+                    }
                 } else {
                     current_value - 1
                 },
@@ -101,6 +143,106 @@ impl DynamicConfig {
         result
     }
 
+    pub(crate) fn is_persistent(&self) -> bool {
+        self.is_persistent.load(Ordering::Relaxed)
+    }
+
+    /// Turns a persistent service into a regular one which is removed as soon as its last owner
+    /// drops it.
+    pub(crate) fn release_persistence(&self) {
+        self.is_persistent.store(false, Ordering::Relaxed);
+    }
+
+    /// Registers the current process as owner of the service and returns the index of the entry
+    /// which has to be passed to [`DynamicConfig::remove_owner()`]. Returns [`None`] when no more
+    /// owners can be registered.
+    pub(crate) fn add_owner(&self) -> Option<u32> {
+        unsafe { self.owners.add_raw(Process::from_self().id()) }
+    }
+
+    /// # Safety
+    ///
+    ///  * The index must be acquired with [`DynamicConfig::add_owner()`] and must not be removed
+    ///    twice
+    pub(crate) unsafe fn remove_owner(&self, index: u32) {
+        self.owners.remove_raw_index(index)
+    }
+
+    /// Marks the service for destruction independent of its owners. Returns false when it was
+    /// already marked.
+    pub(crate) fn mark_for_destruction(&self) -> bool {
+        self.reference_counter
+            .swap(MARKED_FOR_DESTRUCTION, Ordering::Relaxed)
+            != MARKED_FOR_DESTRUCTION
+    }
+
+    /// Marks the service for destruction when all of its owners are dead. Returns true when the
+    /// service was marked and its resources can be removed.
+    pub(crate) fn mark_for_destruction_when_stale(&self) -> bool {
+        let current_value = self.reference_counter.load(Ordering::Relaxed);
+        if current_value == MARKED_FOR_DESTRUCTION || self.is_persistent() {
+            return false;
+        }
+
+        let mut number_of_owners = 0;
+        let mut has_living_owner = false;
+        unsafe { self.owners.get_state() }.for_each(|_, pid| {
+            number_of_owners += 1;
+            has_living_owner |= Process::from_pid(*pid).is_alive();
+        });
+
+        // the service could also be owned by processes which were unable to register themselves
+        // or which are just about to register themselves
+        if has_living_owner || number_of_owners != current_value {
+            return false;
+        }
+
+        self.reference_counter
+            .compare_exchange(
+                current_value,
+                MARKED_FOR_DESTRUCTION,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_ok()
+    }
+
+    /// Acquires the exclusive right to remove dead ports from the service. Returns false when
+    /// another living process is already cleaning up. A lock held by a dead process is taken
+    /// over.
+    pub(crate) fn try_lock_dead_port_cleanup(&self) -> bool {
+        let own_pid = Process::from_self().id().value() as u64;
+        let mut current_owner = NO_DEAD_PORT_CLEANUP_OWNER;
+
+        loop {
+            match self.dead_port_cleanup_owner.compare_exchange(
+                current_owner,
+                own_pid,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(v) => {
+                    if v != NO_DEAD_PORT_CLEANUP_OWNER
+                        && Process::from_pid(ProcessId::new(v as _)).is_alive()
+                    {
+                        return false;
+                    }
+                    current_owner = v;
+                }
+            }
+        }
+    }
+
+    pub(crate) fn unlock_dead_port_cleanup(&self) {
+        self.dead_port_cleanup_owner
+            .store(NO_DEAD_PORT_CLEANUP_OWNER, Ordering::Release);
+    }
+
+    pub(crate) fn messaging_pattern(&self) -> &MessagingPattern {
+        &self.messaging_pattern
+    }
+
     pub(crate) fn publish_subscribe(&self) -> &publish_subscribe::DynamicConfig {
         match &self.messaging_pattern {
             MessagingPattern::PublishSubscribe(ref v) => v,
@@ -118,4 +260,31 @@ impl DynamicConfig {
             }
         }
     }
+
+    pub(crate) fn request_response(&self) -> &request_response::DynamicConfig {
+        match &self.messaging_pattern {
+            MessagingPattern::RequestResponse(ref v) => v,
+            m => {
+                fatal_panic!(from self, "This should never happen! Try to access request_response::DynamicConfig when the messaging pattern is actually {:?}.", m);
+            }
+        }
+    }
+
+    pub(crate) fn blackboard(&self) -> &blackboard::DynamicConfig {
+        match &self.messaging_pattern {
+            MessagingPattern::Blackboard(ref v) => v,
+            m => {
+                fatal_panic!(from self, "This should never happen! Try to access blackboard::DynamicConfig when the messaging pattern is actually {:?}.", m);
+            }
+        }
+    }
+
+    pub(crate) fn pipeline(&self) -> &pipeline::DynamicConfig {
+        match &self.messaging_pattern {
+            MessagingPattern::Pipeline(ref v) => v,
+            m => {
+                fatal_panic!(from self, "This should never happen! Try to access pipeline::DynamicConfig when the messaging pattern is actually {:?}.", m);
+            }
+        }
+    }
 }
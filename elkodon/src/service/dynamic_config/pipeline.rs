@@ -0,0 +1,65 @@
+use elkodon_bb_elementary::relocatable_container::RelocatableContainer;
+use elkodon_bb_lock_free::mpmc::{container::*, unique_index_set::UniqueIndex};
+use elkodon_bb_log::fatal_panic;
+use elkodon_bb_memory::bump_allocator::BumpAllocator;
+
+use crate::port::port_identifiers::{UniqueConsumerId, UniqueProducerId};
+
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicConfigSettings {
+    pub number_of_producers: usize,
+    pub number_of_consumers: usize,
+}
+
+#[derive(Debug)]
+pub struct DynamicConfig {
+    pub(crate) producers: Container<UniqueProducerId>,
+    pub(crate) consumers: Container<UniqueConsumerId>,
+}
+
+impl DynamicConfig {
+    pub fn new(config: &DynamicConfigSettings) -> Self {
+        Self {
+            producers: unsafe { Container::new_uninit(config.number_of_producers) },
+            consumers: unsafe { Container::new_uninit(config.number_of_consumers) },
+        }
+    }
+
+    pub(crate) unsafe fn init(&self, allocator: &BumpAllocator) {
+        fatal_panic!(from "pipeline::DynamicConfig::init",
+            when self.producers.init(allocator),
+            "This should never happen! Unable to initialize producer port id container.");
+        fatal_panic!(from "pipeline::DynamicConfig::init",
+            when self.consumers.init(allocator),
+            "This should never happen! Unable to initialize consumer port id container.");
+    }
+
+    pub fn memory_size(config: &DynamicConfigSettings) -> usize {
+        Container::<UniqueProducerId>::memory_size(config.number_of_producers)
+            + Container::<UniqueConsumerId>::memory_size(config.number_of_consumers)
+    }
+
+    pub fn number_of_supported_producers(&self) -> usize {
+        self.producers.capacity()
+    }
+
+    pub fn number_of_supported_consumers(&self) -> usize {
+        self.consumers.capacity()
+    }
+
+    pub fn add_producer_id(&self, id: UniqueProducerId) -> Option<UniqueIndex> {
+        unsafe { self.producers.add(id) }
+    }
+
+    pub fn add_consumer_id(&self, id: UniqueConsumerId) -> Option<UniqueIndex> {
+        unsafe { self.consumers.add(id) }
+    }
+
+    /// # Safety
+    ///
+    ///  * The process which owns the consumer must be dead, otherwise its [`UniqueIndex`] is
+    ///    released twice
+    pub(crate) unsafe fn remove_dead_consumer_id(&self, index: u32) {
+        self.consumers.remove_raw_index(index)
+    }
+}
@@ -1,8 +1,14 @@
+use std::alloc::Layout;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use elkodon_bb_elementary::allocator::BaseAllocator;
 use elkodon_bb_elementary::relocatable_container::RelocatableContainer;
+use elkodon_bb_elementary::relocatable_ptr::{PointerTrait, RelocatablePointer};
 use elkodon_bb_lock_free::mpmc::{container::*, unique_index_set::UniqueIndex};
 use elkodon_bb_log::fatal_panic;
 use elkodon_bb_memory::bump_allocator::BumpAllocator;
 
+use crate::port::liveliness::heartbeat_timestamp;
 use crate::port::port_identifiers::{UniquePublisherId, UniqueSubscriberId};
 
 #[derive(Debug, Clone, Copy)]
@@ -11,10 +17,60 @@ pub struct DynamicConfigSettings {
     pub number_of_publishers: usize,
 }
 
+/// The counters of a publisher, stored at the index of its [`UniqueIndex`]. It also contains
+/// the timestamp of the last heartbeat of the publisher, see [`crate::port::liveliness`].
+#[derive(Debug, Default)]
+#[repr(C)]
+pub(crate) struct PublisherCounters {
+    pub(crate) sent_samples: AtomicU64,
+    pub(crate) failed_loans: AtomicU64,
+    pub(crate) blocked_time_in_ns: AtomicU64,
+    pub(crate) last_heartbeat_in_ns: AtomicU64,
+}
+
+impl PublisherCounters {
+    fn reset(&self) {
+        self.sent_samples.store(0, Ordering::Relaxed);
+        self.failed_loans.store(0, Ordering::Relaxed);
+        self.blocked_time_in_ns.store(0, Ordering::Relaxed);
+        // a new publisher is alive until it misses its first heartbeat
+        self.last_heartbeat_in_ns
+            .store(heartbeat_timestamp(), Ordering::Relaxed);
+    }
+}
+
+/// The counters of a subscriber, stored at the index of its [`UniqueIndex`]. The dropped samples
+/// are counted by the publishers since they are the ones that discard them. It also contains the
+/// history request of the subscriber which is served by every publisher, see
+/// [`crate::port::subscriber::Subscriber::request_history()`].
+#[derive(Debug, Default)]
+#[repr(C)]
+pub(crate) struct SubscriberCounters {
+    pub(crate) received_samples: AtomicU64,
+    pub(crate) samples_dropped_by_full_buffer: AtomicU64,
+    pub(crate) samples_dropped_by_overflow: AtomicU64,
+    pub(crate) requested_history_size: AtomicU64,
+    pub(crate) history_request_id: AtomicU64,
+    pub(crate) disconnections: AtomicU64,
+}
+
+impl SubscriberCounters {
+    fn reset(&self) {
+        self.received_samples.store(0, Ordering::Relaxed);
+        self.samples_dropped_by_full_buffer
+            .store(0, Ordering::Relaxed);
+        self.samples_dropped_by_overflow.store(0, Ordering::Relaxed);
+        self.history_request_id.store(0, Ordering::Relaxed);
+        self.disconnections.store(0, Ordering::Relaxed);
+    }
+}
+
 #[derive(Debug)]
 pub struct DynamicConfig {
     pub(crate) subscribers: Container<UniqueSubscriberId>,
     pub(crate) publishers: Container<UniquePublisherId>,
+    subscriber_counters: RelocatablePointer<SubscriberCounters>,
+    publisher_counters: RelocatablePointer<PublisherCounters>,
 }
 
 impl DynamicConfig {
@@ -22,6 +78,8 @@ impl DynamicConfig {
         Self {
             subscribers: unsafe { Container::new_uninit(config.number_of_subscribers) },
             publishers: unsafe { Container::new_uninit(config.number_of_publishers) },
+            subscriber_counters: unsafe { RelocatablePointer::new_uninit() },
+            publisher_counters: unsafe { RelocatablePointer::new_uninit() },
         }
     }
 
@@ -32,11 +90,49 @@ impl DynamicConfig {
         fatal_panic!(from "publish_subscribe::DynamicConfig::init",
             when self.publishers.init(allocator),
             "This should never happen! Unable to initialize publisher port id container.");
+
+        let number_of_subscribers = self.subscribers.capacity();
+        let subscriber_counters = fatal_panic!(from "publish_subscribe::DynamicConfig::init",
+            when allocator.allocate(Self::counters_layout::<SubscriberCounters>(number_of_subscribers)),
+            "This should never happen! Unable to allocate the memory for the subscriber statistics.");
+        self.subscriber_counters.init(subscriber_counters);
+        for i in 0..number_of_subscribers {
+            (self.subscriber_counters.as_ptr() as *mut SubscriberCounters)
+                .add(i)
+                .write(SubscriberCounters::default());
+        }
+
+        let number_of_publishers = self.publishers.capacity();
+        let publisher_counters = fatal_panic!(from "publish_subscribe::DynamicConfig::init",
+            when allocator.allocate(Self::counters_layout::<PublisherCounters>(number_of_publishers)),
+            "This should never happen! Unable to allocate the memory for the publisher statistics.");
+        self.publisher_counters.init(publisher_counters);
+        for i in 0..number_of_publishers {
+            (self.publisher_counters.as_ptr() as *mut PublisherCounters)
+                .add(i)
+                .write(PublisherCounters::default());
+        }
+    }
+
+    fn counters_layout<T>(number_of_ports: usize) -> Layout {
+        // a service without ports of one kind still requires a valid allocation
+        Layout::array::<T>(number_of_ports.max(1)).unwrap()
     }
 
     pub fn memory_size(config: &DynamicConfigSettings) -> usize {
+        let subscriber_counters =
+            Self::counters_layout::<SubscriberCounters>(config.number_of_subscribers);
+        let publisher_counters =
+            Self::counters_layout::<PublisherCounters>(config.number_of_publishers);
+
         Container::<UniqueSubscriberId>::memory_size(config.number_of_subscribers)
             + Container::<UniquePublisherId>::memory_size(config.number_of_publishers)
+            + subscriber_counters.size()
+            + subscriber_counters.align()
+            - 1
+            + publisher_counters.size()
+            + publisher_counters.align()
+            - 1
     }
 
     pub fn number_of_supported_publishers(&self) -> usize {
@@ -47,11 +143,58 @@ impl DynamicConfig {
         self.subscribers.capacity()
     }
 
-    pub fn add_subscriber_id(&self, id: UniqueSubscriberId) -> Option<UniqueIndex> {
-        unsafe { self.subscribers.add(id) }
+    /// Adds the subscriber. The publishers deliver at most `requested_history_size` samples of
+    /// their history when they connect to it.
+    pub fn add_subscriber_id(
+        &self,
+        id: UniqueSubscriberId,
+        requested_history_size: usize,
+    ) -> Option<UniqueIndex> {
+        // the slot could have been used by a previous subscriber, the counters must be reset
+        // before a publisher sees the subscriber and reads its history request
+        unsafe {
+            self.subscribers.add_with(id, |index| {
+                let counters = self.subscriber_counters(index as usize);
+                counters.reset();
+                counters
+                    .requested_history_size
+                    .store(requested_history_size as u64, Ordering::Relaxed);
+            })
+        }
     }
 
     pub fn add_publisher_id(&self, id: UniquePublisherId) -> Option<UniqueIndex> {
-        unsafe { self.publishers.add(id) }
+        unsafe {
+            self.publishers
+                .add_with(id, |index| self.publisher_counters(index as usize).reset())
+        }
+    }
+
+    /// Returns the counters of the subscriber which is stored at the provided index.
+    pub(crate) fn subscriber_counters(&self, index: usize) -> &SubscriberCounters {
+        debug_assert!(index < self.subscribers.capacity());
+        unsafe { &*self.subscriber_counters.as_ptr().add(index) }
+    }
+
+    /// Returns the counters of the publisher which is stored at the provided index.
+    pub(crate) fn publisher_counters(&self, index: usize) -> &PublisherCounters {
+        debug_assert!(index < self.publishers.capacity());
+        unsafe { &*self.publisher_counters.as_ptr().add(index) }
+    }
+
+    /// # Safety
+    ///
+    ///  * The process which owns the subscriber must be dead, otherwise its [`UniqueIndex`] is
+    ///    released twice
+    pub(crate) unsafe fn remove_dead_subscriber_id(&self, index: u32) {
+        self.subscribers.remove_raw_index(index)
+    }
+
+    /// # Safety
+    ///
+    ///  * The process which owns the publisher must be dead, otherwise its [`UniqueIndex`] is
+    ///    released twice
+    pub(crate) unsafe fn remove_dead_publisher_id(&self, index: u32) {
+        self.publishers.remove_raw_index(index)
     }
 }
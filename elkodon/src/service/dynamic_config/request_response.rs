@@ -0,0 +1,57 @@
+use elkodon_bb_elementary::relocatable_container::RelocatableContainer;
+use elkodon_bb_lock_free::mpmc::{container::*, unique_index_set::UniqueIndex};
+use elkodon_bb_log::fatal_panic;
+use elkodon_bb_memory::bump_allocator::BumpAllocator;
+
+use crate::port::port_identifiers::{UniqueClientId, UniqueServerId};
+
+#[derive(Debug, Clone, Copy)]
+pub struct DynamicConfigSettings {
+    pub number_of_clients: usize,
+    pub number_of_servers: usize,
+}
+
+#[derive(Debug)]
+pub struct DynamicConfig {
+    pub(crate) clients: Container<UniqueClientId>,
+    pub(crate) servers: Container<UniqueServerId>,
+}
+
+impl DynamicConfig {
+    pub fn new(config: &DynamicConfigSettings) -> Self {
+        Self {
+            clients: unsafe { Container::new_uninit(config.number_of_clients) },
+            servers: unsafe { Container::new_uninit(config.number_of_servers) },
+        }
+    }
+
+    pub(crate) unsafe fn init(&self, allocator: &BumpAllocator) {
+        fatal_panic!(from "request_response::DynamicConfig::init",
+            when self.clients.init(allocator),
+            "This should never happen! Unable to initialize client port id container.");
+        fatal_panic!(from "request_response::DynamicConfig::init",
+            when self.servers.init(allocator),
+            "This should never happen! Unable to initialize server port id container.");
+    }
+
+    pub fn memory_size(config: &DynamicConfigSettings) -> usize {
+        Container::<UniqueClientId>::memory_size(config.number_of_clients)
+            + Container::<UniqueServerId>::memory_size(config.number_of_servers)
+    }
+
+    pub fn number_of_supported_servers(&self) -> usize {
+        self.servers.capacity()
+    }
+
+    pub fn number_of_supported_clients(&self) -> usize {
+        self.clients.capacity()
+    }
+
+    pub fn add_client_id(&self, id: UniqueClientId) -> Option<UniqueIndex> {
+        unsafe { self.clients.add(id) }
+    }
+
+    pub fn add_server_id(&self, id: UniqueServerId) -> Option<UniqueIndex> {
+        unsafe { self.servers.add(id) }
+    }
+}
@@ -1 +1,3 @@
+pub mod pipeline;
 pub mod publish_subscribe;
+pub mod request_response;
@@ -0,0 +1,17 @@
+use crate::port::port_identifiers::UniqueProducerId;
+
+#[derive(Debug)]
+#[repr(C)]
+pub struct Header {
+    producer_port_id: UniqueProducerId,
+}
+
+impl Header {
+    pub fn new(producer_port_id: UniqueProducerId) -> Self {
+        Self { producer_port_id }
+    }
+
+    pub fn producer_id(&self) -> UniqueProducerId {
+        self.producer_port_id
+    }
+}
@@ -2,7 +2,7 @@ use elkodon_bb_posix::clock::{Time, TimeBuilder};
 
 use crate::port::port_identifiers::UniquePublisherId;
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 #[repr(C)]
 struct TimeStamp {
     seconds: u64,
@@ -14,17 +14,17 @@ struct TimeStamp {
 pub struct Header {
     publisher_port_id: UniquePublisherId,
     time_stamp: TimeStamp,
+    number_of_elements: u64,
+    sequence_number: u64,
 }
 
 impl Header {
-    pub fn new(publisher_port_id: UniquePublisherId) -> Self {
-        let now = Time::now().unwrap();
+    pub fn new(publisher_port_id: UniquePublisherId, number_of_elements: usize) -> Self {
         Self {
             publisher_port_id,
-            time_stamp: TimeStamp {
-                seconds: now.seconds(),
-                nanoseconds: now.nanoseconds(),
-            },
+            time_stamp: TimeStamp::default(),
+            number_of_elements: number_of_elements as u64,
+            sequence_number: 0,
         }
     }
 
@@ -32,6 +32,30 @@ impl Header {
         self.publisher_port_id
     }
 
+    /// Returns the number of elements of the payload. It is always 1 unless the payload is a
+    /// slice.
+    pub fn number_of_elements(&self) -> usize {
+        self.number_of_elements as usize
+    }
+
+    /// Returns the position of the sample in the sequence of samples sent by the publisher. It
+    /// starts at 0 and is incremented on every send, a gap means that samples were lost.
+    pub fn sequence_number(&self) -> u64 {
+        self.sequence_number
+    }
+
+    pub(crate) fn set_sequence_number(&mut self, value: u64) {
+        self.sequence_number = value;
+    }
+
+    pub(crate) fn set_time_stamp(&mut self, value: Time) {
+        self.time_stamp = TimeStamp {
+            seconds: value.seconds(),
+            nanoseconds: value.nanoseconds(),
+        };
+    }
+
+    /// Returns the time when the sample was sent.
     pub fn time_stamp(&self) -> Time {
         TimeBuilder::new()
             .nanoseconds(self.time_stamp.nanoseconds)
@@ -0,0 +1,55 @@
+use crate::port::port_identifiers::{UniqueClientId, UniqueServerId};
+
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct RequestHeader {
+    client_port_id: UniqueClientId,
+    request_id: u64,
+}
+
+impl RequestHeader {
+    pub fn new(client_port_id: UniqueClientId, request_id: u64) -> Self {
+        Self {
+            client_port_id,
+            request_id,
+        }
+    }
+
+    pub fn client_id(&self) -> UniqueClientId {
+        self.client_port_id
+    }
+
+    pub fn request_id(&self) -> u64 {
+        self.request_id
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+#[repr(C)]
+pub struct ResponseHeader {
+    server_port_id: UniqueServerId,
+    client_port_id: UniqueClientId,
+    request_id: u64,
+}
+
+impl ResponseHeader {
+    pub fn new(server_port_id: UniqueServerId, request_header: &RequestHeader) -> Self {
+        Self {
+            server_port_id,
+            client_port_id: request_header.client_id(),
+            request_id: request_header.request_id(),
+        }
+    }
+
+    pub fn server_id(&self) -> UniqueServerId {
+        self.server_port_id
+    }
+
+    pub fn client_id(&self) -> UniqueClientId {
+        self.client_port_id
+    }
+
+    pub fn request_id(&self) -> u64 {
+        self.request_id
+    }
+}
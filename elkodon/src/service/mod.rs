@@ -1,17 +1,26 @@
+pub mod access;
+pub mod attribute;
 pub mod builder;
+pub mod discovery;
 pub mod dynamic_config;
 pub mod header;
 pub mod port_factory;
 pub mod static_config;
 
 pub mod process_local;
+pub mod serialization;
 pub mod service_name;
 pub mod zero_copy;
 
 use std::fmt::Debug;
 
 use crate::global_config;
-use crate::port::port_identifiers::{UniqueListenerId, UniquePublisherId, UniqueSubscriberId};
+use crate::node::{NodeRegistry, PortRegistration};
+use crate::port::port_identifiers::{
+    UniqueClientId, UniqueConsumerId, UniqueListenerId, UniqueNotifierId, UniqueProducerId,
+    UniquePublisherId, UniqueServerId, UniqueSubscriberId, UniqueWriterId,
+};
+use crate::port::{client, producer, publisher, server};
 use crate::service::dynamic_config::DynamicConfig;
 use crate::service::static_config::*;
 use elkodon_cal::dynamic_storage::DynamicStorage;
@@ -25,10 +34,13 @@ use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
 use elkodon_cal::static_storage::*;
 use elkodon_cal::zero_copy_connection::ZeroCopyConnection;
 use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_lock_free::mpmc::container::Container;
 use elkodon_bb_log::{fail, fatal_panic, trace, warn};
+use elkodon_bb_posix::unique_system_id::UniqueSystemId;
 use elkodon_bb_system_types::file_name::FileName;
 use elkodon_bb_system_types::path::Path;
 
+use self::attribute::AttributeSet;
 use self::builder::Builder;
 use self::dynamic_config::DecrementReferenceCounterResult;
 use self::service_name::ServiceName;
@@ -45,15 +57,23 @@ pub enum ServiceListError {
     InternalError,
 }
 
-pub(crate) fn event_concept_name(listener_id: &UniqueListenerId) -> FileName {
-    let msg = "The system does not support the required file name length for the listeners event concept name.";
-    let origin = "event_concept_name()";
-    let mut file = fatal_panic!(from origin, when FileName::new(listener_id.0.pid().to_string().as_bytes()), "{}", msg);
+fn port_event_concept_name(port_id: &UniqueSystemId) -> FileName {
+    let msg = "The system does not support the required file name length for the ports event concept name.";
+    let origin = "port_event_concept_name()";
+    let mut file = fatal_panic!(from origin, when FileName::new(port_id.pid().to_string().as_bytes()), "{}", msg);
     fatal_panic!(from origin, when file.push(b'_'), "{}", msg);
-    fatal_panic!(from origin, when file.push_bytes(listener_id.0.value().to_string().as_bytes()), "{}", msg);
+    fatal_panic!(from origin, when file.push_bytes(port_id.value().to_string().as_bytes()), "{}", msg);
     file
 }
 
+pub(crate) fn event_concept_name(listener_id: &UniqueListenerId) -> FileName {
+    port_event_concept_name(&listener_id.0)
+}
+
+pub(crate) fn subscriber_event_concept_name(subscriber_id: &UniqueSubscriberId) -> FileName {
+    port_event_concept_name(&subscriber_id.0)
+}
+
 pub(crate) fn dynamic_config_storage_name(static_config: &StaticConfig) -> FileName {
     FileName::new(static_config.uuid().as_bytes()).unwrap()
 }
@@ -130,13 +150,11 @@ pub(crate) fn static_config_storage_config<
         .path_hint(path_hint)
 }
 
-pub(crate) fn connection_name(
-    publisher_id: UniquePublisherId,
-    subscriber_id: UniqueSubscriberId,
-) -> FileName {
-    let mut file = FileName::new(publisher_id.0.value().to_string().as_bytes()).unwrap();
+/// Name of the connection over which the sender port transmits its samples to the receiver port.
+pub(crate) fn connection_name(sender_id: UniqueSystemId, receiver_id: UniqueSystemId) -> FileName {
+    let mut file = FileName::new(sender_id.value().to_string().as_bytes()).unwrap();
     file.push(b'_').unwrap();
-    file.push_bytes(subscriber_id.0.value().to_string().as_bytes())
+    file.push_bytes(receiver_id.value().to_string().as_bytes())
         .unwrap();
     file
 }
@@ -159,6 +177,300 @@ pub(crate) fn connection_config<
 
     <Service::Connection as NamedConceptMgmt>::Configuration::default().suffix(f)
 }
+
+/// Returns the configuration of the event concepts which the ports of the service create.
+pub(crate) fn event_config<'global_config, Service: crate::service::Details<'global_config>>(
+    static_config: &StaticConfig,
+) -> <Service::Event as NamedConceptMgmt>::Configuration {
+    <Service::Event as NamedConceptMgmt>::Configuration::default()
+        .access_settings(&static_config.access_settings())
+}
+
+/// Reads the static config of the service which is stored under `service_storage`. Returns
+/// [`None`] when it is not readable, for instance since the service is still being created.
+pub(crate) fn read_static_config<'global_config, Service: Details<'global_config>>(
+    service_storage: &FileName,
+    static_storage_config: &<Service::StaticStorage as NamedConceptMgmt>::Configuration,
+) -> Option<StaticConfig> {
+    let origin = "read_static_config()";
+    let reader = match <<Service::StaticStorage as StaticStorage>::Builder as NamedConceptBuilder<
+        Service::StaticStorage,
+    >>::new(service_storage)
+    .config(static_storage_config)
+    .open()
+    {
+        Ok(reader) => reader,
+        Err(StaticStorageOpenError::IsLocked) => {
+            trace!(from origin, "The static service info \"{}\" is still being created.",
+                service_storage);
+            return None;
+        }
+        Err(e) => {
+            warn!(from origin, "Unable to acquire the static service info \"{}\" since it could not be opened for reading ({:?}).",
+                service_storage, e);
+            return None;
+        }
+    };
+
+    let mut content = String::from_utf8(vec![b' '; reader.len() as usize]).unwrap();
+    if let Err(e) = reader.read(unsafe { content.as_mut_vec().as_mut_slice() }) {
+        warn!(from origin, "Unable to acquire the static service info \"{}\" since it could not be read ({:?}).",
+            service_storage, e);
+        return None;
+    }
+
+    let service_config = match Service::ConfigSerializer::deserialize::<StaticConfig>(unsafe {
+        content.as_mut_vec()
+    }) {
+        Ok(service_config) => service_config,
+        Err(e) => {
+            warn!(from origin, "Unable to acquire the static service info \"{}\" since it could not be deserialized ({:?}).",
+                    service_storage, e);
+            return None;
+        }
+    };
+
+    if service_storage.as_bytes() != service_config.uuid().as_bytes() {
+        warn!(from origin, "Detected service {:?} with an inconsistent hash of {}.",
+            service_config, service_storage);
+        return None;
+    }
+
+    Some(service_config)
+}
+
+/// Removes a resource which was left behind by a dead process.
+pub(crate) fn remove_resource<Resource: NamedConceptMgmt>(
+    name: &FileName,
+    config: &Resource::Configuration,
+) {
+    // processes which still have the resource mapped are not affected by the removal
+    if let Err(e) = unsafe { Resource::remove_cfg(name, config) } {
+        warn!(from "remove_resource()",
+            "Unable to remove the resource \"{}\" of a dead process ({:?}).", name, e);
+    }
+}
+
+fn port_ids<T: Copy + Debug>(container: &Container<T>) -> Vec<T> {
+    let mut ids = vec![];
+    unsafe { container.get_state() }.for_each(|_, id| ids.push(*id));
+    ids
+}
+
+fn remove_port_resources<'global_config, Service: Details<'global_config>>(
+    dynamic_config: &DynamicConfig,
+    global_config: &global_config::Entries,
+) {
+    match dynamic_config.messaging_pattern() {
+        dynamic_config::MessagingPattern::PublishSubscribe(ref details) => {
+            let subscribers = port_ids(&details.subscribers);
+            // only present when the service has events enabled
+            for subscriber_id in &subscribers {
+                remove_resource::<Service::Event>(
+                    &subscriber_event_concept_name(subscriber_id),
+                    &<Service::Event as NamedConceptMgmt>::Configuration::default(),
+                );
+            }
+            for publisher_id in port_ids(&details.publishers) {
+                remove_resource::<Service::SharedMemory>(
+                    &publisher::data_segment_name(publisher_id),
+                    &publisher::data_segment_config::<Service>(global_config),
+                );
+                for subscriber_id in &subscribers {
+                    remove_resource::<Service::Connection>(
+                        &connection_name(publisher_id.0, subscriber_id.0),
+                        &connection_config::<Service>(global_config),
+                    );
+                }
+            }
+        }
+        dynamic_config::MessagingPattern::Event(ref details) => {
+            for listener_id in port_ids(&details.listeners) {
+                remove_resource::<Service::Event>(
+                    &event_concept_name(&listener_id),
+                    &<Service::Event as NamedConceptMgmt>::Configuration::default(),
+                );
+            }
+        }
+        dynamic_config::MessagingPattern::RequestResponse(ref details) => {
+            let servers = port_ids(&details.servers);
+            for server_id in &servers {
+                remove_resource::<Service::SharedMemory>(
+                    &server::data_segment_name(*server_id),
+                    &server::data_segment_config::<Service>(global_config),
+                );
+            }
+
+            for client_id in port_ids(&details.clients) {
+                remove_resource::<Service::SharedMemory>(
+                    &client::data_segment_name(client_id),
+                    &client::data_segment_config::<Service>(global_config),
+                );
+                for server_id in &servers {
+                    remove_resource::<Service::Connection>(
+                        &connection_name(client_id.0, server_id.0),
+                        &connection_config::<Service>(global_config),
+                    );
+                    remove_resource::<Service::Connection>(
+                        &connection_name(server_id.0, client_id.0),
+                        &connection_config::<Service>(global_config),
+                    );
+                }
+            }
+        }
+        dynamic_config::MessagingPattern::Pipeline(ref details) => {
+            let consumers = port_ids(&details.consumers);
+            for producer_id in port_ids(&details.producers) {
+                remove_resource::<Service::SharedMemory>(
+                    &producer::data_segment_name(producer_id),
+                    &producer::data_segment_config::<Service>(global_config),
+                );
+                for consumer_id in &consumers {
+                    remove_resource::<Service::Connection>(
+                        &connection_name(producer_id.0, consumer_id.0),
+                        &connection_config::<Service>(global_config),
+                    );
+                }
+            }
+        }
+        // the blackboard entries are stored in the dynamic config itself
+        dynamic_config::MessagingPattern::Blackboard(_) => (),
+    }
+}
+
+/// Opens the dynamic config of a service without becoming one of its owners.
+fn open_dynamic_config<'global_config, Service: Details<'global_config>>(
+    service_config: &StaticConfig,
+    global_config: &global_config::Entries,
+) -> Option<Service::DynamicStorage> {
+    let dynamic_storage_name = dynamic_config_storage_name(service_config);
+    let dynamic_storage_config = dynamic_config_storage_config::<Service>(global_config);
+
+    match <Service::DynamicStorage as NamedConceptMgmt>::does_exist_cfg(
+        &dynamic_storage_name,
+        &dynamic_storage_config,
+    ) {
+        Ok(true) => (),
+        Ok(false) | Err(_) => return None,
+    }
+
+    <<Service::DynamicStorage as DynamicStorage<DynamicConfig>>::Builder as NamedConceptBuilder<
+        Service::DynamicStorage,
+    >>::new(&dynamic_storage_name)
+    .config(&dynamic_storage_config)
+    .has_ownership(false)
+    .open()
+    .ok()
+}
+
+/// Removes the service and all resources of its ports when all processes which own the service
+/// are dead. Returns true when the service was removed.
+pub(crate) fn remove_stale_service<'global_config, Service: Details<'global_config>>(
+    service_config: &StaticConfig,
+    global_config: &global_config::Entries,
+) -> bool {
+    remove_service::<Service>(service_config, global_config, false)
+}
+
+/// Removes the service and all resources of its ports. Without `force` the service is only
+/// removed when all processes which own it are dead. Returns true when the service was removed.
+fn remove_service<'global_config, Service: Details<'global_config>>(
+    service_config: &StaticConfig,
+    global_config: &global_config::Entries,
+    force: bool,
+) -> bool {
+    let origin = "remove_service()";
+    let static_storage_name = static_config_storage_name(service_config.uuid());
+    let static_storage_config = static_config_storage_config::<Service>(global_config);
+
+    let mut dynamic_storage = match open_dynamic_config::<Service>(service_config, global_config) {
+        Some(storage) => storage,
+        None => {
+            // the creator died before the dynamic config was created
+            if force {
+                remove_resource::<Service::StaticStorage>(
+                    &static_storage_name,
+                    &static_storage_config,
+                );
+                trace!(from origin, "removed incomplete service {}", service_config.service_name());
+            }
+            return force;
+        }
+    };
+
+    // from here on the service cannot be opened anymore
+    let is_marked = match force {
+        true => dynamic_storage.get().mark_for_destruction(),
+        false => dynamic_storage.get().mark_for_destruction_when_stale(),
+    };
+    if !is_marked {
+        return false;
+    }
+
+    remove_port_resources::<Service>(dynamic_storage.get(), global_config);
+    remove_resource::<Service::StaticStorage>(&static_storage_name, &static_storage_config);
+    dynamic_storage.acquire_ownership();
+    trace!(from origin, "removed service {}", service_config.service_name());
+
+    true
+}
+
+/// The ids of all ports which are connected to a service.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConnectedPorts {
+    PublishSubscribe {
+        publishers: Vec<UniquePublisherId>,
+        subscribers: Vec<UniqueSubscriberId>,
+    },
+    Event {
+        notifiers: Vec<UniqueNotifierId>,
+        listeners: Vec<UniqueListenerId>,
+    },
+    RequestResponse {
+        clients: Vec<UniqueClientId>,
+        servers: Vec<UniqueServerId>,
+    },
+    Blackboard {
+        writers: Vec<UniqueWriterId>,
+    },
+    Pipeline {
+        producers: Vec<UniqueProducerId>,
+        consumers: Vec<UniqueConsumerId>,
+    },
+}
+
+impl ConnectedPorts {
+    fn new(dynamic_config: &DynamicConfig) -> Self {
+        match dynamic_config.messaging_pattern() {
+            dynamic_config::MessagingPattern::PublishSubscribe(ref details) => {
+                ConnectedPorts::PublishSubscribe {
+                    publishers: port_ids(&details.publishers),
+                    subscribers: port_ids(&details.subscribers),
+                }
+            }
+            dynamic_config::MessagingPattern::Event(ref details) => ConnectedPorts::Event {
+                notifiers: port_ids(&details.notifiers),
+                listeners: port_ids(&details.listeners),
+            },
+            dynamic_config::MessagingPattern::RequestResponse(ref details) => {
+                ConnectedPorts::RequestResponse {
+                    clients: port_ids(&details.clients),
+                    servers: port_ids(&details.servers),
+                }
+            }
+            dynamic_config::MessagingPattern::Blackboard(ref details) => {
+                ConnectedPorts::Blackboard {
+                    writers: port_ids(&details.writers),
+                }
+            }
+            dynamic_config::MessagingPattern::Pipeline(ref details) => ConnectedPorts::Pipeline {
+                producers: port_ids(&details.producers),
+                consumers: port_ids(&details.consumers),
+            },
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ServiceState<
     'global_config,
@@ -169,6 +481,8 @@ pub struct ServiceState<
     pub(crate) global_config: &'global_config global_config::Entries,
     pub(crate) dynamic_storage: Dynamic,
     pub(crate) static_storage: Static,
+    owner_index: Option<u32>,
+    node: Option<&'global_config dyn NodeRegistry>,
 }
 
 impl<'global_config, Static: StaticStorage, Dynamic: DynamicStorage<DynamicConfig>>
@@ -179,22 +493,46 @@ impl<'global_config, Static: StaticStorage, Dynamic: DynamicStorage<DynamicConfi
         global_config: &'global_config global_config::Entries,
         dynamic_storage: Dynamic,
         static_storage: Static,
+        node: Option<&'global_config dyn NodeRegistry>,
     ) -> Self {
+        let owner_index = dynamic_storage.get().add_owner();
         let new_self = Self {
             static_config,
             global_config,
             dynamic_storage,
             static_storage,
+            owner_index,
+            node,
         };
+
+        if new_self.owner_index.is_none() {
+            warn!(from new_self, "Unable to register the process as owner of the service since the maximum number of owners is exceeded. The service will not be detected as stale when this process crashes.");
+        }
+        if let Some(node) = new_self.node {
+            node.register_service(new_self.static_config.uuid());
+        }
         trace!(from new_self, "open service");
         new_self
     }
+
+    /// Records the port in the registry entry of the node which created the service until
+    /// the returned guard is dropped.
+    pub(crate) fn register_port(&self, port_id: u128) -> PortRegistration<'global_config> {
+        PortRegistration::new(self.node, port_id)
+    }
 }
 
 impl<'global_config, Static: StaticStorage, Dynamic: DynamicStorage<DynamicConfig>> Drop
     for ServiceState<'global_config, Static, Dynamic>
 {
     fn drop(&mut self) {
+        if let Some(index) = self.owner_index {
+            unsafe { self.dynamic_storage.get().remove_owner(index) };
+        }
+        if let Some(node) = self.node {
+            node.unregister_service(self.static_config.uuid());
+        }
+
         match self.dynamic_storage.get().decrement_reference_counter() {
             DecrementReferenceCounterResult::HasOwners => {
                 trace!(from self, "close service");
@@ -214,6 +552,62 @@ pub trait Service: Sized {
     fn new(name: &ServiceName) -> Builder<Self> {
         Builder::new(name)
     }
+
+    /// Removes all services whose owning processes are dead together with the resources of
+    /// their ports and returns the names of the removed services.
+    fn cleanup_stale_services() -> Result<Vec<ServiceName>, ServiceListError> {
+        let config = global_config::Config::get_global_config();
+        let mut removed_services = vec![];
+
+        for service_config in <Self::Type<'static> as Details<'static>>::list_from_config(config)? {
+            if remove_stale_service::<Self::Type<'static>>(&service_config, config.get()) {
+                removed_services.push(*service_config.service_name());
+            }
+        }
+
+        Ok(removed_services)
+    }
+
+    /// Removes the service with the provided name when all processes which own it are dead. With
+    /// `force` it is removed even when it is still in use, the processes which use it keep their
+    /// resources but the service cannot be opened anymore. Returns true when the service was
+    /// removed.
+    fn remove(service_name: &ServiceName, force: bool) -> Result<bool, ServiceListError> {
+        let config = global_config::Config::get_global_config();
+
+        for service_config in <Self::Type<'static> as Details<'static>>::list_from_config(config)? {
+            if service_config.service_name() == service_name {
+                return Ok(remove_service::<Self::Type<'static>>(
+                    &service_config,
+                    config.get(),
+                    force,
+                ));
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Returns the ids of the ports which are currently connected to the service without
+    /// opening it. Returns [`None`] when the service does not exist anymore.
+    fn connected_ports(service_config: &StaticConfig) -> Option<ConnectedPorts> {
+        let config = global_config::Config::get_global_config();
+        open_dynamic_config::<Self::Type<'static>>(service_config, config.get())
+            .map(|dynamic_storage| ConnectedPorts::new(dynamic_storage.get()))
+    }
+
+    /// Returns the configs of all services whose attributes fulfill the provided predicate.
+    fn list_matching<F: FnMut(&AttributeSet) -> bool>(
+        mut predicate: F,
+    ) -> Result<Vec<StaticConfig>, ServiceListError> {
+        let config = global_config::Config::get_global_config();
+        Ok(
+            <Self::Type<'static> as Details<'static>>::list_from_config(config)?
+                .into_iter()
+                .filter(|service_config| predicate(service_config.attributes()))
+                .collect(),
+        )
+    }
 }
 
 pub trait Details<'global_config>: Debug + Sized {
@@ -221,6 +615,7 @@ pub trait Details<'global_config>: Debug + Sized {
     type StaticStorage: StaticStorage;
     type ConfigSerializer: Serialize;
     type DynamicStorage: DynamicStorage<DynamicConfig>;
+    type NodePortsStorage: DynamicStorage<Container<u128>>;
     type SharedMemory: SharedMemory<PoolAllocator>;
     type Connection: ZeroCopyConnection;
     type Event: Event<u64>;
@@ -319,46 +714,11 @@ pub trait Details<'global_config>: Debug + Sized {
 
         let mut service_vec = vec![];
         for service_storage in services {
-            let reader =
-                match <<Self::StaticStorage as StaticStorage>::Builder as NamedConceptBuilder<
-                    Self::StaticStorage,
-                >>::new(&service_storage)
-                .config(&static_storage_config.clone())
-                .open()
-                {
-                    Ok(reader) => reader,
-                    Err(e) => {
-                        warn!(from origin, "Unable to acquire a list of all service since the static service info \"{}\" could not be opened for reading ({:?}).",
-                           service_storage, e );
-                        continue;
-                    }
-                };
-
-            let mut content = String::from_utf8(vec![b' '; reader.len() as usize]).unwrap();
-            if let Err(e) = reader.read(unsafe { content.as_mut_vec().as_mut_slice() }) {
-                warn!(from origin, "Unable to acquire a list of all service since the static service info \"{}\" could not be read ({:?}).",
-                           service_storage, e );
-                continue;
+            if let Some(service_config) =
+                read_static_config::<Self>(&service_storage, &static_storage_config)
+            {
+                service_vec.push(service_config);
             }
-
-            let service_config = match Self::ConfigSerializer::deserialize::<StaticConfig>(unsafe {
-                content.as_mut_vec()
-            }) {
-                Ok(service_config) => service_config,
-                Err(e) => {
-                    warn!(from origin, "Unable to acquire a list of all service since the static service info \"{}\" could not be deserialized ({:?}).",
-                       service_storage, e );
-                    continue;
-                }
-            };
-
-            if service_storage.as_bytes() != service_config.uuid().as_bytes() {
-                warn!(from origin, "Detected service {:?} with an inconsistent hash of {} when acquiring services according to config {:?}",
-                    service_config, service_storage, config);
-                continue;
-            }
-
-            service_vec.push(service_config);
         }
 
         Ok(service_vec)
@@ -0,0 +1,117 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use elkodon_cal::dynamic_storage::DynamicStorage;
+
+use crate::port::details::blackboard_entries::release_entry_producer;
+use crate::port::port_identifiers::UniqueWriterId;
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::attribute::AttributeSet;
+use crate::service::service_name::ServiceName;
+
+use super::{reader::PortFactoryReader, writer::PortFactoryWriter};
+
+#[derive(Debug)]
+pub struct PortFactory<
+    'global_config,
+    Service: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug,
+> {
+    pub(crate) service: Service,
+    _phantom_key_type: PhantomData<KeyType>,
+    _phantom_lifetime_b: PhantomData<&'global_config ()>,
+}
+
+unsafe impl<'global_config, Service: service::Details<'global_config>, KeyType: Copy + Eq + Debug>
+    Send for PortFactory<'global_config, Service, KeyType>
+{
+}
+unsafe impl<'global_config, Service: service::Details<'global_config>, KeyType: Copy + Eq + Debug>
+    Sync for PortFactory<'global_config, Service, KeyType>
+{
+}
+
+impl<'global_config, Service: service::Details<'global_config>, KeyType: Copy + Eq + Debug>
+    PortFactory<'global_config, Service, KeyType>
+{
+    pub(crate) fn new(service: Service) -> Self {
+        Self {
+            service,
+            _phantom_key_type: PhantomData,
+            _phantom_lifetime_b: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &ServiceName {
+        self.service.state().static_config.service_name()
+    }
+
+    /// Returns the attributes with which the service was created.
+    pub fn attributes(&self) -> &AttributeSet {
+        self.service.state().static_config.attributes()
+    }
+
+    /// Returns the access settings with which the service was created.
+    pub fn access_settings(&self) -> AccessSettings {
+        self.service.state().static_config.access_settings()
+    }
+
+    pub fn max_supported_writers(&self) -> usize {
+        self.service.state().static_config.blackboard().max_writers
+    }
+
+    pub fn number_of_entries(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .blackboard()
+            .entries
+            .len()
+    }
+
+    pub fn writer<'a>(&'a self) -> PortFactoryWriter<'a, 'global_config, Service, KeyType> {
+        PortFactoryWriter { factory: self }
+    }
+
+    pub fn reader<'a>(&'a self) -> PortFactoryReader<'a, 'global_config, Service, KeyType> {
+        PortFactoryReader { factory: self }
+    }
+
+    /// Removes all writers whose process has died from the service and releases the entries
+    /// they owned so that they can be acquired by another writer. Returns the number of removed
+    /// ports.
+    pub fn cleanup_dead_ports(&self) -> usize {
+        remove_dead_ports(&self.service)
+    }
+}
+
+/// Removes the dead writers of the service, see [`PortFactory::cleanup_dead_ports()`].
+pub(crate) fn remove_dead_ports<'global_config, Service: service::Details<'global_config>>(
+    service: &Service,
+) -> usize {
+    let dynamic_config = service.state().dynamic_storage.get();
+    if !dynamic_config.try_lock_dead_port_cleanup() {
+        return 0;
+    }
+
+    let details = dynamic_config.blackboard();
+    let mut writers = vec![];
+    unsafe { details.writers.get_state() }
+        .for_each(|index, id: &UniqueWriterId| writers.push((index, *id)));
+
+    let mut number_of_removed_ports = 0;
+    for (index, writer_id) in writers {
+        if writer_id.is_owner_alive() {
+            continue;
+        }
+
+        for entry in details.release_entries_of_dead_writer(index) {
+            unsafe { release_entry_producer(service, entry) };
+        }
+        unsafe { details.remove_dead_writer_id(index) };
+        number_of_removed_ports += 1;
+    }
+
+    dynamic_config.unlock_dead_port_cleanup();
+    number_of_removed_ports
+}
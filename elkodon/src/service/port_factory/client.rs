@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+
+use elkodon_bb_log::fail;
+
+use crate::{
+    port::client::{Client, ClientCreateError},
+    service,
+};
+
+use super::request_response::PortFactory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalClientConfig {
+    pub(crate) max_loaned_requests: usize,
+}
+
+#[derive(Debug)]
+pub struct PortFactoryClient<
+    'factory,
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    config: LocalClientConfig,
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, RequestType, ResponseType>,
+}
+
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > PortFactoryClient<'factory, 'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(
+        factory: &'factory PortFactory<'global_config, Service, RequestType, ResponseType>,
+    ) -> Self {
+        Self {
+            config: LocalClientConfig {
+                max_loaned_requests: factory
+                    .service
+                    .state()
+                    .global_config
+                    .defaults
+                    .request_response
+                    .client_max_loaned_requests,
+            },
+            factory,
+        }
+    }
+
+    pub fn max_loaned_requests(mut self, value: usize) -> Self {
+        self.config.max_loaned_requests = value;
+        self
+    }
+
+    pub fn create(
+        self,
+    ) -> Result<
+        Client<'factory, 'global_config, Service, RequestType, ResponseType>,
+        ClientCreateError,
+    > {
+        Ok(
+            fail!(from self, when Client::new(&self.factory.service, self.factory.service.state().static_config.request_response(), &self.config),
+                "Failed to create new Client port."),
+        )
+    }
+}
@@ -0,0 +1,33 @@
+use std::fmt::Debug;
+
+use elkodon_bb_log::fail;
+
+use crate::{
+    port::consumer::{Consumer, ConsumerCreateError},
+    service,
+};
+
+use super::pipeline::PortFactory;
+
+#[derive(Debug)]
+pub struct PortFactoryConsumer<
+    'factory,
+    'global_config,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, MessageType>,
+}
+
+impl<'factory, 'global_config, Service: service::Details<'global_config>, MessageType: Debug>
+    PortFactoryConsumer<'factory, 'global_config, Service, MessageType>
+{
+    pub fn create(
+        &self,
+    ) -> Result<Consumer<'factory, 'global_config, Service, MessageType>, ConsumerCreateError> {
+        Ok(
+            fail!(from self, when Consumer::new(&self.factory.service, self.factory.service.state().static_config.pipeline()),
+                "Failed to create new Consumer port."),
+        )
+    }
+}
@@ -1,5 +1,9 @@
+use crate::port::port_identifiers::{UniqueListenerId, UniqueNotifierId};
 use crate::service;
-use crate::service::ServiceName;
+use crate::service::access::AccessSettings;
+use crate::service::attribute::AttributeSet;
+use crate::service::{event_concept_name, event_config, remove_resource, ServiceName};
+use elkodon_cal::dynamic_storage::DynamicStorage;
 use std::marker::PhantomData;
 
 use super::listener::PortFactoryListener;
@@ -34,6 +38,16 @@ impl<'global_config, Service: service::Details<'global_config>>
         self.service.state().static_config.service_name()
     }
 
+    /// Returns the attributes with which the service was created.
+    pub fn attributes(&self) -> &AttributeSet {
+        self.service.state().static_config.attributes()
+    }
+
+    /// Returns the access settings with which the service was created.
+    pub fn access_settings(&self) -> AccessSettings {
+        self.service.state().static_config.access_settings()
+    }
+
     pub fn max_supported_listeners(&self) -> usize {
         self.service.state().static_config.event().max_listeners
     }
@@ -42,6 +56,27 @@ impl<'global_config, Service: service::Details<'global_config>>
         self.service.state().static_config.event().max_notifiers
     }
 
+    /// Returns true when the service is not removed when its last owner drops it.
+    pub fn is_persistent(&self) -> bool {
+        self.service.state().dynamic_storage.get().is_persistent()
+    }
+
+    /// Removes a persistent service. The service is removed as soon as every other owner has
+    /// dropped it as well.
+    pub fn remove(self) {
+        self.service
+            .state()
+            .dynamic_storage
+            .get()
+            .release_persistence();
+    }
+
+    /// Removes all listeners and notifiers whose process has died from the service, together
+    /// with the event concepts of the listeners. Returns the number of removed ports.
+    pub fn cleanup_dead_ports(&self) -> usize {
+        remove_dead_ports(&self.service)
+    }
+
     pub fn notifier<'a>(&'a self) -> PortFactoryNotifier<'a, 'global_config, Service> {
         PortFactoryNotifier::new(self)
     }
@@ -50,3 +85,49 @@ impl<'global_config, Service: service::Details<'global_config>>
         PortFactoryListener { factory: self }
     }
 }
+
+/// Removes the dead listeners and notifiers of the service, see
+/// [`PortFactory::cleanup_dead_ports()`].
+pub(crate) fn remove_dead_ports<'global_config, Service: service::Details<'global_config>>(
+    service: &Service,
+) -> usize {
+    let dynamic_config = service.state().dynamic_storage.get();
+    if !dynamic_config.try_lock_dead_port_cleanup() {
+        return 0;
+    }
+
+    let details = dynamic_config.event();
+    let mut number_of_removed_ports = 0;
+
+    let mut listeners = vec![];
+    unsafe { details.listeners.get_state() }
+        .for_each(|index, id: &UniqueListenerId| listeners.push((index, *id)));
+    for (index, listener_id) in listeners {
+        if listener_id.is_owner_alive() {
+            continue;
+        }
+
+        // remove it from the service first so that no notifier connects to it anymore
+        unsafe { details.remove_dead_listener_id(index) };
+        remove_resource::<Service::Event>(
+            &event_concept_name(&listener_id),
+            &event_config::<Service>(&service.state().static_config),
+        );
+        number_of_removed_ports += 1;
+    }
+
+    let mut notifiers = vec![];
+    unsafe { details.notifiers.get_state() }
+        .for_each(|index, id: &UniqueNotifierId| notifiers.push((index, *id)));
+    for (index, notifier_id) in notifiers {
+        if notifier_id.is_owner_alive() {
+            continue;
+        }
+
+        unsafe { details.remove_dead_notifier_id(index) };
+        number_of_removed_ports += 1;
+    }
+
+    dynamic_config.unlock_dead_port_cleanup();
+    number_of_removed_ports
+}
@@ -1,6 +1,15 @@
+pub mod blackboard;
+pub mod client;
+pub mod consumer;
 pub mod event;
 pub mod listener;
 pub mod notifier;
+pub mod pipeline;
+pub mod producer;
 pub mod publish_subscribe;
 pub mod publisher;
+pub mod reader;
+pub mod request_response;
+pub mod server;
 pub mod subscriber;
+pub mod writer;
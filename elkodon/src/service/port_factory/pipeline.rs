@@ -0,0 +1,86 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::attribute::AttributeSet;
+use crate::service::service_name::ServiceName;
+
+use super::{consumer::PortFactoryConsumer, producer::PortFactoryProducer};
+
+#[derive(Debug)]
+pub struct PortFactory<
+    'global_config,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    pub(crate) service: Service,
+    _phantom_message_type: PhantomData<MessageType>,
+    _phantom_lifetime_b: PhantomData<&'global_config ()>,
+}
+
+unsafe impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Send
+    for PortFactory<'global_config, Service, MessageType>
+{
+}
+unsafe impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Sync
+    for PortFactory<'global_config, Service, MessageType>
+{
+}
+
+impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug>
+    PortFactory<'global_config, Service, MessageType>
+{
+    pub(crate) fn new(service: Service) -> Self {
+        Self {
+            service,
+            _phantom_message_type: PhantomData,
+            _phantom_lifetime_b: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &ServiceName {
+        self.service.state().static_config.service_name()
+    }
+
+    /// Returns the attributes with which the service was created.
+    pub fn attributes(&self) -> &AttributeSet {
+        self.service.state().static_config.attributes()
+    }
+
+    /// Returns the access settings with which the service was created.
+    pub fn access_settings(&self) -> AccessSettings {
+        self.service.state().static_config.access_settings()
+    }
+
+    pub fn max_supported_producers(&self) -> usize {
+        self.service.state().static_config.pipeline().max_producers
+    }
+
+    pub fn max_supported_consumers(&self) -> usize {
+        self.service.state().static_config.pipeline().max_consumers
+    }
+
+    pub fn consumer_buffer_size(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .pipeline()
+            .consumer_buffer_size
+    }
+
+    pub fn consumer_max_borrowed_samples(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .pipeline()
+            .consumer_max_borrowed_samples
+    }
+
+    pub fn producer<'a>(&'a self) -> PortFactoryProducer<'a, 'global_config, Service, MessageType> {
+        PortFactoryProducer::new(self)
+    }
+
+    pub fn consumer<'a>(&'a self) -> PortFactoryConsumer<'a, 'global_config, Service, MessageType> {
+        PortFactoryConsumer { factory: self }
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Debug;
+
+use elkodon_bb_log::fail;
+use serde::{de::Visitor, Deserialize, Serialize};
+
+use crate::{
+    port::producer::{Producer, ProducerCreateError},
+    service,
+};
+
+use super::pipeline::PortFactory;
+
+/// Defines to which [`crate::port::consumer::Consumer`] a
+/// [`crate::port::producer::Producer`] hands the next sample.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum DistributionStrategy {
+    /// The consumers receive the samples in turns.
+    RoundRobin,
+    /// The consumer with the fewest samples that were sent but not yet returned receives the
+    /// sample.
+    LeastLoaded,
+}
+
+impl Serialize for DistributionStrategy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&std::format!("{:?}", self))
+    }
+}
+
+struct DistributionStrategyVisitor;
+
+impl<'de> Visitor<'de> for DistributionStrategyVisitor {
+    type Value = DistributionStrategy;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a string containing either 'round_robin' or 'least_loaded'")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match v {
+            "round_robin" => Ok(DistributionStrategy::RoundRobin),
+            "least_loaded" => Ok(DistributionStrategy::LeastLoaded),
+            v => Err(E::custom(format!(
+                "Invalid DistributionStrategy provided: \"{:?}\".",
+                v
+            ))),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DistributionStrategy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(DistributionStrategyVisitor)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalProducerConfig {
+    pub(crate) max_loaned_samples: usize,
+    pub(crate) distribution_strategy: DistributionStrategy,
+}
+
+#[derive(Debug)]
+pub struct PortFactoryProducer<
+    'factory,
+    'global_config,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    config: LocalProducerConfig,
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, MessageType>,
+}
+
+impl<'factory, 'global_config, Service: service::Details<'global_config>, MessageType: Debug>
+    PortFactoryProducer<'factory, 'global_config, Service, MessageType>
+{
+    pub(crate) fn new(
+        factory: &'factory PortFactory<'global_config, Service, MessageType>,
+    ) -> Self {
+        Self {
+            config: LocalProducerConfig {
+                max_loaned_samples: factory
+                    .service
+                    .state()
+                    .global_config
+                    .defaults
+                    .pipeline
+                    .producer_max_loaned_samples,
+                distribution_strategy: factory
+                    .service
+                    .state()
+                    .global_config
+                    .defaults
+                    .pipeline
+                    .distribution_strategy,
+            },
+            factory,
+        }
+    }
+
+    pub fn max_loaned_samples(mut self, value: usize) -> Self {
+        self.config.max_loaned_samples = value;
+        self
+    }
+
+    pub fn distribution_strategy(mut self, value: DistributionStrategy) -> Self {
+        self.config.distribution_strategy = value;
+        self
+    }
+
+    pub fn create(
+        self,
+    ) -> Result<Producer<'factory, 'global_config, Service, MessageType>, ProducerCreateError> {
+        Ok(
+            fail!(from self, when Producer::new(&self.factory.service, self.factory.service.state().static_config.pipeline(), &self.config),
+                "Failed to create new Producer port."),
+        )
+    }
+}
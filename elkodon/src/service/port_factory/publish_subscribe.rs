@@ -1,7 +1,21 @@
+use std::time::Duration;
 use std::{fmt::Debug, marker::PhantomData};
 
+use elkodon_cal::dynamic_storage::DynamicStorage;
+use elkodon_cal::named_concept::NamedConceptMgmt;
+
+use crate::message::Payload;
+use crate::port::port_identifiers::{UniquePublisherId, UniqueSubscriberId};
+use crate::port::publisher::{data_segment_config, data_segment_name};
+use crate::port::statistics::Statistics;
 use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::attribute::AttributeSet;
+use crate::service::serialization::SerializationFormat;
 use crate::service::service_name::ServiceName;
+use crate::service::{
+    connection_config, connection_name, remove_resource, subscriber_event_concept_name,
+};
 
 use super::{publisher::PortFactoryPublisher, subscriber::PortFactorySubscriber};
 
@@ -9,29 +23,44 @@ use super::{publisher::PortFactoryPublisher, subscriber::PortFactorySubscriber};
 pub struct PortFactory<
     'global_config,
     Service: service::Details<'global_config>,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug + Default = (),
 > {
     pub(crate) service: Service,
     _phantom_message_type: PhantomData<MessageType>,
+    _phantom_user_header: PhantomData<UserHeader>,
     _phantom_lifetime_b: PhantomData<&'global_config ()>,
 }
 
-unsafe impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Send
-    for PortFactory<'global_config, Service, MessageType>
+unsafe impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > Send for PortFactory<'global_config, Service, MessageType, UserHeader>
 {
 }
-unsafe impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Sync
-    for PortFactory<'global_config, Service, MessageType>
+unsafe impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > Sync for PortFactory<'global_config, Service, MessageType, UserHeader>
 {
 }
 
-impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug>
-    PortFactory<'global_config, Service, MessageType>
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > PortFactory<'global_config, Service, MessageType, UserHeader>
 {
     pub(crate) fn new(service: Service) -> Self {
         Self {
             service,
             _phantom_message_type: PhantomData,
+            _phantom_user_header: PhantomData,
             _phantom_lifetime_b: PhantomData,
         }
     }
@@ -40,6 +69,16 @@ impl<'global_config, Service: service::Details<'global_config>, MessageType: Deb
         self.service.state().static_config.service_name()
     }
 
+    /// Returns the attributes with which the service was created.
+    pub fn attributes(&self) -> &AttributeSet {
+        self.service.state().static_config.attributes()
+    }
+
+    /// Returns the access settings with which the service was created.
+    pub fn access_settings(&self) -> AccessSettings {
+        self.service.state().static_config.access_settings()
+    }
+
     pub fn max_supported_publishers(&self) -> usize {
         self.service
             .state()
@@ -88,15 +127,150 @@ impl<'global_config, Service: service::Details<'global_config>, MessageType: Deb
             .enable_safe_overflow
     }
 
+    /// Returns true when the publishers notify the subscribers on every delivered sample.
+    pub fn has_events(&self) -> bool {
+        self.service
+            .state()
+            .static_config
+            .publish_subscribe()
+            .enable_events
+    }
+
+    /// Returns the max interval between two heartbeats of a publisher or [`None`] when the
+    /// service has no liveliness contract.
+    pub fn max_heartbeat_interval(&self) -> Option<Duration> {
+        self.service
+            .state()
+            .static_config
+            .publish_subscribe()
+            .max_heartbeat_interval
+    }
+
+    /// Returns the format in which the payload is serialized or [`None`] when the service does
+    /// not transport serialized payloads.
+    pub fn serialization_format(&self) -> Option<SerializationFormat> {
+        self.service
+            .state()
+            .static_config
+            .publish_subscribe()
+            .serialization_format
+    }
+
     pub fn subscriber<'a>(
         &'a self,
-    ) -> PortFactorySubscriber<'a, 'global_config, Service, MessageType> {
-        PortFactorySubscriber { factory: self }
+    ) -> PortFactorySubscriber<'a, 'global_config, Service, MessageType, UserHeader> {
+        PortFactorySubscriber::new(self)
     }
 
     pub fn publisher<'a>(
         &'a self,
-    ) -> PortFactoryPublisher<'a, 'global_config, Service, MessageType> {
+    ) -> PortFactoryPublisher<'a, 'global_config, Service, MessageType, UserHeader> {
         PortFactoryPublisher::new(self)
     }
+
+    /// Returns a snapshot of the statistics of all publishers and subscribers of the service.
+    /// It does not require a port, any process which opened the service can acquire it.
+    pub fn statistics(&self) -> Statistics {
+        Statistics::new(
+            self.service
+                .state()
+                .dynamic_storage
+                .get()
+                .publish_subscribe(),
+        )
+    }
+
+    /// Returns true when the service is not removed when its last owner drops it.
+    pub fn is_persistent(&self) -> bool {
+        self.service.state().dynamic_storage.get().is_persistent()
+    }
+
+    /// Removes a persistent service. The service is removed as soon as every other owner has
+    /// dropped it as well.
+    pub fn remove(self) {
+        self.service
+            .state()
+            .dynamic_storage
+            .get()
+            .release_persistence();
+    }
+
+    /// Removes all publishers and subscribers whose process has died from the service, together
+    /// with their data segments and connections. The publishers release the samples a dead
+    /// subscriber still held with their next connection update. Returns the number of removed
+    /// ports.
+    pub fn cleanup_dead_ports(&self) -> usize {
+        remove_dead_ports(&self.service)
+    }
+}
+
+/// Removes the dead publishers and subscribers of the service, see
+/// [`PortFactory::cleanup_dead_ports()`].
+pub(crate) fn remove_dead_ports<'global_config, Service: service::Details<'global_config>>(
+    service: &Service,
+) -> usize {
+    let dynamic_config = service.state().dynamic_storage.get();
+    if !dynamic_config.try_lock_dead_port_cleanup() {
+        return 0;
+    }
+
+    let global_config = service.state().global_config;
+    let remove_connection = |publisher_id: UniquePublisherId, subscriber_id: UniqueSubscriberId| {
+        remove_resource::<Service::Connection>(
+            &connection_name(publisher_id.0, subscriber_id.0),
+            &connection_config::<Service>(global_config),
+        )
+    };
+
+    let details = dynamic_config.publish_subscribe();
+    let mut publishers = vec![];
+    unsafe { details.publishers.get_state() }
+        .for_each(|index, id: &UniquePublisherId| publishers.push((index, *id)));
+    let mut subscribers = vec![];
+    unsafe { details.subscribers.get_state() }
+        .for_each(|index, id: &UniqueSubscriberId| subscribers.push((index, *id)));
+
+    let mut number_of_removed_ports = 0;
+    for (index, publisher_id) in &publishers {
+        if publisher_id.is_owner_alive() {
+            continue;
+        }
+
+        // remove it from the service first so that no new connections are established
+        unsafe { details.remove_dead_publisher_id(*index) };
+        for (_, subscriber_id) in &subscribers {
+            remove_connection(*publisher_id, *subscriber_id);
+        }
+        remove_resource::<Service::SharedMemory>(
+            &data_segment_name(*publisher_id),
+            &data_segment_config::<Service>(global_config),
+        );
+        number_of_removed_ports += 1;
+    }
+
+    let has_events = service
+        .state()
+        .static_config
+        .publish_subscribe()
+        .enable_events;
+    for (index, subscriber_id) in &subscribers {
+        if subscriber_id.is_owner_alive() {
+            continue;
+        }
+
+        unsafe { details.remove_dead_subscriber_id(*index) };
+        for (_, publisher_id) in &publishers {
+            remove_connection(*publisher_id, *subscriber_id);
+        }
+        if has_events {
+            remove_resource::<Service::Event>(
+                &subscriber_event_concept_name(subscriber_id),
+                &<Service::Event as NamedConceptMgmt>::Configuration::default(),
+            );
+        }
+        number_of_removed_ports += 1;
+    }
+
+    dynamic_config.unlock_dead_port_cleanup();
+    number_of_removed_ports
 }
@@ -1,27 +1,82 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use elkodon_bb_log::fail;
 use serde::{de::Visitor, Deserialize, Serialize};
 
 use crate::{
+    message::Payload,
     port::publisher::{Publisher, PublisherCreateError},
     service,
 };
 
 use super::publish_subscribe::PortFactory;
 
+/// Defines the behavior of the publisher when the buffer of a subscriber is full. In the TOML
+/// config it is set with `'block'`, `'block_with_timeout(<duration>)'`, where the duration is an
+/// integer with one of the units `ns`, `us`, `ms` or `s`, or `'discard_sample'`.
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
 pub enum UnableToDeliverStrategy {
     Block,
+    /// Blocks until the subscriber made space or the timeout has passed. Afterwards the sample is
+    /// discarded for this subscriber only and the [`crate::port::DeliveryTimeoutCallback`] of
+    /// the publisher decides how to continue. When it returns
+    /// [`crate::port::DeliveryTimeoutAction::Disconnect`] the subscriber receives no more
+    /// samples, which it can detect with
+    /// [`crate::port::subscriber::Subscriber::number_of_disconnections()`]. The samples it still
+    /// holds are reclaimed when it returns them or when it is gone.
+    ///
+    /// The subscribers are served one after another, a single send blocks up to the number of
+    /// subscribers with a full buffer times the timeout.
+    BlockWithTimeout(Duration),
     DiscardSample,
 }
 
+const BLOCK_WITH_TIMEOUT_PREFIX: &str = "block_with_timeout(";
+const DURATION_UNITS: [(&str, u64); 4] = [
+    ("s", 1_000_000_000),
+    ("ms", 1_000_000),
+    ("us", 1_000),
+    ("ns", 1),
+];
+
+fn format_duration(value: Duration) -> String {
+    let nanoseconds = value.as_nanos() as u64;
+    for (unit, factor) in DURATION_UNITS {
+        if nanoseconds % factor == 0 {
+            return format!("{}{}", nanoseconds / factor, unit);
+        }
+    }
+
+    unreachable!()
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let unit_start = value.find(|c: char| !c.is_ascii_digit())?;
+    let (number, unit) = value.split_at(unit_start);
+    let number = number.parse::<u64>().ok()?;
+    DURATION_UNITS
+        .iter()
+        .find(|(u, _)| *u == unit)
+        .map(|(_, factor)| Duration::from_nanos(number.saturating_mul(*factor)))
+}
+
 impl Serialize for UnableToDeliverStrategy {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
     {
-        serializer.serialize_str(&std::format!("{:?}", self))
+        match self {
+            UnableToDeliverStrategy::Block => serializer.serialize_str("block"),
+            UnableToDeliverStrategy::BlockWithTimeout(timeout) => {
+                serializer.serialize_str(&format!(
+                    "{}{})",
+                    BLOCK_WITH_TIMEOUT_PREFIX,
+                    format_duration(*timeout)
+                ))
+            }
+            UnableToDeliverStrategy::DiscardSample => serializer.serialize_str("discard_sample"),
+        }
     }
 }
 
@@ -31,17 +86,25 @@ impl<'de> Visitor<'de> for UnableToDeliverStrategyVisitor {
     type Value = UnableToDeliverStrategy;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("a string containing either 'block' or 'discard_sample'")
+        formatter.write_str(
+            "a string containing either 'block', 'block_with_timeout(<duration>)' or 'discard_sample'",
+        )
     }
 
     fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
     where
         E: serde::de::Error,
     {
-        match v {
-            "block" => Ok(UnableToDeliverStrategy::Block),
-            "discard_sample" => Ok(UnableToDeliverStrategy::DiscardSample),
-            v => Err(E::custom(format!(
+        let timeout = v
+            .strip_prefix(BLOCK_WITH_TIMEOUT_PREFIX)
+            .and_then(|v| v.strip_suffix(')'))
+            .and_then(parse_duration);
+
+        match (v, timeout) {
+            ("block", _) => Ok(UnableToDeliverStrategy::Block),
+            ("discard_sample", _) => Ok(UnableToDeliverStrategy::DiscardSample),
+            (_, Some(timeout)) => Ok(UnableToDeliverStrategy::BlockWithTimeout(timeout)),
+            (v, None) => Err(E::custom(format!(
                 "Invalid UnableToDeliverStrategy provided: \"{:?}\".",
                 v
             ))),
@@ -62,6 +125,7 @@ impl<'de> Deserialize<'de> for UnableToDeliverStrategy {
 pub struct LocalPublisherConfig {
     pub(crate) max_loaned_samples: usize,
     pub(crate) unable_to_deliver_strategy: UnableToDeliverStrategy,
+    pub(crate) max_slice_len: usize,
 }
 
 #[derive(Debug)]
@@ -69,17 +133,23 @@ pub struct PortFactoryPublisher<
     'factory,
     'global_config,
     Service: service::Details<'global_config>,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug + Default = (),
 > {
     config: LocalPublisherConfig,
-    pub(crate) factory: &'factory PortFactory<'global_config, Service, MessageType>,
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, MessageType, UserHeader>,
 }
 
-impl<'factory, 'global_config, Service: service::Details<'global_config>, MessageType: Debug>
-    PortFactoryPublisher<'factory, 'global_config, Service, MessageType>
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > PortFactoryPublisher<'factory, 'global_config, Service, MessageType, UserHeader>
 {
     pub(crate) fn new(
-        factory: &'factory PortFactory<'global_config, Service, MessageType>,
+        factory: &'factory PortFactory<'global_config, Service, MessageType, UserHeader>,
     ) -> Self {
         Self {
             config: LocalPublisherConfig {
@@ -97,6 +167,7 @@ impl<'factory, 'global_config, Service: service::Details<'global_config>, Messag
                     .defaults
                     .publish_subscribe
                     .unable_to_deliver_strategy,
+                max_slice_len: 1,
             },
             factory,
         }
@@ -114,11 +185,28 @@ impl<'factory, 'global_config, Service: service::Details<'global_config>, Messag
 
     pub fn create(
         self,
-    ) -> Result<Publisher<'factory, 'global_config, Service, MessageType>, PublisherCreateError>
-    {
+    ) -> Result<
+        Publisher<'factory, 'global_config, Service, MessageType, UserHeader>,
+        PublisherCreateError,
+    > {
         Ok(
             fail!(from self, when Publisher::new(&self.factory.service, self.factory.service.state().static_config.publish_subscribe(), &self.config),
                 "Failed to create new Publisher port."),
         )
     }
 }
+
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Debug,
+        UserHeader: Debug + Default,
+    > PortFactoryPublisher<'factory, 'global_config, Service, [MessageType], UserHeader>
+{
+    /// Defines the maximum number of elements a slice loaned by the publisher can contain.
+    pub fn max_slice_len(mut self, value: usize) -> Self {
+        self.config.max_slice_len = std::cmp::max(value, 1);
+        self
+    }
+}
@@ -0,0 +1,30 @@
+use std::fmt::Debug;
+
+use crate::port::reader::Reader;
+use crate::service;
+
+use super::blackboard::PortFactory;
+
+#[derive(Debug)]
+pub struct PortFactoryReader<
+    'factory,
+    'global_config,
+    Service: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug,
+> {
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, KeyType>,
+}
+
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        KeyType: Copy + Eq + Debug,
+    > PortFactoryReader<'factory, 'global_config, Service, KeyType>
+{
+    /// Creates a new [`Reader`]. Readers neither hold a connection nor are they registered in
+    /// the service, therefore the number of readers is unlimited.
+    pub fn create(&self) -> Reader<'factory, 'global_config, Service, KeyType> {
+        Reader::new(&self.factory.service)
+    }
+}
@@ -0,0 +1,129 @@
+use std::{fmt::Debug, marker::PhantomData};
+
+use crate::service;
+use crate::service::access::AccessSettings;
+use crate::service::attribute::AttributeSet;
+use crate::service::service_name::ServiceName;
+
+use super::{client::PortFactoryClient, server::PortFactoryServer};
+
+#[derive(Debug)]
+pub struct PortFactory<
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    pub(crate) service: Service,
+    _phantom_request_type: PhantomData<RequestType>,
+    _phantom_response_type: PhantomData<ResponseType>,
+    _phantom_lifetime_b: PhantomData<&'global_config ()>,
+}
+
+unsafe impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Send for PortFactory<'global_config, Service, RequestType, ResponseType>
+{
+}
+unsafe impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > Sync for PortFactory<'global_config, Service, RequestType, ResponseType>
+{
+}
+
+impl<
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > PortFactory<'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(service: Service) -> Self {
+        Self {
+            service,
+            _phantom_request_type: PhantomData,
+            _phantom_response_type: PhantomData,
+            _phantom_lifetime_b: PhantomData,
+        }
+    }
+
+    pub fn name(&self) -> &ServiceName {
+        self.service.state().static_config.service_name()
+    }
+
+    /// Returns the attributes with which the service was created.
+    pub fn attributes(&self) -> &AttributeSet {
+        self.service.state().static_config.attributes()
+    }
+
+    /// Returns the access settings with which the service was created.
+    pub fn access_settings(&self) -> AccessSettings {
+        self.service.state().static_config.access_settings()
+    }
+
+    pub fn max_supported_clients(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .request_response()
+            .max_clients
+    }
+
+    pub fn max_supported_servers(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .request_response()
+            .max_servers
+    }
+
+    pub fn request_buffer_size(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .request_response()
+            .request_buffer_size
+    }
+
+    pub fn response_buffer_size(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .request_response()
+            .response_buffer_size
+    }
+
+    pub fn server_max_borrowed_requests(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .request_response()
+            .server_max_borrowed_requests
+    }
+
+    pub fn client_max_borrowed_responses(&self) -> usize {
+        self.service
+            .state()
+            .static_config
+            .request_response()
+            .client_max_borrowed_responses
+    }
+
+    pub fn client<'a>(
+        &'a self,
+    ) -> PortFactoryClient<'a, 'global_config, Service, RequestType, ResponseType> {
+        PortFactoryClient::new(self)
+    }
+
+    pub fn server<'a>(
+        &'a self,
+    ) -> PortFactoryServer<'a, 'global_config, Service, RequestType, ResponseType> {
+        PortFactoryServer::new(self)
+    }
+}
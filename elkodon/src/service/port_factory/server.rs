@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+
+use elkodon_bb_log::fail;
+
+use crate::{
+    port::server::{Server, ServerCreateError},
+    service,
+};
+
+use super::request_response::PortFactory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalServerConfig {
+    pub(crate) max_loaned_responses: usize,
+}
+
+#[derive(Debug)]
+pub struct PortFactoryServer<
+    'factory,
+    'global_config,
+    Service: service::Details<'global_config>,
+    RequestType: Debug,
+    ResponseType: Debug,
+> {
+    config: LocalServerConfig,
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, RequestType, ResponseType>,
+}
+
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        RequestType: Debug,
+        ResponseType: Debug,
+    > PortFactoryServer<'factory, 'global_config, Service, RequestType, ResponseType>
+{
+    pub(crate) fn new(
+        factory: &'factory PortFactory<'global_config, Service, RequestType, ResponseType>,
+    ) -> Self {
+        Self {
+            config: LocalServerConfig {
+                max_loaned_responses: factory
+                    .service
+                    .state()
+                    .global_config
+                    .defaults
+                    .request_response
+                    .server_max_loaned_responses,
+            },
+            factory,
+        }
+    }
+
+    pub fn max_loaned_responses(mut self, value: usize) -> Self {
+        self.config.max_loaned_responses = value;
+        self
+    }
+
+    pub fn create(
+        self,
+    ) -> Result<
+        Server<'factory, 'global_config, Service, RequestType, ResponseType>,
+        ServerCreateError,
+    > {
+        Ok(
+            fail!(from self, when Server::new(&self.factory.service, self.factory.service.state().static_config.request_response(), &self.config),
+                "Failed to create new Server port."),
+        )
+    }
+}
@@ -1,33 +1,84 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use elkodon_bb_log::fail;
 
 use crate::{
+    message::Payload,
     port::subscriber::{Subscriber, SubscriberCreateError},
     service,
 };
 
 use super::publish_subscribe::PortFactory;
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LocalSubscriberConfig {
+    pub(crate) close_connection_on_liveliness_violation: bool,
+    pub(crate) deadline: Option<Duration>,
+    pub(crate) history_request: Option<usize>,
+}
+
 #[derive(Debug)]
 pub struct PortFactorySubscriber<
     'factory,
     'global_config,
     Service: service::Details<'global_config>,
-    MessageType: Debug,
+    MessageType: Payload + ?Sized,
+    UserHeader: Debug + Default = (),
 > {
-    pub(crate) factory: &'factory PortFactory<'global_config, Service, MessageType>,
+    config: LocalSubscriberConfig,
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, MessageType, UserHeader>,
 }
 
-impl<'factory, 'global_config, Service: service::Details<'global_config>, MessageType: Debug>
-    PortFactorySubscriber<'factory, 'global_config, Service, MessageType>
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug + Default,
+    > PortFactorySubscriber<'factory, 'global_config, Service, MessageType, UserHeader>
 {
+    pub(crate) fn new(
+        factory: &'factory PortFactory<'global_config, Service, MessageType, UserHeader>,
+    ) -> Self {
+        Self {
+            config: LocalSubscriberConfig::default(),
+            factory,
+        }
+    }
+
+    /// When enabled, the subscriber closes the connection to a publisher which violated the
+    /// liveliness contract of the service and does not reconnect to it. Samples which were not
+    /// yet received from this publisher are discarded.
+    pub fn close_connection_on_liveliness_violation(mut self, value: bool) -> Self {
+        self.config.close_connection_on_liveliness_violation = value;
+        self
+    }
+
+    /// Expects a sample from every connected publisher at least once per deadline. The missed
+    /// deadlines are acquired with
+    /// [`crate::port::subscriber::Subscriber::check_deadlines()`], see
+    /// [`crate::port::deadline`].
+    pub fn deadline(mut self, value: Duration) -> Self {
+        self.config.deadline = Some(value);
+        self
+    }
+
+    /// Limits the number of history samples every publisher delivers when it connects to the
+    /// subscriber to the newest `value` samples. By default the whole history is delivered.
+    pub fn history_request(mut self, value: usize) -> Self {
+        self.config.history_request = Some(value);
+        self
+    }
+
     pub fn create(
         &self,
-    ) -> Result<Subscriber<'factory, 'global_config, Service, MessageType>, SubscriberCreateError>
-    {
+    ) -> Result<
+        Subscriber<'factory, 'global_config, Service, MessageType, UserHeader>,
+        SubscriberCreateError,
+    > {
         Ok(
-            fail!(from self, when Subscriber::new(&self.factory.service, &self.factory.service.state().static_config.publish_subscribe()),
+            fail!(from self, when Subscriber::new(&self.factory.service, &self.factory.service.state().static_config.publish_subscribe(), &self.config),
                 "Failed to create new Subscriber port."),
         )
     }
@@ -0,0 +1,33 @@
+use std::fmt::Debug;
+
+use elkodon_bb_log::fail;
+
+use crate::port::writer::{Writer, WriterCreateError};
+use crate::service;
+
+use super::blackboard::PortFactory;
+
+#[derive(Debug)]
+pub struct PortFactoryWriter<
+    'factory,
+    'global_config,
+    Service: service::Details<'global_config>,
+    KeyType: Copy + Eq + Debug,
+> {
+    pub(crate) factory: &'factory PortFactory<'global_config, Service, KeyType>,
+}
+
+impl<
+        'factory,
+        'global_config,
+        Service: service::Details<'global_config>,
+        KeyType: Copy + Eq + Debug,
+    > PortFactoryWriter<'factory, 'global_config, Service, KeyType>
+{
+    pub fn create(
+        &self,
+    ) -> Result<Writer<'factory, 'global_config, Service, KeyType>, WriterCreateError> {
+        Ok(fail!(from self, when Writer::new(&self.factory.service),
+                    "Failed to create new Writer port."))
+    }
+}
@@ -1,4 +1,5 @@
 use crate::service::dynamic_config::DynamicConfig;
+use elkodon_bb_lock_free::mpmc::container::Container;
 use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
 use elkodon_cal::*;
 
@@ -21,6 +22,7 @@ impl<'global_config> crate::service::Details<'global_config> for Service<'global
     type StaticStorage = static_storage::process_local::Storage;
     type ConfigSerializer = serialize::toml::Toml;
     type DynamicStorage = dynamic_storage::process_local::Storage<DynamicConfig>;
+    type NodePortsStorage = dynamic_storage::process_local::Storage<Container<u128>>;
     type ServiceNameHasher = hash::sha1::Sha1;
     type SharedMemory = shared_memory::process_local::Memory<PoolAllocator>;
     type Connection = zero_copy_connection::process_local::Connection;
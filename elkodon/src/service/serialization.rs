@@ -0,0 +1,71 @@
+//! Publish-subscribe services can transport payload types which cannot be shared zero-copy, like
+//! types with [`String`] or [`Vec`] fields, by serializing them into a byte slice. The
+//! [`SerializationFormat`] is defined on creation and stored in the static service config so
+//! that a participant which requires another format is unable to open the service.
+//!
+//! ```no_run
+//! use elkodon::service::serialization::SerializationFormat;
+//! use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+//! use elkodon_bb_container::semantic_string::SemanticString;
+//!
+//! let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+//! let service = zero_copy::Service::new(&service_name)
+//!     .publish_subscribe()
+//!     .serialization_format(SerializationFormat::Cdr)
+//!     .create::<[u8]>()
+//!     .unwrap();
+//!
+//! let publisher = service.publisher().max_slice_len(1024).create().unwrap();
+//! let subscriber = service.subscriber().create().unwrap();
+//!
+//! publisher.send_serialized(&String::from("hello")).unwrap();
+//! let message: Option<String> = subscriber.receive_deserialized().unwrap();
+//! ```
+
+use elkodon_cal::serialize::{cdr::Cdr, toml::Toml, DeserializeError, Serialize, SerializeError};
+use serde::{Deserialize, Serialize as SerdeSerialize};
+
+/// Defines how the payload of a publish-subscribe service with a byte slice payload is
+/// serialized, see [`elkodon_cal::serialize`].
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, SerdeSerialize, Deserialize)]
+pub enum SerializationFormat {
+    /// The Common Data Representation, see [`elkodon_cal::serialize::cdr`].
+    Cdr,
+    /// See [`elkodon_cal::serialize::toml`]. Only types which serialize to a table are supported.
+    Toml,
+}
+
+impl SerializationFormat {
+    /// Returns the number of bytes the serialized value requires.
+    pub(crate) fn serialized_size<T: serde::Serialize>(
+        &self,
+        value: &T,
+    ) -> Result<usize, SerializeError> {
+        match self {
+            SerializationFormat::Cdr => Cdr::serialized_size(value),
+            SerializationFormat::Toml => Toml::serialized_size(value),
+        }
+    }
+
+    /// Serializes the value into the buffer, it fails when the buffer is too small.
+    pub(crate) fn serialize_into<T: serde::Serialize>(
+        &self,
+        value: &T,
+        buffer: &mut [u8],
+    ) -> Result<(), SerializeError> {
+        match self {
+            SerializationFormat::Cdr => Cdr::serialize_into(buffer, value),
+            SerializationFormat::Toml => Toml::serialize_into(buffer, value),
+        }
+    }
+
+    pub(crate) fn deserialize<T: serde::de::DeserializeOwned>(
+        &self,
+        bytes: &[u8],
+    ) -> Result<T, DeserializeError> {
+        match self {
+            SerializationFormat::Cdr => Cdr::deserialize(bytes),
+            SerializationFormat::Toml => Toml::deserialize(bytes),
+        }
+    }
+}
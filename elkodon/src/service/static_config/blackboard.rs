@@ -0,0 +1,46 @@
+use std::alloc::Layout;
+
+use crate::global_config;
+use crate::zero_copy_type::TypeDescriptor;
+use elkodon_bb_log::fatal_panic;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct EntryDescriptor {
+    pub(crate) type_descriptor: TypeDescriptor,
+    pub(crate) offset: usize,
+    pub(crate) size: usize,
+    pub(crate) alignment: usize,
+}
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct StaticConfig {
+    pub(crate) max_writers: usize,
+    pub(crate) key_type_descriptor: TypeDescriptor,
+    pub(crate) entries: Vec<EntryDescriptor>,
+}
+
+impl StaticConfig {
+    pub fn new(config: &global_config::Entries) -> Self {
+        Self {
+            max_writers: config.defaults.blackboard.max_writers,
+            key_type_descriptor: TypeDescriptor::new::<()>(),
+            entries: vec![],
+        }
+    }
+
+    /// The keys are stored at the beginning of the entry memory, followed by all entries.
+    pub(crate) fn entries_layout(&self) -> Layout {
+        let mut size = self.key_type_descriptor.size() * self.entries.len();
+        let mut alignment = self.key_type_descriptor.alignment();
+
+        for entry in &self.entries {
+            size = std::cmp::max(size, entry.offset + entry.size);
+            alignment = std::cmp::max(alignment, entry.alignment);
+        }
+
+        fatal_panic!(from self, when Layout::from_size_align(std::cmp::max(size, 1), alignment),
+            "This should never happen! The entries of the blackboard require an invalid layout with size {} and alignment {}.",
+            size, alignment)
+    }
+}
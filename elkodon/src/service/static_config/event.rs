@@ -1,4 +1,5 @@
 use crate::global_config;
+use crate::service::service_name::ServiceName;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -8,10 +9,12 @@ pub struct StaticConfig {
 }
 
 impl StaticConfig {
-    pub fn new(config: &global_config::Entries) -> Self {
+    /// Creates the config from the defaults with the overrides of the service applied.
+    pub fn new(config: &global_config::Entries, service_name: &ServiceName) -> Self {
+        let settings = config.event(service_name);
         Self {
-            max_notifiers: config.defaults.event.max_notifiers,
-            max_listeners: config.defaults.event.max_listeners,
+            max_notifiers: settings.max_notifiers,
+            max_listeners: settings.max_listeners,
         }
     }
 }
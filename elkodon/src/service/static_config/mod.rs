@@ -1,5 +1,8 @@
+pub mod blackboard;
 pub mod event;
+pub mod pipeline;
 pub mod publish_subscribe;
+pub mod request_response;
 
 use elkodon_cal::hash::Hash;
 use elkodon_bb_container::semantic_string::SemanticString;
@@ -8,6 +11,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::global_config;
 
+use super::access::{AccessConfig, AccessSettings};
+use super::attribute::AttributeSet;
 use super::service_name::ServiceName;
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
@@ -15,6 +20,9 @@ use super::service_name::ServiceName;
 pub enum MessagingPattern {
     PublishSubscribe(publish_subscribe::StaticConfig),
     Event(event::StaticConfig),
+    RequestResponse(request_response::StaticConfig),
+    Blackboard(blackboard::StaticConfig),
+    Pipeline(pipeline::StaticConfig),
 }
 
 impl MessagingPattern {
@@ -26,6 +34,15 @@ impl MessagingPattern {
             MessagingPattern::Event(_) => {
                 matches!(rhs, MessagingPattern::Event(_))
             }
+            MessagingPattern::RequestResponse(_) => {
+                matches!(rhs, MessagingPattern::RequestResponse(_))
+            }
+            MessagingPattern::Blackboard(_) => {
+                matches!(rhs, MessagingPattern::Blackboard(_))
+            }
+            MessagingPattern::Pipeline(_) => {
+                matches!(rhs, MessagingPattern::Pipeline(_))
+            }
         }
     }
 
@@ -49,7 +66,12 @@ impl MessagingPattern {
 pub struct StaticConfig {
     uuid: String,
     service_name: ServiceName,
+    pub(crate) persistent: bool,
     pub(crate) messaging_pattern: MessagingPattern,
+    #[serde(default, skip_serializing_if = "AttributeSet::is_empty")]
+    pub(crate) attributes: AttributeSet,
+    #[serde(default, skip_serializing_if = "AccessConfig::is_default")]
+    pub(crate) access: AccessConfig,
 }
 
 impl StaticConfig {
@@ -60,7 +82,13 @@ impl StaticConfig {
         Self {
             uuid: Hasher::new(service_name.as_bytes()).as_hex_string(),
             service_name: *service_name,
-            messaging_pattern: MessagingPattern::Event(event::StaticConfig::new(config)),
+            persistent: false,
+            attributes: AttributeSet::default(),
+            access: AccessConfig::default(),
+            messaging_pattern: MessagingPattern::Event(event::StaticConfig::new(
+                config,
+                service_name,
+            )),
         }
     }
 
@@ -71,12 +99,59 @@ impl StaticConfig {
         Self {
             uuid: Hasher::new(service_name.as_bytes()).as_hex_string(),
             service_name: *service_name,
+            persistent: false,
+            attributes: AttributeSet::default(),
+            access: AccessConfig::default(),
             messaging_pattern: MessagingPattern::PublishSubscribe(
-                publish_subscribe::StaticConfig::new(config),
+                publish_subscribe::StaticConfig::new(config, service_name),
             ),
         }
     }
 
+    pub fn new_request_response<Hasher: Hash>(
+        service_name: &ServiceName,
+        config: &global_config::Entries,
+    ) -> Self {
+        Self {
+            uuid: Hasher::new(service_name.as_bytes()).as_hex_string(),
+            service_name: *service_name,
+            persistent: false,
+            attributes: AttributeSet::default(),
+            access: AccessConfig::default(),
+            messaging_pattern: MessagingPattern::RequestResponse(
+                request_response::StaticConfig::new(config),
+            ),
+        }
+    }
+
+    pub fn new_blackboard<Hasher: Hash>(
+        service_name: &ServiceName,
+        config: &global_config::Entries,
+    ) -> Self {
+        Self {
+            uuid: Hasher::new(service_name.as_bytes()).as_hex_string(),
+            service_name: *service_name,
+            persistent: false,
+            attributes: AttributeSet::default(),
+            access: AccessConfig::default(),
+            messaging_pattern: MessagingPattern::Blackboard(blackboard::StaticConfig::new(config)),
+        }
+    }
+
+    pub fn new_pipeline<Hasher: Hash>(
+        service_name: &ServiceName,
+        config: &global_config::Entries,
+    ) -> Self {
+        Self {
+            uuid: Hasher::new(service_name.as_bytes()).as_hex_string(),
+            service_name: *service_name,
+            persistent: false,
+            attributes: AttributeSet::default(),
+            access: AccessConfig::default(),
+            messaging_pattern: MessagingPattern::Pipeline(pipeline::StaticConfig::new(config)),
+        }
+    }
+
     pub fn uuid(&self) -> &str {
         &self.uuid
     }
@@ -85,10 +160,25 @@ impl StaticConfig {
         &self.service_name
     }
 
+    /// Returns true when the service is not removed when its last owner drops it.
+    pub fn is_persistent(&self) -> bool {
+        self.persistent
+    }
+
     pub fn messaging_pattern(&self) -> &MessagingPattern {
         &self.messaging_pattern
     }
 
+    /// Returns the attributes with which the service was created.
+    pub fn attributes(&self) -> &AttributeSet {
+        &self.attributes
+    }
+
+    /// Returns the settings which define who besides the creator can access the service.
+    pub fn access_settings(&self) -> AccessSettings {
+        self.access.settings()
+    }
+
     pub(crate) fn has_same_messaging_pattern(&self, rhs: &StaticConfig) -> bool {
         self.messaging_pattern
             .is_same_pattern(&rhs.messaging_pattern)
@@ -131,4 +221,61 @@ impl StaticConfig {
             }
         }
     }
+
+    pub(crate) fn request_response(&self) -> &request_response::StaticConfig {
+        match &self.messaging_pattern {
+            MessagingPattern::RequestResponse(ref v) => v,
+            m => {
+                fatal_panic!(from self, "This should never happen. Trying to access request_response::StaticConfig when the messaging pattern is actually {:?}!", m)
+            }
+        }
+    }
+
+    pub(crate) fn request_response_mut(&mut self) -> &mut request_response::StaticConfig {
+        let origin = format!("{:?}", self);
+        match &mut self.messaging_pattern {
+            MessagingPattern::RequestResponse(ref mut v) => v,
+            m => {
+                fatal_panic!(from origin, "This should never happen. Trying to access request_response::StaticConfig when the messaging pattern is actually {:?}!", m)
+            }
+        }
+    }
+
+    pub(crate) fn blackboard(&self) -> &blackboard::StaticConfig {
+        match &self.messaging_pattern {
+            MessagingPattern::Blackboard(ref v) => v,
+            m => {
+                fatal_panic!(from self, "This should never happen. Trying to access blackboard::StaticConfig when the messaging pattern is actually {:?}!", m)
+            }
+        }
+    }
+
+    pub(crate) fn blackboard_mut(&mut self) -> &mut blackboard::StaticConfig {
+        let origin = format!("{:?}", self);
+        match &mut self.messaging_pattern {
+            MessagingPattern::Blackboard(ref mut v) => v,
+            m => {
+                fatal_panic!(from origin, "This should never happen. Trying to access blackboard::StaticConfig when the messaging pattern is actually {:?}!", m)
+            }
+        }
+    }
+
+    pub(crate) fn pipeline(&self) -> &pipeline::StaticConfig {
+        match &self.messaging_pattern {
+            MessagingPattern::Pipeline(ref v) => v,
+            m => {
+                fatal_panic!(from self, "This should never happen. Trying to access pipeline::StaticConfig when the messaging pattern is actually {:?}!", m)
+            }
+        }
+    }
+
+    pub(crate) fn pipeline_mut(&mut self) -> &mut pipeline::StaticConfig {
+        let origin = format!("{:?}", self);
+        match &mut self.messaging_pattern {
+            MessagingPattern::Pipeline(ref mut v) => v,
+            m => {
+                fatal_panic!(from origin, "This should never happen. Trying to access pipeline::StaticConfig when the messaging pattern is actually {:?}!", m)
+            }
+        }
+    }
 }
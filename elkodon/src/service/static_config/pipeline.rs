@@ -0,0 +1,33 @@
+use crate::global_config;
+use crate::zero_copy_type::TypeDescriptor;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct StaticConfig {
+    pub(crate) max_producers: usize,
+    pub(crate) max_consumers: usize,
+    pub(crate) consumer_buffer_size: usize,
+    pub(crate) consumer_max_borrowed_samples: usize,
+    pub(crate) type_descriptor: TypeDescriptor,
+}
+
+impl StaticConfig {
+    pub fn new(config: &global_config::Entries) -> Self {
+        Self {
+            max_producers: config.defaults.pipeline.max_producers,
+            max_consumers: config.defaults.pipeline.max_consumers,
+            consumer_buffer_size: config.defaults.pipeline.consumer_buffer_size,
+            consumer_max_borrowed_samples: config.defaults.pipeline.consumer_max_borrowed_samples,
+            type_descriptor: TypeDescriptor::new::<()>(),
+        }
+    }
+
+    pub(crate) fn required_amount_of_samples_per_data_segment(
+        &self,
+        producer_max_loaned_samples: usize,
+    ) -> usize {
+        self.max_consumers * (self.consumer_buffer_size + self.consumer_max_borrowed_samples)
+            + producer_max_loaned_samples
+            + 1
+    }
+}
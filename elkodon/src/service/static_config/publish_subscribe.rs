@@ -1,5 +1,9 @@
 use crate::global_config;
+use crate::service::serialization::SerializationFormat;
+use crate::service::service_name::ServiceName;
+use crate::zero_copy_type::TypeDescriptor;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
 #[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub struct StaticConfig {
@@ -9,22 +13,31 @@ pub struct StaticConfig {
     pub(crate) subscriber_buffer_size: usize,
     pub(crate) subscriber_max_borrowed_samples: usize,
     pub(crate) enable_safe_overflow: bool,
-    pub(crate) type_name: String,
+    pub(crate) enable_events: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) max_heartbeat_interval: Option<Duration>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) serialization_format: Option<SerializationFormat>,
+    pub(crate) type_descriptor: TypeDescriptor,
+    pub(crate) user_header_type_descriptor: TypeDescriptor,
 }
 
 impl StaticConfig {
-    pub fn new(config: &global_config::Entries) -> Self {
+    /// Creates the config from the defaults with the overrides of the service applied.
+    pub fn new(config: &global_config::Entries, service_name: &ServiceName) -> Self {
+        let settings = config.publish_subscribe(service_name);
         Self {
-            max_subscribers: config.defaults.publish_subscribe.max_subscribers,
-            max_publishers: config.defaults.publish_subscribe.max_publishers,
-            history_size: config.defaults.publish_subscribe.publisher_history_size,
-            subscriber_buffer_size: config.defaults.publish_subscribe.subscriber_buffer_size,
-            subscriber_max_borrowed_samples: config
-                .defaults
-                .publish_subscribe
-                .subscriber_max_borrowed_samples,
-            enable_safe_overflow: config.defaults.publish_subscribe.enable_safe_overflow,
-            type_name: String::new(),
+            max_subscribers: settings.max_subscribers,
+            max_publishers: settings.max_publishers,
+            history_size: settings.publisher_history_size,
+            subscriber_buffer_size: settings.subscriber_buffer_size,
+            subscriber_max_borrowed_samples: settings.subscriber_max_borrowed_samples,
+            enable_safe_overflow: settings.enable_safe_overflow,
+            enable_events: settings.enable_events,
+            max_heartbeat_interval: None,
+            serialization_format: None,
+            type_descriptor: TypeDescriptor::new::<()>(),
+            user_header_type_descriptor: TypeDescriptor::new::<()>(),
         }
     }
 }
@@ -0,0 +1,54 @@
+use crate::global_config;
+use crate::zero_copy_type::TypeDescriptor;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct StaticConfig {
+    pub(crate) max_clients: usize,
+    pub(crate) max_servers: usize,
+    pub(crate) request_buffer_size: usize,
+    pub(crate) response_buffer_size: usize,
+    pub(crate) server_max_borrowed_requests: usize,
+    pub(crate) client_max_borrowed_responses: usize,
+    pub(crate) request_type_descriptor: TypeDescriptor,
+    pub(crate) response_type_descriptor: TypeDescriptor,
+}
+
+impl StaticConfig {
+    pub fn new(config: &global_config::Entries) -> Self {
+        Self {
+            max_clients: config.defaults.request_response.max_clients,
+            max_servers: config.defaults.request_response.max_servers,
+            request_buffer_size: config.defaults.request_response.request_buffer_size,
+            response_buffer_size: config.defaults.request_response.response_buffer_size,
+            server_max_borrowed_requests: config
+                .defaults
+                .request_response
+                .server_max_borrowed_requests,
+            client_max_borrowed_responses: config
+                .defaults
+                .request_response
+                .client_max_borrowed_responses,
+            request_type_descriptor: TypeDescriptor::new::<()>(),
+            response_type_descriptor: TypeDescriptor::new::<()>(),
+        }
+    }
+
+    pub(crate) fn required_amount_of_requests_per_data_segment(
+        &self,
+        client_max_loaned_requests: usize,
+    ) -> usize {
+        self.max_servers * (self.request_buffer_size + self.server_max_borrowed_requests)
+            + client_max_loaned_requests
+            + 1
+    }
+
+    pub(crate) fn required_amount_of_responses_per_data_segment(
+        &self,
+        server_max_loaned_responses: usize,
+    ) -> usize {
+        self.max_clients * (self.response_buffer_size + self.client_max_borrowed_responses)
+            + server_max_loaned_responses
+            + 1
+    }
+}
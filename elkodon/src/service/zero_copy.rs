@@ -1,4 +1,5 @@
 use crate::service::dynamic_config::DynamicConfig;
+use elkodon_bb_lock_free::mpmc::container::Container;
 use elkodon_cal::shm_allocator::pool_allocator::PoolAllocator;
 use elkodon_cal::*;
 
@@ -21,6 +22,7 @@ impl<'global_config> crate::service::Details<'global_config> for Service<'global
     type StaticStorage = static_storage::file::Storage;
     type ConfigSerializer = serialize::toml::Toml;
     type DynamicStorage = dynamic_storage::posix_shared_memory::Storage<DynamicConfig>;
+    type NodePortsStorage = dynamic_storage::posix_shared_memory::Storage<Container<u128>>;
     type ServiceNameHasher = hash::sha1::Sha1;
     type SharedMemory = shared_memory::posix::Memory<PoolAllocator>;
     type Connection = zero_copy_connection::posix_shared_memory::Connection;
@@ -0,0 +1,433 @@
+//! The [`WaitSet`] waits on many [`Listener`] ports, [`Subscriber`] ports of services with events
+//! enabled, [`ServiceWatcher`]s and deadlines at once and reports which of its attachments fired.
+//! It is built on the reactor of the services event concept, the
+//! [`elkodon_cal::reactor::posix_select::Reactor`] for [`crate::service::zero_copy::Service`] and
+//! a condition variable based equivalent for [`crate::service::process_local::Service`].
+//!
+//! The notifications of a fired [`Listener`] are not consumed by the [`WaitSet`], they have to be
+//! acquired with [`Listener::try_wait()`]. A fired [`Subscriber`] keeps firing until
+//! [`Subscriber::receive()`] returns [`None`], only then its notifications are discarded. A
+//! fired [`ServiceWatcher`] discards its notifications on
+//! [`ServiceWatcher::try_wait()`].
+
+use std::cell::{Cell, RefCell};
+use std::fmt::Debug;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use elkodon_bb_log::fail;
+use elkodon_bb_posix::file_descriptor::FileDescriptorBased;
+use elkodon_cal::event::{Event, ListenerReactor, ReactorAttachError, ReactorWaitError};
+
+use crate::message::Payload;
+use crate::port::deadline;
+use crate::port::listener::Listener;
+use crate::port::subscriber::Subscriber;
+use crate::service;
+use crate::service::discovery::ServiceWatcher;
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WaitSetCreateError {
+    InternalError,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WaitSetAttachmentError {
+    InsufficientCapacity,
+    EventsNotEnabled,
+    DeadlineNotDefined,
+    InternalError,
+}
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum WaitSetWaitError {
+    Interrupt,
+    InsufficientPermissions,
+    InternalError,
+}
+
+type Reactor<'global_config, Service> =
+    <<Service as service::Details<'global_config>>::Event as Event<u64>>::Reactor;
+
+type ReactorGuard<'waitset, 'attachment, 'global_config, Service> =
+    <Reactor<'global_config, Service> as ListenerReactor<
+        u64,
+        <Service as service::Details<'global_config>>::Event,
+    >>::Guard<'waitset, 'attachment>;
+
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+enum AttachmentKind {
+    Event(usize),
+    Deadline(usize),
+}
+
+/// Identifies an attachment of the [`WaitSet`]. It is acquired with [`WaitSetGuard::id()`] and
+/// provided to the wait callbacks when the attachment fired.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+pub struct AttachmentId(AttachmentKind);
+
+#[derive(Debug)]
+struct Deadline {
+    id: usize,
+    period: Duration,
+    next_expiration: Instant,
+    // the next deadline miss of a subscriber, see Subscriber::next_deadline_miss()
+    next_deadline_miss: Option<Arc<AtomicU64>>,
+}
+
+impl Deadline {
+    /// The deadline of a subscriber is postponed as long as its publishers deliver samples in
+    /// time.
+    fn expiration(&self) -> Instant {
+        match &self.next_deadline_miss {
+            None => self.next_expiration,
+            Some(next_miss) => {
+                let time_until_miss = Duration::from_nanos(next_miss.load(Ordering::Relaxed))
+                    .saturating_sub(deadline::now());
+                self.next_expiration.max(Instant::now() + time_until_miss)
+            }
+        }
+    }
+}
+
+/// Detaches the attachment from the [`WaitSet`] when it goes out of scope.
+pub struct WaitSetGuard<
+    'waitset,
+    'attachment,
+    'global_config,
+    Service: service::Details<'global_config>,
+> {
+    waitset: &'waitset WaitSet<'global_config, Service>,
+    id: AttachmentId,
+    _reactor_guard: Option<ReactorGuard<'waitset, 'attachment, 'global_config, Service>>,
+}
+
+impl<'global_config, Service: service::Details<'global_config>> Debug
+    for WaitSetGuard<'_, '_, 'global_config, Service>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WaitSetGuard {{ id: {:?} }}", self.id)
+    }
+}
+
+impl<'global_config, Service: service::Details<'global_config>>
+    WaitSetGuard<'_, '_, 'global_config, Service>
+{
+    /// Returns the [`AttachmentId`] with which the attachment is reported when it fired.
+    pub fn id(&self) -> AttachmentId {
+        self.id
+    }
+}
+
+impl<'global_config, Service: service::Details<'global_config>> Drop
+    for WaitSetGuard<'_, '_, 'global_config, Service>
+{
+    fn drop(&mut self) {
+        if let AttachmentKind::Deadline(id) = self.id.0 {
+            self.waitset.deadlines.borrow_mut().retain(|d| d.id != id);
+        }
+    }
+}
+
+/// Waits on multiple [`Listener`] and [`Subscriber`] ports and deadlines of one service type at
+/// once.
+#[derive(Debug)]
+pub struct WaitSet<'global_config, Service: service::Details<'global_config>> {
+    reactor: Reactor<'global_config, Service>,
+    deadlines: RefCell<Vec<Deadline>>,
+    next_deadline_id: Cell<usize>,
+}
+
+impl<'global_config, Service: service::Details<'global_config>> WaitSet<'global_config, Service> {
+    pub fn new() -> Result<Self, WaitSetCreateError> {
+        Ok(Self {
+            reactor: fail!(from "WaitSet::new()",
+                        when Reactor::<'global_config, Service>::new(),
+                        with WaitSetCreateError::InternalError,
+                        "Unable to create WaitSet since the underlying reactor could not be created."),
+            deadlines: RefCell::new(vec![]),
+            next_deadline_id: Cell::new(0),
+        })
+    }
+
+    /// Returns the number of attached ports and deadlines.
+    pub fn len(&self) -> usize {
+        self.reactor.len() + self.deadlines.borrow().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the maximum number of ports which can be attached.
+    pub fn capacity() -> usize {
+        Reactor::<'global_config, Service>::capacity()
+    }
+
+    fn attach_event<'waitset, 'attachment>(
+        &'waitset self,
+        listener: &'attachment <Service::Event as Event<u64>>::Listener,
+        msg: &str,
+    ) -> Result<WaitSetGuard<'waitset, 'attachment, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        let id = Reactor::<'global_config, Service>::attachment_id(listener);
+        self.create_guard(id, self.reactor.attach(listener), msg)
+    }
+
+    fn create_guard<'waitset, 'attachment>(
+        &'waitset self,
+        id: usize,
+        reactor_guard: Result<
+            ReactorGuard<'waitset, 'attachment, 'global_config, Service>,
+            ReactorAttachError,
+        >,
+        msg: &str,
+    ) -> Result<WaitSetGuard<'waitset, 'attachment, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        let reactor_guard = match reactor_guard {
+            Ok(guard) => guard,
+            Err(ReactorAttachError::CapacityExceeded) => {
+                fail!(from self, with WaitSetAttachmentError::InsufficientCapacity,
+                    "{} since it would exceed the capacity of {} ports.", msg, Self::capacity());
+            }
+            Err(ReactorAttachError::NotSupported) => {
+                fail!(from self, with WaitSetAttachmentError::InternalError,
+                    "{} since the underlying reactor does not support it.", msg);
+            }
+            Err(ReactorAttachError::UnknownError(v)) => {
+                fail!(from self, with WaitSetAttachmentError::InternalError,
+                    "{} due to an internal failure in the underlying reactor ({}).", msg, v);
+            }
+        };
+
+        Ok(WaitSetGuard {
+            waitset: self,
+            id: AttachmentId(AttachmentKind::Event(id)),
+            _reactor_guard: Some(reactor_guard),
+        })
+    }
+
+    /// Attaches a [`Listener`]. It fires whenever the [`Listener`] has pending notifications.
+    pub fn attach_listener<'waitset, 'attachment, 'a>(
+        &'waitset self,
+        listener: &'attachment Listener<'a, 'global_config, Service>,
+    ) -> Result<WaitSetGuard<'waitset, 'attachment, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        self.attach_event(
+            listener.event_listener(),
+            "Unable to attach listener to WaitSet",
+        )
+    }
+
+    /// Attaches a [`Subscriber`] of a service with events enabled. It fires whenever a publisher
+    /// delivered new samples.
+    pub fn attach_subscriber<
+        'waitset,
+        'attachment,
+        'a,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug,
+    >(
+        &'waitset self,
+        subscriber: &'attachment Subscriber<'a, 'global_config, Service, MessageType, UserHeader>,
+    ) -> Result<WaitSetGuard<'waitset, 'attachment, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        let msg = "Unable to attach subscriber to WaitSet";
+        match subscriber.event_listener() {
+            Some(listener) => self.attach_event(listener, msg),
+            None => {
+                fail!(from self, with WaitSetAttachmentError::EventsNotEnabled,
+                    "{} since the service does not have events enabled.", msg);
+            }
+        }
+    }
+
+    /// Attaches the deadline of a [`Subscriber`]. It fires when a connected publisher misses its
+    /// deadline, derived from the last sample the [`Subscriber`] received from every publisher.
+    /// The missed deadlines are acquired with [`Subscriber::check_deadlines()`]. Until then, or
+    /// until the next sample is received, it fires once per deadline.
+    pub fn attach_subscriber_deadline<
+        'waitset,
+        'a,
+        MessageType: Payload + ?Sized,
+        UserHeader: Debug,
+    >(
+        &'waitset self,
+        subscriber: &Subscriber<'a, 'global_config, Service, MessageType, UserHeader>,
+    ) -> Result<WaitSetGuard<'waitset, 'static, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        match subscriber.deadline() {
+            Some(deadline) => {
+                Ok(self.add_deadline(deadline, Some(subscriber.next_deadline_miss())))
+            }
+            None => {
+                fail!(from self, with WaitSetAttachmentError::DeadlineNotDefined,
+                    "Unable to attach the deadline of the subscriber to WaitSet since the subscriber has no deadline.");
+            }
+        }
+    }
+
+    /// Attaches a deadline. It fires whenever the period elapsed since it was attached or since it
+    /// fired the last time.
+    pub fn attach_deadline<'waitset>(
+        &'waitset self,
+        period: Duration,
+    ) -> Result<WaitSetGuard<'waitset, 'static, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        Ok(self.add_deadline(period, None))
+    }
+
+    fn add_deadline<'waitset, 'attachment>(
+        &'waitset self,
+        period: Duration,
+        next_deadline_miss: Option<Arc<AtomicU64>>,
+    ) -> WaitSetGuard<'waitset, 'attachment, 'global_config, Service> {
+        let id = self.next_deadline_id.get();
+        self.next_deadline_id.set(id + 1);
+        self.deadlines.borrow_mut().push(Deadline {
+            id,
+            period,
+            next_expiration: Instant::now() + period,
+            next_deadline_miss,
+        });
+
+        WaitSetGuard {
+            waitset: self,
+            id: AttachmentId(AttachmentKind::Deadline(id)),
+            _reactor_guard: None,
+        }
+    }
+
+    /// Attaches a [`ServiceWatcher`]. It fires whenever the directory of the static service
+    /// storages has changed, or in the polling interval of the watcher when the directory cannot
+    /// be watched. The changes are acquired with [`ServiceWatcher::try_wait()`].
+    pub fn attach_service_watcher<'waitset, 'attachment>(
+        &'waitset self,
+        watcher: &'attachment ServiceWatcher<'global_config, Service>,
+    ) -> Result<WaitSetGuard<'waitset, 'attachment, 'global_config, Service>, WaitSetAttachmentError>
+    {
+        match watcher.directory_watcher() {
+            Some(directory_watcher) => {
+                let id = unsafe { directory_watcher.file_descriptor().native_handle() as usize };
+                self.create_guard(
+                    id,
+                    self.reactor.attach_file_descriptor(directory_watcher),
+                    "Unable to attach service watcher to WaitSet",
+                )
+            }
+            None => Ok(self.add_deadline(watcher.polling_interval, None)),
+        }
+    }
+
+    /// Calls the provided callback for every attachment which fired without blocking.
+    pub fn try_wait<F: FnMut(AttachmentId)>(&self, fn_call: F) -> Result<(), WaitSetWaitError> {
+        self.wait(fn_call, Some(Duration::ZERO))
+    }
+
+    /// Waits until at least one attachment fired or the timeout has passed and calls the provided
+    /// callback for every attachment which fired.
+    pub fn timed_wait<F: FnMut(AttachmentId)>(
+        &self,
+        fn_call: F,
+        timeout: Duration,
+    ) -> Result<(), WaitSetWaitError> {
+        self.wait(fn_call, Some(timeout))
+    }
+
+    /// Waits until at least one attachment fired and calls the provided callback for every
+    /// attachment which fired.
+    pub fn blocking_wait<F: FnMut(AttachmentId)>(
+        &self,
+        fn_call: F,
+    ) -> Result<(), WaitSetWaitError> {
+        self.wait(fn_call, None)
+    }
+
+    fn time_until_next_deadline(&self) -> Option<Duration> {
+        let now = Instant::now();
+        self.deadlines
+            .borrow()
+            .iter()
+            .map(|d| d.expiration().saturating_duration_since(now))
+            .min()
+    }
+
+    fn expired_deadlines(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let mut expired = vec![];
+        for deadline in self.deadlines.borrow_mut().iter_mut() {
+            if deadline.expiration() <= now {
+                deadline.next_expiration = now + deadline.period;
+                expired.push(deadline.id);
+            }
+        }
+        expired
+    }
+
+    fn wait_on_reactor(&self, timeout: Option<Duration>) -> Result<Vec<usize>, WaitSetWaitError> {
+        let msg = "Unable to wait on WaitSet";
+        let timeout = match (timeout, self.time_until_next_deadline()) {
+            (Some(timeout), Some(deadline)) => Some(timeout.min(deadline)),
+            (timeout, None) => timeout,
+            (None, deadline) => deadline,
+        };
+
+        let mut fired_listeners = vec![];
+        let result = match timeout {
+            Some(timeout) => self
+                .reactor
+                .timed_wait(|id| fired_listeners.push(id), timeout),
+            None => self.reactor.blocking_wait(|id| fired_listeners.push(id)),
+        };
+
+        match result {
+            Ok(()) => Ok(fired_listeners),
+            Err(ReactorWaitError::Interrupt) => {
+                fail!(from self, with WaitSetWaitError::Interrupt,
+                    "{} since an interrupt signal was received.", msg);
+            }
+            Err(ReactorWaitError::InsufficientPermissions) => {
+                fail!(from self, with WaitSetWaitError::InsufficientPermissions,
+                    "{} due to insufficient permissions.", msg);
+            }
+            Err(ReactorWaitError::UnknownError) => {
+                fail!(from self, with WaitSetWaitError::InternalError,
+                    "{} due to an internal failure in the underlying reactor.", msg);
+            }
+        }
+    }
+
+    fn wait<F: FnMut(AttachmentId)>(
+        &self,
+        mut fn_call: F,
+        timeout: Option<Duration>,
+    ) -> Result<(), WaitSetWaitError> {
+        let start = Instant::now();
+        loop {
+            let remaining_timeout = timeout.map(|t| t.saturating_sub(start.elapsed()));
+            let fired_listeners = self.wait_on_reactor(remaining_timeout)?;
+            let expired_deadlines = self.expired_deadlines();
+            let has_fired = !fired_listeners.is_empty() || !expired_deadlines.is_empty();
+
+            for id in fired_listeners {
+                fn_call(AttachmentId(AttachmentKind::Event(id)));
+            }
+
+            for id in expired_deadlines {
+                fn_call(AttachmentId(AttachmentKind::Deadline(id)));
+            }
+
+            // the underlying reactor may wake up slightly before a deadline expired
+            if has_fired || remaining_timeout == Some(Duration::ZERO) {
+                return Ok(());
+            }
+
+            if let Some(timeout) = timeout {
+                if start.elapsed() >= timeout {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::{fmt::Debug, ops::Deref, ptr::NonNull};
+
+use crate::{
+    message::Message, port::consumer::Consumer, service, service::header::pipeline::Header,
+};
+
+#[derive(Debug)]
+pub struct WorkItem<
+    'a,
+    'consumer,
+    'global_config,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    pub(crate) consumer: &'consumer Consumer<'a, 'global_config, Service, MessageType>,
+    pub(crate) ptr: NonNull<Message<Header, MessageType>>,
+    pub(crate) channel_id: usize,
+}
+
+impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Deref
+    for WorkItem<'_, '_, 'global_config, Service, MessageType>
+{
+    type Target = MessageType;
+    fn deref(&self) -> &Self::Target {
+        unsafe { &(*self.ptr.as_ptr()).data }
+    }
+}
+
+impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Drop
+    for WorkItem<'_, '_, 'global_config, Service, MessageType>
+{
+    fn drop(&mut self) {
+        self.consumer
+            .release_sample(self.channel_id, self.ptr.as_ptr() as *const u8);
+    }
+}
+
+impl<
+        'a,
+        'consumer,
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Debug,
+    > WorkItem<'a, 'consumer, 'global_config, Service, MessageType>
+{
+    pub fn as_ptr(&self) -> *const MessageType {
+        &unsafe { self.ptr.as_ref() }.data
+    }
+
+    pub fn header(&self) -> &Header {
+        &unsafe { self.ptr.as_ref() }.header
+    }
+}
@@ -0,0 +1,70 @@
+use elkodon_cal::shared_memory::*;
+use std::{fmt::Debug, mem::MaybeUninit, ptr::NonNull, sync::atomic::Ordering};
+
+use crate::{
+    message::Message, port::producer::Producer, service, service::header::pipeline::Header,
+};
+
+/// # Important
+///
+/// Does not implement [`Send`] since it releases unsent samples in the [`Producer`] and the
+/// [`Producer`] is not thread-safe!
+#[derive(Debug)]
+pub struct WorkItemMut<
+    'a,
+    'producer,
+    'global_config,
+    Service: service::Details<'global_config>,
+    MessageType: Debug,
+> {
+    producer: &'producer Producer<'a, 'global_config, Service, MessageType>,
+    ptr: NonNull<MaybeUninit<Message<Header, MessageType>>>,
+    offset_to_chunk: PointerOffset,
+}
+
+impl<'global_config, Service: service::Details<'global_config>, MessageType: Debug> Drop
+    for WorkItemMut<'_, '_, 'global_config, Service, MessageType>
+{
+    fn drop(&mut self) {
+        self.producer.release_sample(self.offset_to_chunk);
+        self.producer.loan_counter.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl<
+        'a,
+        'producer,
+        'global_config,
+        Service: service::Details<'global_config>,
+        MessageType: Debug,
+    > WorkItemMut<'a, 'producer, 'global_config, Service, MessageType>
+{
+    pub(crate) fn new(
+        producer: &'producer Producer<'a, 'global_config, Service, MessageType>,
+        ptr: NonNull<MaybeUninit<Message<Header, MessageType>>>,
+        offset_to_chunk: PointerOffset,
+    ) -> Self {
+        producer.loan_counter.fetch_add(1, Ordering::Relaxed);
+        Self {
+            producer,
+            ptr,
+            offset_to_chunk,
+        }
+    }
+
+    pub(crate) fn offset_to_chunk(&self) -> PointerOffset {
+        self.offset_to_chunk
+    }
+
+    pub fn header(&self) -> &Header {
+        &unsafe { &*self.ptr.as_ref().as_ptr() }.header
+    }
+
+    pub fn as_ptr(&self) -> *const MessageType {
+        &unsafe { &*self.ptr.as_ref().as_ptr() }.data
+    }
+
+    pub fn as_mut_ptr(&mut self) -> *mut MessageType {
+        &mut unsafe { &mut *self.ptr.as_mut().as_mut_ptr() }.data
+    }
+}
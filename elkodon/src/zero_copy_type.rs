@@ -0,0 +1,344 @@
+//! Types which are transmitted via services implement [`ZeroCopyType`]. It provides a stable
+//! identifier which, together with the size and alignment, forms the [`TypeDescriptor`] that is
+//! used to verify that all participants of a service agree on the memory representation of the
+//! transmitted data.
+//!
+//! A service accepts a type only when it is [`PayloadFor`] the service. Every [`ShmSafe`] type
+//! is accepted by every service, so that no type which contains pointers to process local
+//! memory, like a [`Box`] or a [`String`], can be transmitted via shared memory. Custom types
+//! derive both, the derive of [`ShmSafe`] fails when a field is not [`ShmSafe`]:
+//!
+//! ```
+//! use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+//!
+//! #[derive(Debug, ShmSafe, ZeroCopyType)]
+//! #[repr(C)]
+//! struct Position {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! // the identifier can be overridden so that it survives refactorings
+//! #[derive(Debug, ShmSafe, ZeroCopyType)]
+//! #[zero_copy_type(identifier = "my_app::Velocity")]
+//! #[repr(C)]
+//! struct Velocity {
+//!     dx: f64,
+//!     dy: f64,
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use elkodon::zero_copy_type::ShmSafe;
+//!
+//! #[derive(Debug, ShmSafe)]
+//! #[repr(C)]
+//! struct Name {
+//!     value: String,
+//! }
+//! ```
+//!
+//! Both derives require `#[repr(C)]` or `#[repr(transparent)]` since the compiler may arrange
+//! the fields of any other type differently in every process:
+//!
+//! ```compile_fail
+//! use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+//!
+//! #[derive(Debug, ShmSafe, ZeroCopyType)]
+//! struct Position {
+//!     x: f64,
+//!     y: f64,
+//! }
+//! ```
+
+use std::marker::PhantomData;
+
+use serde::{Deserialize, Serialize};
+
+use elkodon_bb_container::byte_string::FixedSizeByteString;
+use elkodon_bb_container::queue::FixedSizeQueue;
+use elkodon_bb_container::vec::FixedSizeVec;
+
+pub use elkodon_bb_derive_macros::{ShmSafe, ZeroCopyType};
+pub use elkodon_bb_elementary::shm_safe::ShmSafe;
+
+/// Provides a stable identifier of a type that does not depend on the compiler version or the
+/// module path like [`std::any::type_name()`].
+pub trait ZeroCopyType {
+    fn type_identifier() -> String;
+}
+
+/// Marks the types which can be transmitted by the service `S`. Every [`ShmSafe`] type can be
+/// transmitted by every service. The process local services never share their memory with
+/// another process, a type which is not [`ShmSafe`] can opt in for their request-response,
+/// pipeline and blackboard services. The payload and user header of a publish-subscribe
+/// service have to be [`ShmSafe`] in every service, see [`crate::message::Payload`].
+///
+/// ```
+/// use elkodon::service::process_local;
+/// use elkodon::zero_copy_type::{PayloadFor, ZeroCopyType};
+///
+/// #[derive(Debug, ZeroCopyType)]
+/// #[zero_copy_type(identifier = "my_app::Name")]
+/// #[repr(C)]
+/// struct Name {
+///     value: String,
+/// }
+///
+/// // the pointers of the String stay valid since the service never leaves the process
+/// unsafe impl<'a> PayloadFor<process_local::Service<'a>> for Name {}
+/// ```
+///
+/// ```compile_fail
+/// # use elkodon::service::{process_local, service_name::ServiceName, Service};
+/// # use elkodon::zero_copy_type::{PayloadFor, ZeroCopyType};
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Name")]
+/// # #[repr(C)]
+/// # struct Name {
+/// #     value: String,
+/// # }
+/// # unsafe impl<'a> PayloadFor<process_local::Service<'a>> for Name {}
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = process_local::Service::new(&service_name)
+///     .publish_subscribe()
+///     .create::<Name>();
+/// ```
+///
+/// Every service which transmits data via shared memory rejects these types on creation of the
+/// service, for the payload and the user header of a publish-subscribe service
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Name")]
+/// # #[repr(C)]
+/// # struct Name {
+/// #     value: String,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name)
+///     .publish_subscribe()
+///     .create::<Name>();
+/// ```
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, Default, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Name")]
+/// # #[repr(C)]
+/// # struct Name {
+/// #     value: String,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name)
+///     .publish_subscribe()
+///     .user_header::<Name>()
+///     .create::<u64>();
+/// ```
+///
+/// for the request and response of a request-response service
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Name")]
+/// # #[repr(C)]
+/// # struct Name {
+/// #     value: String,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name)
+///     .request_response()
+///     .create::<Name, u64>();
+/// ```
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Name")]
+/// # #[repr(C)]
+/// # struct Name {
+/// #     value: String,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name)
+///     .request_response()
+///     .create::<u64, Name>();
+/// ```
+///
+/// for the message of a pipeline service
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Name")]
+/// # #[repr(C)]
+/// # struct Name {
+/// #     value: String,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name)
+///     .pipeline()
+///     .create::<Name>();
+/// ```
+///
+/// and for the keys and values of a blackboard service.
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, Clone, Copy, PartialEq, Eq, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Key")]
+/// # #[repr(C)]
+/// # struct Key {
+/// #     value: &'static u64,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name).blackboard::<Key>();
+/// ```
+///
+/// ```compile_fail
+/// # use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+/// # use elkodon::zero_copy_type::ZeroCopyType;
+/// # use elkodon_bb_container::semantic_string::SemanticString;
+/// # #[derive(Debug, Clone, Copy, ZeroCopyType)]
+/// # #[zero_copy_type(identifier = "my_app::Value")]
+/// # #[repr(C)]
+/// # struct Value {
+/// #     value: &'static u64,
+/// # }
+/// let service_name = ServiceName::new(b"My/Funk/ServiceName").unwrap();
+/// let service = zero_copy::Service::new(&service_name)
+///     .blackboard::<u64>()
+///     .add(0, Value { value: &0 })
+///     .create();
+/// ```
+///
+/// # Safety
+///
+///  * the type must stay valid in every process which has access to the memory of the service
+///
+pub unsafe trait PayloadFor<S: ?Sized> {}
+
+unsafe impl<T: ShmSafe + ?Sized, S: ?Sized> PayloadFor<S> for T {}
+
+macro_rules! impl_zero_copy_type {
+    ($($t:ty),*) => {
+        $(impl ZeroCopyType for $t {
+            fn type_identifier() -> String {
+                stringify!($t).to_string()
+            }
+        })*
+    };
+}
+
+impl_zero_copy_type! {
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ()
+}
+
+impl<T: ZeroCopyType, const N: usize> ZeroCopyType for [T; N] {
+    fn type_identifier() -> String {
+        format!("[{}; {}]", T::type_identifier(), N)
+    }
+}
+
+impl<T: ZeroCopyType, const CAPACITY: usize> ZeroCopyType for FixedSizeVec<T, CAPACITY> {
+    fn type_identifier() -> String {
+        format!("FixedSizeVec<{}, {}>", T::type_identifier(), CAPACITY)
+    }
+}
+
+impl<T: ZeroCopyType, const CAPACITY: usize> ZeroCopyType for FixedSizeQueue<T, CAPACITY> {
+    fn type_identifier() -> String {
+        format!("FixedSizeQueue<{}, {}>", T::type_identifier(), CAPACITY)
+    }
+}
+
+// zero sized, the marked type does not affect the layout
+impl<T: ?Sized> ZeroCopyType for PhantomData<T> {
+    fn type_identifier() -> String {
+        "PhantomData".to_string()
+    }
+}
+
+impl<const CAPACITY: usize> ZeroCopyType for FixedSizeByteString<CAPACITY> {
+    fn type_identifier() -> String {
+        format!("FixedSizeByteString<{}>", CAPACITY)
+    }
+}
+
+/// Describes the memory representation of a type with its identifier, size and alignment.
+/// Together they describe the whole layout only since the derive of [`ZeroCopyType`] requires
+/// a type whose field order is defined by its `#[repr]`, the fields and their types are part
+/// of the derived identifier.
+///
+/// ```compile_fail
+/// use elkodon::zero_copy_type::ZeroCopyType;
+///
+/// // the compiler could reorder the fields differently in another process
+/// #[derive(Debug, ZeroCopyType)]
+/// #[zero_copy_type(identifier = "my_app::Sample")]
+/// struct Sample {
+///     flag: u8,
+///     value: u64,
+///     counter: u16,
+/// }
+/// ```
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
+pub struct TypeDescriptor {
+    type_identifier: String,
+    size: usize,
+    alignment: usize,
+}
+
+impl TypeDescriptor {
+    pub fn new<T: ZeroCopyType>() -> Self {
+        Self {
+            type_identifier: T::type_identifier(),
+            size: std::mem::size_of::<T>(),
+            alignment: std::mem::align_of::<T>(),
+        }
+    }
+
+    /// Describes a slice of `T` whose length is only known at runtime. The size and alignment
+    /// are the ones of a single element.
+    pub fn new_slice<T: ZeroCopyType>() -> Self {
+        Self {
+            type_identifier: format!("[{}]", T::type_identifier()),
+            size: std::mem::size_of::<T>(),
+            alignment: std::mem::align_of::<T>(),
+        }
+    }
+
+    pub fn type_identifier(&self) -> &str {
+        &self.type_identifier
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn alignment(&self) -> usize {
+        self.alignment
+    }
+
+    /// Returns true when both descriptors have the same size and alignment. With equal derived
+    /// type identifiers this means that both sides use the same field offsets, see
+    /// [`TypeDescriptor`]. A custom identifier has to be changed whenever the fields change.
+    pub fn has_same_layout(&self, other: &TypeDescriptor) -> bool {
+        self.size == other.size && self.alignment == other.alignment
+    }
+}
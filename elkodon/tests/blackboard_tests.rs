@@ -0,0 +1,262 @@
+#[generic_tests::define]
+mod blackboard {
+    use elkodon::port::reader::ReaderEntryError;
+    use elkodon::port::writer::{WriterCreateError, WriterEntryError};
+    use elkodon::service::builder::blackboard::{BlackboardCreateError, BlackboardOpenError};
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"blackboard_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq, ShmSafe, ZeroCopyType)]
+    #[repr(C)]
+    struct Calibration {
+        offset: f64,
+        gain: f64,
+    }
+
+    // claims to be a u32 but has a different layout
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ShmSafe, ZeroCopyType)]
+    #[zero_copy_type(identifier = "u32")]
+    #[repr(C)]
+    struct WideKey {
+        value: u64,
+    }
+
+    #[test]
+    fn creating_blackboard_works<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .add(0, 12u64)
+            .add(1, false)
+            .create();
+
+        assert_that!(sut, is_ok);
+        let sut = sut.unwrap();
+        assert_that!(*sut.name(), eq service_name);
+        assert_that!(sut.number_of_entries(), eq 2);
+    }
+
+    #[test]
+    fn creating_blackboard_without_entries_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name).blackboard::<u32>().create();
+
+        assert_that!(sut, is_err);
+        assert_that!(sut.err().unwrap(), eq BlackboardCreateError::NoEntriesProvided);
+    }
+
+    #[test]
+    fn creating_blackboard_with_duplicate_key_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .add(5, 12u64)
+            .add(5, 13u8)
+            .create();
+
+        assert_that!(sut, is_err);
+        assert_that!(sut.err().unwrap(), eq BlackboardCreateError::DuplicateKey);
+    }
+
+    #[test]
+    fn open_fails_with_different_key_type<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name).blackboard::<u64>().open();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq BlackboardOpenError::IncompatibleKeyType);
+
+        let sut2 = Sut::new(&service_name).blackboard::<u32>().open();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn open_fails_with_same_key_type_identifier_but_different_layout<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name).blackboard::<WideKey>().open();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq BlackboardOpenError::IncompatibleKeyType);
+    }
+
+    #[test]
+    fn reader_receives_initial_and_updated_values<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .add(0, 12u64)
+            .add(1, 3u8)
+            .add(
+                2,
+                Calibration {
+                    offset: 0.5,
+                    gain: 2.0,
+                },
+            )
+            .create()
+            .unwrap();
+
+        let opened = Sut::new(&service_name).blackboard::<u32>().open().unwrap();
+        let reader = opened.reader().create();
+
+        assert_that!(reader.get::<u64>(&0), eq Ok(12));
+        assert_that!(reader.get::<u8>(&1), eq Ok(3));
+        assert_that!(reader.get::<Calibration>(&2), eq Ok(Calibration { offset: 0.5, gain: 2.0 }));
+
+        let writer = sut.writer().create().unwrap();
+        let entry = writer.entry::<u64>(&0).unwrap();
+        entry.update(8912);
+        let calibration = writer.entry::<Calibration>(&2).unwrap();
+        calibration.update(Calibration {
+            offset: 1.5,
+            gain: 4.0,
+        });
+
+        let reader_entry = reader.entry::<u64>(&0).unwrap();
+        assert_that!(reader_entry.get(), eq 8912);
+        assert_that!(reader.get::<Calibration>(&2), eq Ok(Calibration { offset: 1.5, gain: 4.0 }));
+    }
+
+    #[test]
+    fn accessing_entry_with_wrong_key_or_type_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+
+        let reader = sut.reader().create();
+        assert_that!(reader.get::<u64>(&1), eq Err(ReaderEntryError::EntryDoesNotExist));
+        assert_that!(reader.get::<i64>(&0), eq Err(ReaderEntryError::IncompatibleValueType));
+
+        let writer = sut.writer().create().unwrap();
+        let result = writer.entry::<u64>(&1);
+        assert_that!(result.err().unwrap(), eq WriterEntryError::EntryDoesNotExist);
+        let result = writer.entry::<u32>(&0);
+        assert_that!(result.err().unwrap(), eq WriterEntryError::IncompatibleValueType);
+    }
+
+    #[test]
+    fn entry_can_be_owned_by_only_one_writer_at_a_time<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .max_writers(2)
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+
+        let writer = sut.writer().create().unwrap();
+        let writer2 = sut.writer().create().unwrap();
+
+        let entry = writer.entry::<u64>(&0).unwrap();
+        let result = writer2.entry::<u64>(&0);
+        assert_that!(result.err().unwrap(), eq WriterEntryError::EntryIsAlreadyOwnedByAnotherWriter);
+
+        drop(entry);
+        assert_that!(writer2.entry::<u64>(&0), is_ok);
+    }
+
+    #[test]
+    fn number_of_writers_is_limited_but_readers_are_not<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .max_writers(1)
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+
+        let _writer = sut.writer().create().unwrap();
+        let writer2 = sut.writer().create();
+        assert_that!(writer2, is_err);
+        assert_that!(writer2.err().unwrap(), eq WriterCreateError::ExceedsMaxSupportedWriters);
+
+        let mut readers = vec![];
+        for _ in 0..128 {
+            readers.push(sut.reader().create());
+        }
+
+        for reader in &readers {
+            assert_that!(reader.get::<u64>(&0), eq Ok(12));
+        }
+    }
+
+    #[test]
+    fn open_fails_when_required_attributes_are_missing<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .attribute("unit", "m/s")
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+        assert_that!(sut.attributes().get("unit"), eq Some("m/s"));
+
+        let sut2 = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .attribute("unit", "km/h")
+            .open();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq BlackboardOpenError::IncompatibleAttributes);
+
+        let sut2 = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .attribute("unit", "m/s")
+            .open();
+        assert_that!(sut2, is_ok);
+        assert_that!(sut2.unwrap().attributes(), eq sut.attributes());
+    }
+
+    #[test]
+    fn list_matching_finds_service_by_attributes<Sut: Service>() {
+        let service_name = generate_name();
+        let tag = service_name.to_string();
+        let _sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .attribute("tag", &tag)
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+
+        let services = Sut::list_matching(|attributes| attributes.get("tag") == Some(tag.as_str()));
+        assert_that!(services, is_ok);
+        let services = services.unwrap();
+        assert_that!(services, len 1);
+        assert_that!(*services[0].service_name(), eq service_name);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
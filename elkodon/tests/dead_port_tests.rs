@@ -0,0 +1,459 @@
+use std::io::{BufRead, BufReader};
+use std::process::{Child, ChildStdout, Command, Stdio};
+use std::time::{Duration, Instant};
+
+use elkodon::service::builder::publish_subscribe::PublishSubscribeOpenError;
+use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+use elkodon_bb_container::semantic_string::*;
+use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+use elkodon_bb_testing::assert_that;
+
+// the tests start themselves a second time as child process, the child finds the service name in
+// this environment variable
+const CHILD_SERVICE_NAME: &str = "ELKODON_DEAD_PORT_TESTS_SERVICE_NAME";
+const CHILD_IS_READY: &str = "ELKODON_DEAD_PORT_TESTS_CHILD_IS_READY";
+const TIMEOUT: Duration = Duration::from_millis(10);
+const MAX_CHILD_LIFETIME: Duration = Duration::from_secs(60);
+
+type Sut = zero_copy::Service;
+
+fn generate_name() -> ServiceName {
+    let mut service = ServiceName::new(b"dead_port_tests_").unwrap();
+    service
+        .push_bytes(
+            UniqueSystemId::new()
+                .unwrap()
+                .value()
+                .to_string()
+                .as_bytes(),
+        )
+        .unwrap();
+    service
+}
+
+fn child_service_name() -> Option<ServiceName> {
+    std::env::var(CHILD_SERVICE_NAME)
+        .ok()
+        .map(|name| ServiceName::new(name.as_bytes()).unwrap())
+}
+
+fn spawn_child(test_name: &str, service_name: &ServiceName) -> (Child, BufReader<ChildStdout>) {
+    let mut child = Command::new(std::env::current_exe().unwrap())
+        .args([test_name, "--exact", "--nocapture", "--test-threads=1"])
+        .env(
+            CHILD_SERVICE_NAME,
+            String::from_utf8(service_name.as_bytes().to_vec()).unwrap(),
+        )
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+
+    let output = BufReader::new(child.stdout.take().unwrap());
+    (child, output)
+}
+
+fn wait_until_child_is_ready(output: &mut BufReader<ChildStdout>) {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        assert_that!(output.read_line(&mut line).unwrap(), ne 0);
+        if line.contains(CHILD_IS_READY) {
+            return;
+        }
+    }
+}
+
+// the child is killed by the parent, the timeout only ensures that it does not outlive a failed
+// test
+fn signal_ready_and_wait_to_be_killed() {
+    println!("{}", CHILD_IS_READY);
+    std::thread::sleep(MAX_CHILD_LIFETIME);
+}
+
+fn kill(mut child: Child) {
+    child.kill().unwrap();
+    // reaps the child, otherwise it remains as zombie and is still considered alive
+    child.wait().unwrap();
+}
+
+#[test]
+fn dead_subscriber_is_removed_and_its_samples_are_reclaimed() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<u64>()
+            .unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+
+        let mut samples = vec![];
+        while samples.len() < 2 {
+            match subscriber.receive().unwrap() {
+                Some(sample) => samples.push(sample),
+                None => std::thread::sleep(TIMEOUT),
+            }
+        }
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name)
+        .publish_subscribe()
+        .max_subscribers(1)
+        .subscriber_buffer_size(2)
+        .subscriber_max_borrowed_samples(2)
+        .history_size(0)
+        .create::<u64>()
+        .unwrap();
+    let publisher = service.publisher().max_loaned_samples(1).create().unwrap();
+
+    let (child, mut output) = spawn_child(
+        "dead_subscriber_is_removed_and_its_samples_are_reclaimed",
+        &service_name,
+    );
+
+    let start = Instant::now();
+    while publisher.number_of_subscribers() == 0 {
+        assert_that!(start.elapsed(), lt MAX_CHILD_LIFETIME);
+        assert_that!(publisher.update_connections(), is_ok);
+        std::thread::sleep(TIMEOUT);
+    }
+
+    // the child borrows the first two samples, the others remain in its buffer
+    assert_that!(publisher.send_copy(1), is_ok);
+    assert_that!(publisher.send_copy(2), is_ok);
+    wait_until_child_is_ready(&mut output);
+    assert_that!(publisher.send_copy(3), is_ok);
+    assert_that!(publisher.send_copy(4), is_ok);
+    kill(child);
+
+    assert_that!(service.cleanup_dead_ports(), eq 1);
+    assert_that!(service.cleanup_dead_ports(), eq 0);
+    assert_that!(publisher.update_connections(), is_ok);
+    assert_that!(publisher.number_of_subscribers(), eq 0);
+
+    // the data segment runs out of memory unless the samples of the dead subscriber were
+    // reclaimed
+    let subscriber = service.subscriber().create().unwrap();
+    assert_that!(publisher.send_copy(5), is_ok);
+    assert_that!(publisher.send_copy(6), is_ok);
+    let sample_5 = subscriber.receive().unwrap().unwrap();
+    let sample_6 = subscriber.receive().unwrap().unwrap();
+    assert_that!(publisher.send_copy(7), is_ok);
+    let result = publisher.send_copy(8);
+    assert_that!(result, is_ok);
+    assert_that!(result.ok().unwrap(), eq 1);
+
+    assert_that!(*sample_5, eq 5);
+    assert_that!(*sample_6, eq 6);
+}
+
+#[test]
+fn dead_publisher_is_removed_with_its_data_segment() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<u64>()
+            .unwrap();
+        let publisher = service.publisher().create().unwrap();
+
+        let start = Instant::now();
+        while publisher.number_of_subscribers() == 0 && start.elapsed() < MAX_CHILD_LIFETIME {
+            publisher.update_connections().unwrap();
+            std::thread::sleep(TIMEOUT);
+        }
+        publisher.send_copy(42).unwrap();
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name)
+        .publish_subscribe()
+        .max_publishers(1)
+        .create::<u64>()
+        .unwrap();
+    let subscriber = service.subscriber().create().unwrap();
+
+    let (child, mut output) = spawn_child(
+        "dead_publisher_is_removed_with_its_data_segment",
+        &service_name,
+    );
+    wait_until_child_is_ready(&mut output);
+
+    let sample = subscriber.receive().unwrap();
+    assert_that!(sample, is_some);
+    assert_that!(*sample.unwrap(), eq 42);
+    kill(child);
+
+    // the slot of the dead publisher is reclaimed when it is required
+    let publisher = service.publisher().create().unwrap();
+    assert_that!(service.cleanup_dead_ports(), eq 0);
+    assert_that!(subscriber.update_connections(), is_ok);
+    assert_that!(subscriber.number_of_publishers(), eq 1);
+
+    assert_that!(publisher.send_copy(43), is_ok);
+    let sample = subscriber.receive().unwrap();
+    assert_that!(sample, is_some);
+    assert_that!(*sample.unwrap(), eq 43);
+}
+
+#[test]
+fn samples_of_dead_consumer_are_redistributed() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name).pipeline().open::<u64>().unwrap();
+        let _consumer = service.consumer().create().unwrap();
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name)
+        .pipeline()
+        .max_consumers(2)
+        .consumer_buffer_size(2)
+        .create::<u64>()
+        .unwrap();
+    let producer = service.producer().create().unwrap();
+
+    let (child, mut output) =
+        spawn_child("samples_of_dead_consumer_are_redistributed", &service_name);
+    wait_until_child_is_ready(&mut output);
+
+    let start = Instant::now();
+    while producer.number_of_consumers() == 0 {
+        assert_that!(start.elapsed(), lt MAX_CHILD_LIFETIME);
+        assert_that!(producer.update_connections(), is_ok);
+        std::thread::sleep(TIMEOUT);
+    }
+
+    // the child never receives, both samples remain in its buffer
+    assert_that!(producer.send_copy(1), is_ok);
+    assert_that!(producer.send_copy(2), is_ok);
+    kill(child);
+
+    let consumer = service.consumer().create().unwrap();
+    assert_that!(producer.update_connections(), is_ok);
+    assert_that!(producer.number_of_consumers(), eq 1);
+
+    assert_that!(*consumer.receive().unwrap().unwrap(), eq 1);
+    assert_that!(*consumer.receive().unwrap().unwrap(), eq 2);
+    assert_that!(consumer.receive().unwrap(), is_none);
+
+    // the dead consumer no longer occupies a slot of the service
+    let consumer_2 = service.consumer().create();
+    assert_that!(consumer_2, is_ok);
+}
+
+#[test]
+fn dead_consumer_with_free_buffer_space_receives_no_more_samples() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name).pipeline().open::<u64>().unwrap();
+        let _consumer = service.consumer().create().unwrap();
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name)
+        .pipeline()
+        .max_consumers(2)
+        .consumer_buffer_size(4)
+        .create::<u64>()
+        .unwrap();
+    let producer = service.producer().create().unwrap();
+    let consumer = service.consumer().create().unwrap();
+
+    let (child, mut output) = spawn_child(
+        "dead_consumer_with_free_buffer_space_receives_no_more_samples",
+        &service_name,
+    );
+    wait_until_child_is_ready(&mut output);
+
+    let start = Instant::now();
+    while producer.number_of_consumers() < 2 {
+        assert_that!(start.elapsed(), lt MAX_CHILD_LIFETIME);
+        assert_that!(producer.update_connections(), is_ok);
+        std::thread::sleep(TIMEOUT);
+    }
+
+    assert_that!(producer.send_copy(1), is_ok);
+    assert_that!(producer.send_copy(2), is_ok);
+    kill(child);
+
+    // the buffer of the dead consumer still has space left
+    assert_that!(producer.send_copy(3), is_ok);
+    assert_that!(producer.send_copy(4), is_ok);
+    assert_that!(producer.number_of_consumers(), eq 1);
+
+    let mut received = vec![];
+    while let Some(sample) = consumer.receive().unwrap() {
+        received.push(*sample);
+    }
+    received.sort();
+    assert_that!(received, eq vec![1, 2, 3, 4]);
+}
+
+#[test]
+fn dead_listener_and_notifier_are_removed() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name).event().open().unwrap();
+        let _listener = service.listener().create().unwrap();
+        let _notifier = service.notifier().create().unwrap();
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name).event().create().unwrap();
+
+    let (child, mut output) = spawn_child("dead_listener_and_notifier_are_removed", &service_name);
+    wait_until_child_is_ready(&mut output);
+    kill(child);
+
+    assert_that!(service.cleanup_dead_ports(), eq 2);
+    assert_that!(service.cleanup_dead_ports(), eq 0);
+}
+
+#[test]
+fn slots_of_dead_listener_and_notifier_are_reclaimed_on_creation() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name).event().open().unwrap();
+        let _listener = service.listener().create().unwrap();
+        let _notifier = service.notifier().create().unwrap();
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name)
+        .event()
+        .max_listeners(1)
+        .max_notifiers(1)
+        .create()
+        .unwrap();
+
+    let (child, mut output) = spawn_child(
+        "slots_of_dead_listener_and_notifier_are_reclaimed_on_creation",
+        &service_name,
+    );
+    wait_until_child_is_ready(&mut output);
+    kill(child);
+
+    let listener = service.listener().create().unwrap();
+    let notifier = service.notifier().create().unwrap();
+    assert_that!(service.cleanup_dead_ports(), eq 0);
+
+    assert_that!(notifier.notify(), eq Ok(1));
+    let mut ids = vec![];
+    assert_that!(listener.try_wait(|id| { ids.push(id); true }), eq Ok(1));
+}
+
+#[test]
+fn entries_of_dead_writer_are_released() {
+    if let Some(service_name) = child_service_name() {
+        let service = Sut::new(&service_name).blackboard::<u32>().open().unwrap();
+        let writer = service.writer().create().unwrap();
+        let _entry = writer.entry::<u64>(&0).unwrap();
+
+        signal_ready_and_wait_to_be_killed();
+        return;
+    }
+
+    let service_name = generate_name();
+    let service = Sut::new(&service_name)
+        .blackboard::<u32>()
+        .max_writers(1)
+        .add(0, 0u64)
+        .create()
+        .unwrap();
+
+    let (child, mut output) = spawn_child("entries_of_dead_writer_are_released", &service_name);
+    wait_until_child_is_ready(&mut output);
+    kill(child);
+
+    // the slot of the dead writer is reclaimed on creation
+    let writer = service.writer().create().unwrap();
+    assert_that!(service.cleanup_dead_ports(), eq 0);
+
+    let entry = writer.entry::<u64>(&0).unwrap();
+    entry.update(42);
+    let reader = service.reader().create();
+    assert_that!(reader.get::<u64>(&0), eq Ok(42));
+}
+
+fn spawn_stale_service_owner(test_name: &str, service_name: &ServiceName) -> Child {
+    let (child, mut output) = spawn_child(test_name, service_name);
+    wait_until_child_is_ready(&mut output);
+    child
+}
+
+fn own_service_and_wait_to_be_killed(service_name: &ServiceName) {
+    let service = Sut::new(service_name)
+        .publish_subscribe()
+        .max_publishers(1)
+        .create::<u64>()
+        .unwrap();
+    let publisher = service.publisher().create().unwrap();
+    let _subscriber = service.subscriber().create().unwrap();
+    publisher.send_copy(1).unwrap();
+
+    signal_ready_and_wait_to_be_killed();
+}
+
+#[test]
+fn stale_service_is_removed_when_all_owners_are_dead() {
+    if let Some(service_name) = child_service_name() {
+        own_service_and_wait_to_be_killed(&service_name);
+        return;
+    }
+
+    let service_name = generate_name();
+    let child = spawn_stale_service_owner(
+        "stale_service_is_removed_when_all_owners_are_dead",
+        &service_name,
+    );
+    kill(child);
+
+    let removed_services = Sut::cleanup_stale_services();
+    assert_that!(removed_services, is_ok);
+    assert_that!(removed_services.unwrap().contains(&service_name), eq true);
+
+    let service = Sut::new(&service_name).publish_subscribe().open::<u64>();
+    assert_that!(service, is_err);
+    assert_that!(service.err().unwrap(), eq PublishSubscribeOpenError::DoesNotExist);
+
+    let service = Sut::new(&service_name).publish_subscribe().create::<u64>();
+    assert_that!(service, is_ok);
+}
+
+#[test]
+fn open_or_create_replaces_stale_service() {
+    if let Some(service_name) = child_service_name() {
+        own_service_and_wait_to_be_killed(&service_name);
+        return;
+    }
+
+    let service_name = generate_name();
+    let child = spawn_stale_service_owner("open_or_create_replaces_stale_service", &service_name);
+    kill(child);
+
+    // the stale service supports only one publisher, opening it would fail
+    let service = Sut::new(&service_name)
+        .publish_subscribe()
+        .max_publishers(2)
+        .open_or_create::<u64>();
+    assert_that!(service, is_ok);
+
+    let service = service.unwrap();
+    let publisher = service.publisher().create().unwrap();
+    let subscriber = service.subscriber().create().unwrap();
+    assert_that!(publisher.send_copy(2), is_ok);
+    assert_that!(*subscriber.receive().unwrap().unwrap(), eq 2);
+}
@@ -0,0 +1,143 @@
+#[generic_tests::define]
+mod deadline {
+    use std::time::Duration;
+
+    use elkodon::port::deadline::MissedDeadline;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::clock::Time;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    const DEADLINE: Duration = Duration::from_millis(50);
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"deadline_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn subscriber_without_deadline_reports_nothing<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let _publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().create().unwrap();
+        assert_that!(sut.deadline(), is_none);
+
+        std::thread::sleep(DEADLINE * 2);
+        assert_that!(sut.check_deadlines(|_| {}).unwrap(), eq 0);
+        assert_that!(sut.missed_deadlines(), eq 0);
+    }
+
+    #[test]
+    fn quiet_publisher_misses_deadlines<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().deadline(DEADLINE).create().unwrap();
+        assert_that!(sut.deadline(), eq Some(DEADLINE));
+        assert_that!(sut.check_deadlines(|_| {}).unwrap(), eq 0);
+
+        std::thread::sleep(DEADLINE * 2);
+        let mut reports: Vec<MissedDeadline> = vec![];
+        assert_that!(sut.check_deadlines(|r| reports.push(r)).unwrap(), eq 1);
+        assert_that!(reports[0].publisher_id(), eq publisher.id());
+        assert_that!(reports[0].missed_deadlines(), ge 2);
+        assert_that!(reports[0].time_since_last_sample(), ge DEADLINE * 2);
+        assert_that!(sut.missed_deadlines(), eq reports[0].missed_deadlines());
+
+        // missed deadlines are reported only once
+        assert_that!(sut.check_deadlines(|_| {}).unwrap(), eq 0);
+    }
+
+    #[test]
+    fn periodic_publisher_does_not_miss_deadlines<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().deadline(DEADLINE).create().unwrap();
+
+        for i in 0..3 {
+            std::thread::sleep(DEADLINE / 4);
+            assert_that!(publisher.send_copy(i), is_ok);
+            assert_that!(sut.receive().unwrap(), is_some);
+            assert_that!(sut.check_deadlines(|_| {}).unwrap(), eq 0);
+        }
+
+        assert_that!(sut.missed_deadlines(), eq 0);
+    }
+
+    #[test]
+    fn missed_deadlines_are_counted_on_receive<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().deadline(DEADLINE).create().unwrap();
+
+        std::thread::sleep(DEADLINE * 2);
+        assert_that!(publisher.send_copy(1), is_ok);
+        assert_that!(sut.receive().unwrap(), is_some);
+        assert_that!(sut.missed_deadlines(), ge 2);
+
+        let mut reports: Vec<MissedDeadline> = vec![];
+        assert_that!(sut.check_deadlines(|r| reports.push(r)).unwrap(), eq 1);
+        assert_that!(reports[0].missed_deadlines(), eq sut.missed_deadlines());
+        assert_that!(reports[0].time_since_last_sample(), lt DEADLINE);
+    }
+
+    #[test]
+    fn time_stamp_of_sample_loaned_long_before_send_is_the_send_time<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().deadline(DEADLINE).create().unwrap();
+
+        let mut sample = publisher.loan().unwrap();
+        unsafe { *sample.as_mut_ptr() = 1 };
+        std::thread::sleep(DEADLINE * 2);
+
+        let send_time = Time::now().unwrap();
+        assert_that!(publisher.send(sample), is_ok);
+        let sample = sut.receive().unwrap().unwrap();
+        assert_that!(sample.header().time_stamp().as_duration(), ge send_time.as_duration());
+
+        let mut reports: Vec<MissedDeadline> = vec![];
+        assert_that!(sut.check_deadlines(|r| reports.push(r)).unwrap(), eq 1);
+        assert_that!(reports[0].time_since_last_sample(), lt DEADLINE);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
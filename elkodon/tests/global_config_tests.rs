@@ -0,0 +1,232 @@
+use std::time::Duration;
+
+use elkodon::global_config::*;
+use elkodon::service::port_factory::publisher::UnableToDeliverStrategy;
+use elkodon::service::service_name::ServiceName;
+use elkodon_bb_container::semantic_string::*;
+use elkodon_bb_posix::config::TEMP_DIRECTORY;
+use elkodon_bb_posix::directory::Directory;
+use elkodon_bb_posix::file::*;
+use elkodon_bb_posix::permission::Permission;
+use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+use elkodon_bb_system_types::file_name::FileName;
+use elkodon_bb_system_types::file_path::FilePath;
+use elkodon_bb_testing::assert_that;
+
+fn create_config_file(contents: &str) -> FilePath {
+    let mut file_name = FileName::new(b"global_config_tests_").unwrap();
+    file_name
+        .push_bytes(
+            UniqueSystemId::new()
+                .unwrap()
+                .value()
+                .to_string()
+                .as_bytes(),
+        )
+        .unwrap();
+    let config_file = FilePath::from_path_and_file(&TEMP_DIRECTORY, &file_name).unwrap();
+
+    let mut file = FileBuilder::new(&config_file)
+        .creation_mode(CreationMode::PurgeAndCreate)
+        .create()
+        .unwrap();
+    file.write(contents.as_bytes()).unwrap();
+    config_file
+}
+
+#[test]
+fn global_config_keys_missing_in_file_keep_their_default_value() {
+    let config_file = create_config_file(
+        r#"
+        [defaults.event]
+        max_notifiers = 3
+        "#,
+    );
+
+    let sut = Config::from_file(&config_file).unwrap();
+    File::remove(&config_file).unwrap();
+
+    let defaults = Entries::default();
+    assert_that!(sut.get().defaults.event.max_notifiers, eq 3);
+    assert_that!(sut.get().defaults.event.max_listeners, eq defaults.defaults.event.max_listeners);
+    assert_that!(sut.get().global.root_path, eq defaults.global.root_path);
+    assert_that!(sut.issues(), is_empty);
+}
+
+#[test]
+fn global_config_reports_unknown_keys() {
+    let config_file = create_config_file(
+        r#"
+        [defaults.event]
+        max_notifier = 3
+
+        [services."camera/front"]
+        subscriber_buffer_size = 4
+        buffer_size = 4
+        "#,
+    );
+
+    let sut = Config::from_file(&config_file).unwrap();
+    File::remove(&config_file).unwrap();
+
+    let issues = sut.issues().to_vec();
+    assert_that!(issues, len 2);
+    assert_that!(issues, contains ConfigIssue::UnknownKey(
+        "defaults.event.max_notifier".to_string()));
+    assert_that!(issues, contains ConfigIssue::UnknownKey(
+        "services.\"camera/front\".buffer_size".to_string()));
+}
+
+#[test]
+fn global_config_reads_per_service_overrides() {
+    let config_file = create_config_file(
+        r#"
+        [services."camera/front"]
+        subscriber_buffer_size = 8
+        max_listeners = 3
+        "#,
+    );
+
+    let sut = Config::from_file(&config_file).unwrap();
+    File::remove(&config_file).unwrap();
+
+    let camera = ServiceName::new(b"camera/front").unwrap();
+    let other = ServiceName::new(b"camera/rear").unwrap();
+    let defaults = Entries::default().defaults;
+    assert_that!(sut.get().publish_subscribe(&camera).subscriber_buffer_size, eq 8);
+    assert_that!(sut.get().event(&camera).max_listeners, eq 3);
+    assert_that!(sut.get().publish_subscribe(&other), eq defaults.publish_subscribe);
+    assert_that!(sut.get().event(&other), eq defaults.event);
+}
+
+#[test]
+fn global_config_validation_reports_history_larger_than_buffer() {
+    let mut entries = Entries::default();
+    assert_that!(entries.validate(), is_empty);
+
+    entries.services.insert(
+        "camera/front".to_string(),
+        ServiceOverrides {
+            subscriber_buffer_size: Some(2),
+            publisher_history_size: Some(3),
+            enable_safe_overflow: Some(false),
+            ..Default::default()
+        },
+    );
+
+    let issues = entries.validate();
+    assert_that!(issues, len 1);
+    assert_that!(
+        matches!(&issues[0], ConfigIssue::ImpossibleCombination { section, .. }
+            if section == "services.\"camera/front\""), eq true
+    );
+
+    // with safe overflow the history can be larger than the buffer
+    entries
+        .services
+        .get_mut("camera/front")
+        .unwrap()
+        .enable_safe_overflow = Some(true);
+    assert_that!(entries.validate(), is_empty);
+}
+
+#[test]
+fn global_config_keys_can_be_overridden_via_environment() {
+    std::env::set_var("ELKODON__DEFAULTS__EVENT__MAX_NOTIFIERS", "13");
+    std::env::set_var("ELKODON__GLOBAL__SERVICE__DIRECTORY", "env_services");
+    let sut = Config::load();
+    std::env::remove_var("ELKODON__DEFAULTS__EVENT__MAX_NOTIFIERS");
+    std::env::remove_var("ELKODON__GLOBAL__SERVICE__DIRECTORY");
+
+    let sut = sut.unwrap();
+    assert_that!(sut.get().defaults.event.max_notifiers, eq 13);
+    assert_that!(sut.get().global.service.directory, eq "env_services");
+}
+
+#[test]
+fn global_config_service_overrides_via_environment_keep_the_case_of_the_service_name() {
+    std::env::set_var(
+        "ELKODON__SERVICES__Camera/Front__SUBSCRIBER_BUFFER_SIZE",
+        "11",
+    );
+    let sut = Config::load();
+    std::env::remove_var("ELKODON__SERVICES__Camera/Front__SUBSCRIBER_BUFFER_SIZE");
+
+    let sut = sut.unwrap();
+    let camera = ServiceName::new(b"Camera/Front").unwrap();
+    assert_that!(sut.get().publish_subscribe(&camera).subscriber_buffer_size, eq 11);
+    assert_that!(sut.issues(), is_empty);
+}
+
+#[test]
+fn global_config_reads_the_user_config_file() {
+    let mut config_dir = TEMP_DIRECTORY;
+    config_dir
+        .add_path_entry(
+            &FileName::new(
+                format!(
+                    "global_config_tests_{}",
+                    UniqueSystemId::new().unwrap().value()
+                )
+                .as_bytes(),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+    let mut elkodon_dir = config_dir;
+    elkodon_dir
+        .add_path_entry(&FileName::new(b"elkodon").unwrap())
+        .unwrap();
+    Directory::create(&config_dir, Permission::OWNER_ALL).unwrap();
+    Directory::create(&elkodon_dir, Permission::OWNER_ALL).unwrap();
+
+    let config_file =
+        FilePath::from_path_and_file(&elkodon_dir, &FileName::new(b"config.toml").unwrap())
+            .unwrap();
+    let mut file = FileBuilder::new(&config_file)
+        .creation_mode(CreationMode::PurgeAndCreate)
+        .create()
+        .unwrap();
+    file.write(b"[defaults.pipeline]\nmax_producers = 7\n")
+        .unwrap();
+
+    std::env::set_var("XDG_CONFIG_HOME", config_dir.to_string());
+    let sut = Config::load();
+    std::env::remove_var("XDG_CONFIG_HOME");
+    File::remove(&config_file).unwrap();
+    Directory::remove(&elkodon_dir).unwrap();
+    Directory::remove(&config_dir).unwrap();
+
+    assert_that!(sut.unwrap().get().defaults.pipeline.max_producers, eq 7);
+}
+
+#[test]
+fn global_config_unable_to_deliver_strategy_with_timeout_can_be_configured() {
+    let config_file = create_config_file(
+        r#"
+        [defaults.publish_subscribe]
+        unable_to_deliver_strategy = 'block_with_timeout(250ms)'
+        "#,
+    );
+
+    let sut = Config::from_file(&config_file).unwrap();
+    File::remove(&config_file).unwrap();
+
+    assert_that!(sut.get().defaults.publish_subscribe.unable_to_deliver_strategy,
+        eq UnableToDeliverStrategy::BlockWithTimeout(Duration::from_millis(250)));
+}
+
+#[test]
+fn global_config_with_invalid_unable_to_deliver_strategy_fails() {
+    let config_file = create_config_file(
+        r#"
+        [defaults.publish_subscribe]
+        unable_to_deliver_strategy = 'block_with_timeout(250 minutes)'
+        "#,
+    );
+
+    let sut = Config::from_file(&config_file);
+    File::remove(&config_file).unwrap();
+
+    assert_that!(sut, is_err);
+}
@@ -0,0 +1,196 @@
+#[generic_tests::define]
+mod history_request {
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"history_request_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn late_joiner_receives_full_history_by_default<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .history_size(3)
+            .subscriber_buffer_size(5)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        for i in 0..3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let sut = service.subscriber().create().unwrap();
+        assert_that!(publisher.update_connections(), is_ok);
+
+        for i in 0..3 {
+            assert_that!(*sut.receive().unwrap().unwrap(), eq i);
+        }
+        assert_that!(sut.receive().unwrap(), is_none);
+    }
+
+    #[test]
+    fn late_joiner_receives_only_requested_history<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .history_size(3)
+            .subscriber_buffer_size(5)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        for i in 0..3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let sut = service.subscriber().history_request(1).create().unwrap();
+        let no_history = service.subscriber().history_request(0).create().unwrap();
+        assert_that!(publisher.update_connections(), is_ok);
+
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 2);
+        assert_that!(sut.receive().unwrap(), is_none);
+        assert_that!(no_history.receive().unwrap(), is_none);
+    }
+
+    #[test]
+    fn requested_history_is_limited_by_history_and_buffer_size<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .history_size(4)
+            .subscriber_buffer_size(2)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        for i in 0..4 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let sut = service.subscriber().history_request(10).create().unwrap();
+        assert_that!(publisher.update_connections(), is_ok);
+
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 2);
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 3);
+        assert_that!(sut.receive().unwrap(), is_none);
+    }
+
+    #[test]
+    fn subscriber_can_request_history_explicitly<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .history_size(3)
+            .subscriber_buffer_size(5)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().history_request(0).create().unwrap();
+
+        for i in 0..3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+        for i in 0..3 {
+            assert_that!(*sut.receive().unwrap().unwrap(), eq i);
+        }
+
+        sut.request_history(2);
+        assert_that!(sut.receive().unwrap(), is_none);
+        assert_that!(publisher.update_connections(), is_ok);
+
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 1);
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 2);
+        assert_that!(sut.receive().unwrap(), is_none);
+
+        // a request is served only once
+        assert_that!(publisher.update_connections(), is_ok);
+        assert_that!(sut.receive().unwrap(), is_none);
+    }
+
+    #[test]
+    fn history_request_is_served_by_every_publisher<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_publishers(2)
+            .history_size(2)
+            .subscriber_buffer_size(5)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher_1 = service.publisher().create().unwrap();
+        let publisher_2 = service.publisher().create().unwrap();
+        let sut = service.subscriber().history_request(0).create().unwrap();
+
+        assert_that!(publisher_1.send_copy(1), is_ok);
+        assert_that!(publisher_2.send_copy(2), is_ok);
+        assert_that!(sut.receive().unwrap(), is_some);
+        assert_that!(sut.receive().unwrap(), is_some);
+
+        sut.request_history(1);
+        assert_that!(publisher_1.update_connections(), is_ok);
+        assert_that!(publisher_2.update_connections(), is_ok);
+
+        let mut received = vec![];
+        while let Some(sample) = sut.receive().unwrap() {
+            received.push(*sample);
+        }
+        received.sort();
+        assert_that!(received, eq vec![1, 2]);
+    }
+
+    #[test]
+    fn reused_subscriber_slot_receives_history_of_new_subscriber<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_subscribers(1)
+            .history_size(3)
+            .subscriber_buffer_size(5)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        for i in 0..3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let previous = service.subscriber().history_request(3).create().unwrap();
+        assert_that!(publisher.update_connections(), is_ok);
+        previous.request_history(3);
+        drop(previous);
+
+        let sut = service.subscriber().history_request(0).create().unwrap();
+        assert_that!(publisher.update_connections(), is_ok);
+        assert_that!(sut.receive().unwrap(), is_none);
+        drop(sut);
+
+        let sut = service.subscriber().history_request(2).create().unwrap();
+        assert_that!(publisher.update_connections(), is_ok);
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 1);
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 2);
+        assert_that!(sut.receive().unwrap(), is_none);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
@@ -0,0 +1,188 @@
+#[generic_tests::define]
+mod liveliness {
+    use std::time::Duration;
+
+    use elkodon::port::liveliness::{LivelinessChange, LivelinessState};
+    use elkodon::service::builder::publish_subscribe::PublishSubscribeOpenError;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    const MAX_HEARTBEAT_INTERVAL: Duration = Duration::from_millis(50);
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"liveliness_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn services_have_no_liveliness_contract_by_default<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        assert_that!(sut.max_heartbeat_interval(), is_none);
+
+        let _publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        std::thread::sleep(MAX_HEARTBEAT_INTERVAL * 2);
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 0);
+    }
+
+    #[test]
+    fn open_fails_when_liveliness_contract_is_less_strict<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL)
+            .create::<u64>()
+            .unwrap();
+        assert_that!(sut.max_heartbeat_interval(), eq Some(MAX_HEARTBEAT_INTERVAL));
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL * 2)
+            .open::<u64>();
+        assert_that!(sut2, is_ok);
+
+        let sut3 = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL / 2)
+            .open::<u64>();
+        assert_that!(sut3, is_err);
+        assert_that!(sut3.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleLivelinessContract);
+    }
+
+    #[test]
+    fn open_fails_when_service_has_no_liveliness_contract<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL)
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleLivelinessContract);
+    }
+
+    #[test]
+    fn quiet_publisher_violates_liveliness_contract<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 0);
+
+        std::thread::sleep(MAX_HEARTBEAT_INTERVAL * 2);
+        let mut changes: Vec<LivelinessChange> = vec![];
+        assert_that!(subscriber.check_liveliness(|c| changes.push(c)).unwrap(), eq 1);
+        assert_that!(changes[0].publisher_id(), eq publisher.id());
+        assert_that!(changes[0].state(), eq LivelinessState::Violated);
+        assert_that!(changes[0].time_since_last_heartbeat(), ge MAX_HEARTBEAT_INTERVAL);
+
+        // a violation is reported only once
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 0);
+    }
+
+    #[test]
+    fn send_and_assert_liveliness_announce_liveliness<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        std::thread::sleep(MAX_HEARTBEAT_INTERVAL * 2);
+        assert_that!(publisher.send_copy(1), is_ok);
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 0);
+
+        std::thread::sleep(MAX_HEARTBEAT_INTERVAL * 2);
+        publisher.assert_liveliness();
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 0);
+    }
+
+    #[test]
+    fn publisher_which_recovers_is_reported_alive<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        std::thread::sleep(MAX_HEARTBEAT_INTERVAL * 2);
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 1);
+
+        publisher.assert_liveliness();
+        let mut changes: Vec<LivelinessChange> = vec![];
+        assert_that!(subscriber.check_liveliness(|c| changes.push(c)).unwrap(), eq 1);
+        assert_that!(changes[0].state(), eq LivelinessState::Alive);
+
+        assert_that!(publisher.send_copy(7), is_ok);
+        let sample = subscriber.receive().unwrap();
+        assert_that!(sample, is_some);
+        assert_that!(*sample.unwrap(), eq 7);
+    }
+
+    #[test]
+    fn subscriber_closes_connection_on_violation_when_configured<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_heartbeat_interval(MAX_HEARTBEAT_INTERVAL)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut
+            .subscriber()
+            .close_connection_on_liveliness_violation(true)
+            .create()
+            .unwrap();
+        assert_that!(subscriber.number_of_publishers(), eq 1);
+
+        std::thread::sleep(MAX_HEARTBEAT_INTERVAL * 2);
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 1);
+        assert_that!(subscriber.number_of_publishers(), eq 0);
+
+        // the hung publisher is not connected again
+        assert_that!(publisher.send_copy(3), is_ok);
+        assert_that!(subscriber.receive().unwrap(), is_none);
+        assert_that!(subscriber.check_liveliness(|_| {}).unwrap(), eq 0);
+        assert_that!(subscriber.number_of_publishers(), eq 0);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
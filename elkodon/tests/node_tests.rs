@@ -0,0 +1,195 @@
+#[generic_tests::define]
+mod node {
+    use elkodon::global_config::{Config, Entries};
+    use elkodon::node::{node_name::NodeName, Node, NodeBuilder};
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"node_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn node_is_registered_until_it_is_dropped<Sut: Service>() {
+        let node_name = NodeName::new(b"planner").unwrap();
+        let sut = NodeBuilder::new().name(&node_name).create::<Sut>().unwrap();
+        assert_that!(sut.name(), eq Some(&node_name));
+
+        let nodes = Node::<Sut>::list().unwrap();
+        let node = nodes.iter().find(|node| node.id() == sut.id());
+        assert_that!(node, is_some);
+        assert_that!(node.unwrap().name(), eq Some(&node_name));
+        assert_that!(node.unwrap().is_alive(), eq true);
+
+        let id = *sut.id();
+        drop(sut);
+
+        let nodes = Node::<Sut>::list().unwrap();
+        assert_that!(nodes.iter().any(|node| *node.id() == id), eq false);
+    }
+
+    #[test]
+    fn nodes_without_name_are_registered<Sut: Service>() {
+        let sut = NodeBuilder::new().create::<Sut>().unwrap();
+        assert_that!(sut.name(), is_none);
+
+        let nodes = Node::<Sut>::list().unwrap();
+        let node = nodes.iter().find(|node| node.id() == sut.id());
+        assert_that!(node, is_some);
+        assert_that!(node.unwrap().name(), is_none);
+    }
+
+    #[test]
+    fn cleanup_does_not_remove_living_nodes<Sut: Service>() {
+        let sut = NodeBuilder::new().create::<Sut>().unwrap();
+
+        let removed_nodes = Node::<Sut>::cleanup_dead_nodes().unwrap();
+        assert_that!(removed_nodes.contains(sut.id()), eq false);
+
+        let nodes = Node::<Sut>::list().unwrap();
+        assert_that!(nodes.iter().any(|node| node.id() == sut.id()), eq true);
+    }
+
+    #[test]
+    fn services_created_via_node_can_communicate<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = NodeBuilder::new().create::<Sut>().unwrap();
+
+        let service = sut
+            .service_builder(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let publisher = service.publisher().create().unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+
+        assert_that!(publisher.send_copy(8192), is_ok);
+        let sample = subscriber.receive().unwrap();
+        assert_that!(sample, is_some);
+        assert_that!(*sample.unwrap(), eq 8192);
+
+        let event = sut
+            .service_builder(&generate_name())
+            .event()
+            .create()
+            .unwrap();
+        let listener = event.listener().create().unwrap();
+        let notifier = event.notifier().create().unwrap();
+        assert_that!(notifier.notify(), is_ok);
+        assert_that!(listener.try_wait(|_| true).unwrap(), eq 1);
+    }
+
+    #[test]
+    fn services_created_via_node_use_config_of_node<Sut: Service>() {
+        let service_name = generate_name();
+        let mut entries = Entries::default();
+        entries.defaults.publish_subscribe.max_subscribers = 3;
+        entries.defaults.publish_subscribe.max_publishers = 5;
+
+        let sut = NodeBuilder::new()
+            .config(&Config::from_entries(&entries))
+            .create::<Sut>()
+            .unwrap();
+        assert_that!(sut.config().get().defaults.publish_subscribe.max_subscribers, eq 3);
+
+        let service = sut
+            .service_builder(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        assert_that!(service.max_supported_subscribers(), eq 3);
+        assert_that!(service.max_supported_publishers(), eq 5);
+    }
+
+    #[test]
+    fn node_lists_its_services_and_ports_until_they_are_dropped<Sut: Service>() {
+        let sut = NodeBuilder::new().create::<Sut>().unwrap();
+        let registered_node = || {
+            Node::<Sut>::list()
+                .unwrap()
+                .into_iter()
+                .find(|node| node.id() == sut.id())
+                .unwrap()
+        };
+
+        let service = sut
+            .service_builder(&generate_name())
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let publisher = service.publisher().create().unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+
+        let node = registered_node();
+        assert_that!(node.services(), len 1);
+        assert_that!(node.ports(), len 2);
+        assert_that!(node.ports().contains(&publisher.id().value()), eq true);
+        assert_that!(node.ports().contains(&subscriber.id().value()), eq true);
+
+        drop(publisher);
+        let node = registered_node();
+        assert_that!(node.ports(), len 1);
+        assert_that!(node.ports().contains(&subscriber.id().value()), eq true);
+
+        drop(subscriber);
+        drop(service);
+        let node = registered_node();
+        assert_that!(node.services(), is_empty);
+        assert_that!(node.ports(), is_empty);
+    }
+
+    #[test]
+    fn node_lists_at_most_max_ports<Sut: Service>() {
+        let mut entries = Entries::default();
+        entries.global.node.max_ports = 1;
+        let sut = NodeBuilder::new()
+            .config(&Config::from_entries(&entries))
+            .create::<Sut>()
+            .unwrap();
+
+        let service = sut
+            .service_builder(&generate_name())
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let publisher = service.publisher().create().unwrap();
+        let subscriber = service.subscriber().create();
+        assert_that!(subscriber, is_ok);
+
+        let nodes = Node::<Sut>::list().unwrap();
+        let node = nodes.iter().find(|node| node.id() == sut.id()).unwrap();
+        assert_that!(node.ports(), len 1);
+        assert_that!(node.ports()[0], eq publisher.id().value());
+    }
+
+    #[test]
+    fn services_created_without_node_are_not_listed<Sut: Service>() {
+        let sut = NodeBuilder::new().create::<Sut>().unwrap();
+        let _service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let nodes = Node::<Sut>::list().unwrap();
+        let node = nodes.iter().find(|node| node.id() == sut.id()).unwrap();
+        assert_that!(node.services(), is_empty);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
@@ -0,0 +1,342 @@
+#[generic_tests::define]
+mod pipeline {
+    use elkodon::port::consumer::ConsumerCreateError;
+    use elkodon::port::producer::{ProducerCreateError, ProducerSendCopyError, ProducerSendError};
+    use elkodon::service::builder::pipeline::{PipelineCreateError, PipelineOpenError};
+    use elkodon::service::port_factory::producer::DistributionStrategy;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, Copy, ShmSafe, ZeroCopyType)]
+    #[zero_copy_type(identifier = "u32")]
+    #[repr(C)]
+    struct WideU32 {
+        value: u64,
+    }
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"pipeline_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn creating_non_existing_service_works<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name).pipeline().create::<u64>();
+
+        assert_that!(sut, is_ok);
+        let sut = sut.unwrap();
+        assert_that!(*sut.name(), eq service_name);
+    }
+
+    #[test]
+    fn creating_same_service_twice_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name).pipeline().create::<u64>();
+        assert_that!(sut, is_ok);
+
+        let sut2 = Sut::new(&service_name).pipeline().create::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineCreateError::AlreadyExists);
+    }
+
+    #[test]
+    fn open_fails_when_service_has_wrong_type_or_pattern<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name).pipeline().create::<u64>().unwrap();
+
+        let sut2 = Sut::new(&service_name).pipeline().open::<i64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineOpenError::IncompatibleTypes);
+
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name).pipeline().open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineOpenError::IncompatibleMessagingPattern);
+    }
+
+    #[test]
+    fn open_fails_with_same_type_identifier_but_different_layout<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name).pipeline().create::<u32>().unwrap();
+
+        let sut2 = Sut::new(&service_name).pipeline().open::<WideU32>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineOpenError::IncompatibleTypeLayout);
+    }
+
+    #[test]
+    fn open_fails_when_service_does_not_fulfill_requirements<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .pipeline()
+            .max_producers(1)
+            .max_consumers(2)
+            .create::<u64>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .pipeline()
+            .max_consumers(3)
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineOpenError::DoesNotSupportRequestedAmountOfConsumers);
+
+        let sut2 = Sut::new(&service_name)
+            .pipeline()
+            .max_producers(2)
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineOpenError::DoesNotSupportRequestedAmountOfProducers);
+
+        let sut2 = Sut::new(&service_name)
+            .pipeline()
+            .max_consumers(2)
+            .open::<u64>();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn number_of_producers_and_consumers_is_limited<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .max_producers(1)
+            .max_consumers(1)
+            .create::<u64>()
+            .unwrap();
+
+        let _producer = sut.producer().create().unwrap();
+        let producer2 = sut.producer().create();
+        assert_that!(producer2, is_err);
+        assert_that!(producer2.err().unwrap(), eq ProducerCreateError::ExceedsMaxSupportedProducers);
+
+        let _consumer = sut.consumer().create().unwrap();
+        let consumer2 = sut.consumer().create();
+        assert_that!(consumer2, is_err);
+        assert_that!(consumer2.err().unwrap(), eq ConsumerCreateError::ExceedsMaxSupportedConsumers);
+    }
+
+    #[test]
+    fn send_without_consumer_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name).pipeline().create::<u64>().unwrap();
+
+        let producer = sut.producer().create().unwrap();
+        let result = producer.send_copy(12);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq ProducerSendCopyError::SendError(ProducerSendError::NoConsumerConnected));
+    }
+
+    #[test]
+    fn send_fails_when_all_consumer_buffers_are_full<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .consumer_buffer_size(1)
+            .create::<u64>()
+            .unwrap();
+
+        let producer = sut.producer().create().unwrap();
+        let consumer = sut.consumer().create().unwrap();
+
+        assert_that!(producer.send_copy(1), is_ok);
+        let result = producer.send_copy(2);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq ProducerSendCopyError::SendError(ProducerSendError::AllConsumerBuffersAreFull));
+
+        assert_that!(*consumer.receive().unwrap().unwrap(), eq 1);
+        assert_that!(producer.send_copy(3), is_ok);
+        assert_that!(*consumer.receive().unwrap().unwrap(), eq 3);
+    }
+
+    #[test]
+    fn every_sample_is_received_by_exactly_one_consumer<Sut: Service>() {
+        const NUMBER_OF_CONSUMERS: usize = 3;
+        const NUMBER_OF_SAMPLES: u64 = 6;
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .max_consumers(NUMBER_OF_CONSUMERS)
+            .create::<u64>()
+            .unwrap();
+
+        let producer = sut
+            .producer()
+            .distribution_strategy(DistributionStrategy::RoundRobin)
+            .create()
+            .unwrap();
+        let mut consumers = vec![];
+        for _ in 0..NUMBER_OF_CONSUMERS {
+            consumers.push(sut.consumer().create().unwrap());
+        }
+
+        let mut receivers = vec![];
+        for i in 0..NUMBER_OF_SAMPLES {
+            receivers.push(producer.send_copy(i).unwrap());
+        }
+        assert_that!(producer.number_of_consumers(), eq NUMBER_OF_CONSUMERS);
+
+        for i in 0..NUMBER_OF_CONSUMERS {
+            assert_that!(receivers[i], eq receivers[i + NUMBER_OF_CONSUMERS]);
+            assert_that!(receivers[i], ne receivers[(i + 1) % NUMBER_OF_CONSUMERS]);
+        }
+
+        let mut received_samples = vec![];
+        for consumer in &consumers {
+            let mut number_of_received_samples = 0;
+            while let Some(sample) = consumer.receive().unwrap() {
+                received_samples.push(*sample);
+                number_of_received_samples += 1;
+            }
+            assert_that!(number_of_received_samples, eq 2);
+        }
+
+        received_samples.sort();
+        assert_that!(
+            received_samples,
+            eq(0..NUMBER_OF_SAMPLES).collect::<Vec<u64>>()
+        );
+    }
+
+    #[test]
+    fn least_loaded_strategy_prefers_consumer_with_fewest_pending_samples<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .max_consumers(2)
+            .consumer_buffer_size(4)
+            .create::<u64>()
+            .unwrap();
+
+        let producer = sut
+            .producer()
+            .distribution_strategy(DistributionStrategy::LeastLoaded)
+            .create()
+            .unwrap();
+        let consumer_a = sut.consumer().create().unwrap();
+        let consumer_b = sut.consumer().create().unwrap();
+
+        let busy_consumer = producer.send_copy(1).unwrap();
+        let (fast, slow) = match consumer_a.receive().unwrap() {
+            Some(sample) => {
+                assert_that!(*sample, eq 1);
+                (&consumer_a, &consumer_b)
+            }
+            None => (&consumer_b, &consumer_a),
+        };
+        while fast.receive().unwrap().is_some() {}
+
+        let slow_consumer = producer.send_copy(2).unwrap();
+        assert_that!(slow_consumer, ne busy_consumer);
+
+        // the fast consumer returns every sample right away, the slow one keeps them pending
+        for i in 3..6 {
+            assert_that!(producer.send_copy(i).unwrap(), eq busy_consumer);
+            assert_that!(*fast.receive().unwrap().unwrap(), eq i);
+        }
+
+        assert_that!(*slow.receive().unwrap().unwrap(), eq 2);
+        assert_that!(slow.receive().unwrap(), is_none);
+    }
+
+    #[test]
+    fn samples_of_removed_consumer_are_redistributed<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .max_consumers(2)
+            .consumer_buffer_size(4)
+            .create::<u64>()
+            .unwrap();
+
+        let producer = sut.producer().create().unwrap();
+        let consumer_a = sut.consumer().create().unwrap();
+        let consumer_b = sut.consumer().create().unwrap();
+
+        for i in 0..4 {
+            assert_that!(producer.send_copy(i), is_ok);
+        }
+
+        let sample = consumer_a.receive().unwrap();
+        assert_that!(sample, is_some);
+        let mut received_samples = vec![*sample.unwrap()];
+        drop(consumer_b);
+
+        assert_that!(producer.update_connections(), is_ok);
+        assert_that!(producer.number_of_consumers(), eq 1);
+
+        while let Some(sample) = consumer_a.receive().unwrap() {
+            received_samples.push(*sample);
+        }
+
+        received_samples.sort();
+        assert_that!(received_samples, eq vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn open_fails_when_required_attributes_are_missing<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .attribute("unit", "m/s")
+            .create::<u64>()
+            .unwrap();
+        assert_that!(sut.attributes().get("unit"), eq Some("m/s"));
+
+        let sut2 = Sut::new(&service_name)
+            .pipeline()
+            .attribute("unit", "km/h")
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PipelineOpenError::IncompatibleAttributes);
+
+        let sut2 = Sut::new(&service_name)
+            .pipeline()
+            .attribute("unit", "m/s")
+            .open::<u64>();
+        assert_that!(sut2, is_ok);
+        assert_that!(sut2.unwrap().attributes(), eq sut.attributes());
+    }
+
+    #[test]
+    fn list_matching_finds_service_by_attributes<Sut: Service>() {
+        let service_name = generate_name();
+        let tag = service_name.to_string();
+        let _sut = Sut::new(&service_name)
+            .pipeline()
+            .attribute("tag", &tag)
+            .create::<u64>()
+            .unwrap();
+
+        let services = Sut::list_matching(|attributes| attributes.get("tag") == Some(tag.as_str()));
+        assert_that!(services, is_ok);
+        let services = services.unwrap();
+        assert_that!(services, len 1);
+        assert_that!(*services[0].service_name(), eq service_name);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
@@ -0,0 +1,157 @@
+#[generic_tests::define]
+mod port_statistics {
+    use elkodon::service::port_factory::publisher::UnableToDeliverStrategy;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    const BUFFER_SIZE: usize = 4;
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"port_statistics_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn statistics_contain_every_port<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let statistics = sut.statistics();
+        assert_that!(statistics.publishers(), is_empty);
+        assert_that!(statistics.subscribers(), is_empty);
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        let statistics = sut.statistics();
+        assert_that!(statistics.publishers(), len 1);
+        assert_that!(statistics.subscribers(), len 1);
+        assert_that!(statistics.publisher(publisher.id()), is_some);
+        assert_that!(statistics.subscriber(subscriber.id()), is_some);
+
+        drop(publisher);
+        assert_that!(sut.statistics().publishers(), is_empty);
+    }
+
+    #[test]
+    fn sent_and_received_samples_are_counted<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        for i in 0..3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+        assert_that!(subscriber.receive().unwrap(), is_some);
+        assert_that!(subscriber.receive().unwrap(), is_some);
+
+        let statistics = sut.statistics();
+        let publisher_statistics = statistics.publisher(publisher.id()).unwrap();
+        assert_that!(publisher_statistics.sent_samples(), eq 3);
+        assert_that!(publisher_statistics.failed_loans(), eq 0);
+
+        let subscriber_statistics = statistics.subscriber(subscriber.id()).unwrap();
+        assert_that!(subscriber_statistics.received_samples(), eq 2);
+        assert_that!(subscriber_statistics.samples_dropped_by_full_buffer(), eq 0);
+        assert_that!(subscriber_statistics.samples_dropped_by_overflow(), eq 0);
+    }
+
+    #[test]
+    fn samples_dropped_by_overflow_are_counted<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_safe_overflow(true)
+            .subscriber_buffer_size(BUFFER_SIZE)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        for i in 0..BUFFER_SIZE as u64 + 3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let statistics = sut.statistics();
+        let subscriber_statistics = statistics.subscriber(subscriber.id()).unwrap();
+        assert_that!(subscriber_statistics.samples_dropped_by_overflow(), eq 3);
+        assert_that!(subscriber_statistics.samples_dropped_by_full_buffer(), eq 0);
+    }
+
+    #[test]
+    fn samples_dropped_by_full_buffer_are_counted<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_safe_overflow(false)
+            .history_size(0)
+            .subscriber_buffer_size(BUFFER_SIZE)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut
+            .publisher()
+            .unable_to_deliver_strategy(UnableToDeliverStrategy::DiscardSample)
+            .create()
+            .unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        for i in 0..BUFFER_SIZE as u64 + 2 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let statistics = sut.statistics();
+        let subscriber_statistics = statistics.subscriber(subscriber.id()).unwrap();
+        assert_that!(subscriber_statistics.samples_dropped_by_full_buffer(), eq 2);
+        assert_that!(subscriber_statistics.samples_dropped_by_overflow(), eq 0);
+        assert_that!(
+            statistics.publisher(publisher.id()).unwrap().sent_samples(), eq
+            BUFFER_SIZE as u64 + 2
+        );
+    }
+
+    #[test]
+    fn statistics_can_be_read_without_ports<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let publisher = sut.publisher().create().unwrap();
+        assert_that!(publisher.send_copy(1234), is_ok);
+
+        let monitor = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<u64>()
+            .unwrap();
+
+        let statistics = monitor.statistics();
+        assert_that!(statistics.publisher(publisher.id()).unwrap().sent_samples(), eq 1);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
@@ -1,8 +1,10 @@
 #[generic_tests::define]
 mod publisher {
+    use std::cell::RefCell;
     use std::time::{Duration, Instant};
 
     use elkodon::port::publisher::LoanError;
+    use elkodon::port::DeliveryTimeoutAction;
     use elkodon::service::port_factory::publisher::UnableToDeliverStrategy;
     use elkodon::service::{service_name::ServiceName, Service};
     use elkodon_bb_container::semantic_string::*;
@@ -181,6 +183,171 @@ mod publisher {
         });
     }
 
+    #[test]
+    fn publisher_can_send_slices_with_different_lengths<Sut: Service>() {
+        const MAX_SLICE_LEN: usize = 8;
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<[u64]>()
+            .unwrap();
+
+        let sut = service
+            .publisher()
+            .max_slice_len(MAX_SLICE_LEN)
+            .create()
+            .unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+
+        assert_that!(sut.send_copy_slice(&[1, 2, 3]), is_ok);
+
+        let mut sample = sut.loan_slice(MAX_SLICE_LEN).unwrap();
+        let payload = sample.as_mut_ptr() as *mut u64;
+        for i in 0..MAX_SLICE_LEN {
+            unsafe { payload.add(i).write(i as u64 * 10) };
+        }
+        assert_that!(sample.header().number_of_elements(), eq MAX_SLICE_LEN);
+        assert_that!(sut.send(sample), is_ok);
+
+        let sample = subscriber.receive().unwrap().unwrap();
+        assert_that!(sample.header().number_of_elements(), eq 3);
+        assert_that!(*sample, eq [1, 2, 3]);
+
+        let sample = subscriber.receive().unwrap().unwrap();
+        assert_that!(sample.len(), eq MAX_SLICE_LEN);
+        for (i, element) in sample.iter().enumerate() {
+            assert_that!(*element, eq i as u64 * 10);
+        }
+    }
+
+    #[test]
+    fn publisher_loan_slice_fails_when_exceeding_max_slice_len<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<[u8]>()
+            .unwrap();
+
+        let sut = service.publisher().max_slice_len(16).create().unwrap();
+
+        assert_that!(sut.loan_slice(16), is_ok);
+        let sample = sut.loan_slice(17);
+        assert_that!(sample, is_err);
+        assert_that!(sample.err().unwrap(), eq LoanError::ExceedsMaxLoanSize);
+    }
+
+    #[test]
+    fn publisher_block_with_timeout_discards_sample_after_timeout<Sut: Service>() {
+        let reported_subscribers = RefCell::new(vec![]);
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .subscriber_buffer_size(1)
+            .history_size(0)
+            .enable_safe_overflow(false)
+            .create::<u64>()
+            .unwrap();
+
+        let mut sut = service
+            .publisher()
+            .unable_to_deliver_strategy(UnableToDeliverStrategy::BlockWithTimeout(TIMEOUT))
+            .create()
+            .unwrap();
+        sut.set_delivery_timeout_callback(Some(|_, _, subscriber_id| {
+            reported_subscribers.borrow_mut().push(subscriber_id);
+            DeliveryTimeoutAction::Ignore
+        }));
+        let subscriber = service.subscriber().create().unwrap();
+
+        assert_that!(sut.send_copy(1), eq Ok(1));
+        let start = Instant::now();
+        assert_that!(sut.send_copy(2), eq Ok(0));
+        assert_that!(start.elapsed(), ge TIMEOUT);
+        assert_that!(*reported_subscribers.borrow(), eq vec![subscriber.id()]);
+
+        assert_that!(*subscriber.receive().unwrap().unwrap(), eq 1);
+        assert_that!(subscriber.receive().unwrap(), is_none);
+        assert_that!(sut.send_copy(3), eq Ok(1));
+        assert_that!(*subscriber.receive().unwrap().unwrap(), eq 3);
+    }
+
+    #[test]
+    fn publisher_block_with_timeout_disconnects_subscriber_on_disconnect<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .subscriber_buffer_size(1)
+            .history_size(0)
+            .enable_safe_overflow(false)
+            .create::<u64>()
+            .unwrap();
+
+        let mut sut = service
+            .publisher()
+            .unable_to_deliver_strategy(UnableToDeliverStrategy::BlockWithTimeout(TIMEOUT))
+            .create()
+            .unwrap();
+        sut.set_delivery_timeout_callback(Some(|_, _, _| DeliveryTimeoutAction::Disconnect));
+        let subscriber = service.subscriber().create().unwrap();
+
+        assert_that!(subscriber.number_of_disconnections(), eq 0);
+        assert_that!(sut.send_copy(1), eq Ok(1));
+        assert_that!(sut.send_copy(2), eq Ok(0));
+        assert_that!(sut.number_of_subscribers(), eq 0);
+        assert_that!(subscriber.number_of_disconnections(), eq 1);
+
+        // the disconnected subscriber is not connected again
+        assert_that!(*subscriber.receive().unwrap().unwrap(), eq 1);
+        let start = Instant::now();
+        assert_that!(sut.send_copy(3), eq Ok(0));
+        assert_that!(start.elapsed(), lt TIMEOUT);
+        assert_that!(sut.number_of_subscribers(), eq 0);
+    }
+
+    #[test]
+    fn publisher_block_with_timeout_reclaims_samples_of_disconnected_subscribers<Sut: Service>() {
+        const ITERATIONS: usize = 16;
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .max_subscribers(1)
+            .subscriber_buffer_size(1)
+            .subscriber_max_borrowed_samples(1)
+            .history_size(0)
+            .enable_safe_overflow(false)
+            .create::<u64>()
+            .unwrap();
+
+        let mut sut = service
+            .publisher()
+            .max_loaned_samples(1)
+            .unable_to_deliver_strategy(UnableToDeliverStrategy::BlockWithTimeout(TIMEOUT))
+            .create()
+            .unwrap();
+        sut.set_delivery_timeout_callback(Some(|_, _, _| DeliveryTimeoutAction::Disconnect));
+
+        // the data segment holds only a few samples, every lost sample would exhaust it
+        for i in 0..ITERATIONS as u64 {
+            let subscriber = service.subscriber().create().unwrap();
+            assert_that!(sut.update_connections(), is_ok);
+
+            assert_that!(sut.send_copy(i), eq Ok(1));
+            assert_that!(sut.send_copy(i + 1), eq Ok(0));
+            assert_that!(sut.number_of_subscribers(), eq 0);
+
+            if i % 2 == 0 {
+                // the returned sample is reclaimed with the next loan while the subscriber is
+                // still alive, it receives no further samples
+                assert_that!(*subscriber.receive().unwrap().unwrap(), eq i);
+                assert_that!(sut.send_copy(i), eq Ok(0));
+            }
+            drop(subscriber);
+        }
+
+        assert_that!(sut.update_connections(), is_ok);
+        assert_that!(sut.send_copy(0), is_ok);
+    }
+
     #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
     mod zero_copy {}
 
@@ -0,0 +1,357 @@
+#[generic_tests::define]
+mod request_response {
+    use elkodon::port::client::ClientCreateError;
+    use elkodon::port::server::{ServerCreateError, ServerSendCopyError, ServerSendError};
+    use elkodon::service::builder::request_response::{
+        RequestResponseCreateError, RequestResponseOpenError,
+    };
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, Copy, ShmSafe, ZeroCopyType)]
+    #[zero_copy_type(identifier = "u32")]
+    #[repr(C)]
+    struct WideU32 {
+        value: u64,
+    }
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"request_response_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn creating_non_existing_service_works<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .create::<u64, u64>();
+
+        assert_that!(sut, is_ok);
+        let sut = sut.unwrap();
+        assert_that!(*sut.name(), eq service_name);
+    }
+
+    #[test]
+    fn creating_same_service_twice_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .create::<u64, u64>();
+        assert_that!(sut, is_ok);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .create::<u64, u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseCreateError::AlreadyExists);
+    }
+
+    #[test]
+    fn open_fails_when_service_does_not_exist<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .open::<u64, u64>();
+        assert_that!(sut, is_err);
+        assert_that!(sut.err().unwrap(), eq RequestResponseOpenError::DoesNotExist);
+    }
+
+    #[test]
+    fn open_fails_when_service_has_wrong_request_or_response_type<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .request_response()
+            .create::<u64, u32>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open::<i64, u32>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::IncompatibleTypes);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open::<u64, i32>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::IncompatibleTypes);
+    }
+
+    #[test]
+    fn open_fails_with_same_type_identifier_but_different_layout<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .request_response()
+            .create::<u32, u32>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open::<WideU32, u32>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::IncompatibleTypeLayout);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open::<u32, WideU32>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::IncompatibleTypeLayout);
+    }
+
+    #[test]
+    fn open_fails_when_service_is_publish_subscribe<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open::<u64, u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::IncompatibleMessagingPattern);
+    }
+
+    #[test]
+    fn open_fails_when_service_does_not_fulfill_requirements<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .request_response()
+            .max_clients(2)
+            .max_servers(1)
+            .create::<u64, u64>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .max_clients(3)
+            .open::<u64, u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::DoesNotSupportRequestedAmountOfClients);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .max_servers(2)
+            .open::<u64, u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::DoesNotSupportRequestedAmountOfServers);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .max_clients(1)
+            .max_servers(1)
+            .open::<u64, u64>();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn open_or_create_opens_existing_service<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .open_or_create::<u64, u64>();
+        assert_that!(sut, is_ok);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open_or_create::<u64, u64>();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn number_of_clients_and_servers_is_limited<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .max_clients(1)
+            .max_servers(1)
+            .create::<u64, u64>()
+            .unwrap();
+
+        let _client = sut.client().create().unwrap();
+        let client2 = sut.client().create();
+        assert_that!(client2, is_err);
+        assert_that!(client2.err().unwrap(), eq ClientCreateError::ExceedsMaxSupportedClients);
+
+        let _server = sut.server().create().unwrap();
+        let server2 = sut.server().create();
+        assert_that!(server2, is_err);
+        assert_that!(server2.err().unwrap(), eq ServerCreateError::ExceedsMaxSupportedServers);
+    }
+
+    #[test]
+    fn request_response_round_trip_works<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .create::<u64, u64>()
+            .unwrap();
+
+        let server = sut.server().create().unwrap();
+        let client = sut.client().create().unwrap();
+        assert_that!(server.update_connections(), is_ok);
+        assert_that!(client.number_of_servers(), eq 1);
+        assert_that!(server.number_of_clients(), eq 1);
+
+        assert_that!(client.send_copy(21), is_ok);
+
+        let request = server.receive().unwrap();
+        assert_that!(request, is_some);
+        let request = request.unwrap();
+        assert_that!(*request, eq 21);
+
+        assert_that!(server.send_copy(request.header(), *request * 2), is_ok);
+        drop(request);
+
+        let response = client.receive().unwrap();
+        assert_that!(response, is_some);
+        assert_that!(*response.unwrap(), eq 42);
+        assert_that!(client.receive().unwrap(), is_none);
+    }
+
+    #[test]
+    fn response_is_routed_only_to_requesting_client<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .max_clients(2)
+            .create::<u64, u64>()
+            .unwrap();
+
+        let server = sut.server().create().unwrap();
+        let client_a = sut.client().create().unwrap();
+        let client_b = sut.client().create().unwrap();
+
+        assert_that!(client_b.send_copy(5), is_ok);
+
+        let request = server.receive().unwrap().unwrap();
+        let mut response = server.loan(request.header()).unwrap();
+        unsafe { response.as_mut_ptr().write(*request + 1) };
+        assert_that!(response.header().request_id(), eq request.header().request_id());
+        assert_that!(server.send(response), is_ok);
+
+        assert_that!(client_a.receive().unwrap(), is_none);
+        let response = client_b.receive().unwrap();
+        assert_that!(response, is_some);
+        assert_that!(*response.unwrap(), eq 6);
+    }
+
+    #[test]
+    fn request_is_delivered_to_exactly_one_server<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .max_servers(2)
+            .create::<u64, u64>()
+            .unwrap();
+
+        let server_a = sut.server().create().unwrap();
+        let server_b = sut.server().create().unwrap();
+        let client = sut.client().create().unwrap();
+        assert_that!(client.number_of_servers(), eq 2);
+
+        for i in 0..4 {
+            assert_that!(client.send_copy(i), eq Ok(1));
+        }
+
+        let mut received_a = vec![];
+        while let Some(request) = server_a.receive().unwrap() {
+            received_a.push(*request);
+        }
+        let mut received_b = vec![];
+        while let Some(request) = server_b.receive().unwrap() {
+            received_b.push(*request);
+        }
+
+        assert_that!(received_a, len 2);
+        assert_that!(received_b, len 2);
+        let mut received = [received_a, received_b].concat();
+        received.sort();
+        assert_that!(received, eq vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn response_to_disconnected_client_fails<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .create::<u64, u64>()
+            .unwrap();
+
+        let server = sut.server().create().unwrap();
+        let client = sut.client().create().unwrap();
+        assert_that!(client.send_copy(5), is_ok);
+
+        let request = server.receive().unwrap().unwrap();
+        let header = *request.header();
+        drop(request);
+        drop(client);
+
+        let result = server.send_copy(&header, 6);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq ServerSendCopyError::SendError(ServerSendError::ClientNotConnected));
+    }
+
+    #[test]
+    fn open_fails_when_required_attributes_are_missing<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .attribute("unit", "m/s")
+            .create::<u64, u64>()
+            .unwrap();
+        assert_that!(sut.attributes().get("unit"), eq Some("m/s"));
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .attribute("unit", "km/h")
+            .open::<u64, u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq RequestResponseOpenError::IncompatibleAttributes);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .attribute("unit", "m/s")
+            .open::<u64, u64>();
+        assert_that!(sut2, is_ok);
+        assert_that!(sut2.unwrap().attributes(), eq sut.attributes());
+    }
+
+    #[test]
+    fn list_matching_finds_service_by_attributes<Sut: Service>() {
+        let service_name = generate_name();
+        let tag = service_name.to_string();
+        let _sut = Sut::new(&service_name)
+            .request_response()
+            .attribute("tag", &tag)
+            .create::<u64, u64>()
+            .unwrap();
+
+        let services = Sut::list_matching(|attributes| attributes.get("tag") == Some(tag.as_str()));
+        assert_that!(services, is_ok);
+        let services = services.unwrap();
+        assert_that!(services, len 1);
+        assert_that!(*services[0].service_name(), eq service_name);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
@@ -0,0 +1,145 @@
+#[generic_tests::define]
+mod sample_loss {
+    use std::cell::RefCell;
+
+    use elkodon::port::sample_loss::SampleLoss;
+    use elkodon::service::port_factory::publisher::UnableToDeliverStrategy;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"sample_loss_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn sequence_numbers_increase_on_every_send<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .subscriber_buffer_size(5)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        for i in 0..3 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        for i in 0..3 {
+            let sample = sut.receive().unwrap().unwrap();
+            assert_that!(sample.header().sequence_number(), eq i);
+        }
+        assert_that!(sut.lost_samples(), eq 0);
+        assert_that!(sut.lost_samples_of(publisher.id()), eq Some(0));
+    }
+
+    #[test]
+    fn samples_lost_by_safe_overflow_are_detected<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .subscriber_buffer_size(2)
+            .history_size(0)
+            .enable_safe_overflow(true)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        assert_that!(publisher.send_copy(0), is_ok);
+        assert_that!(sut.receive().unwrap(), is_some);
+
+        for i in 1..5 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+
+        let sample = sut.receive().unwrap().unwrap();
+        assert_that!(*sample, eq 3);
+        assert_that!(sut.lost_samples(), eq 2);
+        assert_that!(sut.lost_samples_of(publisher.id()), eq Some(2));
+    }
+
+    #[test]
+    fn samples_discarded_by_publisher_are_detected<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .subscriber_buffer_size(1)
+            .history_size(0)
+            .enable_safe_overflow(false)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service
+            .publisher()
+            .unable_to_deliver_strategy(UnableToDeliverStrategy::DiscardSample)
+            .create()
+            .unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        assert_that!(publisher.send_copy(0), is_ok);
+        assert_that!(sut.receive().unwrap(), is_some);
+
+        assert_that!(publisher.send_copy(1), is_ok);
+        assert_that!(publisher.send_copy(2), is_ok);
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 1);
+        assert_that!(sut.lost_samples(), eq 0);
+
+        assert_that!(publisher.send_copy(3), is_ok);
+        assert_that!(*sut.receive().unwrap().unwrap(), eq 3);
+        assert_that!(sut.lost_samples(), eq 1);
+    }
+
+    #[test]
+    fn sample_loss_callback_is_called_on_gap<Sut: Service>() {
+        let reported_losses: RefCell<Vec<SampleLoss>> = RefCell::new(vec![]);
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .subscriber_buffer_size(1)
+            .history_size(0)
+            .enable_safe_overflow(true)
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let mut sut = service.subscriber().create().unwrap();
+        sut.set_sample_loss_callback(Some(|loss| reported_losses.borrow_mut().push(loss)));
+
+        assert_that!(publisher.send_copy(0), is_ok);
+        assert_that!(sut.receive().unwrap(), is_some);
+        assert_that!(reported_losses.borrow().len(), eq 0);
+
+        for i in 1..4 {
+            assert_that!(publisher.send_copy(i), is_ok);
+        }
+        assert_that!(sut.receive().unwrap(), is_some);
+
+        let losses = reported_losses.borrow();
+        assert_that!(losses.len(), eq 1);
+        assert_that!(losses[0].publisher_id(), eq publisher.id());
+        assert_that!(losses[0].number_of_lost_samples(), eq 2);
+        assert_that!(losses[0].sequence_number(), eq 3);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
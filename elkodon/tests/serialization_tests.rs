@@ -0,0 +1,186 @@
+#[generic_tests::define]
+mod serialization {
+    use elkodon::port::publisher::{LoanError, SendCopyError, SendSerializedError};
+    use elkodon::port::subscriber::ReceiveDeserializedError;
+    use elkodon::service::builder::publish_subscribe::{
+        PublishSubscribeCreateError, PublishSubscribeOpenError,
+    };
+    use elkodon::service::serialization::SerializationFormat;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+    use serde::{Deserialize, Serialize};
+
+    const MAX_SLICE_LEN: usize = 1024;
+
+    #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    struct Message {
+        name: String,
+        values: Vec<u64>,
+    }
+
+    fn message() -> Message {
+        Message {
+            name: "funky".to_string(),
+            values: vec![1, 2, 3, 4],
+        }
+    }
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"serialization_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    fn send_and_receive_works<Sut: Service>(format: SerializationFormat) {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(format)
+            .create::<[u8]>()
+            .unwrap();
+        assert_that!(service.serialization_format(), eq Some(format));
+
+        let publisher = service
+            .publisher()
+            .max_slice_len(MAX_SLICE_LEN)
+            .create()
+            .unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        assert_that!(sut.receive_deserialized::<Message>().unwrap(), is_none);
+        assert_that!(publisher.send_serialized(&message()), eq Ok(1));
+        assert_that!(sut.receive_deserialized::<Message>().unwrap(), eq Some(message()));
+        assert_that!(sut.receive_deserialized::<Message>().unwrap(), is_none);
+    }
+
+    #[test]
+    fn send_and_receive_with_cdr_works<Sut: Service>() {
+        send_and_receive_works::<Sut>(SerializationFormat::Cdr);
+    }
+
+    #[test]
+    fn send_and_receive_with_toml_works<Sut: Service>() {
+        send_and_receive_works::<Sut>(SerializationFormat::Toml);
+    }
+
+    #[test]
+    fn open_fails_with_incompatible_serialization_format<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Cdr)
+            .create::<[u8]>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Cdr)
+            .open::<[u8]>();
+        assert_that!(sut2, is_ok);
+
+        let sut3 = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Toml)
+            .open::<[u8]>();
+        assert_that!(sut3, is_err);
+        assert_that!(sut3.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleSerializationFormat);
+
+        let sut4 = Sut::new(&service_name).publish_subscribe().open::<[u8]>();
+        assert_that!(sut4, is_ok);
+        assert_that!(sut4.unwrap().serialization_format(), eq Some(SerializationFormat::Cdr));
+    }
+
+    #[test]
+    fn serialization_requires_serialization_format<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<[u8]>()
+            .unwrap();
+        assert_that!(service.serialization_format(), is_none);
+
+        let publisher = service
+            .publisher()
+            .max_slice_len(MAX_SLICE_LEN)
+            .create()
+            .unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        assert_that!(publisher.send_serialized(&message()).err(),
+            eq Some(SendSerializedError::SerializationFormatNotDefined));
+        assert_that!(sut.receive_deserialized::<Message>().err(),
+            eq Some(ReceiveDeserializedError::SerializationFormatNotDefined));
+    }
+
+    #[test]
+    fn send_fails_when_serialized_value_exceeds_max_slice_len<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Cdr)
+            .create::<[u8]>()
+            .unwrap();
+
+        let publisher = service.publisher().max_slice_len(4).create().unwrap();
+
+        let expected_error = SendSerializedError::SendCopyError(SendCopyError::LoanError(
+            LoanError::ExceedsMaxLoanSize,
+        ));
+        assert_that!(publisher.send_serialized(&message()).err(), eq Some(expected_error));
+    }
+
+    #[test]
+    fn create_fails_when_serialization_format_is_defined_for_non_byte_slice<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Cdr)
+            .create::<u64>();
+        assert_that!(sut.err(),
+            eq Some(PublishSubscribeCreateError::SerializationFormatRequiresByteSlicePayload));
+
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Cdr)
+            .create::<[u64]>();
+        assert_that!(sut.err(),
+            eq Some(PublishSubscribeCreateError::SerializationFormatRequiresByteSlicePayload));
+    }
+
+    #[test]
+    fn receive_fails_when_sample_is_not_deserializable<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .serialization_format(SerializationFormat::Cdr)
+            .create::<[u8]>()
+            .unwrap();
+
+        let publisher = service
+            .publisher()
+            .max_slice_len(MAX_SLICE_LEN)
+            .create()
+            .unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        assert_that!(publisher.send_copy_slice(&[0xff]), is_ok);
+        assert_that!(sut.receive_deserialized::<Message>().err(),
+            eq Some(ReceiveDeserializedError::DeserializationFailure));
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
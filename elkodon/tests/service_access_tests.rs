@@ -0,0 +1,160 @@
+#[generic_tests::define]
+mod service_access {
+    use elkodon::service::access::*;
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"service_access_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn services_have_no_additional_access_by_default<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        assert_that!(sut.access_settings(), eq AccessSettings::default());
+    }
+
+    #[test]
+    fn access_settings_are_stored_in_the_service<Sut: Service>() {
+        let service_name = generate_name();
+        let access = AccessSettings::default()
+            .permission(Permission::GROUP_READ | Permission::GROUP_WRITE | Permission::OTHERS_READ);
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .access_settings(&access)
+            .create::<u64>()
+            .unwrap();
+        assert_that!(sut.access_settings(), eq access);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<u64>()
+            .unwrap();
+        assert_that!(sut2.access_settings(), eq access);
+    }
+
+    #[test]
+    fn owner_can_communicate_with_restricted_access_settings<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .access_settings(&AccessSettings::default())
+            .create::<u64>()
+            .unwrap();
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        assert_that!(publisher.send_copy(1234), is_ok);
+        let sample = subscriber.receive().unwrap();
+        assert_that!(sample, is_some);
+        assert_that!(*sample.unwrap(), eq 1234);
+    }
+
+    #[test]
+    fn owner_can_notify_with_shared_access_settings<Sut: Service>() {
+        let service_name = generate_name();
+        let access = AccessSettings::default().permission(Permission::GROUP_ALL);
+        let sut = Sut::new(&service_name)
+            .event()
+            .access_settings(&access)
+            .create()
+            .unwrap();
+        assert_that!(sut.access_settings(), eq access);
+
+        let listener = sut.listener().create().unwrap();
+        let notifier = sut.notifier().create().unwrap();
+
+        assert_that!(notifier.notify(), is_ok);
+        assert_that!(listener.try_wait(|_| true).unwrap(), eq 1);
+    }
+
+    #[test]
+    fn client_and_server_communicate_with_shared_access_settings<Sut: Service>() {
+        let service_name = generate_name();
+        let access = AccessSettings::default().permission(Permission::GROUP_ALL);
+        let sut = Sut::new(&service_name)
+            .request_response()
+            .access_settings(&access)
+            .create::<u64, u64>()
+            .unwrap();
+        assert_that!(sut.access_settings(), eq access);
+
+        let sut2 = Sut::new(&service_name)
+            .request_response()
+            .open::<u64, u64>()
+            .unwrap();
+        assert_that!(sut2.access_settings(), eq access);
+
+        let server = sut.server().create().unwrap();
+        let client = sut2.client().create().unwrap();
+        assert_that!(server.update_connections(), is_ok);
+
+        assert_that!(client.send_copy(21), is_ok);
+        let request = server.receive().unwrap().unwrap();
+        assert_that!(server.send_copy(request.header(), *request * 2), is_ok);
+        drop(request);
+        assert_that!(*client.receive().unwrap().unwrap(), eq 42);
+    }
+
+    #[test]
+    fn producer_and_consumer_communicate_with_shared_access_settings<Sut: Service>() {
+        let service_name = generate_name();
+        let access = AccessSettings::default().permission(Permission::GROUP_ALL);
+        let sut = Sut::new(&service_name)
+            .pipeline()
+            .access_settings(&access)
+            .create::<u64>()
+            .unwrap();
+        assert_that!(sut.access_settings(), eq access);
+
+        let sut2 = Sut::new(&service_name).pipeline().open::<u64>().unwrap();
+        assert_that!(sut2.access_settings(), eq access);
+
+        let consumer = sut2.consumer().create().unwrap();
+        let producer = sut.producer().create().unwrap();
+
+        assert_that!(producer.send_copy(1234), is_ok);
+        assert_that!(*consumer.receive().unwrap().unwrap(), eq 1234);
+    }
+
+    #[test]
+    fn blackboard_stores_access_settings<Sut: Service>() {
+        let service_name = generate_name();
+        let access = AccessSettings::default().permission(Permission::GROUP_READ);
+        let sut = Sut::new(&service_name)
+            .blackboard::<u32>()
+            .access_settings(&access)
+            .add(0, 12u64)
+            .create()
+            .unwrap();
+        assert_that!(sut.access_settings(), eq access);
+
+        let sut2 = Sut::new(&service_name).blackboard::<u32>().open().unwrap();
+        assert_that!(sut2.access_settings(), eq access);
+        assert_that!(sut2.reader().create().get::<u64>(&0), eq Ok(12));
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
@@ -0,0 +1,299 @@
+#[generic_tests::define]
+mod service_discovery {
+    use std::time::{Duration, Instant};
+
+    use elkodon::service::discovery::{DiscoveryEvent, ServiceWatcher};
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::waitset::WaitSet;
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    const TIMEOUT: Duration = Duration::from_millis(50);
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"service_discovery_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    // other tests create services concurrently, therefore only the events of the service under
+    // test are collected
+    fn events_of(
+        service_name: &ServiceName,
+        events: &mut Vec<DiscoveryEvent>,
+    ) -> impl FnMut(DiscoveryEvent) + '_ {
+        let service_name = *service_name;
+        move |event| {
+            let name = match &event {
+                DiscoveryEvent::ServiceAdded(config) => *config.service_name(),
+                DiscoveryEvent::ServiceRemoved(name) => *name,
+            };
+
+            if name == service_name {
+                events.push(event);
+            }
+        }
+    }
+
+    #[test]
+    fn existing_services_are_reported_as_added<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name).event().create().unwrap();
+
+        let watcher = ServiceWatcher::<Sut::Type<'_>>::new();
+        let mut events = vec![];
+        assert_that!(
+            watcher.try_wait(events_of(&service_name, &mut events)),
+            is_ok
+        );
+        assert_that!(events, len 1);
+        assert_that!(matches!(events[0], DiscoveryEvent::ServiceAdded(_)), eq true);
+
+        // a service is reported only once
+        let mut events = vec![];
+        assert_that!(
+            watcher.try_wait(events_of(&service_name, &mut events)),
+            is_ok
+        );
+        assert_that!(events, is_empty);
+    }
+
+    #[test]
+    fn added_and_removed_services_are_reported<Sut: Service>() {
+        let service_name = generate_name();
+        let watcher = ServiceWatcher::<Sut::Type<'_>>::new();
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .attribute("unit", "m/s")
+            .create::<u64>()
+            .unwrap();
+
+        let mut events = vec![];
+        assert_that!(
+            watcher.try_wait(events_of(&service_name, &mut events)),
+            is_ok
+        );
+        assert_that!(events, len 1);
+        let has_attribute = match &events[0] {
+            DiscoveryEvent::ServiceAdded(config) => config.attributes().get("unit") == Some("m/s"),
+            DiscoveryEvent::ServiceRemoved(_) => false,
+        };
+        assert_that!(has_attribute, eq true);
+
+        drop(sut);
+
+        let mut events = vec![];
+        assert_that!(
+            watcher.try_wait(events_of(&service_name, &mut events)),
+            is_ok
+        );
+        assert_that!(events, eq vec![DiscoveryEvent::ServiceRemoved(service_name)]);
+    }
+
+    #[test]
+    fn blocking_wait_wakes_up_when_service_is_created_in_other_thread<Sut: Service>() {
+        let service_name = generate_name();
+        let watcher = ServiceWatcher::<Sut::Type<'_>>::new().polling_interval(TIMEOUT / 10);
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+
+        let mut events = vec![];
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                std::thread::sleep(TIMEOUT);
+                let _sut = Sut::new(&service_name).event().create().unwrap();
+                std::thread::sleep(TIMEOUT * 4);
+            });
+
+            while events.is_empty() {
+                assert_that!(
+                    watcher.blocking_wait(events_of(&service_name, &mut events)),
+                    is_ok
+                );
+            }
+        });
+
+        assert_that!(events, len 1);
+        assert_that!(matches!(events[0], DiscoveryEvent::ServiceAdded(_)), eq true);
+    }
+
+    #[test]
+    fn timed_wait_returns_after_timeout_without_changes<Sut: Service>() {
+        let service_name = generate_name();
+        let watcher = ServiceWatcher::<Sut::Type<'_>>::new();
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+
+        let start = Instant::now();
+        let mut events = vec![];
+        assert_that!(
+            watcher.timed_wait(events_of(&service_name, &mut events), TIMEOUT),
+            is_ok
+        );
+        assert_that!(events, is_empty);
+        assert_that!(start.elapsed(), lt TIMEOUT * 100);
+    }
+
+    #[test]
+    fn service_watcher_can_be_attached_to_waitset<Sut: Service>() {
+        let service_name = generate_name();
+        let watcher = ServiceWatcher::<Sut::Type<'_>>::new().polling_interval(TIMEOUT);
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+
+        let waitset = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard = waitset.attach_service_watcher(&watcher).unwrap();
+        let _sut = Sut::new(&service_name).event().create().unwrap();
+
+        let mut fired_attachments = vec![];
+        assert_that!(
+            waitset.blocking_wait(|id| fired_attachments.push(id)),
+            is_ok
+        );
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+
+        let mut events = vec![];
+        assert_that!(
+            watcher.try_wait(events_of(&service_name, &mut events)),
+            is_ok
+        );
+        assert_that!(events, len 1);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
+
+// the static configs of the zero copy service reside in the file system, therefore its
+// watchers are woken up by the directory watcher and never have to poll
+#[cfg(target_os = "linux")]
+mod service_discovery_directory_watcher {
+    use std::time::{Duration, Instant};
+
+    use elkodon::global_config::{Config, Entries};
+    use elkodon::service::discovery::{DiscoveryEvent, ServiceWatcher};
+    use elkodon::service::{service_name::ServiceName, zero_copy, Service};
+    use elkodon::waitset::WaitSet;
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::directory::Directory;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    const TIMEOUT: Duration = Duration::from_millis(50);
+    const POLLING_INTERVAL: Duration = Duration::from_secs(3600);
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"service_discovery_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    fn is_added(event: &DiscoveryEvent, service_name: &ServiceName) -> bool {
+        matches!(event, DiscoveryEvent::ServiceAdded(config) if config.service_name() == service_name)
+    }
+
+    #[test]
+    fn missing_service_directory_is_not_created_by_watcher() {
+        let mut entries = Entries::default();
+        entries.global.service.directory =
+            String::from_utf8(generate_name().as_bytes().to_vec()).unwrap();
+        let config = Config::from_entries(&entries);
+
+        let watcher = ServiceWatcher::<zero_copy::Service>::new_with_custom_config(&config);
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+        assert_that!(
+            Directory::does_exist(&entries.global.get_absolute_service_dir()),
+            eq Ok(false)
+        );
+    }
+
+    #[test]
+    fn blocking_wait_is_woken_up_by_created_service_without_polling() {
+        let service_name = generate_name();
+        // the directory is created with the first service, the watcher watches only an existing
+        // directory
+        let _existing_service = zero_copy::Service::new(&generate_name())
+            .event()
+            .create()
+            .unwrap();
+        let watcher =
+            ServiceWatcher::<zero_copy::Service>::new().polling_interval(POLLING_INTERVAL);
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+
+        let start = Instant::now();
+        let mut has_service = false;
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                std::thread::sleep(TIMEOUT);
+                let _sut = zero_copy::Service::new(&service_name)
+                    .event()
+                    .create()
+                    .unwrap();
+                std::thread::sleep(TIMEOUT * 4);
+            });
+
+            while !has_service {
+                assert_that!(
+                    watcher.blocking_wait(|e| has_service |= is_added(&e, &service_name)),
+                    is_ok
+                );
+            }
+        });
+
+        assert_that!(start.elapsed(), lt POLLING_INTERVAL);
+    }
+
+    #[test]
+    fn waitset_is_woken_up_by_created_service_without_polling() {
+        let service_name = generate_name();
+        // the directory is created with the first service, the watcher watches only an existing
+        // directory
+        let _existing_service = zero_copy::Service::new(&generate_name())
+            .event()
+            .create()
+            .unwrap();
+        let watcher =
+            ServiceWatcher::<zero_copy::Service>::new().polling_interval(POLLING_INTERVAL);
+        assert_that!(watcher.try_wait(|_| {}), is_ok);
+
+        let waitset = WaitSet::<zero_copy::Service>::new().unwrap();
+        let guard = waitset.attach_service_watcher(&watcher).unwrap();
+        let _sut = zero_copy::Service::new(&service_name)
+            .event()
+            .create()
+            .unwrap();
+
+        let mut fired_attachments = vec![];
+        assert_that!(
+            waitset.timed_wait(|id| fired_attachments.push(id), TIMEOUT * 10),
+            is_ok
+        );
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+
+        let mut has_service = false;
+        assert_that!(
+            watcher.try_wait(|e| has_service |= is_added(&e, &service_name)),
+            is_ok
+        );
+        assert_that!(has_service, eq true);
+    }
+}
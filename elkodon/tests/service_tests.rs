@@ -1,16 +1,51 @@
 #[generic_tests::define]
 mod service_publish_subscribe {
-    use elkodon::global_config::{Config, Entries};
+    use elkodon::global_config::{Config, Entries, ServiceOverrides};
     use elkodon::port::publisher::{LoanError, PublisherCreateError};
     use elkodon::port::subscriber::SubscriberCreateError;
     use elkodon::service::builder::publish_subscribe::PublishSubscribeCreateError;
     use elkodon::service::builder::publish_subscribe::PublishSubscribeOpenError;
     use elkodon::service::port_factory::publisher::UnableToDeliverStrategy;
-    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::service::{service_name::ServiceName, ConnectedPorts, Service};
+    use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
     use elkodon_bb_container::semantic_string::*;
     use elkodon_bb_posix::unique_system_id::UniqueSystemId;
     use elkodon_bb_testing::assert_that;
 
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, ShmSafe, ZeroCopyType)]
+    #[repr(C)]
+    struct FrameHeader {
+        frame_id: u64,
+        source: u32,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ShmSafe, ZeroCopyType)]
+    #[zero_copy_type(identifier = "Position")]
+    #[repr(C)]
+    struct Position2d {
+        x: f64,
+        y: f64,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ShmSafe, ZeroCopyType)]
+    #[zero_copy_type(identifier = "Position")]
+    #[repr(C)]
+    struct Position3d {
+        x: f64,
+        y: f64,
+        z: f64,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, ShmSafe, ZeroCopyType)]
+    #[repr(C)]
+    struct Extent {
+        width: f64,
+        height: f64,
+    }
+
     fn generate_name() -> ServiceName {
         let mut service = ServiceName::new(b"service_tests_").unwrap();
         service
@@ -90,6 +125,84 @@ mod service_publish_subscribe {
         assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleTypes);
     }
 
+    #[test]
+    fn open_fails_when_service_has_wrong_user_header_type<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .user_header::<FrameHeader>()
+            .create::<u64>();
+        assert_that!(sut, is_ok);
+
+        let sut2 = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleTypes);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .user_header::<u64>()
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleTypes);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .user_header::<FrameHeader>()
+            .open::<u64>();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn open_fails_when_service_has_same_type_identifier_but_different_layout<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<Position2d>();
+        assert_that!(sut, is_ok);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<Position3d>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleTypeLayout);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open_or_create::<Position3d>();
+        assert_that!(sut2, is_err);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<Position2d>();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn open_fails_when_service_has_same_layout_but_different_type_identifier<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<Position2d>();
+        assert_that!(sut, is_ok);
+
+        let sut2 = Sut::new(&service_name).publish_subscribe().open::<Extent>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleTypes);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<[f64; 2]>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleTypes);
+    }
+
+    #[test]
+    fn derived_type_identifier_contains_fields<Sut: Service>() {
+        assert_that!(Extent::type_identifier(), eq "Extent { width: f64, height: f64 }");
+        assert_that!(Position3d::type_identifier(), eq "Position");
+        assert_that!(<[u8; 4]>::type_identifier(), eq "[u8; 4]");
+    }
+
     #[test]
     fn open_fails_when_service_does_not_fulfill_publisher_requirements<Sut: Service>() {
         let service_name = generate_name();
@@ -143,6 +256,145 @@ mod service_publish_subscribe {
         assert_that!(sut3.err().unwrap(), eq PublishSubscribeOpenError::DoesNotExist);
     }
 
+    #[test]
+    fn persistent_service_outlives_its_last_owner<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .persistent(true)
+            .create::<u64>();
+        assert_that!(sut, is_ok);
+        assert_that!(sut.unwrap().is_persistent(), eq true);
+
+        let sut = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut, is_ok);
+        let sut = sut.unwrap();
+        assert_that!(sut.is_persistent(), eq true);
+
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+        assert_that!(publisher.send_copy(1234), is_ok);
+        assert_that!(*subscriber.receive().unwrap().unwrap(), eq 1234);
+        drop(publisher);
+        drop(subscriber);
+        drop(sut);
+
+        let sut = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut, is_ok);
+        sut.unwrap().remove();
+    }
+
+    #[test]
+    fn removed_persistent_service_is_gone_when_last_owner_drops_it<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .persistent(true)
+            .create::<u64>()
+            .unwrap();
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<u64>()
+            .unwrap();
+
+        sut.remove();
+        let sut3 = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut3, is_ok);
+        drop(sut3);
+
+        drop(sut2);
+        let sut = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut, is_err);
+        assert_that!(sut.err().unwrap(), eq PublishSubscribeOpenError::DoesNotExist);
+    }
+
+    #[test]
+    fn attributes_are_stored_in_the_service<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .attribute("unit", "m/s")
+            .attribute("schema_version", "1")
+            .attribute("schema_version", "2")
+            .create::<u64>()
+            .unwrap();
+
+        assert_that!(sut.attributes(), len 2);
+        assert_that!(sut.attributes().get("unit"), eq Some("m/s"));
+        assert_that!(sut.attributes().get("schema_version"), eq Some("2"));
+        assert_that!(sut.attributes().get("owner"), is_none);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .open::<u64>()
+            .unwrap();
+        assert_that!(sut2.attributes(), eq sut.attributes());
+    }
+
+    #[test]
+    fn open_fails_when_required_attributes_are_missing<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .attribute("unit", "m/s")
+            .create::<u64>()
+            .unwrap();
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .attribute("unit", "km/h")
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleAttributes);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .attribute("unit", "m/s")
+            .attribute("owner", "me")
+            .open::<u64>();
+        assert_that!(sut2, is_err);
+        assert_that!(sut2.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleAttributes);
+
+        let sut2 = Sut::new(&service_name)
+            .publish_subscribe()
+            .attribute("unit", "m/s")
+            .open::<u64>();
+        assert_that!(sut2, is_ok);
+    }
+
+    #[test]
+    fn list_matching_finds_services_by_attributes<Sut: Service>() {
+        let tag = generate_name().to_string();
+        let service_name_a = generate_name();
+        let service_name_b = generate_name();
+        let _sut_a = Sut::new(&service_name_a)
+            .publish_subscribe()
+            .attribute("tag", &tag)
+            .attribute("frame_rate", "30")
+            .create::<u64>()
+            .unwrap();
+        let _sut_b = Sut::new(&service_name_b)
+            .event()
+            .attribute("tag", &tag)
+            .create()
+            .unwrap();
+
+        let services = Sut::list_matching(|attributes| attributes.get("tag") == Some(tag.as_str()));
+        assert_that!(services, is_ok);
+        let services = services.unwrap();
+        assert_that!(services, len 2);
+        assert_that!(services.iter().any(|s| *s.service_name() == service_name_a), eq true);
+        assert_that!(services.iter().any(|s| *s.service_name() == service_name_b), eq true);
+
+        let services = Sut::list_matching(|attributes| {
+            attributes.get("tag") == Some(tag.as_str())
+                && attributes.get("frame_rate") == Some("30")
+        })
+        .unwrap();
+        assert_that!(services, len 1);
+        assert_that!(*services[0].service_name(), eq service_name_a);
+    }
+
     #[test]
     fn open_or_create_creates_service_if_it_does_not_exist<Sut: Service>() {
         let service_name = generate_name();
@@ -168,6 +420,85 @@ mod service_publish_subscribe {
         assert_that!(sut, is_ok);
     }
 
+    #[test]
+    fn cleanup_stale_services_keeps_services_with_living_owners<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        let removed_services = Sut::cleanup_stale_services();
+        assert_that!(removed_services, is_ok);
+        assert_that!(removed_services.unwrap().contains(&service_name), eq false);
+
+        let sut = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut, is_ok);
+    }
+
+    #[test]
+    fn remove_keeps_services_with_living_owners<Sut: Service>() {
+        let service_name = generate_name();
+        let _sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        assert_that!(Sut::remove(&service_name, false), eq Ok(false));
+
+        let sut = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut, is_ok);
+    }
+
+    #[test]
+    fn forced_remove_removes_services_in_use<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+
+        assert_that!(Sut::remove(&service_name, true), eq Ok(true));
+        assert_that!(Sut::remove(&service_name, true), eq Ok(false));
+
+        let sut2 = Sut::new(&service_name).publish_subscribe().open::<u64>();
+        assert_that!(sut2, is_err);
+
+        drop(sut);
+        let sut = Sut::new(&service_name).publish_subscribe().create::<u64>();
+        assert_that!(sut, is_ok);
+    }
+
+    #[test]
+    fn connected_ports_contains_all_ports<Sut: Service>() {
+        let service_name = generate_name();
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let publisher = sut.publisher().create().unwrap();
+        let subscriber = sut.subscriber().create().unwrap();
+
+        let service_config = Sut::list_matching(|_| true)
+            .unwrap()
+            .into_iter()
+            .find(|s| *s.service_name() == service_name)
+            .unwrap();
+
+        assert_that!(
+            Sut::connected_ports(&service_config), eq
+            Some(ConnectedPorts::PublishSubscribe {
+                publishers: vec![publisher.id()],
+                subscribers: vec![subscriber.id()],
+            })
+        );
+
+        drop(publisher);
+        drop(subscriber);
+        drop(sut);
+        assert_that!(Sut::connected_ports(&service_config), is_none);
+    }
+
     #[test]
     fn max_publishers_and_subscribers_is_set_to_config_default<Sut: Service>() {
         let service_name = generate_name();
@@ -235,6 +566,55 @@ mod service_publish_subscribe {
         assert_that!(sut2.max_supported_subscribers(), eq 1);
     }
 
+    #[test]
+    fn service_settings_can_be_overridden_per_service_via_custom_config<Sut: Service>() {
+        let service_name = generate_name();
+        let other_service_name = generate_name();
+        let mut entries = Entries::default();
+        entries.services.insert(
+            service_name.to_string(),
+            ServiceOverrides {
+                subscriber_buffer_size: Some(7),
+                ..Default::default()
+            },
+        );
+
+        let custom_config = Config::from_entries(&entries);
+        let sut = Sut::new(&service_name)
+            .publish_subscribe_with_custom_config(&custom_config)
+            .create::<u64>()
+            .unwrap();
+        assert_that!(sut.subscriber_buffer_size(), eq 7);
+        assert_that!(
+            sut.max_supported_subscribers(), eq
+            entries.defaults.publish_subscribe.max_subscribers
+        );
+
+        let sut = Sut::new(&other_service_name)
+            .publish_subscribe_with_custom_config(&custom_config)
+            .create::<u64>()
+            .unwrap();
+        assert_that!(
+            sut.subscriber_buffer_size(), eq
+            entries.defaults.publish_subscribe.subscriber_buffer_size
+        );
+
+        let event_name = generate_name();
+        entries.services.insert(
+            event_name.to_string(),
+            ServiceOverrides {
+                max_listeners: Some(5),
+                ..Default::default()
+            },
+        );
+        let custom_config = Config::from_entries(&entries);
+        let sut = Sut::new(&event_name)
+            .event_with_custom_config(&custom_config)
+            .create()
+            .unwrap();
+        assert_that!(sut.max_supported_listeners(), eq 5);
+    }
+
     #[test]
     fn simple_communication_works_subscriber_created_first<Sut: Service>() {
         let service_name = generate_name();
@@ -265,6 +645,36 @@ mod service_publish_subscribe {
         assert_that!(*result.unwrap(), eq 4567);
     }
 
+    #[test]
+    fn user_header_is_delivered_with_the_sample<Sut: Service>() {
+        let service_name = generate_name();
+
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .user_header::<FrameHeader>()
+            .create::<u64>()
+            .unwrap();
+
+        let subscriber = sut.subscriber().create().unwrap();
+        let publisher = sut.publisher().create().unwrap();
+
+        let mut sample = publisher.loan().unwrap();
+        assert_that!(*sample.user_header(), eq FrameHeader::default());
+        *sample.user_header_mut() = FrameHeader {
+            frame_id: 42,
+            source: 7,
+        };
+        unsafe { sample.as_mut_ptr().write(1234) };
+        assert_that!(publisher.send(sample), is_ok);
+
+        let result = subscriber.receive().unwrap();
+        assert_that!(result, is_some);
+        let result = result.unwrap();
+        assert_that!(*result, eq 1234);
+        assert_that!(result.user_header().frame_id, eq 42);
+        assert_that!(result.user_header().source, eq 7);
+    }
+
     #[test]
     fn simple_communication_works_publisher_created_first<Sut: Service>() {
         let service_name = generate_name();
@@ -0,0 +1,166 @@
+#[generic_tests::define]
+mod shm_safe {
+    use std::marker::PhantomData;
+
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+    use elkodon_bb_container::byte_string::FixedSizeByteString;
+    use elkodon_bb_container::queue::FixedSizeQueue;
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_container::vec::FixedSizeVec;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    #[derive(Debug, ShmSafe, ZeroCopyType)]
+    #[repr(C)]
+    struct Record {
+        name: FixedSizeByteString<16>,
+        values: FixedSizeVec<u64, 4>,
+        pending: FixedSizeQueue<u32, 2>,
+        state: State,
+    }
+
+    #[allow(dead_code)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, ShmSafe, ZeroCopyType)]
+    #[repr(C)]
+    enum State {
+        Idle,
+        Busy([u8; 4]),
+    }
+
+    #[derive(Debug, ShmSafe, ZeroCopyType)]
+    #[repr(C)]
+    struct Tagged<Tag> {
+        value: u64,
+        _tag: PhantomData<Tag>,
+    }
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"shm_safe_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn payload_with_fixed_size_containers_can_be_sent<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .create::<Record>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        let mut record = Record {
+            name: FixedSizeByteString::from_bytes(b"funky").unwrap(),
+            values: FixedSizeVec::new(),
+            pending: FixedSizeQueue::default(),
+            state: State::Busy([1, 2, 3, 4]),
+        };
+        assert_that!(record.values.push(42), eq true);
+        assert_that!(record.values.push(73), eq true);
+        assert_that!(record.pending.push(5), eq true);
+        assert_that!(publisher.send_copy(record), is_ok);
+
+        let sample = sut.receive().unwrap().unwrap();
+        assert_that!(sample.name, eq b"funky");
+        assert_that!(sample.values.len(), eq 2);
+        assert_that!(*sample.values.get(0).unwrap(), eq 42);
+        assert_that!(*sample.values.get(1).unwrap(), eq 73);
+        assert_that!(sample.pending.len(), eq 1);
+        assert_that!(sample.state, eq State::Busy([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn payload_with_phantom_data_can_be_sent<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            // the marked type does not have to be ShmSafe
+            .create::<Tagged<String>>()
+            .unwrap();
+
+        let publisher = service.publisher().create().unwrap();
+        let sut = service.subscriber().create().unwrap();
+
+        let value = Tagged {
+            value: 12,
+            _tag: PhantomData,
+        };
+        assert_that!(publisher.send_copy(value), is_ok);
+        assert_that!(sut.receive().unwrap().unwrap().value, eq 12);
+    }
+
+    #[test]
+    fn type_identifier_of_fixed_size_containers_contains_capacity<Sut: Service>() {
+        assert_that!(FixedSizeByteString::<16>::type_identifier(), eq "FixedSizeByteString<16>");
+        assert_that!(FixedSizeVec::<u64, 4>::type_identifier(), eq "FixedSizeVec<u64, 4>");
+        assert_that!(FixedSizeQueue::<u32, 2>::type_identifier(), eq "FixedSizeQueue<u32, 2>");
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
+
+mod process_local_payload {
+    use elkodon::service::{process_local, service_name::ServiceName, Service};
+    use elkodon::zero_copy_type::{PayloadFor, ZeroCopyType};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    #[derive(Debug, ZeroCopyType)]
+    #[zero_copy_type(identifier = "shm_safe_tests::Name")]
+    #[repr(C)]
+    struct Name {
+        value: String,
+    }
+
+    unsafe impl<'a> PayloadFor<process_local::Service<'a>> for Name {}
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"shm_safe_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn process_local_service_accepts_payload_which_is_not_shm_safe() {
+        let service_name = generate_name();
+        let service = process_local::Service::new(&service_name)
+            .pipeline()
+            .create::<Name>()
+            .unwrap();
+
+        let producer = service.producer().create().unwrap();
+        let sut = service.consumer().create().unwrap();
+
+        let name = Name {
+            value: "funky".to_string(),
+        };
+        assert_that!(producer.send_copy(name), is_ok);
+
+        let sample = sut.receive().unwrap().unwrap();
+        assert_that!(sample.value, eq "funky");
+    }
+}
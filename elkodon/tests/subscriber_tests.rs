@@ -1,10 +1,16 @@
 #[generic_tests::define]
 mod subscriber {
+    use std::time::{Duration, Instant};
+
+    use elkodon::port::subscriber::ReceiveError;
+    use elkodon::service::builder::publish_subscribe::PublishSubscribeOpenError;
     use elkodon::service::{service_name::ServiceName, Service};
     use elkodon_bb_container::semantic_string::*;
     use elkodon_bb_posix::unique_system_id::UniqueSystemId;
     use elkodon_bb_testing::assert_that;
 
+    const TIMEOUT: Duration = Duration::from_millis(50);
+
     fn generate_name() -> ServiceName {
         let mut service = ServiceName::new(b"service_tests_").unwrap();
         service
@@ -36,6 +42,110 @@ mod subscriber {
         assert_that!(sut.number_of_publishers(), eq 1);
     }
 
+    #[test]
+    fn timed_receive_fails_when_events_are_disabled<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(false)
+            .create::<u64>()
+            .unwrap();
+
+        let sut = service.subscriber().create().unwrap();
+        let result = sut.timed_receive(TIMEOUT);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq ReceiveError::EventsNotEnabled);
+    }
+
+    #[test]
+    fn timed_receive_returns_none_after_timeout<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(true)
+            .create::<u64>()
+            .unwrap();
+
+        let sut = service.subscriber().create().unwrap();
+        let start = Instant::now();
+        let result = sut.timed_receive(TIMEOUT);
+        assert_that!(start.elapsed(), ge TIMEOUT);
+        assert_that!(result, is_ok);
+        assert_that!(result.unwrap(), is_none);
+    }
+
+    #[test]
+    fn timed_receive_returns_sample_which_is_already_present<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(true)
+            .create::<u64>()
+            .unwrap();
+
+        let sut = service.subscriber().create().unwrap();
+        let publisher = service.publisher().create().unwrap();
+        assert_that!(publisher.send_copy(42), eq Ok(1));
+
+        let sample = sut.timed_receive(TIMEOUT).unwrap();
+        assert_that!(sample, is_some);
+        assert_that!(*sample.unwrap(), eq 42);
+    }
+
+    #[test]
+    fn blocking_receive_wakes_up_when_sample_is_sent<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(true)
+            .create::<u64>()
+            .unwrap();
+
+        let sut = service.subscriber().create().unwrap();
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let service = Sut::new(&service_name)
+                    .publish_subscribe()
+                    .open::<u64>()
+                    .unwrap();
+                let publisher = service.publisher().create().unwrap();
+                std::thread::sleep(TIMEOUT);
+                publisher.send_copy(1234).unwrap();
+            });
+
+            let start = Instant::now();
+            let sample = sut.blocking_receive();
+            assert_that!(start.elapsed(), ge TIMEOUT);
+            assert_that!(sample, is_ok);
+            assert_that!(*sample.unwrap(), eq 1234);
+        });
+    }
+
+    #[test]
+    fn open_fails_when_event_behavior_differs<Sut: Service>() {
+        let service_name = generate_name();
+        let _service = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(true)
+            .create::<u64>()
+            .unwrap();
+
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(false)
+            .open::<u64>();
+        assert_that!(sut, is_err);
+        assert_that!(sut.err().unwrap(), eq PublishSubscribeOpenError::IncompatibleEventBehavior);
+
+        let sut = Sut::new(&service_name)
+            .publish_subscribe()
+            .enable_events(true)
+            .open::<u64>();
+        assert_that!(sut, is_ok);
+        assert_that!(sut.unwrap().has_events(), eq true);
+    }
+
     #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
     mod zero_copy {}
 
@@ -0,0 +1,325 @@
+#[generic_tests::define]
+mod waitset {
+    use std::time::{Duration, Instant};
+
+    use elkodon::service::{service_name::ServiceName, Service};
+    use elkodon::waitset::{WaitSet, WaitSetAttachmentError};
+    use elkodon_bb_container::semantic_string::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_testing::assert_that;
+
+    const TIMEOUT: Duration = Duration::from_millis(50);
+
+    fn generate_name() -> ServiceName {
+        let mut service = ServiceName::new(b"waitset_tests_").unwrap();
+        service
+            .push_bytes(
+                UniqueSystemId::new()
+                    .unwrap()
+                    .value()
+                    .to_string()
+                    .as_bytes(),
+            )
+            .unwrap();
+        service
+    }
+
+    #[test]
+    fn attach_and_detach_works<Sut: Service>() {
+        let service_a = Sut::new(&generate_name()).event().create().unwrap();
+        let service_b = Sut::new(&generate_name()).event().create().unwrap();
+        let listener_a = service_a.listener().create().unwrap();
+        let listener_b = service_b.listener().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        assert_that!(sut, is_empty);
+
+        let guard_a = sut.attach_listener(&listener_a).unwrap();
+        let guard_b = sut.attach_listener(&listener_b).unwrap();
+        let guard_deadline = sut.attach_deadline(TIMEOUT).unwrap();
+        assert_that!(sut, len 3);
+        assert_that!(guard_a.id(), ne guard_b.id());
+        assert_that!(guard_a.id(), ne guard_deadline.id());
+
+        drop(guard_b);
+        assert_that!(sut, len 2);
+        drop(guard_deadline);
+        assert_that!(sut, len 1);
+        drop(guard_a);
+        assert_that!(sut, is_empty);
+    }
+
+    #[test]
+    fn try_wait_does_not_block_without_notifications<Sut: Service>() {
+        let service = Sut::new(&generate_name()).event().create().unwrap();
+        let listener = service.listener().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let _guard = sut.attach_listener(&listener).unwrap();
+
+        let mut number_of_fired_attachments = 0;
+        assert_that!(sut.try_wait(|_| number_of_fired_attachments += 1), is_ok);
+        assert_that!(number_of_fired_attachments, eq 0);
+    }
+
+    #[test]
+    fn wait_reports_notified_listeners<Sut: Service>() {
+        let service_a = Sut::new(&generate_name()).event().create().unwrap();
+        let service_b = Sut::new(&generate_name()).event().create().unwrap();
+        let service_c = Sut::new(&generate_name()).event().create().unwrap();
+        let listener_a = service_a.listener().create().unwrap();
+        let listener_b = service_b.listener().create().unwrap();
+        let listener_c = service_c.listener().create().unwrap();
+        let notifier_a = service_a.notifier().create().unwrap();
+        let notifier_c = service_c.notifier().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard_a = sut.attach_listener(&listener_a).unwrap();
+        let _guard_b = sut.attach_listener(&listener_b).unwrap();
+        let guard_c = sut.attach_listener(&listener_c).unwrap();
+
+        assert_that!(notifier_a.notify_with_custom_trigger_id(12), eq Ok(1));
+        assert_that!(notifier_c.notify_with_custom_trigger_id(34), eq Ok(1));
+
+        let mut fired_attachments = vec![];
+        assert_that!(
+            sut.timed_wait(|id| fired_attachments.push(id), TIMEOUT),
+            is_ok
+        );
+        assert_that!(fired_attachments, len 2);
+        assert_that!(fired_attachments, contains guard_a.id());
+        assert_that!(fired_attachments, contains guard_c.id());
+
+        // the notifications are not consumed by the waitset
+        let mut ids = vec![];
+        assert_that!(
+            listener_a.try_wait(|id| {
+                ids.push(id);
+                true
+            }),
+            is_ok
+        );
+        assert_that!(
+            listener_c.try_wait(|id| {
+                ids.push(id);
+                true
+            }),
+            is_ok
+        );
+        assert_that!(ids, eq vec![12, 34]);
+
+        let mut number_of_fired_attachments = 0;
+        assert_that!(sut.try_wait(|_| number_of_fired_attachments += 1), is_ok);
+        assert_that!(number_of_fired_attachments, eq 0);
+    }
+
+    #[test]
+    fn blocking_wait_wakes_up_on_notification_from_other_thread<Sut: Service>() {
+        let service_name = generate_name();
+        let service = Sut::new(&service_name).event().create().unwrap();
+        let listener = service.listener().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard = sut.attach_listener(&listener).unwrap();
+
+        let mut fired_attachments = vec![];
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let service = Sut::new(&service_name).event().open().unwrap();
+                let notifier = service.notifier().create().unwrap();
+                std::thread::sleep(TIMEOUT);
+                notifier.notify().unwrap();
+            });
+
+            assert_that!(sut.blocking_wait(|id| fired_attachments.push(id)), is_ok);
+        });
+
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+    }
+
+    #[test]
+    fn wait_reports_subscribers_with_new_samples<Sut: Service>() {
+        let service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .enable_events(true)
+            .create::<u64>()
+            .unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+        let publisher = service.publisher().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard = sut.attach_subscriber(&subscriber).unwrap();
+
+        assert_that!(publisher.send_copy(5), eq Ok(1));
+        assert_that!(publisher.send_copy(6), eq Ok(1));
+
+        let mut fired_attachments = vec![];
+        assert_that!(
+            sut.timed_wait(|id| fired_attachments.push(id), TIMEOUT),
+            is_ok
+        );
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+
+        assert_that!(*subscriber.receive().unwrap().unwrap(), eq 5);
+        assert_that!(*subscriber.receive().unwrap().unwrap(), eq 6);
+        assert_that!(subscriber.receive().unwrap(), is_none);
+
+        let mut number_of_fired_attachments = 0;
+        assert_that!(sut.try_wait(|_| number_of_fired_attachments += 1), is_ok);
+        assert_that!(number_of_fired_attachments, eq 0);
+    }
+
+    #[test]
+    fn subscriber_keeps_firing_until_every_sample_is_received<Sut: Service>() {
+        let service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .enable_events(true)
+            .subscriber_buffer_size(3)
+            .create::<u64>()
+            .unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+        let publisher = service.publisher().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard = sut.attach_subscriber(&subscriber).unwrap();
+
+        for value in 0..3 {
+            assert_that!(publisher.send_copy(value), eq Ok(1));
+        }
+
+        // only one sample is received per wake up
+        for value in 0..3 {
+            let mut fired_attachments = vec![];
+            assert_that!(
+                sut.timed_wait(|id| fired_attachments.push(id), TIMEOUT),
+                is_ok
+            );
+            assert_that!(fired_attachments, eq vec![guard.id()]);
+            assert_that!(*subscriber.receive().unwrap().unwrap(), eq value);
+        }
+
+        let mut fired_attachments = vec![];
+        assert_that!(
+            sut.timed_wait(|id| fired_attachments.push(id), TIMEOUT),
+            is_ok
+        );
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+        assert_that!(subscriber.receive().unwrap(), is_none);
+
+        let mut number_of_fired_attachments = 0;
+        assert_that!(sut.try_wait(|_| number_of_fired_attachments += 1), is_ok);
+        assert_that!(number_of_fired_attachments, eq 0);
+    }
+
+    #[test]
+    fn attaching_subscriber_without_events_fails<Sut: Service>() {
+        let service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .enable_events(false)
+            .create::<u64>()
+            .unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let result = sut.attach_subscriber(&subscriber);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq WaitSetAttachmentError::EventsNotEnabled);
+    }
+
+    #[test]
+    fn deadline_fires_periodically<Sut: Service>() {
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard = sut.attach_deadline(TIMEOUT).unwrap();
+
+        for _ in 0..2 {
+            let start = Instant::now();
+            let mut fired_attachments = vec![];
+            assert_that!(sut.blocking_wait(|id| fired_attachments.push(id)), is_ok);
+            assert_that!(start.elapsed(), ge TIMEOUT);
+            assert_that!(fired_attachments, eq vec![guard.id()]);
+        }
+    }
+
+    #[test]
+    fn subscriber_deadline_fires_periodically<Sut: Service>() {
+        let service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let _publisher = service.publisher().create().unwrap();
+        let subscriber = service.subscriber().deadline(TIMEOUT).create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let guard = sut.attach_subscriber_deadline(&subscriber).unwrap();
+
+        let start = Instant::now();
+        let mut fired_attachments = vec![];
+        assert_that!(sut.blocking_wait(|id| fired_attachments.push(id)), is_ok);
+        assert_that!(start.elapsed(), ge TIMEOUT);
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+        assert_that!(subscriber.check_deadlines(|_| {}).unwrap(), eq 1);
+    }
+
+    #[test]
+    fn subscriber_deadline_is_postponed_by_received_samples<Sut: Service>() {
+        let service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let publisher = service.publisher().create().unwrap();
+        let subscriber = service.subscriber().deadline(TIMEOUT * 2).create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let start = Instant::now();
+        let guard = sut.attach_subscriber_deadline(&subscriber).unwrap();
+
+        std::thread::sleep(TIMEOUT);
+        assert_that!(publisher.send_copy(1), is_ok);
+        assert_that!(subscriber.receive().unwrap(), is_some);
+
+        let mut fired_attachments = vec![];
+        assert_that!(sut.blocking_wait(|id| fired_attachments.push(id)), is_ok);
+        assert_that!(start.elapsed(), ge TIMEOUT * 3);
+        assert_that!(fired_attachments, eq vec![guard.id()]);
+        assert_that!(subscriber.check_deadlines(|_| {}).unwrap(), eq 1);
+    }
+
+    #[test]
+    fn attaching_subscriber_without_deadline_fails<Sut: Service>() {
+        let service = Sut::new(&generate_name())
+            .publish_subscribe()
+            .create::<u64>()
+            .unwrap();
+        let subscriber = service.subscriber().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let result = sut.attach_subscriber_deadline(&subscriber);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq WaitSetAttachmentError::DeadlineNotDefined);
+    }
+
+    #[test]
+    fn timed_wait_returns_after_timeout_without_notifications<Sut: Service>() {
+        let service = Sut::new(&generate_name()).event().create().unwrap();
+        let listener = service.listener().create().unwrap();
+
+        let sut = WaitSet::<Sut::Type<'_>>::new().unwrap();
+        let _guard = sut.attach_listener(&listener).unwrap();
+        let _deadline_guard = sut.attach_deadline(TIMEOUT * 100).unwrap();
+
+        let start = Instant::now();
+        let mut number_of_fired_attachments = 0;
+        assert_that!(
+            sut.timed_wait(|_| number_of_fired_attachments += 1, TIMEOUT),
+            is_ok
+        );
+        assert_that!(start.elapsed(), ge TIMEOUT);
+        assert_that!(number_of_fired_attachments, eq 0);
+    }
+
+    #[instantiate_tests(<elkodon::service::zero_copy::Service>)]
+    mod zero_copy {}
+
+    #[instantiate_tests(<elkodon::service::process_local::Service>)]
+    mod process_local {}
+}
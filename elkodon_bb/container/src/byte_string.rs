@@ -24,6 +24,7 @@ use std::{
     ops::{Deref, DerefMut},
 };
 
+use elkodon_bb_elementary::shm_safe::ShmSafe;
 use elkodon_bb_log::{fail, fatal_panic};
 
 /// Returns the length of a string
@@ -59,6 +60,7 @@ pub struct FixedSizeByteString<const CAPACITY: usize> {
 
 unsafe impl<const CAPACITY: usize> Send for FixedSizeByteString<CAPACITY> {}
 unsafe impl<const CAPACITY: usize> Sync for FixedSizeByteString<CAPACITY> {}
+unsafe impl<const CAPACITY: usize> ShmSafe for FixedSizeByteString<CAPACITY> {}
 
 impl<const CAPACITY: usize> Hash for FixedSizeByteString<CAPACITY> {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
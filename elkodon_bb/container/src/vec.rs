@@ -59,7 +59,7 @@ use std::{
 
 use elkodon_bb_elementary::{
     math::align_to, pointer_trait::PointerTrait, relocatable_container::RelocatableContainer,
-    relocatable_ptr::RelocatablePointer,
+    relocatable_ptr::RelocatablePointer, shm_safe::ShmSafe,
 };
 use elkodon_bb_log::{fail, fatal_panic};
 
@@ -322,6 +322,7 @@ impl<T, const CAPACITY: usize> Default for FixedSizeVec<T, CAPACITY> {
 
 unsafe impl<T: Send, const CAPACITY: usize> Send for FixedSizeVec<T, CAPACITY> {}
 unsafe impl<T: Sync, const CAPACITY: usize> Sync for FixedSizeVec<T, CAPACITY> {}
+unsafe impl<T: ShmSafe, const CAPACITY: usize> ShmSafe for FixedSizeVec<T, CAPACITY> {}
 
 impl<T, const CAPACITY: usize> FixedSizeVec<T, CAPACITY> {
     /// Creates a new vector.
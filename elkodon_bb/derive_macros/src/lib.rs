@@ -0,0 +1,255 @@
+//! Derive macros of elkodon.
+//!
+//! [`ZeroCopyType`] implements `elkodon::zero_copy_type::ZeroCopyType`. By default the type
+//! identifier is composed of the type name and the names and type identifiers of all fields so
+//! that two types with the same name but different fields are not considered compatible. It can
+//! be overridden with a stable identifier:
+//!
+//! ```ignore
+//! use elkodon::zero_copy_type::ZeroCopyType;
+//!
+//! #[derive(Debug, ZeroCopyType)]
+//! #[zero_copy_type(identifier = "my_app::Position")]
+//! #[repr(C)]
+//! struct Position {
+//!     x: f64,
+//!     y: f64,
+//! }
+//! ```
+//!
+//! [`ShmSafe`] implements `elkodon::zero_copy_type::ShmSafe` when every field of the type is
+//! `ShmSafe`, otherwise the compilation fails. It is required for every type which is
+//! transmitted via shared memory, see `elkodon::zero_copy_type::PayloadFor`:
+//!
+//! ```ignore
+//! use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+//!
+//! #[derive(Debug, ShmSafe, ZeroCopyType)]
+//! #[repr(C)]
+//! struct Position {
+//!     x: f64,
+//!     y: f64,
+//! }
+//! ```
+//!
+//! Both derives require `#[repr(C)]` or `#[repr(transparent)]`, enums may use a primitive
+//! representation like `#[repr(u8)]` instead. The layout of every other type is chosen by the
+//! compiler and can differ between the processes which share the type.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, LitStr, Type};
+
+const PRIMITIVE_REPRS: [&str; 10] = [
+    "u8", "u16", "u32", "u64", "usize", "i8", "i16", "i32", "i64", "isize",
+];
+
+/// Fails when the layout of the type is not defined by its `#[repr]` attribute.
+fn verify_repr(input: &DeriveInput, derive: &str) -> syn::Result<()> {
+    let mut has_defined_layout = false;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("repr") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            let is_primitive = PRIMITIVE_REPRS
+                .iter()
+                .any(|primitive| meta.path.is_ident(primitive));
+            if meta.path.is_ident("C")
+                || meta.path.is_ident("transparent")
+                || (is_primitive && matches!(input.data, Data::Enum(_)))
+            {
+                has_defined_layout = true;
+            }
+
+            // align(N) and packed(N)
+            if meta.input.peek(syn::token::Paren) {
+                let content;
+                syn::parenthesized!(content in meta.input);
+                content.parse::<TokenStream2>()?;
+            }
+            Ok(())
+        })?;
+    }
+
+    if !has_defined_layout {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            format!(
+                "{} requires #[repr(C)] or #[repr(transparent)] since the layout of the type must be the same in every process",
+                derive
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn custom_identifier(input: &DeriveInput) -> syn::Result<Option<LitStr>> {
+    let mut identifier = None;
+    for attr in &input.attrs {
+        if !attr.path().is_ident("zero_copy_type") {
+            continue;
+        }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("identifier") {
+                identifier = Some(meta.value()?.parse::<LitStr>()?);
+                Ok(())
+            } else {
+                Err(meta.error("unsupported zero_copy_type attribute, expected `identifier`"))
+            }
+        })?;
+    }
+
+    Ok(identifier)
+}
+
+fn append(value: &str) -> TokenStream2 {
+    quote! { identifier.push_str(#value); }
+}
+
+fn append_fields(fields: &Fields, field_types: &mut Vec<Type>) -> TokenStream2 {
+    let mut result = TokenStream2::new();
+    let (start, end) = match fields {
+        Fields::Named(_) => (" { ", " }"),
+        Fields::Unnamed(_) => ("(", ")"),
+        Fields::Unit => return result,
+    };
+
+    result.extend(append(start));
+    for (n, field) in fields.iter().enumerate() {
+        if n != 0 {
+            result.extend(append(", "));
+        }
+
+        if let Some(name) = &field.ident {
+            result.extend(append(&format!("{}: ", name)));
+        }
+
+        let ty = &field.ty;
+        result.extend(quote! {
+            identifier.push_str(
+                &<#ty as ::elkodon::zero_copy_type::ZeroCopyType>::type_identifier());
+        });
+        field_types.push(field.ty.clone());
+    }
+    result.extend(append(end));
+
+    result
+}
+
+/// Implements `elkodon::zero_copy_type::ZeroCopyType`, see the crate documentation.
+#[proc_macro_derive(ZeroCopyType, attributes(zero_copy_type))]
+pub fn zero_copy_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    if let Err(e) = verify_repr(&input, "ZeroCopyType") {
+        return e.to_compile_error().into();
+    }
+
+    let custom_identifier = match custom_identifier(&input) {
+        Ok(v) => v,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let mut field_types = vec![];
+    let body = match custom_identifier {
+        Some(identifier) => quote! { ::std::string::String::from(#identifier) },
+        None => {
+            let mut statements = append(&name.to_string());
+            match &input.data {
+                Data::Struct(data) => {
+                    statements.extend(append_fields(&data.fields, &mut field_types));
+                }
+                Data::Enum(data) => {
+                    statements.extend(append(" { "));
+                    for (n, variant) in data.variants.iter().enumerate() {
+                        if n != 0 {
+                            statements.extend(append(", "));
+                        }
+                        statements.extend(append(&variant.ident.to_string()));
+                        statements.extend(append_fields(&variant.fields, &mut field_types));
+                    }
+                    statements.extend(append(" }"));
+                }
+                Data::Union(_) => {
+                    return syn::Error::new_spanned(
+                        &input.ident,
+                        "ZeroCopyType cannot be derived for unions without a custom identifier",
+                    )
+                    .to_compile_error()
+                    .into();
+                }
+            }
+
+            quote! {
+                let mut identifier = ::std::string::String::new();
+                #statements
+                identifier
+            }
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ty in field_types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::elkodon::zero_copy_type::ZeroCopyType));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        impl #impl_generics ::elkodon::zero_copy_type::ZeroCopyType for #name #ty_generics
+            #where_clause
+        {
+            fn type_identifier() -> ::std::string::String {
+                #body
+            }
+        }
+    }
+    .into()
+}
+
+/// Implements `elkodon::zero_copy_type::ShmSafe`, see the crate documentation.
+#[proc_macro_derive(ShmSafe)]
+pub fn shm_safe(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    if let Err(e) = verify_repr(&input, "ShmSafe") {
+        return e.to_compile_error().into();
+    }
+
+    let field_types: Vec<Type> = match &input.data {
+        Data::Struct(data) => data.fields.iter().map(|f| f.ty.clone()).collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|f| f.ty.clone()))
+            .collect(),
+        Data::Union(data) => data.fields.named.iter().map(|f| f.ty.clone()).collect(),
+    };
+
+    // every field must be ShmSafe, a field which is not fails the compilation
+    let mut generics = input.generics.clone();
+    let where_clause = generics.make_where_clause();
+    for ty in field_types {
+        where_clause
+            .predicates
+            .push(parse_quote!(#ty: ::elkodon::zero_copy_type::ShmSafe));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    quote! {
+        unsafe impl #impl_generics ::elkodon::zero_copy_type::ShmSafe for #name #ty_generics
+            #where_clause
+        {
+        }
+    }
+    .into()
+}
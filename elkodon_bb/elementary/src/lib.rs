@@ -10,4 +10,5 @@ pub mod pointer_trait;
 pub mod relocatable_container;
 pub mod relocatable_ptr;
 pub mod scope_guard;
+pub mod shm_safe;
 pub mod unique_id;
@@ -0,0 +1,44 @@
+//! Marks types which can be shared between processes via shared memory. Such a type must be
+//! self-contained, it must not contain pointers, references or handles to process local
+//! resources like a [`Box`], a [`String`] or a [`Vec`], since they are invalid in every other
+//! process.
+//!
+//! # Example
+//!
+//! ```
+//! use elkodon_bb_elementary::shm_safe::ShmSafe;
+//!
+//! #[repr(C)]
+//! struct Position {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! // every field is ShmSafe
+//! unsafe impl ShmSafe for Position {}
+//! ```
+
+use std::marker::PhantomData;
+
+/// Marker for types which can be shared between processes via shared memory.
+///
+/// # Safety
+///
+///  * the type must not contain pointers, references or any other process local resource
+///  * all fields of the type must be [`ShmSafe`]
+///
+pub unsafe trait ShmSafe {}
+
+macro_rules! impl_shm_safe {
+    ($($t:ty),*) => {
+        $(unsafe impl ShmSafe for $t {})*
+    };
+}
+
+impl_shm_safe! {
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, char, ()
+}
+
+unsafe impl<T: ShmSafe, const N: usize> ShmSafe for [T; N] {}
+unsafe impl<T: ShmSafe> ShmSafe for [T] {}
+unsafe impl<T: ?Sized> ShmSafe for PhantomData<T> {}
@@ -242,6 +242,24 @@ impl<T: Copy + Debug> Container<T> {
     ///     before calling this method
     ///
     pub unsafe fn add(&self, value: T) -> Option<UniqueIndex<'_>> {
+        self.add_with(value, |_| ())
+    }
+
+    /// Adds a new element to the [`Container`] like [`Container::add()`] but calls `initialize`
+    /// with the acquired index before the element becomes visible in the [`ContainerState`].
+    /// Data which is stored alongside the container at the same index can be prepared there so
+    /// that everyone who sees the element also sees the prepared data.
+    ///
+    /// # Safety
+    ///
+    ///  * Ensure that the either [`Container::new()`] was used or [`Container::init()`] was used
+    ///     before calling this method
+    ///
+    pub unsafe fn add_with<F: FnOnce(u32)>(
+        &self,
+        value: T,
+        initialize: F,
+    ) -> Option<UniqueIndex<'_>> {
         self.verify_memory_initialization("add");
         match self
             .index_set
@@ -252,27 +270,45 @@ impl<T: Copy + Debug> Container<T> {
                     .store(false, Ordering::Relaxed);
             }) {
             Some(index) => {
-                unsafe {
-                    *(*self.data_ptr.as_ptr().offset(index.value() as isize)).get() =
-                        MaybeUninit::new(value)
-                };
-
-                //////////////////////////////////////
-                // SYNC POINT with reading data values
-                //////////////////////////////////////
-                unsafe {
-                    &*self
-                        .active_index_ptr
-                        .as_ptr()
-                        .offset(index.value() as isize)
-                }
-                .store(true, Ordering::Release);
+                initialize(index.value());
+                self.store_value(index.value(), value);
                 Some(index)
             }
             None => None,
         }
     }
 
+    /// Adds a new element to the [`Container`] and returns the raw index of the element. If there
+    /// is no more space available it returns [`None`]. In contrast to [`Container::add()`] the
+    /// element is not removed automatically, it stays in the container until
+    /// [`Container::remove_raw_index()`] is called.
+    ///
+    /// # Safety
+    ///
+    ///  * Ensure that the either [`Container::new()`] was used or [`Container::init()`] was used
+    ///     before calling this method
+    ///  * The element must be removed manually with [`Container::remove_raw_index()`] otherwise
+    ///     the index is leaked
+    ///
+    pub unsafe fn add_raw(&self, value: T) -> Option<u32> {
+        self.verify_memory_initialization("add_raw");
+        let index = self.index_set.acquire_raw_index()?;
+        self.store_value(index, value);
+        Some(index)
+    }
+
+    fn store_value(&self, index: u32, value: T) {
+        unsafe {
+            *(*self.data_ptr.as_ptr().offset(index as isize)).get() = MaybeUninit::new(value)
+        };
+
+        //////////////////////////////////////
+        // SYNC POINT with reading data values
+        //////////////////////////////////////
+        unsafe { &*self.active_index_ptr.as_ptr().offset(index as isize) }
+            .store(true, Ordering::Release);
+    }
+
     /// Useful in IPC context when an application holding the UniqueIndex has died.
     ///
     /// # Safety
@@ -430,6 +466,22 @@ impl<T: Copy + Debug, const CAPACITY: usize> FixedSizeContainer<T, CAPACITY> {
         unsafe { self.state.add(value) }
     }
 
+    /// Adds a new element to the [`FixedSizeContainer`], see [`Container::add_with()`].
+    pub fn add_with<F: FnOnce(u32)>(&self, value: T, initialize: F) -> Option<UniqueIndex<'_>> {
+        unsafe { self.state.add_with(value, initialize) }
+    }
+
+    /// Adds a new element to the [`FixedSizeContainer`] and returns the raw index of the element.
+    /// See [`Container::add_raw()`].
+    ///
+    /// # Safety
+    ///
+    ///  * The element must be removed manually with [`FixedSizeContainer::remove_raw_index()`]
+    ///    otherwise the index is leaked
+    pub unsafe fn add_raw(&self, value: T) -> Option<u32> {
+        self.state.add_raw(value)
+    }
+
     /// Useful in IPC context when an application holding the UniqueIndex has died.
     ///
     /// # Safety
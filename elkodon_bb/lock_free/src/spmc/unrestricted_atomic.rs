@@ -44,20 +44,50 @@ impl<'a, T: Copy> Producer<'a, T> {
 
 impl<'a, T: Copy> Drop for Producer<'a, T> {
     fn drop(&mut self) {
-        self.atomic.has_producer.store(true, Ordering::Relaxed);
+        unsafe { self.atomic.mgmt.release_producer() };
     }
 }
 
 unsafe impl<'a, T: Copy> Send for Producer<'a, T> {}
 unsafe impl<'a, T: Copy> Sync for Producer<'a, T> {}
 
+/// The type independent management part of the [`UnrestrictedAtomic`]. It is always stored at
+/// the beginning of the [`UnrestrictedAtomic`] so that it can be accessed in shared memory
+/// without knowing the underlying type.
+#[repr(C)]
+#[derive(Debug)]
+pub struct UnrestrictedAtomicMgmt {
+    write_cell: AtomicU64,
+    has_producer: AtomicBool,
+}
+
+impl UnrestrictedAtomicMgmt {
+    fn new() -> Self {
+        Self {
+            write_cell: AtomicU64::new(1),
+            has_producer: AtomicBool::new(true),
+        }
+    }
+
+    /// Releases the producer so that it can be acquired again with
+    /// [`UnrestrictedAtomic::acquire_producer()`].
+    ///
+    /// # Safety
+    ///
+    ///  * the current [`Producer`] must not be used anymore, for instance since the process that
+    ///    owned it has died
+    ///
+    pub unsafe fn release_producer(&self) {
+        self.has_producer.store(true, Ordering::Relaxed);
+    }
+}
+
 /// An atomic implementation where the underlying type has to by copyable but is otherwise
 /// unrestricted.
 #[repr(C)]
 pub struct UnrestrictedAtomic<T: Copy> {
-    write_cell: AtomicU64,
+    mgmt: UnrestrictedAtomicMgmt,
     data: [UnsafeCell<MaybeUninit<T>>; NUMBER_OF_CELLS],
-    has_producer: AtomicBool,
 }
 
 impl<T: Copy + Debug> Debug for UnrestrictedAtomic<T> {
@@ -66,9 +96,9 @@ impl<T: Copy + Debug> Debug for UnrestrictedAtomic<T> {
             f,
             "UnrestrictedAtomic<{}> {{ write_cell: {}, data: {:?}, has_producer: {} }}",
             std::any::type_name::<T>(),
-            self.write_cell.load(Ordering::Relaxed),
+            self.mgmt.write_cell.load(Ordering::Relaxed),
             self.load(),
-            self.has_producer.load(Ordering::Relaxed)
+            self.mgmt.has_producer.load(Ordering::Relaxed)
         )
     }
 }
@@ -80,8 +110,7 @@ impl<T: Copy> UnrestrictedAtomic<T> {
     /// Creates a new atomic containing the provided value.
     pub fn new(value: T) -> Self {
         Self {
-            has_producer: AtomicBool::new(true),
-            write_cell: AtomicU64::new(1),
+            mgmt: UnrestrictedAtomicMgmt::new(),
             data: [
                 UnsafeCell::new(MaybeUninit::new(value)),
                 UnsafeCell::new(MaybeUninit::uninit()),
@@ -91,17 +120,24 @@ impl<T: Copy> UnrestrictedAtomic<T> {
 
     /// Returns a producer if one is available otherwise [`None`].
     pub fn acquire_producer(&self) -> Option<Producer<'_, T>> {
-        match self
-            .has_producer
-            .compare_exchange(true, false, Ordering::Relaxed, Ordering::Relaxed)
-        {
+        match self.mgmt.has_producer.compare_exchange(
+            true,
+            false,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ) {
             Ok(_) => Some(Producer { atomic: self }),
             Err(_) => None,
         }
     }
 
+    /// Returns the type independent management part of the atomic.
+    pub fn mgmt(&self) -> &UnrestrictedAtomicMgmt {
+        &self.mgmt
+    }
+
     fn store(&self, new_value: T) {
-        let write_cell = self.write_cell.load(Ordering::Relaxed);
+        let write_cell = self.mgmt.write_cell.load(Ordering::Relaxed);
         unsafe {
             (*self.data[write_cell as usize % NUMBER_OF_CELLS].get())
                 .as_mut_ptr()
@@ -111,7 +147,7 @@ impl<T: Copy> UnrestrictedAtomic<T> {
         /////////////////////////
         // SYNC POINT - write
         /////////////////////////
-        self.write_cell.fetch_add(1, Ordering::Release);
+        self.mgmt.write_cell.fetch_add(1, Ordering::Release);
     }
 
     /// Loads the underlying value and returns a copy of it.
@@ -119,7 +155,7 @@ impl<T: Copy> UnrestrictedAtomic<T> {
         /////////////////////////
         // SYNC POINT - read
         /////////////////////////
-        let mut read_cell = self.write_cell.load(Ordering::Acquire) - 1;
+        let mut read_cell = self.mgmt.write_cell.load(Ordering::Acquire) - 1;
         let mut read_cell_update;
 
         let mut return_value;
@@ -133,7 +169,7 @@ impl<T: Copy> UnrestrictedAtomic<T> {
             /////////////////////////
             // SYNC POINT - read (for write while reading)
             /////////////////////////
-            read_cell_update = self.write_cell.load(Ordering::Acquire) - 1;
+            read_cell_update = self.mgmt.write_cell.load(Ordering::Acquire) - 1;
 
             if read_cell_update == read_cell {
                 break;
@@ -130,6 +130,32 @@ fn mpmc_container_add_and_unsafe_remove_elements_works() {
     }
 }
 
+#[test]
+fn mpmc_container_add_raw_and_remove_raw_index_works() {
+    let sut = FixedSizeContainer::<usize, CAPACITY>::new();
+    let mut indices = vec![];
+
+    for i in 0..CAPACITY {
+        let index = unsafe { sut.add_raw(i * 5 + 3) };
+        assert_that!(index, is_some);
+        indices.push(index.unwrap());
+    }
+    assert_that!(unsafe { sut.add_raw(0) }, is_none);
+
+    for index in indices.iter().step_by(2) {
+        unsafe { sut.remove_raw_index(*index) };
+    }
+
+    let state = sut.get_state();
+    let mut contained_values = vec![];
+    state.for_each(|_: u32, value: &usize| contained_values.push(*value));
+
+    assert_that!(contained_values, len CAPACITY / 2);
+    for value in contained_values {
+        assert_that!((value - 3) / 5 % 2, eq 1);
+    }
+}
+
 #[test]
 fn mpmc_container_state_not_updated_when_contents_do_not_change() {
     let sut = FixedSizeContainer::<usize, CAPACITY>::new();
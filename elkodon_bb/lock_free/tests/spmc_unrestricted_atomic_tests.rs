@@ -55,6 +55,21 @@ fn spmc_unrestricted_atomic_acquire_multiple_producer_fails() {
     assert_that!(p3, is_some);
 }
 
+#[test]
+fn spmc_unrestricted_atomic_release_producer_of_lost_producer_works() {
+    let _test_lock = TEST_LOCK.lock().unwrap();
+    let sut = UnrestrictedAtomic::<[u8; DATA_SIZE]>::new([0xff; DATA_SIZE]);
+
+    // simulates a producer whose owner died without releasing it
+    std::mem::forget(sut.acquire_producer());
+    assert_that!(sut.acquire_producer(), is_none);
+
+    unsafe { sut.mgmt().release_producer() };
+
+    let producer = sut.acquire_producer();
+    assert_that!(producer, is_some);
+}
+
 #[test]
 fn spmc_unrestricted_atomic_load_store_works() {
     let _test_lock = TEST_LOCK.lock().unwrap();
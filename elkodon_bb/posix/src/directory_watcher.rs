@@ -0,0 +1,190 @@
+//! Watches a directory and becomes readable whenever an entry of the directory was created,
+//! removed, renamed or modified. It is based on inotify and therefore only supported on linux,
+//! on every other platform the creation fails with [`DirectoryWatcherCreateError::NotSupported`].
+//!
+//! Since it is [`SynchronousMultiplexing`] it can be attached to a
+//! [`crate::file_descriptor_set::FileDescriptorSet`] together with other file descriptors.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use elkodon_bb_posix::directory_watcher::*;
+//! use elkodon_bb_system_types::path::Path;
+//! use elkodon_bb_container::semantic_string::SemanticString;
+//! use std::time::Duration;
+//!
+//! let watcher = DirectoryWatcher::new(&Path::new(b"/tmp").unwrap()).unwrap();
+//!
+//! if watcher.timed_wait(Duration::from_millis(100)).unwrap() {
+//!     println!("the contents of /tmp have changed");
+//! }
+//! ```
+
+use std::time::Duration;
+
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_log::{fail, fatal_panic};
+use elkodon_bb_system_types::path::Path;
+use elkodon_pal_posix::posix::{self, Errno};
+
+use crate::file_descriptor::{FileDescriptor, FileDescriptorBased};
+use crate::file_descriptor_set::{
+    FileDescriptorSet, FileDescriptorSetWaitError, FileEvent, SynchronousMultiplexing,
+};
+use crate::handle_errno;
+
+const EVENT_BUFFER_SIZE: usize = 4096;
+const WATCHED_EVENTS: u32 = posix::IN_CREATE
+    | posix::IN_DELETE
+    | posix::IN_MOVED_FROM
+    | posix::IN_MOVED_TO
+    | posix::IN_MODIFY
+    | posix::IN_CLOSE_WRITE
+    | posix::IN_ATTRIB;
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum DirectoryWatcherCreateError {
+    InsufficientPermissions,
+    DoesNotExist,
+    PerProcessFileHandleLimitReached,
+    SystemWideFileHandleLimitReached,
+    WatchLimitReached,
+    InsufficientMemory,
+    NotSupported,
+    UnknownError(i32),
+}
+
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum DirectoryWatcherWaitError {
+    Interrupt,
+    InternalError,
+    UnknownError(i32),
+}
+
+/// Watches the entries of a directory, see the [module level documentation](self).
+#[derive(Debug)]
+pub struct DirectoryWatcher {
+    path: Path,
+    file_descriptor: FileDescriptor,
+}
+
+impl DirectoryWatcher {
+    /// Starts watching the directory at the provided path.
+    pub fn new(path: &Path) -> Result<Self, DirectoryWatcherCreateError> {
+        let msg = format!("Unable to watch directory \"{}\"", path);
+        let origin = "DirectoryWatcher::new";
+
+        let fd = unsafe { posix::inotify_init1(posix::IN_NONBLOCK | posix::IN_CLOEXEC) };
+        if fd < 0 {
+            handle_errno!(DirectoryWatcherCreateError, from origin,
+                Errno::EMFILE => (PerProcessFileHandleLimitReached, "{} since the file descriptor or inotify instance limit was reached.", msg),
+                Errno::ENFILE => (SystemWideFileHandleLimitReached, "{} since the system-wide limit of file descriptors was reached.", msg),
+                Errno::ENOMEM => (InsufficientMemory, "{} due to insufficient memory.", msg),
+                Errno::ENOSYS => (NotSupported, "{} since it is not supported on this platform.", msg),
+                v => (UnknownError(v as i32), "{} since an unknown error occurred ({}).", msg, v)
+            );
+        }
+
+        let file_descriptor = match FileDescriptor::new(fd) {
+            Some(fd) => fd,
+            None => {
+                fatal_panic!(from origin,
+                    "This should never happen! {} since the inotify instance is not a valid file descriptor.", msg);
+            }
+        };
+
+        if unsafe {
+            posix::inotify_add_watch(
+                file_descriptor.native_handle(),
+                path.as_c_str(),
+                WATCHED_EVENTS,
+            )
+        } < 0
+        {
+            handle_errno!(DirectoryWatcherCreateError, from origin,
+                Errno::EACCES => (InsufficientPermissions, "{} due to insufficient permissions.", msg),
+                Errno::ENOENT => (DoesNotExist, "{} since the directory does not exist.", msg),
+                Errno::ENOSPC => (WatchLimitReached, "{} since the system-wide limit of inotify watches was reached.", msg),
+                Errno::ENOMEM => (InsufficientMemory, "{} due to insufficient memory.", msg),
+                v => (UnknownError(v as i32), "{} since an unknown error occurred ({}).", msg, v)
+            );
+        }
+
+        Ok(Self {
+            path: *path,
+            file_descriptor,
+        })
+    }
+
+    /// Returns the path of the watched directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Discards all pending changes without blocking and returns true when there were any.
+    pub fn try_wait(&self) -> Result<bool, DirectoryWatcherWaitError> {
+        let mut buffer = [0u8; EVENT_BUFFER_SIZE];
+        let mut has_changes = false;
+        let msg = "Unable to acquire the changes of the watched directory";
+
+        loop {
+            let bytes_read = unsafe {
+                posix::read(
+                    self.file_descriptor.native_handle(),
+                    buffer.as_mut_ptr() as *mut posix::void,
+                    buffer.len(),
+                )
+            };
+
+            if bytes_read > 0 {
+                has_changes = true;
+                continue;
+            }
+
+            if bytes_read == 0 || Errno::get() == Errno::EAGAIN {
+                return Ok(has_changes);
+            }
+
+            handle_errno!(DirectoryWatcherWaitError, from self,
+                Errno::EINTR => (Interrupt, "{} since an interrupt signal was received.", msg),
+                v => (UnknownError(v as i32), "{} since an unknown error occurred ({}).", msg, v)
+            );
+        }
+    }
+
+    /// Waits until the directory has changed or the timeout has passed. All pending changes
+    /// are discarded and true is returned when there were any.
+    pub fn timed_wait(&self, timeout: Duration) -> Result<bool, DirectoryWatcherWaitError> {
+        let msg = "Unable to wait for changes of the watched directory";
+        let fd_set = FileDescriptorSet::new();
+        let _guard = fatal_panic!(from self, when fd_set.add(self),
+                            "This should never happen! {} since the watcher could not be attached to a fd set.", msg);
+
+        match fd_set.timed_wait(timeout, FileEvent::Read, |_| ()) {
+            Ok(()) => self.try_wait(),
+            Err(FileDescriptorSetWaitError::Interrupt) => {
+                fail!(from self, with DirectoryWatcherWaitError::Interrupt,
+                    "{} since an interrupt signal was received.", msg);
+            }
+            Err(v) => {
+                fail!(from self, with DirectoryWatcherWaitError::InternalError,
+                    "{} since an unknown failure occurred in the underlying fd set ({:?}).", msg, v);
+            }
+        }
+    }
+
+    /// Waits until the directory has changed. All pending changes are discarded.
+    pub fn blocking_wait(&self) -> Result<(), DirectoryWatcherWaitError> {
+        const INFINITE_TIMEOUT: Duration = Duration::from_secs(3600 * 24 * 365);
+        while !self.timed_wait(INFINITE_TIMEOUT)? {}
+        Ok(())
+    }
+}
+
+impl FileDescriptorBased for DirectoryWatcher {
+    fn file_descriptor(&self) -> &FileDescriptor {
+        &self.file_descriptor
+    }
+}
+
+impl SynchronousMultiplexing for DirectoryWatcher {}
@@ -31,6 +31,7 @@ pub mod udp_socket;
 #[macro_use]
 pub mod handle_errno;
 pub mod directory;
+pub mod directory_watcher;
 pub mod file;
 pub mod file_descriptor;
 pub mod file_descriptor_set;
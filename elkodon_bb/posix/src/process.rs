@@ -141,9 +141,11 @@ impl Process {
         }
     }
 
-    /// Checks if the process is still alive
+    /// Checks if the process is still alive. A process which exists but which the caller is not
+    /// permitted to signal is also alive.
     pub fn is_alive(&self) -> bool {
         unsafe { posix::kill(self.pid.0, 0 as i32) == 0 }
+        || Errno::get() == Errno::EPERM
     }
 
     /// Returns the id of the process.
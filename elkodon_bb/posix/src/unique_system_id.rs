@@ -60,6 +60,14 @@ impl Display for UniqueSystemId {
     }
 }
 
+impl From<u128> for UniqueSystemId {
+    /// Restores an id from the value of [`UniqueSystemId::value()`], for instance after it was
+    /// stored in a file name.
+    fn from(value: u128) -> Self {
+        Self { value }
+    }
+}
+
 impl UniqueSystemId {
     /// Creates a new system wide unique id
     pub fn new() -> Result<Self, UniqueSystemIdCreationError> {
@@ -0,0 +1,85 @@
+#[cfg(target_os = "linux")]
+mod directory_watcher {
+    use std::time::{Duration, Instant};
+
+    use elkodon_bb_container::semantic_string::SemanticString;
+    use elkodon_bb_posix::config::*;
+    use elkodon_bb_posix::directory::*;
+    use elkodon_bb_posix::directory_watcher::*;
+    use elkodon_bb_posix::file::*;
+    use elkodon_bb_posix::unique_system_id::UniqueSystemId;
+    use elkodon_bb_system_types::file_name::FileName;
+    use elkodon_bb_system_types::file_path::FilePath;
+    use elkodon_bb_system_types::path::Path;
+    use elkodon_bb_testing::assert_that;
+
+    const TIMEOUT: Duration = Duration::from_millis(25);
+
+    fn generate_name(prefix: &[u8]) -> FileName {
+        let mut name = FileName::new(prefix).unwrap();
+        name.push_bytes(
+            UniqueSystemId::new()
+                .unwrap()
+                .value()
+                .to_string()
+                .as_bytes(),
+        )
+        .unwrap();
+        name
+    }
+
+    fn create_directory() -> Path {
+        let mut path = TEMP_DIRECTORY;
+        path.add_path_entry(&generate_name(b"directory_watcher_tests_"))
+            .unwrap();
+        Directory::create(&path, Permission::OWNER_ALL).unwrap();
+        path
+    }
+
+    #[test]
+    fn directory_watcher_reports_created_and_removed_files() {
+        let path = create_directory();
+        let sut = DirectoryWatcher::new(&path).unwrap();
+        assert_that!(*sut.path(), eq path);
+        assert_that!(sut.try_wait().unwrap(), eq false);
+
+        let file_path =
+            FilePath::from_path_and_file(&path, &generate_name(b"watched_file_")).unwrap();
+        let file = FileBuilder::new(&file_path)
+            .creation_mode(CreationMode::PurgeAndCreate)
+            .create()
+            .unwrap();
+        assert_that!(sut.try_wait().unwrap(), eq true);
+        assert_that!(sut.try_wait().unwrap(), eq false);
+
+        assert_that!(file.remove_self().unwrap(), eq true);
+        assert_that!(sut.timed_wait(TIMEOUT).unwrap(), eq true);
+        assert_that!(sut.try_wait().unwrap(), eq false);
+
+        drop(sut);
+        Directory::remove(&path).unwrap();
+    }
+
+    #[test]
+    fn directory_watcher_timed_wait_blocks_until_timeout_without_changes() {
+        let path = create_directory();
+        let sut = DirectoryWatcher::new(&path).unwrap();
+
+        let start = Instant::now();
+        assert_that!(sut.timed_wait(TIMEOUT).unwrap(), eq false);
+        assert_that!(start.elapsed(), ge TIMEOUT);
+
+        drop(sut);
+        Directory::remove(&path).unwrap();
+    }
+
+    #[test]
+    fn directory_watcher_fails_for_non_existing_directory() {
+        let mut path = TEMP_DIRECTORY;
+        path.add_path_entry(&generate_name(b"directory_watcher_tests_"))
+            .unwrap();
+
+        let sut = DirectoryWatcher::new(&path);
+        assert_that!(sut.err(), eq Some(DirectoryWatcherCreateError::DoesNotExist));
+    }
+}
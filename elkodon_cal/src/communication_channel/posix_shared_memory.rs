@@ -195,7 +195,10 @@ impl CommunicationChannelConnector<usize, Channel> for Connector {
             .try_open()
         {
             Ok(shared_memory) => Ok(Sender { shared_memory }),
+            // the receiver and the sender belong to the same service, therefore the storage is
+            // inaccessible only while the receiver still initializes it
             Err(DynamicStorageOpenError::DoesNotExist)
+            | Err(DynamicStorageOpenError::InsufficientPermissions)
             | Err(DynamicStorageOpenError::InitializationNotYetFinalized) => {
                 Err(CommunicationChannelOpenError::DoesNotExist)
             }
@@ -65,6 +65,7 @@ pub const DEFAULT_PATH_HINT: Path = TEMP_DIRECTORY;
 pub enum DynamicStorageCreateError {
     AlreadyExists,
     Creation,
+    InsufficientPermissions,
     Write,
     InitializationFailed,
     InternalError,
@@ -75,6 +76,7 @@ pub enum DynamicStorageCreateError {
 pub enum DynamicStorageOpenError {
     DoesNotExist,
     Open,
+    InsufficientPermissions,
     InitializationNotYetFinalized,
     InternalError,
 }
@@ -37,6 +37,7 @@ use std::marker::PhantomData;
 use std::ptr::NonNull;
 
 pub use crate::dynamic_storage::*;
+use crate::named_concept::{AccessSettings, AccessSettingsApplyError};
 use crate::static_storage::file::NamedConceptConfiguration;
 use crate::static_storage::file::NamedConceptRemoveError;
 use elkodon_bb_system_types::path::Path;
@@ -58,6 +59,7 @@ pub struct Builder<T: Debug> {
 pub struct Configuration {
     suffix: FileName,
     path: Path,
+    access_settings: AccessSettings,
 }
 
 impl Default for Configuration {
@@ -65,6 +67,7 @@ impl Default for Configuration {
         Self {
             path: DEFAULT_PATH_HINT,
             suffix: DEFAULT_SUFFIX,
+            access_settings: AccessSettings::default(),
         }
     }
 }
@@ -87,6 +90,11 @@ impl NamedConceptConfiguration for Configuration {
     fn get_path_hint(&self) -> &Path {
         &self.path
     }
+
+    fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.access_settings = value.clone();
+        self
+    }
 }
 
 impl<T: Send + Sync + Debug> NamedConceptBuilder<Storage<T>> for Builder<T> {
@@ -167,9 +175,10 @@ impl<T: Send + Sync + Debug> DynamicStorageBuilder<T, Storage<T>> for Builder<T>
                 "{} since the initialization of the underlying construct failed.", msg);
         }
 
-        fail!(from self, when shm.set_permission(FINAL_PERMISSIONS),
-            with DynamicStorageCreateError::Creation,
-            "{} since the final permissions {} could not be set.", msg, FINAL_PERMISSIONS);
+        fail!(from self, when self.config.access_settings.apply(&mut shm, FINAL_PERMISSIONS),
+            map AccessSettingsApplyError::InsufficientPermissions => DynamicStorageCreateError::InsufficientPermissions,
+            unmatched DynamicStorageCreateError::Creation,
+            "{} since the access settings {:?} could not be applied.", msg, self.config.access_settings);
 
         Ok(Storage {
             shm,
@@ -188,6 +197,9 @@ impl<T: Send + Sync + Debug> DynamicStorageBuilder<T, Storage<T>> for Builder<T>
             Err(DynamicStorageOpenError::InitializationNotYetFinalized) => {
                 fail!(from origin, with DynamicStorageOpenError::InitializationNotYetFinalized, "{} since it is not yet readable - most likely since it is not finalized.", msg);
             }
+            Err(DynamicStorageOpenError::InsufficientPermissions) => {
+                fail!(from origin, with DynamicStorageOpenError::InsufficientPermissions, "{} due to insufficient permissions or since it is still being initialized by its owner.", msg);
+            }
             Err(e) => Err(e),
             Ok(s) => Ok(s),
         }
@@ -205,7 +217,7 @@ impl<T: Send + Sync + Debug> DynamicStorageBuilder<T, Storage<T>> for Builder<T>
                 return Err(DynamicStorageOpenError::DoesNotExist);
             }
             Err(SharedMemoryCreationError::InsufficientPermissions) => {
-                return Err(DynamicStorageOpenError::InitializationNotYetFinalized);
+                return Err(DynamicStorageOpenError::InsufficientPermissions);
             }
             Err(_) => {
                 fail!(from self, with DynamicStorageOpenError::Open, "{} since the underlying shared memory could not be opened.", msg);
@@ -214,7 +226,8 @@ impl<T: Send + Sync + Debug> DynamicStorageBuilder<T, Storage<T>> for Builder<T>
 
         let permission = fail!(from self, when shm.permission(), with DynamicStorageOpenError::InternalError,
             "{} due to a failure while acquiring the current access permissions of the underlying shared memory.", msg);
-        if permission != FINAL_PERMISSIONS {
+        // the group and others may have additional permissions defined by the access settings
+        if permission & Permission::OWNER_ALL != FINAL_PERMISSIONS {
             return Err(DynamicStorageOpenError::InitializationNotYetFinalized);
         }
 
@@ -4,7 +4,9 @@ pub mod unix_datagram_socket;
 use std::{fmt::Debug, time::Duration};
 
 pub use crate::named_concept::{NamedConcept, NamedConceptBuilder, NamedConceptMgmt};
+pub use crate::reactor::{ReactorAttachError, ReactorCreateError, ReactorWaitError};
 use elkodon_bb_posix::config::TEMP_DIRECTORY;
+use elkodon_bb_posix::file_descriptor_set::SynchronousMultiplexing;
 pub use elkodon_bb_system_types::file_name::FileName;
 pub use elkodon_bb_system_types::path::Path;
 
@@ -65,9 +67,51 @@ pub trait ListenerBuilder<Id: TriggerId, T: Event<Id>>: NamedConceptBuilder<T> +
     fn create(self) -> Result<T::Listener, ListenerCreateError>;
 }
 
+/// Waits on multiple [`Listener`]s of the same [`Event`] concept at once. A [`Listener`] is
+/// attached as long as the returned guard lives and is identified in the wait callbacks by its
+/// [`ListenerReactor::attachment_id()`].
+pub trait ListenerReactor<Id: TriggerId, T: Event<Id>>: Sized + Debug {
+    type Guard<'reactor, 'listener>
+    where
+        Self: 'reactor;
+
+    fn new() -> Result<Self, ReactorCreateError>;
+    fn capacity() -> usize;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool;
+
+    /// Returns the id with which the [`Listener`] is reported in the wait callbacks.
+    fn attachment_id(listener: &T::Listener) -> usize;
+
+    fn attach<'reactor, 'listener>(
+        &'reactor self,
+        listener: &'listener T::Listener,
+    ) -> Result<Self::Guard<'reactor, 'listener>, ReactorAttachError>;
+
+    /// Attaches a construct which is based on a file descriptor, like a
+    /// [`elkodon_bb_posix::directory_watcher::DirectoryWatcher`]. It is reported in the wait
+    /// callbacks with its native file descriptor as attachment id. Reactors which are not based
+    /// on file descriptors fail with [`ReactorAttachError::NotSupported`].
+    fn attach_file_descriptor<'reactor, 'attachment, F: SynchronousMultiplexing + Debug>(
+        &'reactor self,
+        value: &'attachment F,
+    ) -> Result<Self::Guard<'reactor, 'attachment>, ReactorAttachError>;
+
+    /// Calls the provided callback with the attachment id of every [`Listener`] that has pending
+    /// events. The events themselves are not consumed.
+    fn try_wait<F: FnMut(usize)>(&self, fn_call: F) -> Result<(), ReactorWaitError>;
+    fn timed_wait<F: FnMut(usize)>(
+        &self,
+        fn_call: F,
+        timeout: Duration,
+    ) -> Result<(), ReactorWaitError>;
+    fn blocking_wait<F: FnMut(usize)>(&self, fn_call: F) -> Result<(), ReactorWaitError>;
+}
+
 pub trait Event<Id: TriggerId>: Sized + NamedConceptMgmt + Debug {
     type Notifier: Notifier<Id>;
     type NotifierBuilder: NotifierBuilder<Id, Self>;
     type Listener: Listener<Id>;
     type ListenerBuilder: ListenerBuilder<Id, Self>;
+    type Reactor: ListenerReactor<Id, Self>;
 }
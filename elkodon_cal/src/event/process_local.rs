@@ -1,5 +1,6 @@
 use std::{
     any::Any,
+    cell::RefCell,
     collections::HashMap,
     marker::PhantomData,
     sync::{
@@ -13,7 +14,9 @@ use crate::static_storage::file::NamedConceptConfiguration;
 use elkodon_bb_container::queue::FixedSizeQueue;
 use elkodon_bb_log::{fail, fatal_panic};
 use elkodon_bb_posix::{
-    condition_variable::{ConditionVariable, ConditionVariableBuilder, ConditionVariableData},
+    condition_variable::{
+        ConditionVariable, ConditionVariableBuilder, ConditionVariableData, MultiConditionVariable,
+    },
     mutex::{Mutex, MutexBuilder, MutexHandle},
 };
 pub use elkodon_bb_system_types::file_name::FileName;
@@ -22,6 +25,7 @@ use once_cell::sync::Lazy;
 use ouroboros::self_referencing;
 
 const DEFAULT_CAPACITY: usize = 2048;
+const REACTOR_CAPACITY: usize = 1024;
 
 #[self_referencing]
 #[derive(Debug)]
@@ -51,6 +55,21 @@ static PROCESS_LOCAL_STORAGE: Lazy<Mutex<HashMap<FilePath, StorageEntry>>> = Laz
     result.unwrap()
 });
 
+// every notification is additionally signaled here so that a reactor can wait on multiple
+// listeners at once, the value counts the notifications
+static PROCESS_LOCAL_REACTOR_MTX_HANDLE: Lazy<MutexHandle<u64>> = Lazy::new(|| MutexHandle::new());
+static PROCESS_LOCAL_REACTOR_SIGNAL: Lazy<MultiConditionVariable<u64>> = Lazy::new(|| {
+    let result = ConditionVariableBuilder::new()
+        .is_interprocess_capable(false)
+        .create_multi_condition_variable(0, &PROCESS_LOCAL_REACTOR_MTX_HANDLE);
+
+    if result.is_err() {
+        fatal_panic!(from "PROCESS_LOCAL_REACTOR_SIGNAL", "Failed to create global reactor signal");
+    }
+
+    result.unwrap()
+});
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct Configuration {
     suffix: FileName,
@@ -118,10 +137,27 @@ impl<Id: crate::event::TriggerId + 'static> Notifier<Id> for Duplex<Id> {
                     "{} since the underlying queue is full.", msg);
         }
 
+        if PROCESS_LOCAL_REACTOR_SIGNAL
+            .modify_notify_all(|counter| *counter = counter.wrapping_add(1))
+            .is_err()
+        {
+            fail!(from self, with NotifierNotifyError::InternalFailure,
+                    "{} since the attached reactors could not be signaled.", msg);
+        }
+
         Ok(())
     }
 }
 
+impl<Id: crate::event::TriggerId + 'static> Duplex<Id> {
+    fn has_pending_events(management: &Management<Id>) -> bool {
+        matches!(
+            management.borrow_cvar().timed_wait_while(Duration::ZERO),
+            Ok(Some(_))
+        )
+    }
+}
+
 impl<Id: crate::event::TriggerId + 'static> Listener<Id> for Duplex<Id> {
     fn try_wait(&self) -> Result<Option<Id>, ListenerWaitError> {
         let msg = "Failed to try_wait";
@@ -293,6 +329,7 @@ impl<Id: crate::event::TriggerId + Copy + 'static> crate::event::Event<Id> for E
     type Listener = Duplex<Id>;
     type NotifierBuilder = Builder<Id>;
     type ListenerBuilder = Builder<Id>;
+    type Reactor = Reactor<Id>;
 }
 
 impl<Id: crate::event::TriggerId + Copy> NamedConceptMgmt for Event<Id> {
@@ -350,3 +387,145 @@ impl<Id: crate::event::TriggerId + Copy> NamedConceptMgmt for Event<Id> {
         Ok(guard.unwrap().remove(&storage_name).is_some())
     }
 }
+
+/// Detaches the [`Duplex`] from the [`Reactor`] when it goes out of scope.
+#[derive(Debug)]
+pub struct ReactorGuard<'reactor, 'listener, Id: crate::event::TriggerId + 'static> {
+    reactor: &'reactor Reactor<Id>,
+    listener: &'listener Duplex<Id>,
+}
+
+impl<Id: crate::event::TriggerId + 'static> Drop for ReactorGuard<'_, '_, Id> {
+    fn drop(&mut self) {
+        let id = Reactor::<Id>::id_of(&self.listener.management);
+        self.reactor
+            .attachments
+            .borrow_mut()
+            .retain(|(attachment_id, _)| *attachment_id != id);
+    }
+}
+
+/// Waits on multiple [`Duplex`] listeners at once. Since they are not based on file descriptors
+/// every notification wakes up all reactors which then check their attachments for pending
+/// events.
+#[derive(Debug)]
+pub struct Reactor<Id: crate::event::TriggerId + 'static> {
+    attachments: RefCell<Vec<(usize, Arc<Management<Id>>)>>,
+}
+
+impl<Id: crate::event::TriggerId + 'static> Reactor<Id> {
+    fn id_of(management: &Arc<Management<Id>>) -> usize {
+        Arc::as_ptr(management) as usize
+    }
+
+    fn collect_pending(&self, pending: &mut Vec<usize>) -> bool {
+        pending.clear();
+        for (id, management) in self.attachments.borrow().iter() {
+            if Duplex::has_pending_events(management) {
+                pending.push(*id);
+            }
+        }
+        !pending.is_empty()
+    }
+
+    fn wait<F: FnMut(usize)>(
+        &self,
+        mut fn_call: F,
+        timeout: Option<Duration>,
+    ) -> Result<(), ReactorWaitError> {
+        let msg = "Unable to wait on event::process_local::Reactor";
+        let mut pending = vec![];
+        let has_failed = match timeout {
+            Some(timeout) => PROCESS_LOCAL_REACTOR_SIGNAL
+                .timed_wait_while(timeout, |_| self.collect_pending(&mut pending))
+                .is_err(),
+            None => PROCESS_LOCAL_REACTOR_SIGNAL
+                .wait_while(|_| self.collect_pending(&mut pending))
+                .is_err(),
+        };
+
+        if has_failed {
+            fail!(from self, with ReactorWaitError::UnknownError,
+                "{} due to a failure in the underlying condition variable.", msg);
+        }
+
+        for id in pending {
+            fn_call(id);
+        }
+
+        Ok(())
+    }
+}
+
+impl<Id: crate::event::TriggerId + Copy + 'static> ListenerReactor<Id, Event<Id>> for Reactor<Id> {
+    type Guard<'reactor, 'listener>
+        = ReactorGuard<'reactor, 'listener, Id>
+    where
+        Self: 'reactor;
+
+    fn new() -> Result<Self, ReactorCreateError> {
+        Ok(Self {
+            attachments: RefCell::new(vec![]),
+        })
+    }
+
+    fn capacity() -> usize {
+        REACTOR_CAPACITY
+    }
+
+    fn len(&self) -> usize {
+        self.attachments.borrow().len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.attachments.borrow().is_empty()
+    }
+
+    fn attachment_id(listener: &Duplex<Id>) -> usize {
+        Self::id_of(&listener.management)
+    }
+
+    fn attach<'reactor, 'listener>(
+        &'reactor self,
+        listener: &'listener Duplex<Id>,
+    ) -> Result<Self::Guard<'reactor, 'listener>, ReactorAttachError> {
+        if self.len() == Self::capacity() {
+            fail!(from self, with ReactorAttachError::CapacityExceeded,
+                "Unable to attach {:?} to reactor since the capacity of {} was exceeded.",
+                listener, Self::capacity());
+        }
+
+        self.attachments
+            .borrow_mut()
+            .push((Self::attachment_id(listener), listener.management.clone()));
+
+        Ok(ReactorGuard {
+            reactor: self,
+            listener,
+        })
+    }
+
+    fn attach_file_descriptor<'reactor, 'attachment, F: SynchronousMultiplexing + Debug>(
+        &'reactor self,
+        value: &'attachment F,
+    ) -> Result<Self::Guard<'reactor, 'attachment>, ReactorAttachError> {
+        fail!(from self, with ReactorAttachError::NotSupported,
+            "Unable to attach {:?} to reactor since it is not based on file descriptors.", value);
+    }
+
+    fn try_wait<F: FnMut(usize)>(&self, fn_call: F) -> Result<(), ReactorWaitError> {
+        self.wait(fn_call, Some(Duration::ZERO))
+    }
+
+    fn timed_wait<F: FnMut(usize)>(
+        &self,
+        fn_call: F,
+        timeout: Duration,
+    ) -> Result<(), ReactorWaitError> {
+        self.wait(fn_call, Some(timeout))
+    }
+
+    fn blocking_wait<F: FnMut(usize)>(&self, fn_call: F) -> Result<(), ReactorWaitError> {
+        self.wait(fn_call, None)
+    }
+}
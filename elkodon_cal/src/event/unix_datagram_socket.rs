@@ -1,10 +1,14 @@
 use std::{marker::PhantomData, mem::MaybeUninit};
 
 pub use crate::event::*;
+use crate::named_concept::AccessSettings;
+use crate::reactor::{Reactor as _, ReactorBuilder as _};
 use crate::static_storage::file::NamedConceptConfiguration;
 use elkodon_bb_log::fail;
 use elkodon_bb_posix::{
-    file_descriptor::FileDescriptorBased, file_descriptor_set::SynchronousMultiplexing,
+    file_descriptor::FileDescriptorBased,
+    file_descriptor_set::{FileDescriptorSetGuard, SynchronousMultiplexing},
+    permission::Permission,
     unix_datagram_socket::*,
 };
 pub use elkodon_bb_system_types::file_name::FileName;
@@ -13,6 +17,7 @@ pub use elkodon_bb_system_types::file_name::FileName;
 pub struct Configuration {
     suffix: FileName,
     path: Path,
+    permission: Permission,
 }
 
 impl Default for Configuration {
@@ -20,6 +25,7 @@ impl Default for Configuration {
         Self {
             path: DEFAULT_PATH_HINT,
             suffix: DEFAULT_SUFFIX,
+            permission: Permission::OWNER_ALL,
         }
     }
 }
@@ -42,6 +48,13 @@ impl NamedConceptConfiguration for Configuration {
     fn get_path_hint(&self) -> &Path {
         &self.path
     }
+
+    /// The ownership and the access control list of a socket cannot be changed after it was
+    /// bound, therefore only the permission of the [`AccessSettings`] is applied.
+    fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.permission = value.permission_for(Permission::OWNER_ALL);
+        self
+    }
 }
 
 impl From<Configuration> for crate::communication_channel::unix_datagram::Configuration {
@@ -87,6 +100,7 @@ impl<Id: crate::event::TriggerId + Copy> crate::event::Event<Id> for Event<Id> {
     type Listener = Listener<Id>;
     type NotifierBuilder = NotifierBuilder<Id>;
     type ListenerBuilder = ListenerBuilder<Id>;
+    type Reactor = Reactor<Id>;
 }
 
 #[derive(Debug)]
@@ -279,6 +293,7 @@ impl<Id: crate::event::TriggerId + Copy> crate::event::ListenerBuilder<Id, Event
         let full_name = self.config.path_for(&self.name);
         match UnixDatagramReceiverBuilder::new(&full_name)
             .creation_mode(CreationMode::CreateExclusive)
+            .permission(self.config.permission)
             .create()
         {
             Ok(r) => Ok(Listener {
@@ -303,3 +318,77 @@ impl<Id: crate::event::TriggerId + Copy> crate::event::ListenerBuilder<Id, Event
         }
     }
 }
+
+/// Waits on multiple [`Listener`]s at once with the [`crate::reactor::posix_select::Reactor`].
+#[derive(Debug)]
+pub struct Reactor<Id: crate::event::TriggerId + Copy> {
+    reactor: crate::reactor::posix_select::Reactor,
+    _data: PhantomData<Id>,
+}
+
+impl<Id: crate::event::TriggerId + Copy> ListenerReactor<Id, Event<Id>> for Reactor<Id> {
+    type Guard<'reactor, 'listener>
+        = FileDescriptorSetGuard<'reactor, 'listener>
+    where
+        Self: 'reactor;
+
+    fn new() -> Result<Self, ReactorCreateError> {
+        Ok(Self {
+            reactor: fail!(from "event::unix_datagram_socket::Reactor::new()",
+                    when crate::reactor::posix_select::ReactorBuilder::new().create(),
+                    "Unable to create reactor since the underlying posix_select::Reactor could not be created."),
+            _data: PhantomData,
+        })
+    }
+
+    fn capacity() -> usize {
+        crate::reactor::posix_select::Reactor::capacity()
+    }
+
+    fn len(&self) -> usize {
+        self.reactor.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.reactor.is_empty()
+    }
+
+    fn attachment_id(listener: &Listener<Id>) -> usize {
+        unsafe { listener.file_descriptor().native_handle() as usize }
+    }
+
+    fn attach<'reactor, 'listener>(
+        &'reactor self,
+        listener: &'listener Listener<Id>,
+    ) -> Result<Self::Guard<'reactor, 'listener>, ReactorAttachError> {
+        self.reactor.attach(listener)
+    }
+
+    fn attach_file_descriptor<'reactor, 'attachment, F: SynchronousMultiplexing + Debug>(
+        &'reactor self,
+        value: &'attachment F,
+    ) -> Result<Self::Guard<'reactor, 'attachment>, ReactorAttachError> {
+        self.reactor.attach(value)
+    }
+
+    fn try_wait<F: FnMut(usize)>(&self, mut fn_call: F) -> Result<(), ReactorWaitError> {
+        self.reactor
+            .try_wait(|fd| fn_call(unsafe { fd.native_handle() as usize }))
+    }
+
+    fn timed_wait<F: FnMut(usize)>(
+        &self,
+        mut fn_call: F,
+        timeout: std::time::Duration,
+    ) -> Result<(), ReactorWaitError> {
+        self.reactor.timed_wait(
+            |fd| fn_call(unsafe { fd.native_handle() as usize }),
+            timeout,
+        )
+    }
+
+    fn blocking_wait<F: FnMut(usize)>(&self, mut fn_call: F) -> Result<(), ReactorWaitError> {
+        self.reactor
+            .blocking_wait(|fd| fn_call(unsafe { fd.native_handle() as usize }))
+    }
+}
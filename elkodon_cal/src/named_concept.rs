@@ -1,7 +1,15 @@
 use std::fmt::Debug;
 
 use elkodon_bb_container::semantic_string::SemanticString;
-use elkodon_bb_log::fatal_panic;
+use elkodon_bb_log::{fail, fatal_panic};
+use elkodon_bb_posix::access_control_list::{
+    AccessControlList, AccessControlListApplyError, AccessControlListCreationError, Acl,
+    AclPermission,
+};
+use elkodon_bb_posix::file::{FileSetOwnerError, FileSetPermissionError};
+use elkodon_bb_posix::file_descriptor::FileDescriptorManagement;
+use elkodon_bb_posix::ownership::Ownership;
+use elkodon_bb_posix::permission::Permission;
 pub use elkodon_bb_system_types::file_name::FileName;
 pub use elkodon_bb_system_types::file_path::FilePath;
 pub use elkodon_bb_system_types::path::Path;
@@ -26,6 +34,173 @@ pub enum NamedConceptListError {
     InternalError,
 }
 
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum AccessSettingsApplyError {
+    InsufficientPermissions,
+    InternalError,
+}
+
+/// An additional entry of the POSIX access control list of the underlying resources.
+#[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
+pub enum AclEntry {
+    User(u32, AclPermission),
+    Group(u32, AclPermission),
+}
+
+/// Defines the owner of the underlying resources of a [`NamedConcept`] and which rights the
+/// group, others and additional users and groups have. Non-owners never get more rights than the
+/// owner has. Concepts which are not represented in the file system ignore the settings.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct AccessSettings {
+    ownership: Option<Ownership>,
+    permission: Permission,
+    acl_entries: Vec<AclEntry>,
+}
+
+impl AccessSettings {
+    /// Sets the owning user and group. Changing the owning user requires elevated privileges.
+    pub fn ownership(mut self, value: Ownership) -> Self {
+        self.ownership = Some(value);
+        self
+    }
+
+    /// Sets the permissions of the group and others, the owner permissions are ignored.
+    pub fn permission(mut self, value: Permission) -> Self {
+        self.permission = value & (Permission::GROUP_ALL | Permission::OTHERS_ALL);
+        self
+    }
+
+    /// Adds an entry to the access control list.
+    pub fn acl_entry(mut self, value: AclEntry) -> Self {
+        self.acl_entries.push(value);
+        self
+    }
+
+    pub fn get_ownership(&self) -> Option<Ownership> {
+        self.ownership
+    }
+
+    pub fn get_permission(&self) -> Permission {
+        self.permission
+    }
+
+    pub fn get_acl_entries(&self) -> &[AclEntry] {
+        &self.acl_entries
+    }
+
+    /// Returns the full permission of a resource whose owner has `owner_permission`.
+    pub fn permission_for(&self, owner_permission: Permission) -> Permission {
+        let owner_permission = owner_permission & Permission::OWNER_ALL;
+        let group_permission = Permission::from_bits_truncate(owner_permission.bits() >> 3);
+        let others_permission = Permission::from_bits_truncate(owner_permission.bits() >> 6);
+
+        owner_permission
+            | (self.permission & group_permission)
+            | (self.permission & others_permission)
+    }
+
+    /// Applies the ownership, the permission for a resource whose owner has
+    /// `owner_permission` and the access control list entries to `resource`.
+    pub fn apply<T: FileDescriptorManagement>(
+        &self,
+        resource: &mut T,
+        owner_permission: Permission,
+    ) -> Result<(), AccessSettingsApplyError> {
+        let msg = "Unable to apply the access settings";
+        if let Some(ownership) = self.ownership {
+            fail!(from self, when resource.set_ownership(ownership),
+                map FileSetOwnerError::InsufficientPermissions => AccessSettingsApplyError::InsufficientPermissions,
+                unmatched AccessSettingsApplyError::InternalError,
+                "{} since the ownership could not be changed to {:?}.", msg, ownership);
+        }
+
+        let permission = self.permission_for(owner_permission);
+        fail!(from self, when resource.set_permission(permission),
+            map FileSetPermissionError::InsufficientPermissions => AccessSettingsApplyError::InsufficientPermissions,
+            unmatched AccessSettingsApplyError::InternalError,
+            "{} since the permission could not be changed to {}.", msg, permission);
+
+        if self.acl_entries.is_empty() {
+            return Ok(());
+        }
+
+        let mut acl = fail!(from self, when resource.access_control_list(),
+            with AccessSettingsApplyError::InternalError,
+            "{} since the access control list could not be acquired.", msg);
+
+        fail!(from self, when self.add_acl_entries(&mut acl, owner_permission),
+            with AccessSettingsApplyError::InternalError,
+            "{} since the access control list entries could not be added.", msg);
+
+        fail!(from self, when resource.set_access_control_list(&acl),
+            map AccessControlListApplyError::InsufficientPermissions => AccessSettingsApplyError::InsufficientPermissions,
+            unmatched AccessSettingsApplyError::InternalError,
+            "{} since the access control list could not be applied.", msg);
+
+        Ok(())
+    }
+
+    fn add_acl_entries(
+        &self,
+        acl: &mut AccessControlList,
+        owner_permission: Permission,
+    ) -> Result<(), AccessControlListCreationError> {
+        for entry in &self.acl_entries {
+            match *entry {
+                AclEntry::User(uid, rights) => {
+                    acl.add_user(uid, restrict(rights, owner_permission))?
+                }
+                AclEntry::Group(gid, rights) => {
+                    acl.add_group(gid, restrict(rights, owner_permission))?
+                }
+            }
+        }
+
+        // the entries are already restricted to the rights of the owner, therefore the mask
+        // must not restrict them any further
+        acl.set(
+            Acl::MaxAccessRightsForNonOwners,
+            AclPermission::ReadWriteExecute,
+        )
+    }
+}
+
+/// Removes all rights from `rights` which the owner with `owner_permission` does not have.
+fn restrict(rights: AclPermission, owner_permission: Permission) -> AclPermission {
+    let read = matches!(
+        rights,
+        AclPermission::Read
+            | AclPermission::ReadWrite
+            | AclPermission::ReadExecute
+            | AclPermission::ReadWriteExecute
+    ) && owner_permission.contains(Permission::OWNER_READ);
+    let write = matches!(
+        rights,
+        AclPermission::Write
+            | AclPermission::ReadWrite
+            | AclPermission::WriteExecute
+            | AclPermission::ReadWriteExecute
+    ) && owner_permission.contains(Permission::OWNER_WRITE);
+    let execute = matches!(
+        rights,
+        AclPermission::Execute
+            | AclPermission::ReadExecute
+            | AclPermission::WriteExecute
+            | AclPermission::ReadWriteExecute
+    ) && owner_permission.contains(Permission::OWNER_EXEC);
+
+    match (read, write, execute) {
+        (false, false, false) => AclPermission::None,
+        (true, false, false) => AclPermission::Read,
+        (false, true, false) => AclPermission::Write,
+        (false, false, true) => AclPermission::Execute,
+        (true, true, false) => AclPermission::ReadWrite,
+        (true, false, true) => AclPermission::ReadExecute,
+        (false, true, true) => AclPermission::WriteExecute,
+        (true, true, true) => AclPermission::ReadWriteExecute,
+    }
+}
+
 /// Every [`NamedConcept`] must have a custom configuration that at least allows the user to define
 /// a custom [`NamedConceptConfiguration::suffix()`] for all file names that are transparent during
 /// usage as well as a [`NamedConceptConfiguration::path_hint()`] that can be ignored if the
@@ -45,6 +220,12 @@ pub trait NamedConceptConfiguration: Default + Clone + Debug {
     /// Returns the configurations path hint.
     fn get_path_hint(&self) -> &Path;
 
+    /// Defines who besides the owner can access the underlying resources. Concepts which are
+    /// not represented in the file system ignore the settings.
+    fn access_settings(self, _value: &AccessSettings) -> Self {
+        self
+    }
+
     /// Returns the full path for a given value under the given configuration.
     fn path_for(&self, value: &FileName) -> FilePath {
         let mut path = *self.get_path_hint();
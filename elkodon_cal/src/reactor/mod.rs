@@ -14,6 +14,7 @@ pub enum ReactorCreateError {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ReactorAttachError {
     CapacityExceeded,
+    NotSupported,
     UnknownError(i32),
 }
 
@@ -18,6 +18,20 @@ impl Serialize for Cdr {
         )
     }
 
+    fn serialized_size<T: serde::Serialize>(value: &T) -> Result<usize, SerializeError> {
+        Ok(cdr::calc_serialized_size(value) as usize)
+    }
+
+    fn serialize_into<T: serde::Serialize, W: std::io::Write>(
+        writer: W,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        Ok(
+            fail!(from "Cdr::serialize_into", when cdr::serialize_into::<_, _, _, CdrBe>(writer, value, Infinite),
+                with SerializeError::InternalError, "Failed to serialize object" ),
+        )
+    }
+
     fn deserialize<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, DeserializeError> {
         Ok(
             fail!(from "Cdr::deserialize", when cdr::deserialize::<T>(bytes),
@@ -2,6 +2,10 @@
 //!  * [`Serialize::serialize()`] - serialize a given object
 //!  * [`Serialize::deserialize()`] - deserialize a given byte reference into the source object
 //!
+//! An object can also be serialized into a preallocated buffer with
+//! [`Serialize::serialize_into()`] whose required size is provided by
+//! [`Serialize::serialized_size()`].
+//!
 //! # Example
 //!
 //! ```
@@ -47,6 +51,24 @@ pub trait Serialize {
     /// Serializes a value
     fn serialize<T: serde::Serialize>(value: &T) -> Result<Vec<u8>, SerializeError>;
 
+    /// Returns the number of bytes [`Serialize::serialize_into()`] writes for the value. The
+    /// default implementation serializes the value.
+    fn serialized_size<T: serde::Serialize>(value: &T) -> Result<usize, SerializeError> {
+        Ok(Self::serialize(value)?.len())
+    }
+
+    /// Serializes a value into the writer. The default implementation serializes the value
+    /// into a temporary buffer and copies it.
+    fn serialize_into<T: serde::Serialize, W: std::io::Write>(
+        mut writer: W,
+        value: &T,
+    ) -> Result<(), SerializeError> {
+        let bytes = Self::serialize(value)?;
+        writer
+            .write_all(&bytes)
+            .map_err(|_| SerializeError::InternalError)
+    }
+
     /// Deserialize a value from a given byte slice
     fn deserialize<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, DeserializeError>;
 }
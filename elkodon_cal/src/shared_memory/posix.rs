@@ -13,6 +13,7 @@ use elkodon_bb_posix::unix_datagram_socket::CreationMode;
 use elkodon_bb_system_types::file_name::FileName;
 use elkodon_bb_system_types::path::Path;
 
+use crate::named_concept::{AccessSettings, AccessSettingsApplyError};
 use crate::static_storage::file::{
     NamedConcept, NamedConceptBuilder, NamedConceptConfiguration, NamedConceptMgmt,
     NamedConceptRemoveError,
@@ -25,6 +26,7 @@ pub struct Configuration {
     pub zero_memory: bool,
     path: Path,
     suffix: FileName,
+    access_settings: AccessSettings,
 }
 
 impl Default for Configuration {
@@ -35,6 +37,7 @@ impl Default for Configuration {
             zero_memory: true,
             path: DEFAULT_PATH_HINT,
             suffix: DEFAULT_SUFFIX,
+            access_settings: AccessSettings::default(),
         }
     }
 }
@@ -57,6 +60,11 @@ impl NamedConceptConfiguration for Configuration {
     fn get_path_hint(&self) -> &Path {
         &self.path
     }
+
+    fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.access_settings = value.clone();
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -179,10 +187,10 @@ impl<Allocator: ShmAllocator + Debug>
                 with SharedMemoryCreateError::InternalError,
                 "{} since the management memory for the allocator could not be initialized.", msg);
 
-        if let Err(v) = shm.set_permission(self.config.permission) {
-            fail!(from self, with SharedMemoryCreateError::InternalError,
-                    "{} since the configured shared memory permission could not be applied ({:?}).", msg,v);
-        }
+        fail!(from self, when self.config.access_settings.apply(&mut shm, self.config.permission),
+                map AccessSettingsApplyError::InsufficientPermissions => SharedMemoryCreateError::InsufficientPermissions,
+                unmatched SharedMemoryCreateError::InternalError,
+                "{} since the configured shared memory permission and access settings could not be applied.", msg);
 
         Ok(Memory::<Allocator> {
             shared_memory: shm,
@@ -225,7 +233,7 @@ impl<Allocator: ShmAllocator + Debug>
                         with SharedMemoryOpenError::InternalError,
                         "{} due to a failure while reading the shared memory permissions.", msg);
 
-        if permissions == Permission::OWNER_WRITE {
+        if permissions & Permission::OWNER_ALL == Permission::OWNER_WRITE {
             fail!(from self, with SharedMemoryOpenError::InternalError,
                     "{} since the creation of the shared memory is not yet finished.", msg);
         }
@@ -41,11 +41,18 @@ use elkodon_bb_posix::{
 
 const FINAL_PERMISSIONS: Permission = Permission::OWNER_READ;
 
+/// The group and others may have additional permissions defined by the [`AccessSettings`],
+/// therefore only the owner permissions signal if the storage is initialized.
+fn is_initialized(permission: Permission) -> bool {
+    permission & Permission::OWNER_ALL == FINAL_PERMISSIONS
+}
+
 /// The custom configuration of [``].
 #[derive(Clone, Debug)]
 pub struct Configuration {
     path: Path,
     suffix: FileName,
+    access_settings: AccessSettings,
 }
 
 impl Default for Configuration {
@@ -53,6 +60,7 @@ impl Default for Configuration {
         Configuration {
             path: DEFAULT_PATH_HINT,
             suffix: DEFAULT_SUFFIX,
+            access_settings: AccessSettings::default(),
         }
     }
 }
@@ -75,6 +83,11 @@ impl crate::named_concept::NamedConceptConfiguration for Configuration {
     fn get_path_hint(&self) -> &Path {
         &self.path
     }
+
+    fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.access_settings = value.clone();
+        self
+    }
 }
 
 impl crate::static_storage::StaticStorageConfiguration for Configuration {}
@@ -105,10 +118,11 @@ impl StaticStorageLocked<Storage> for Locked {
                 msg, contents.len(), bytes_written);
         }
 
-        fail!(from self, when self.static_storage.file.set_permission(FINAL_PERMISSIONS),
-                map FileSetPermissionError::InsufficientPermissions => StaticStorageUnlockError::InsufficientPermissions,
+        let access_settings = &self.static_storage.config.access_settings;
+        fail!(from self, when access_settings.apply(&mut self.static_storage.file, FINAL_PERMISSIONS),
+                map AccessSettingsApplyError::InsufficientPermissions => StaticStorageUnlockError::InsufficientPermissions,
                 unmatched StaticStorageUnlockError::InternalError,
-                "{} due to a failure while updating the permissions to {}.", msg, FINAL_PERMISSIONS);
+                "{} due to a failure while applying the access settings {:?}.", msg, access_settings);
 
         self.static_storage.len = contents.len() as u64;
 
@@ -184,8 +198,7 @@ impl crate::named_concept::NamedConceptMgmt for Storage {
         let mut result = vec![];
         for entry in &entries {
             let metadata = entry.metadata();
-            if metadata.file_type() == FileType::File && metadata.permission() == FINAL_PERMISSIONS
-            {
+            if metadata.file_type() == FileType::File && is_initialized(metadata.permission()) {
                 if let Some(entry_name) = config.extract_name_from_file(entry.name()) {
                     result.push(entry_name);
                 }
@@ -214,6 +227,11 @@ impl crate::named_concept::NamedConceptMgmt for Storage {
         };
 
         let file = FileBuilder::new(&adjusted_path).open_existing(AccessMode::Read);
+        if let Err(FileOpenError::InsufficientPermissions) = file {
+            fail!(from origin, with NamedConceptDoesExistError::InsufficientPermissions,
+                "{} since the file could not be opened for reading due to insufficient permissions.", msg);
+        }
+
         if file.is_err() {
             fail!(from origin, with NamedConceptDoesExistError::UnderlyingResourcesCorrupted,
                 "{} since the file could not be opened for reading ({:?}), is static storage in a corrupted state?", msg, file.err().unwrap() );
@@ -228,7 +246,7 @@ impl crate::named_concept::NamedConceptMgmt for Storage {
         }
         let metadata = metadata.unwrap();
 
-        if metadata.file_type() == FileType::File && metadata.permission() == FINAL_PERMISSIONS {
+        if metadata.file_type() == FileType::File && is_initialized(metadata.permission()) {
             return Ok(true);
         }
 
@@ -249,6 +267,10 @@ impl crate::static_storage::StaticStorage for Storage {
         self.has_ownership = true
     }
 
+    fn directory(config: &Configuration) -> Option<Path> {
+        Some(config.path)
+    }
+
     fn len(&self) -> u64 {
         self.len
     }
@@ -352,14 +374,15 @@ impl crate::static_storage::StaticStorageBuilder<Storage> for Builder {
 
         let file = fail!(from origin,
             when FileBuilder::new(&self.config.path_for(&self.storage_name)).open_existing(AccessMode::Read),
-            with StaticStorageOpenError::DoesNotExist,
+            map FileOpenError::InsufficientPermissions => StaticStorageOpenError::InsufficientPermissions,
+            unmatched StaticStorageOpenError::DoesNotExist,
             "{} due to a failure while opening the file.", msg);
 
         let metadata = fail!(from origin,
             when file.metadata(), with StaticStorageOpenError::Read,
             "{} due to a failure while reading the files metadata.", msg);
 
-        if metadata.permission() != FINAL_PERMISSIONS {
+        if !is_initialized(metadata.permission()) {
             fail!(from origin, with StaticStorageOpenError::IsLocked,
                 "{} since the static storage is still being created (in locked state), try later.", msg);
         }
@@ -33,6 +33,7 @@ pub enum StaticStorageCreateError {
 #[derive(Debug, Clone, Copy, Eq, Hash, PartialEq)]
 pub enum StaticStorageOpenError {
     DoesNotExist,
+    InsufficientPermissions,
     Read,
     IsLocked,
     InternalError,
@@ -115,4 +116,12 @@ pub trait StaticStorage: Debug + Sized + NamedConceptMgmt + NamedConcept {
     /// Acquires the ownership of the static storage. If the object goes out of scope the
     /// underlying resources are removed.
     fn acquire_ownership(&mut self);
+
+    /// Returns the directory which contains the static storages of the provided configuration
+    /// or [`None`] when they do not reside in the file system. It can be watched with a
+    /// [`elkodon_bb_posix::directory_watcher::DirectoryWatcher`] to get notified whenever a
+    /// static storage is created or removed.
+    fn directory(_config: &<Self as NamedConceptMgmt>::Configuration) -> Option<Path> {
+        None
+    }
 }
@@ -2,6 +2,7 @@ pub mod posix_shared_memory;
 pub mod process_local;
 
 use std::fmt::Debug;
+use std::time::Duration;
 
 pub use crate::shared_memory::PointerOffset;
 use crate::static_storage::file::{NamedConcept, NamedConceptBuilder, NamedConceptMgmt};
@@ -33,6 +34,11 @@ pub enum ZeroCopyReceiveError {
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ZeroCopyReclaimError {}
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZeroCopyAcquireUnreceivedError {
+    ReceiverIsStillConnected,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ZeroCopyReleaseError {
     RetrieveBufferFull,
@@ -70,7 +76,28 @@ pub trait ZeroCopySender: Debug + ZeroCopyPortDetails + NamedConcept {
     fn blocking_send(&self, ptr: PointerOffset)
         -> Result<Option<PointerOffset>, ZeroCopySendError>;
 
+    /// Blocks until the receive buffer has space or the timeout has passed. Fails with
+    /// [`ZeroCopySendError::ReceiveBufferFull`] when the buffer is still full after the timeout.
+    fn timed_send(
+        &self,
+        ptr: PointerOffset,
+        timeout: Duration,
+    ) -> Result<Option<PointerOffset>, ZeroCopySendError>;
+
     fn reclaim(&self) -> Result<Option<PointerOffset>, ZeroCopyReclaimError>;
+
+    /// Acquires a sample that was sent but never received. Since the receive channel supports
+    /// only one consumer it fails as long as a receiver is connected.
+    fn acquire_unreceived(&self) -> Result<Option<PointerOffset>, ZeroCopyAcquireUnreceivedError>;
+
+    /// Detaches a receiver which died without disconnecting so that its unreceived samples can
+    /// be acquired with [`ZeroCopySender::acquire_unreceived()`].
+    ///
+    /// # Safety
+    ///
+    ///  * the receiver must be dead, e.g. its process terminated, otherwise it may receive a
+    ///    sample that was already acquired by the sender
+    unsafe fn detach_dead_receiver(&self);
 }
 
 pub trait ZeroCopyReceiver: Debug + ZeroCopyPortDetails + NamedConcept {
@@ -21,10 +21,11 @@ use elkodon_bb_posix::shared_memory::{SharedMemory, SharedMemoryBuilder};
 
 const MAX_CREATION_DURATION: Duration = Duration::from_millis(10);
 
-#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Configuration {
     suffix: FileName,
     path_hint: Path,
+    access_settings: AccessSettings,
 }
 
 impl Default for Configuration {
@@ -32,6 +33,7 @@ impl Default for Configuration {
         Self {
             suffix: DEFAULT_SUFFIX,
             path_hint: DEFAULT_PATH_HINT,
+            access_settings: AccessSettings::default(),
         }
     }
 }
@@ -54,6 +56,11 @@ impl NamedConceptConfiguration for Configuration {
     fn get_path_hint(&self) -> &Path {
         &self.path_hint
     }
+
+    fn access_settings(mut self, value: &AccessSettings) -> Self {
+        self.access_settings = value.clone();
+        self
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -213,8 +220,8 @@ impl Builder {
                 fatal_panic!(from self, when unsafe { (*mgmt_ptr).retrieve_channel.init(&allocator) },
                             "{} since the retrieve channel allocation failed. - This is an implementation bug!", msg);
 
-                fail!(from self, when shm.set_permission(Permission::OWNER_ALL),
-                        with ZeroCopyCreationError::InternalError, "{} since the permission could not be updated.", msg);
+                fail!(from self, when self.config.access_settings.apply(&mut shm, Permission::OWNER_ALL),
+                        with ZeroCopyCreationError::InternalError, "{} since the access settings could not be applied.", msg);
                 shm.release_ownership();
             }
             false => {
@@ -445,12 +452,49 @@ impl ZeroCopySender for Sender {
         self.try_send(ptr)
     }
 
+    fn timed_send(
+        &self,
+        ptr: PointerOffset,
+        timeout: Duration,
+    ) -> Result<Option<PointerOffset>, ZeroCopySendError> {
+        if !self.mgmt().enable_safe_overflow {
+            AdaptiveWaitBuilder::new()
+                .create()
+                .unwrap()
+                .timed_wait_while(
+                    || -> Result<bool, ()> { Ok(self.mgmt().receive_channel.is_full()) },
+                    timeout,
+                )
+                .unwrap();
+        }
+
+        self.try_send(ptr)
+    }
+
     fn reclaim(&self) -> Result<Option<PointerOffset>, ZeroCopyReclaimError> {
         match unsafe { self.mgmt().retrieve_channel.pop() } {
             None => Ok(None),
             Some(v) => Ok(Some(PointerOffset::new(v))),
         }
     }
+
+    fn acquire_unreceived(&self) -> Result<Option<PointerOffset>, ZeroCopyAcquireUnreceivedError> {
+        if self.mgmt().state.load(Ordering::Relaxed) & State::Receiver.value() != 0 {
+            fail!(from self, with ZeroCopyAcquireUnreceivedError::ReceiverIsStillConnected,
+                "Unable to acquire unreceived sample since a receiver is still connected.");
+        }
+
+        match unsafe { self.mgmt().receive_channel.pop() } {
+            None => Ok(None),
+            Some(v) => Ok(Some(PointerOffset::new(v))),
+        }
+    }
+
+    unsafe fn detach_dead_receiver(&self) {
+        self.mgmt()
+            .state
+            .fetch_and(!State::Receiver.value(), Ordering::Relaxed);
+    }
 }
 
 #[derive(Debug)]
@@ -16,6 +16,7 @@ use std::{
         atomic::{AtomicU8, Ordering},
         Arc,
     },
+    time::Duration,
 };
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
@@ -326,12 +327,49 @@ impl ZeroCopySender for Sender {
         self.try_send(ptr)
     }
 
+    fn timed_send(
+        &self,
+        ptr: PointerOffset,
+        timeout: Duration,
+    ) -> Result<Option<PointerOffset>, ZeroCopySendError> {
+        if !self.mgmt.enable_safe_overflow {
+            AdaptiveWaitBuilder::new()
+                .create()
+                .unwrap()
+                .timed_wait_while(
+                    || -> Result<bool, ()> { Ok(self.mgmt.receive_channel.is_full()) },
+                    timeout,
+                )
+                .unwrap();
+        }
+
+        self.try_send(ptr)
+    }
+
     fn reclaim(&self) -> Result<Option<PointerOffset>, ZeroCopyReclaimError> {
         match unsafe { self.mgmt.retrieve_channel.pop() } {
             None => Ok(None),
             Some(v) => Ok(Some(PointerOffset::new(v))),
         }
     }
+
+    fn acquire_unreceived(&self) -> Result<Option<PointerOffset>, ZeroCopyAcquireUnreceivedError> {
+        if self.mgmt.state.load(Ordering::Relaxed) & State::Receiver as u8 != 0 {
+            fail!(from self, with ZeroCopyAcquireUnreceivedError::ReceiverIsStillConnected,
+                "Unable to acquire unreceived sample since a receiver is still connected.");
+        }
+
+        match unsafe { self.mgmt.receive_channel.pop() } {
+            None => Ok(None),
+            Some(v) => Ok(Some(PointerOffset::new(v))),
+        }
+    }
+
+    unsafe fn detach_dead_receiver(&self) {
+        self.mgmt
+            .state
+            .fetch_and(!(State::Receiver as u8), Ordering::Relaxed);
+    }
 }
 
 #[derive(Debug)]
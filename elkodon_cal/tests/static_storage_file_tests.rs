@@ -3,6 +3,7 @@ use elkodon_bb_container::semantic_string::*;
 use elkodon_bb_posix::config::*;
 use elkodon_bb_posix::directory::Directory;
 use elkodon_bb_posix::file::*;
+use elkodon_bb_posix::file_descriptor::FileDescriptorManagement;
 use elkodon_bb_posix::unique_system_id::UniqueSystemId;
 use elkodon_bb_system_types::file_name::FileName;
 use elkodon_bb_system_types::file_path::FilePath;
@@ -133,3 +134,30 @@ fn static_storage_file_custom_path_and_suffix_list_storage_works() {
         File::remove(file).unwrap();
     }
 }
+
+#[test]
+fn static_storage_file_applies_access_settings() {
+    let storage_name = generate_name();
+    let content = "restricted content".to_string();
+    let config = Configuration::default().access_settings(
+        &AccessSettings::default().permission(Permission::GROUP_ALL | Permission::OTHERS_READ),
+    );
+
+    let _storage_guard = Builder::new(&storage_name)
+        .config(&config)
+        .create(content.as_bytes())
+        .unwrap();
+
+    // the storage is read-only, therefore the group does not get more than read access
+    let file = FileBuilder::new(&config.path_for(&storage_name))
+        .open_existing(AccessMode::Read)
+        .unwrap();
+    assert_that!(
+        file.metadata().unwrap().permission(), eq
+        Permission::OWNER_READ | Permission::GROUP_READ | Permission::OTHERS_READ
+    );
+
+    assert_that!(Storage::does_exist_cfg(&storage_name, &config), eq Ok(true));
+    assert_that!(Storage::list_cfg(&config).unwrap(), contains storage_name);
+    assert_that!(Builder::new(&storage_name).config(&config).open(), is_ok);
+}
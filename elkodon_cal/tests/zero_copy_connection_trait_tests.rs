@@ -206,6 +206,55 @@ mod zero_copy_connection {
         assert_that!(retrieval, is_none);
     }
 
+    #[test]
+    fn acquire_unreceived_samples_works_only_without_receiver<Sut: ZeroCopyConnection>() {
+        let name = generate_name();
+
+        let sut_sender = Sut::Builder::new(&name).create_sender().unwrap();
+        let sut_receiver = Sut::Builder::new(&name).create_receiver().unwrap();
+
+        assert_that!(sut_sender.try_send(PointerOffset::new(123)), is_ok);
+        assert_that!(sut_sender.try_send(PointerOffset::new(456)), is_ok);
+
+        let result = sut_sender.acquire_unreceived();
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq ZeroCopyAcquireUnreceivedError::ReceiverIsStillConnected);
+
+        let sample = sut_receiver.receive().unwrap();
+        assert_that!(sample.unwrap().value(), eq 123);
+        assert_that!(sut_receiver.release(sample.unwrap()), is_ok);
+        drop(sut_receiver);
+
+        let sample = sut_sender.acquire_unreceived().unwrap();
+        assert_that!(sample, is_some);
+        assert_that!(sample.unwrap().value(), eq 456);
+        assert_that!(sut_sender.acquire_unreceived().unwrap(), is_none);
+    }
+
+    #[test]
+    fn acquire_unreceived_samples_works_after_detaching_dead_receiver<Sut: ZeroCopyConnection>() {
+        let name = generate_name();
+
+        let sut_sender = Sut::Builder::new(&name).create_sender().unwrap();
+        let sut_receiver = Sut::Builder::new(&name).create_receiver().unwrap();
+
+        assert_that!(sut_sender.try_send(PointerOffset::new(123)), is_ok);
+        assert_that!(sut_sender.try_send(PointerOffset::new(456)), is_ok);
+        assert_that!(sut_receiver.receive().unwrap(), is_some);
+
+        // a dead receiver never disconnects
+        std::mem::forget(sut_receiver);
+        assert_that!(sut_sender.is_connected(), eq true);
+
+        unsafe { sut_sender.detach_dead_receiver() };
+        assert_that!(sut_sender.is_connected(), eq false);
+
+        let sample = sut_sender.acquire_unreceived().unwrap();
+        assert_that!(sample, is_some);
+        assert_that!(sample.unwrap().value(), eq 456);
+        assert_that!(sut_sender.acquire_unreceived().unwrap(), is_none);
+    }
+
     #[test]
     fn send_until_buffer_is_full_works<Sut: ZeroCopyConnection>() {
         let name = generate_name();
@@ -398,6 +447,60 @@ mod zero_copy_connection {
         });
     }
 
+    #[test]
+    fn timed_send_blocks_until_receiver_makes_space<Sut: ZeroCopyConnection>() {
+        let name = generate_name();
+
+        let sut_sender = Sut::Builder::new(&name)
+            .buffer_size(1)
+            .create_sender()
+            .unwrap();
+
+        let handle = BarrierHandle::new();
+        let barrier = BarrierBuilder::new(2).create(&handle).unwrap();
+
+        std::thread::scope(|s| {
+            s.spawn(|| {
+                let sut_receiver = Sut::Builder::new(&name)
+                    .buffer_size(1)
+                    .create_receiver()
+                    .unwrap();
+
+                barrier.wait();
+                std::thread::sleep(TIMEOUT);
+                let sample = sut_receiver.receive().unwrap().unwrap();
+                assert_that!(sample.value(), eq 7789);
+            });
+
+            barrier.wait();
+            let now = Instant::now();
+            assert_that!(sut_sender.try_send(PointerOffset::new(7789)), is_ok);
+            assert_that!(
+                sut_sender.timed_send(PointerOffset::new(227789), TIMEOUT * 100),
+                is_ok
+            );
+            assert_that!(now.elapsed(), ge TIMEOUT);
+        });
+    }
+
+    #[test]
+    fn timed_send_fails_after_timeout_when_buffer_is_full<Sut: ZeroCopyConnection>() {
+        let name = generate_name();
+
+        let sut_sender = Sut::Builder::new(&name)
+            .buffer_size(1)
+            .create_sender()
+            .unwrap();
+
+        assert_that!(sut_sender.try_send(PointerOffset::new(1)), is_ok);
+
+        let now = Instant::now();
+        let result = sut_sender.timed_send(PointerOffset::new(2), TIMEOUT);
+        assert_that!(now.elapsed(), ge TIMEOUT);
+        assert_that!(result, is_err);
+        assert_that!(result.err().unwrap(), eq ZeroCopySendError::ReceiveBufferFull);
+    }
+
     #[test]
     fn list_connections_works<Sut: ZeroCopyConnection>() {
         let mut sut_names = vec![];
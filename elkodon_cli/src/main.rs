@@ -0,0 +1,407 @@
+//! Command line tool to inspect and administrate the services of the system.
+//!
+//! ```console
+//! elkodon list --pattern event
+//! elkodon nodes --cleanup
+//! elkodon --format json show my/service
+//! elkodon remove --force my/service
+//! elkodon notify my/event 12
+//! elkodon --config my_config.toml config validate
+//! ```
+
+use clap::{Parser, Subcommand, ValueEnum};
+use elkodon::global_config::Config;
+use elkodon::node::{Node, NodeView};
+use elkodon::service::static_config::{MessagingPattern, StaticConfig};
+use elkodon::service::{service_name::ServiceName, zero_copy, ConnectedPorts, Details, Service};
+use elkodon_bb_container::semantic_string::SemanticString;
+use elkodon_bb_system_types::file_path::FilePath;
+use serde_json::json;
+
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct CliArgs {
+    /// The config file, when it is not set the config is loaded from the system and user
+    /// config files and the environment
+    #[clap(short, long)]
+    config: Option<String>,
+
+    #[clap(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    format: OutputFormat,
+
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Lists all services
+    List {
+        #[clap(short, long, value_enum)]
+        pattern: Option<Pattern>,
+    },
+    /// Shows the static config and the connected ports of a service
+    Show { service: String },
+    /// Lists all nodes together with their process and whether it is alive
+    Nodes {
+        /// Removes the nodes whose process is dead
+        #[clap(long)]
+        cleanup: bool,
+    },
+    /// Removes a service whose owners are dead
+    Remove {
+        service: String,
+        /// Removes the service even when it is still in use
+        #[clap(long)]
+        force: bool,
+    },
+    /// Notifies the listeners of an event service
+    Notify { service: String, trigger_id: u64 },
+    /// Shows or validates the config
+    #[clap(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Shows the config which is used by the services
+    Show,
+    /// Reports unknown keys and impossible combinations of values in the config
+    Validate,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Pattern {
+    PublishSubscribe,
+    Event,
+    RequestResponse,
+    Blackboard,
+    Pipeline,
+}
+
+impl Pattern {
+    fn of(messaging_pattern: &MessagingPattern) -> Self {
+        match messaging_pattern {
+            MessagingPattern::PublishSubscribe(_) => Pattern::PublishSubscribe,
+            MessagingPattern::Event(_) => Pattern::Event,
+            MessagingPattern::RequestResponse(_) => Pattern::RequestResponse,
+            MessagingPattern::Blackboard(_) => Pattern::Blackboard,
+            MessagingPattern::Pipeline(_) => Pattern::Pipeline,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Pattern::PublishSubscribe => "publish_subscribe",
+            Pattern::Event => "event",
+            Pattern::RequestResponse => "request_response",
+            Pattern::Blackboard => "blackboard",
+            Pattern::Pipeline => "pipeline",
+        }
+    }
+}
+
+fn main() {
+    let args = CliArgs::parse();
+
+    let result = match &args.command {
+        Command::Config(ConfigCommand::Validate) => validate_config(&args),
+        command => setup_config(&args).and_then(|_| match command {
+            Command::List { pattern } => list(&args, *pattern),
+            Command::Show { service } => show(&args, service),
+            Command::Nodes { cleanup } => nodes(&args, *cleanup),
+            Command::Remove { service, force } => remove(service, *force),
+            Command::Notify {
+                service,
+                trigger_id,
+            } => notify(service, *trigger_id),
+            Command::Config(_) => show_config(&args),
+        }),
+    };
+
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn load_config(args: &CliArgs) -> Result<Config, String> {
+    match &args.config {
+        Some(config) => {
+            let config_file = FilePath::new(config.as_bytes())
+                .map_err(|e| format!("invalid config file path \"{}\" ({:?})", config, e))?;
+            Config::from_file(&config_file)
+                .map_err(|e| format!("unable to load the config file \"{}\" ({:?})", config, e))
+        }
+        None => Config::load().map_err(|e| format!("unable to load the config ({:?})", e)),
+    }
+}
+
+fn setup_config(args: &CliArgs) -> Result<(), String> {
+    if let Some(config) = &args.config {
+        let config_file = FilePath::new(config.as_bytes())
+            .map_err(|e| format!("invalid config file path \"{}\" ({:?})", config, e))?;
+        Config::setup_from_file(&config_file)
+            .map_err(|e| format!("unable to load the config file \"{}\" ({:?})", config, e))?;
+    }
+
+    Ok(())
+}
+
+fn service_name(name: &str) -> Result<ServiceName, String> {
+    ServiceName::new(name.as_bytes())
+        .map_err(|e| format!("invalid service name \"{}\" ({:?})", name, e))
+}
+
+fn find_service(name: &str) -> Result<StaticConfig, String> {
+    let service_name = service_name(name)?;
+    zero_copy::Service::list()
+        .map_err(|e| format!("unable to list the services ({:?})", e))?
+        .into_iter()
+        .find(|service| *service.service_name() == service_name)
+        .ok_or_else(|| format!("the service \"{}\" does not exist", name))
+}
+
+fn list(args: &CliArgs, pattern: Option<Pattern>) -> Result<(), String> {
+    let services: Vec<StaticConfig> = zero_copy::Service::list()
+        .map_err(|e| format!("unable to list the services ({:?})", e))?
+        .into_iter()
+        .filter(|service| match pattern {
+            Some(pattern) => Pattern::of(service.messaging_pattern()) == pattern,
+            None => true,
+        })
+        .collect();
+
+    match args.format {
+        OutputFormat::Text => {
+            for service in &services {
+                println!(
+                    "{:<20} {}",
+                    Pattern::of(service.messaging_pattern()).name(),
+                    service.service_name()
+                );
+            }
+        }
+        OutputFormat::Json => print_json(&services)?,
+    }
+
+    Ok(())
+}
+
+/// Returns the kind of the ports and their ids for every kind of port of the service.
+fn port_lists(ports: &ConnectedPorts) -> Vec<(&'static str, Vec<u128>)> {
+    fn ids<T>(ports: &[T], value: fn(&T) -> u128) -> Vec<u128> {
+        ports.iter().map(value).collect()
+    }
+
+    match ports {
+        ConnectedPorts::PublishSubscribe {
+            publishers,
+            subscribers,
+        } => vec![
+            ("publishers", ids(publishers, |p| p.value())),
+            ("subscribers", ids(subscribers, |p| p.value())),
+        ],
+        ConnectedPorts::Event {
+            notifiers,
+            listeners,
+        } => vec![
+            ("notifiers", ids(notifiers, |p| p.value())),
+            ("listeners", ids(listeners, |p| p.value())),
+        ],
+        ConnectedPorts::RequestResponse { clients, servers } => vec![
+            ("clients", ids(clients, |p| p.value())),
+            ("servers", ids(servers, |p| p.value())),
+        ],
+        ConnectedPorts::Blackboard { writers } => vec![("writers", ids(writers, |p| p.value()))],
+        ConnectedPorts::Pipeline {
+            producers,
+            consumers,
+        } => vec![
+            ("producers", ids(producers, |p| p.value())),
+            ("consumers", ids(consumers, |p| p.value())),
+        ],
+    }
+}
+
+fn show(args: &CliArgs, name: &str) -> Result<(), String> {
+    let service = find_service(name)?;
+    let ports = zero_copy::Service::connected_ports(&service)
+        .ok_or_else(|| format!("the service \"{}\" does not exist anymore", name))?;
+    let ports = port_lists(&ports);
+
+    match args.format {
+        OutputFormat::Text => {
+            print!(
+                "{}",
+                toml::to_string(&service)
+                    .map_err(|e| format!("unable to serialize the service config ({})", e))?
+            );
+            println!();
+            println!("[ports]");
+            for (kind, ids) in &ports {
+                println!("{} = {:?}", kind, ids);
+            }
+        }
+        OutputFormat::Json => {
+            // the ids exceed the range of JSON numbers, therefore they are stored as strings
+            let ports: serde_json::Map<String, serde_json::Value> = ports
+                .into_iter()
+                .map(|(kind, ids)| {
+                    let ids = ids.iter().map(|id| id.to_string()).collect::<Vec<_>>();
+                    (kind.to_string(), json!(ids))
+                })
+                .collect();
+            print_json(&json!({ "service": service, "ports": ports }))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn nodes(args: &CliArgs, cleanup: bool) -> Result<(), String> {
+    if cleanup {
+        let removed_nodes = Node::<zero_copy::Service>::cleanup_dead_nodes()
+            .map_err(|e| format!("unable to remove the dead nodes ({:?})", e))?;
+        if args.format == OutputFormat::Text {
+            println!("removed {} dead node(s)", removed_nodes.len());
+        }
+    }
+
+    let nodes = Node::<zero_copy::Service>::list()
+        .map_err(|e| format!("unable to list the nodes ({:?})", e))?;
+    let state = |node: &NodeView| match node.is_alive() {
+        true => "alive",
+        false => "dead",
+    };
+    let name = |node: &NodeView| node.name().map(|name| name.to_string()).unwrap_or_default();
+
+    match args.format {
+        OutputFormat::Text => {
+            for node in &nodes {
+                println!(
+                    "{:<40} {:<8} {:<6} {}",
+                    node.id().value(),
+                    node.id().pid(),
+                    state(node),
+                    name(node)
+                );
+                for service in node.services() {
+                    println!("    service {}", service);
+                }
+                for port in node.ports() {
+                    println!("    port    {}", port);
+                }
+            }
+        }
+        OutputFormat::Json => {
+            // the ids exceed the range of JSON numbers, therefore they are stored as strings
+            let nodes: Vec<serde_json::Value> = nodes
+                .iter()
+                .map(|node| {
+                    json!({
+                        "id": node.id().value().to_string(),
+                        "pid": node.id().pid().value(),
+                        "state": state(node),
+                        "name": name(node),
+                        "services": node.services(),
+                        "ports": node
+                            .ports()
+                            .iter()
+                            .map(|id| id.to_string())
+                            .collect::<Vec<_>>(),
+                    })
+                })
+                .collect();
+            print_json(&nodes)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn remove(name: &str, force: bool) -> Result<(), String> {
+    let service_name = service_name(name)?;
+    match zero_copy::Service::remove(&service_name, force)
+        .map_err(|e| format!("unable to remove the service \"{}\" ({:?})", name, e))?
+    {
+        true => {
+            println!("removed service \"{}\"", name);
+            Ok(())
+        }
+        false if force => Err(format!("the service \"{}\" does not exist", name)),
+        false => Err(format!(
+            "the service \"{}\" does not exist or is still in use, use --force to remove it anyway",
+            name
+        )),
+    }
+}
+
+fn notify(name: &str, trigger_id: u64) -> Result<(), String> {
+    let service_name = service_name(name)?;
+    let event = zero_copy::Service::new(&service_name)
+        .event()
+        .open()
+        .map_err(|e| format!("unable to open the event service \"{}\" ({:?})", name, e))?;
+    let notifier = event
+        .notifier()
+        .default_trigger_id(trigger_id)
+        .create()
+        .map_err(|e| format!("unable to create a notifier ({:?})", e))?;
+
+    let number_of_listeners = notifier
+        .notify()
+        .map_err(|_| format!("unable to notify the listeners of \"{}\"", name))?;
+    println!("notified {} listener(s)", number_of_listeners);
+
+    Ok(())
+}
+
+fn show_config(args: &CliArgs) -> Result<(), String> {
+    let entries = Config::get_global_config().get();
+    match args.format {
+        OutputFormat::Text => print!(
+            "{}",
+            toml::to_string(entries)
+                .map_err(|e| format!("unable to serialize the config ({})", e))?
+        ),
+        OutputFormat::Json => print_json(entries)?,
+    }
+
+    Ok(())
+}
+
+fn validate_config(args: &CliArgs) -> Result<(), String> {
+    let config = load_config(args)?;
+    let issues: Vec<String> = config.issues().iter().map(|i| i.to_string()).collect();
+
+    match args.format {
+        OutputFormat::Text if issues.is_empty() => println!("the config is valid"),
+        OutputFormat::Text => {
+            for issue in &issues {
+                println!("{}", issue);
+            }
+        }
+        OutputFormat::Json => print_json(&json!({ "issues": issues, "config": config.get() }))?,
+    }
+
+    match issues.is_empty() {
+        true => Ok(()),
+        false => Err(format!("the config contains {} issue(s)", issues.len())),
+    }
+}
+
+fn print_json<T: serde::Serialize + ?Sized>(value: &T) -> Result<(), String> {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value)
+            .map_err(|e| format!("unable to serialize the output ({})", e))?
+    );
+    Ok(())
+}
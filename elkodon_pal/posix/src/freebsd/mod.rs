@@ -4,6 +4,7 @@ pub mod dirent;
 pub mod errno;
 pub mod fcntl;
 pub mod inet;
+pub mod inotify;
 pub mod mman;
 pub mod mqueue;
 pub mod pthread;
@@ -30,6 +31,7 @@ pub use crate::freebsd::dirent::*;
 pub use crate::freebsd::errno::*;
 pub use crate::freebsd::fcntl::*;
 pub use crate::freebsd::inet::*;
+pub use crate::freebsd::inotify::*;
 pub use crate::freebsd::mman::*;
 pub use crate::freebsd::mqueue::*;
 pub use crate::freebsd::pthread::*;
@@ -0,0 +1,23 @@
+#![allow(non_camel_case_types, non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+
+use crate::posix::types::*;
+
+pub const IN_NONBLOCK: int = crate::internal::IN_NONBLOCK as _;
+pub const IN_CLOEXEC: int = crate::internal::IN_CLOEXEC as _;
+
+pub const IN_MODIFY: u32 = crate::internal::IN_MODIFY as _;
+pub const IN_ATTRIB: u32 = crate::internal::IN_ATTRIB as _;
+pub const IN_CLOSE_WRITE: u32 = crate::internal::IN_CLOSE_WRITE as _;
+pub const IN_MOVED_FROM: u32 = crate::internal::IN_MOVED_FROM as _;
+pub const IN_MOVED_TO: u32 = crate::internal::IN_MOVED_TO as _;
+pub const IN_CREATE: u32 = crate::internal::IN_CREATE as _;
+pub const IN_DELETE: u32 = crate::internal::IN_DELETE as _;
+
+pub unsafe fn inotify_init1(flags: int) -> int {
+    crate::internal::inotify_init1(flags)
+}
+
+pub unsafe fn inotify_add_watch(fd: int, pathname: *const char, mask: u32) -> int {
+    crate::internal::inotify_add_watch(fd, pathname, mask)
+}
@@ -4,6 +4,7 @@ pub mod dirent;
 pub mod errno;
 pub mod fcntl;
 pub mod inet;
+pub mod inotify;
 pub mod mman;
 pub mod mqueue;
 pub mod pthread;
@@ -30,6 +31,7 @@ pub use crate::linux::dirent::*;
 pub use crate::linux::errno::*;
 pub use crate::linux::fcntl::*;
 pub use crate::linux::inet::*;
+pub use crate::linux::inotify::*;
 pub use crate::linux::mman::*;
 pub use crate::linux::mqueue::*;
 pub use crate::linux::pthread::*;
@@ -0,0 +1,26 @@
+#![allow(non_camel_case_types, non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+#![allow(unused_variables)]
+
+use crate::posix::{types::*, Errno};
+
+pub const IN_NONBLOCK: int = 0o4000;
+pub const IN_CLOEXEC: int = 0o2000000;
+
+pub const IN_MODIFY: u32 = 0x00000002;
+pub const IN_ATTRIB: u32 = 0x00000004;
+pub const IN_CLOSE_WRITE: u32 = 0x00000008;
+pub const IN_MOVED_FROM: u32 = 0x00000040;
+pub const IN_MOVED_TO: u32 = 0x00000080;
+pub const IN_CREATE: u32 = 0x00000100;
+pub const IN_DELETE: u32 = 0x00000200;
+
+pub unsafe fn inotify_init1(flags: int) -> int {
+    Errno::set(Errno::ENOSYS);
+    -1
+}
+
+pub unsafe fn inotify_add_watch(fd: int, pathname: *const char, mask: u32) -> int {
+    Errno::set(Errno::ENOSYS);
+    -1
+}
@@ -4,6 +4,7 @@ pub mod dirent;
 pub mod errno;
 pub mod fcntl;
 pub mod inet;
+pub mod inotify;
 pub mod mman;
 pub mod mqueue;
 pub mod pthread;
@@ -30,6 +31,7 @@ pub use crate::macos::dirent::*;
 pub use crate::macos::errno::*;
 pub use crate::macos::fcntl::*;
 pub use crate::macos::inet::*;
+pub use crate::macos::inotify::*;
 pub use crate::macos::mman::*;
 pub use crate::macos::mqueue::*;
 pub use crate::macos::pthread::*;
@@ -4,6 +4,7 @@ pub mod dirent;
 pub mod errno;
 pub mod fcntl;
 pub mod inet;
+pub mod inotify;
 pub mod mman;
 pub mod mqueue;
 pub mod pthread;
@@ -36,6 +37,7 @@ pub use crate::windows::dirent::*;
 pub use crate::windows::errno::*;
 pub use crate::windows::fcntl::*;
 pub use crate::windows::inet::*;
+pub use crate::windows::inotify::*;
 pub use crate::windows::mman::*;
 pub use crate::windows::mqueue::*;
 pub use crate::windows::pthread::*;
@@ -1,9 +1,34 @@
+use elkodon::service::discovery::{DiscoveryEvent, ServiceWatcher};
 use elkodon::service::{zero_copy, Details};
+use elkodon_bb_posix::signal::SignalHandler;
 
 fn main() {
+    if std::env::args().any(|arg| arg == "--watch") {
+        watch();
+        return;
+    }
+
     let services = zero_copy::Service::list().expect("failed to acquire list of current services");
 
     for service in services {
         println!("\n{:#?}", &service);
     }
 }
+
+fn watch() {
+    let watcher = ServiceWatcher::<zero_copy::Service>::new();
+
+    while !SignalHandler::was_ctrl_c_pressed() {
+        watcher
+            .timed_wait(
+                |event| match event {
+                    DiscoveryEvent::ServiceAdded(service) => println!("\nadded: {:#?}", &service),
+                    DiscoveryEvent::ServiceRemoved(name) => println!("\nremoved: {}", name),
+                },
+                std::time::Duration::from_secs(1),
+            )
+            .expect("failed to wait for service changes");
+    }
+
+    println!("exit ...");
+}
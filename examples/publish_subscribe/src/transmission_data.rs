@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use elkodon::zero_copy_type::{ShmSafe, ZeroCopyType};
+
+#[derive(Debug, ShmSafe, ZeroCopyType)]
 #[repr(C)]
 pub struct TransmissionData {
     pub x: i32,